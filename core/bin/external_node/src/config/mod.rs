@@ -1,15 +1,15 @@
-use std::{env, time::Duration};
+use std::{env, net::IpAddr, num::NonZeroU32, time::Duration};
 
 use anyhow::Context;
 use serde::Deserialize;
 use url::Url;
 use zksync_basic_types::{Address, L1ChainId, L2ChainId};
-use zksync_config::ObjectStoreConfig;
+use zksync_config::{configs::api::MethodWeight, ObjectStoreConfig};
 use zksync_consensus_roles::node;
 use zksync_core::{
     api_server::{
         tx_sender::TxSenderConfig,
-        web3::{state::InternalApiConfig, Namespace},
+        web3::{state::InternalApiConfig, MethodFilter, Namespace},
     },
     consensus,
 };
@@ -108,12 +108,40 @@ pub struct OptionalENConfig {
     /// Limit for fee history block range.
     #[serde(default = "OptionalENConfig::default_fee_history_limit")]
     pub fee_history_limit: u64,
+    /// Maximum number of VM instructions recorded by the `structLogger` tracer. Larger `maxSteps` values
+    /// requested by clients are clamped to this value.
+    #[serde(default = "OptionalENConfig::default_struct_log_max_steps")]
+    pub struct_log_max_steps: usize,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     #[serde(default = "OptionalENConfig::default_max_batch_request_size")]
     pub max_batch_request_size: usize,
     /// Maximum response body size in MiBs. Default is 10 MiB.
     #[serde(default = "OptionalENConfig::default_max_response_body_size_mb")]
     pub max_response_body_size_mb: usize,
+    /// Quota of request weight units per minute for a single API client. The quota is shared by the HTTP and WebSocket servers.
+    /// Clients are identified by an API key from `api_keys` provided in the `api_key_header` header, or by the client IP address
+    /// (see `trusted_proxies`). If not set, per-client quotas are disabled.
+    pub requests_quota_per_minute: Option<NonZeroU32>,
+    /// Weights of RPC methods used for rate limiting, specified as `method=weight` entries. Methods not mentioned
+    /// in this list have the weight of 1.
+    #[serde(default)]
+    pub method_weights: Vec<MethodWeight>,
+    /// Name of the HTTP header containing the API key used to identify API clients for per-client quotas.
+    pub api_key_header: Option<String>,
+    /// API keys recognized in the `api_key_header` header. Requests with other keys are identified by the client IP address.
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+    /// IP addresses of reverse proxies / load balancers trusted to append the client address to the `X-Forwarded-For` header.
+    /// For requests coming from other peers, the header is ignored and the peer address is used to identify the client.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// RPC methods exposed by the API servers. If not empty, all other methods are disabled,
+    /// even if their namespace is enabled.
+    #[serde(default)]
+    pub api_method_allowlist: Vec<String>,
+    /// RPC methods disabled for the API servers.
+    #[serde(default)]
+    pub api_method_denylist: Vec<String>,
 
     // Other API config settings
     /// Interval between polling DB for pubsub (in ms).
@@ -225,6 +253,23 @@ pub struct OptionalENConfig {
     /// Path to KZG trusted setup path.
     #[serde(default = "OptionalENConfig::default_kzg_trusted_setup_path")]
     pub kzg_trusted_setup_path: String,
+
+    // Pruning config
+    /// Enables pruning of historical Postgres data (blocks, transactions, events, storage logs etc.).
+    /// Only data for L1 batches executed on L1 is pruned; the state necessary to execute new transactions is always retained.
+    #[serde(default)]
+    pub pruning_enabled: bool,
+    /// Maximum number of L1 batches pruned at a time.
+    #[serde(default = "OptionalENConfig::default_pruning_chunk_size")]
+    pub pruning_chunk_size: u32,
+    /// Delay between soft-pruning data (i.e., no longer serving it via the API) and removing it from Postgres.
+    #[serde(default = "OptionalENConfig::default_pruning_removal_delay_sec")]
+    pruning_removal_delay_sec: u64,
+    /// Minimum age of L1 batches to be pruned.
+    #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
+    pruning_data_retention_sec: u64,
+    /// If set, at least this many latest L1 batches are retained regardless of their age.
+    pub pruning_data_retention_batches: Option<u32>,
 }
 
 impl OptionalENConfig {
@@ -315,6 +360,10 @@ impl OptionalENConfig {
         1_024
     }
 
+    const fn default_struct_log_max_steps() -> usize {
+        100_000
+    }
+
     const fn default_max_batch_request_size() -> usize {
         500 // The default limit is chosen to be reasonably permissive.
     }
@@ -335,6 +384,18 @@ impl OptionalENConfig {
         "./trusted_setup.json".to_owned()
     }
 
+    const fn default_pruning_chunk_size() -> u32 {
+        10
+    }
+
+    const fn default_pruning_removal_delay_sec() -> u64 {
+        60
+    }
+
+    const fn default_pruning_data_retention_sec() -> u64 {
+        7 * 24 * 3_600 // 7 days
+    }
+
     pub fn polling_interval(&self) -> Duration {
         Duration::from_millis(self.polling_interval)
     }
@@ -373,6 +434,14 @@ impl OptionalENConfig {
         Duration::from_secs(self.merkle_tree_stalled_writes_timeout_sec)
     }
 
+    pub fn pruning_removal_delay(&self) -> Duration {
+        Duration::from_secs(self.pruning_removal_delay_sec)
+    }
+
+    pub fn pruning_data_retention(&self) -> Duration {
+        Duration::from_secs(self.pruning_data_retention_sec)
+    }
+
     pub fn long_connection_threshold(&self) -> Option<Duration> {
         self.database_long_connection_threshold_ms
             .map(Duration::from_millis)
@@ -383,6 +452,13 @@ impl OptionalENConfig {
             .map(Duration::from_millis)
     }
 
+    pub fn api_method_filter(&self) -> MethodFilter {
+        MethodFilter::new(
+            self.api_method_allowlist.clone(),
+            self.api_method_denylist.clone(),
+        )
+    }
+
     pub fn api_namespaces(&self) -> Vec<Namespace> {
         self.api_namespaces
             .clone()
@@ -602,6 +678,7 @@ impl From<ExternalNodeConfig> for InternalApiConfig {
             req_entities_limit: config.optional.req_entities_limit,
             fee_history_limit: config.optional.fee_history_limit,
             filters_disabled: config.optional.filters_disabled,
+            struct_log_max_steps: config.optional.struct_log_max_steps,
         }
     }
 }
@@ -625,6 +702,8 @@ impl From<ExternalNodeConfig> for TxSenderConfig {
             l1_to_l2_transactions_compatibility_mode: config
                 .optional
                 .l1_to_l2_transactions_compatibility_mode,
+            // Pending transaction limits are enforced by the main node.
+            account_tx_policy: Default::default(),
         }
     }
 }
@@ -8,6 +8,7 @@ fn parsing_optional_config_from_empty_env() {
     assert_eq!(config.filters_limit, 10_000);
     assert_eq!(config.subscriptions_limit, 10_000);
     assert_eq!(config.fee_history_limit, 1_024);
+    assert_eq!(config.struct_log_max_steps, 100_000);
     assert_eq!(config.polling_interval(), Duration::from_millis(200));
     assert_eq!(config.max_tx_size, 1_000_000);
     assert_eq!(
@@ -25,6 +26,20 @@ fn parsing_optional_config_from_empty_env() {
         128 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), 10 * BYTES_IN_MEGABYTE);
+    assert_eq!(config.requests_quota_per_minute, None);
+    assert!(config.method_weights.is_empty());
+    assert!(config.api_keys.is_empty());
+    assert!(config.trusted_proxies.is_empty());
+    assert!(config.api_method_allowlist.is_empty());
+    assert!(config.api_method_denylist.is_empty());
+    assert!(!config.pruning_enabled);
+    assert_eq!(config.pruning_chunk_size, 10);
+    assert_eq!(config.pruning_removal_delay(), Duration::from_secs(60));
+    assert_eq!(
+        config.pruning_data_retention(),
+        Duration::from_secs(7 * 24 * 3_600)
+    );
+    assert_eq!(config.pruning_data_retention_batches, None);
 }
 
 #[test]
@@ -45,6 +60,17 @@ fn parsing_optional_config_from_env() {
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
+        ("EN_REQUESTS_QUOTA_PER_MINUTE", "600"),
+        ("EN_METHOD_WEIGHTS", "eth_call=10,debug_traceCall=100"),
+        ("EN_API_KEY_HEADER", "x-api-key"),
+        ("EN_API_KEYS", "key1,key2"),
+        ("EN_TRUSTED_PROXIES", "10.0.0.1,10.0.0.2"),
+        ("EN_API_METHOD_DENYLIST", "eth_getLogs,debug_traceCall"),
+        ("EN_PRUNING_ENABLED", "true"),
+        ("EN_PRUNING_CHUNK_SIZE", "5"),
+        ("EN_PRUNING_REMOVAL_DELAY_SEC", "120"),
+        ("EN_PRUNING_DATA_RETENTION_SEC", "3600"),
+        ("EN_PRUNING_DATA_RETENTION_BATCHES", "100"),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -72,4 +98,27 @@ fn parsing_optional_config_from_env() {
         32 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), BYTES_IN_MEGABYTE);
+    assert_eq!(config.requests_quota_per_minute, NonZeroU32::new(600));
+    let method_weights: Vec<_> = config
+        .method_weights
+        .iter()
+        .map(|weight| (weight.method.as_str(), weight.weight.get()))
+        .collect();
+    assert_eq!(method_weights, [("eth_call", 10), ("debug_traceCall", 100)]);
+    assert_eq!(config.api_key_header.as_deref(), Some("x-api-key"));
+    assert_eq!(config.api_keys, ["key1", "key2"]);
+    assert_eq!(
+        config.trusted_proxies,
+        [IpAddr::from([10, 0, 0, 1]), IpAddr::from([10, 0, 0, 2])]
+    );
+    assert!(config.api_method_allowlist.is_empty());
+    assert_eq!(
+        config.api_method_denylist,
+        ["eth_getLogs", "debug_traceCall"]
+    );
+    assert!(config.pruning_enabled);
+    assert_eq!(config.pruning_chunk_size, 5);
+    assert_eq!(config.pruning_removal_delay(), Duration::from_secs(120));
+    assert_eq!(config.pruning_data_retention(), Duration::from_secs(3_600));
+    assert_eq!(config.pruning_data_retention_batches, Some(100));
 }
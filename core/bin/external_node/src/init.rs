@@ -37,6 +37,11 @@ pub(crate) async fn ensure_storage_initialized(
         .get_applied_snapshot_status()
         .await
         .context("failed getting snapshot recovery info")?;
+    let pruning_info = storage
+        .pruning_dal()
+        .get_pruning_info()
+        .await
+        .context("failed getting pruning info")?;
     drop(storage);
 
     let decision = match (genesis_l1_batch, snapshot_recovery) {
@@ -54,6 +59,13 @@ pub(crate) async fn ensure_storage_initialized(
             tracing::info!("Node has no genesis L1 batch and snapshot recovery information: {snapshot_recovery:?}");
             InitDecision::SnapshotRecovery
         }
+        (None, None) if pruning_info.last_soft_pruned_l1_batch.is_some() => {
+            // The genesis L1 batch was removed by the DB pruner; the node storage is already initialized.
+            tracing::info!(
+                "Node has no genesis L1 batch and snapshot recovery info, but it was pruned: {pruning_info:?}"
+            );
+            return Ok(());
+        }
         (None, None) => {
             tracing::info!("Node has neither genesis L1 batch, nor snapshot recovery info");
             if consider_snapshot_recovery {
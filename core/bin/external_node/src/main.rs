@@ -14,12 +14,13 @@ use zksync_core::{
         execution_sandbox::VmConcurrencyLimiter,
         healthcheck::HealthCheckHandle,
         tx_sender::{proxy::TxProxy, ApiContracts, TxSenderBuilder},
-        web3::{ApiBuilder, Namespace},
+        web3::{ApiBuilder, Namespace, RpcRateLimits},
     },
     block_reverter::{BlockReverter, BlockReverterFlags, L1ExecutedBatchesRevert},
     commitment_generator::CommitmentGenerator,
     consensus,
     consistency_checker::ConsistencyChecker,
+    db_pruner::{DbPruner, DbPrunerConfig},
     l1_gas_price::MainNodeFeeParamsFetcher,
     metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig},
     reorg_detector::ReorgDetector,
@@ -252,6 +253,25 @@ async fn init_tasks(
     );
     app_health.insert_component(batch_status_updater.health_check());
 
+    if config.optional.pruning_enabled {
+        tracing::info!("Postgres data pruning is enabled");
+        let db_pruner = DbPruner::new(
+            DbPrunerConfig {
+                removal_delay: config.optional.pruning_removal_delay(),
+                next_iterations_delay: Duration::from_secs(30),
+                pruned_batch_chunk_size: config.optional.pruning_chunk_size,
+                minimum_l1_batch_age: Some(config.optional.pruning_data_retention()),
+                retained_l1_batches: config.optional.pruning_data_retention_batches,
+            },
+            singleton_pool_builder
+                .build()
+                .await
+                .context("failed to build a connection pool for DbPruner")?,
+        );
+        app_health.insert_component(db_pruner.health_check());
+        task_handles.push(tokio::spawn(db_pruner.run(stop_receiver.clone())));
+    }
+
     // Run the components.
     let tree_stop_receiver = stop_receiver.clone();
     let tree_pool = singleton_pool_builder
@@ -330,12 +350,26 @@ async fn init_tasks(
         )
     };
 
+    // Rate limits are shared by HTTP and WS servers, so that per-client quotas are shared as well.
+    let mut rate_limits = RpcRateLimits::new(config.optional.requests_quota_per_minute)
+        .with_method_weights(config.optional.method_weights.clone())
+        .with_api_keys(config.optional.api_keys.clone())
+        .with_trusted_proxies(config.optional.trusted_proxies.clone());
+    if let Some(header) = &config.optional.api_key_header {
+        rate_limits = rate_limits
+            .with_api_key_header(header)
+            .context("api_key_header")?;
+    }
+    let rate_limits = Arc::new(rate_limits);
+
     let http_server_handles =
         ApiBuilder::jsonrpsee_backend(config.clone().into(), connection_pool.clone())
             .http(config.required.http_port)
             .with_filter_limit(config.optional.filters_limit)
             .with_batch_request_size_limit(config.optional.max_batch_request_size)
             .with_response_body_size_limit(config.optional.max_response_body_size())
+            .with_rate_limits(rate_limits.clone())
+            .with_method_filter(config.optional.api_method_filter())
             .with_tx_sender(tx_sender.clone(), vm_barrier.clone())
             .with_sync_state(sync_state.clone())
             .enable_api_namespaces(config.optional.api_namespaces())
@@ -350,6 +384,8 @@ async fn init_tasks(
             .with_subscriptions_limit(config.optional.subscriptions_limit)
             .with_batch_request_size_limit(config.optional.max_batch_request_size)
             .with_response_body_size_limit(config.optional.max_response_body_size())
+            .with_rate_limits(rate_limits)
+            .with_method_filter(config.optional.api_method_filter())
             .with_polling_interval(config.optional.polling_interval())
             .with_tx_sender(tx_sender, vm_barrier)
             .with_sync_state(sync_state)
@@ -0,0 +1,168 @@
+//! Offline fee simulator: replays historical L1 fees and L1 batch stats from Postgres through the fee input strategy
+//! configured for the state keeper and reports revenue, costs and margins per batch.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::{
+        chain::StateKeeperConfig,
+        eth_sender::{PubdataSendingMode, SenderConfig},
+        ObservabilityConfig,
+    },
+    PostgresConfig,
+};
+use zksync_core::fee_model::simulator::{FeeSimulator, L1FeeSample};
+use zksync_dal::ConnectionPool;
+use zksync_env_config::FromEnv;
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Offline fee simulator",
+    long_about = None
+)]
+struct Cli {
+    /// Path to a JSON file with an array of L1 fee samples, each having `timestamp`, `base_fee_per_gas`
+    /// and (optionally) `base_fee_per_blob_gas` fields. If not specified, L1 gas prices recorded
+    /// for the simulated batches in Postgres are used; these prices already include the L1 gas price scale factor,
+    /// and blob base fees are not recorded.
+    #[arg(long = "l1-fees")]
+    l1_fees: Option<PathBuf>,
+    /// First L1 batch to simulate (inclusive).
+    #[arg(long = "from-batch")]
+    from_batch: u32,
+    /// Last L1 batch to simulate (inclusive). If not specified, simulation is performed up to the last sealed batch.
+    #[arg(long = "to-batch")]
+    to_batch: Option<u32>,
+    /// Path to the output file. Results are written as JSON lines: one line per simulated batch followed by
+    /// the summary line. If not specified, results are written to stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+impl Cli {
+    async fn run(self, pool: &ConnectionPool, simulator: &FeeSimulator) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage_tagged("fee_simulator").await?;
+        let to_batch = match self.to_batch {
+            Some(number) => L1BatchNumber(number),
+            None => storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await?
+                .context("no sealed L1 batches in the database")?,
+        };
+        let from_batch = L1BatchNumber(self.from_batch);
+        anyhow::ensure!(
+            from_batch <= to_batch,
+            "invalid batch range: {from_batch}..={to_batch}"
+        );
+        tracing::info!("Loading stats for L1 batches {from_batch}..={to_batch}");
+        let batches = storage
+            .blocks_dal()
+            .get_l1_batch_fee_stats(from_batch..=to_batch)
+            .await?;
+
+        let mut l1_fees: Vec<L1FeeSample> = if let Some(path) = &self.l1_fees {
+            let l1_fees = fs::read_to_string(path)
+                .with_context(|| format!("failed reading L1 fees from {path:?}"))?;
+            serde_json::from_str(&l1_fees).context("failed parsing L1 fees")?
+        } else {
+            tracing::info!(
+                "Loading L1 gas prices for L1 batches {from_batch}..={to_batch} from Postgres"
+            );
+            let l1_gas_prices = storage
+                .blocks_dal()
+                .get_l1_gas_prices_for_l1_batches(from_batch..=to_batch)
+                .await?;
+            l1_gas_prices
+                .into_iter()
+                .map(|(timestamp, base_fee_per_gas)| L1FeeSample {
+                    timestamp,
+                    base_fee_per_gas,
+                    base_fee_per_blob_gas: 0,
+                })
+                .collect()
+        };
+        drop(storage);
+        l1_fees.sort_unstable_by_key(|sample| sample.timestamp);
+
+        let report = simulator.simulate(&l1_fees, &batches);
+        if !report.skipped_batches.is_empty() {
+            tracing::warn!(
+                "Skipped {} L1 batches without preceding L1 fee samples: {:?}",
+                report.skipped_batches.len(),
+                report.skipped_batches
+            );
+        }
+        tracing::info!(
+            "Simulated {} L1 batches; total margin: {} wei",
+            report.batches.len(),
+            report.total_margin
+        );
+
+        let mut output: Box<dyn Write> = match &self.output {
+            Some(path) => Box::new(io::BufWriter::new(
+                fs::File::create(path).with_context(|| format!("failed creating {path:?}"))?,
+            )),
+            None => Box::new(io::stdout().lock()),
+        };
+        for batch in &report.batches {
+            serde_json::to_writer(&mut output, batch)?;
+            writeln!(output)?;
+        }
+        let summary = serde_json::json!({
+            "skipped_batches": report.skipped_batches,
+            "total_revenue": report.total_revenue,
+            "total_cost": report.total_cost,
+            "total_margin": report.total_margin,
+        });
+        serde_json::to_writer(&mut output, &summary)?;
+        writeln!(output)?;
+        output.flush()?;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let state_keeper_config =
+        StateKeeperConfig::from_env().context("StateKeeperConfig::from_env()")?;
+    let sender_config = SenderConfig::from_env().context("SenderConfig::from_env()")?;
+
+    let pool = ConnectionPool::singleton(postgres_config.replica_url()?)
+        .build()
+        .await?;
+    let simulator = FeeSimulator::new(&state_keeper_config, sender_config.pubdata_sending_mode);
+    let cli = Cli::parse();
+    if cli.l1_fees.is_none() && sender_config.pubdata_sending_mode == PubdataSendingMode::Blobs {
+        tracing::warn!(
+            "Blob base fees are not recorded in Postgres; pubdata published in blobs will be priced at zero. \
+             Use `--l1-fees` to provide blob base fees"
+        );
+    }
+    cli.run(&pool, &simulator).await
+}
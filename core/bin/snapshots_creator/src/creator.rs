@@ -1,6 +1,6 @@
 //! [`SnapshotCreator`] and tightly related types.
 
-use std::sync::Arc;
+use std::{ops, sync::Arc};
 
 use anyhow::Context as _;
 use tokio::sync::Semaphore;
@@ -23,6 +23,8 @@ use crate::tests::HandleEvent;
 #[derive(Debug)]
 struct SnapshotProgress {
     l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot if the snapshot is incremental.
+    base_l1_batch_number: Option<L1BatchNumber>,
     /// `true` if the snapshot is new (i.e., its progress is not recovered from Postgres).
     is_new_snapshot: bool,
     chunk_count: u64,
@@ -30,9 +32,14 @@ struct SnapshotProgress {
 }
 
 impl SnapshotProgress {
-    fn new(l1_batch_number: L1BatchNumber, chunk_count: u64) -> Self {
+    fn new(
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        chunk_count: u64,
+    ) -> Self {
         Self {
             l1_batch_number,
+            base_l1_batch_number,
             is_new_snapshot: true,
             chunk_count,
             remaining_chunk_ids: (0..chunk_count).collect(),
@@ -49,6 +56,7 @@ impl SnapshotProgress {
 
         Self {
             l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number: snapshot.base_l1_batch_number,
             is_new_snapshot: false,
             chunk_count: snapshot.storage_logs_filepaths.len() as u64,
             remaining_chunk_ids,
@@ -76,7 +84,7 @@ impl SnapshotCreator {
     async fn process_storage_logs_single_chunk(
         &self,
         semaphore: &Semaphore,
-        miniblock_number: MiniblockNumber,
+        miniblock_range: ops::RangeInclusive<MiniblockNumber>,
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
         chunk_count: u64,
@@ -94,7 +102,7 @@ impl SnapshotCreator {
             METRICS.storage_logs_processing_duration[&StorageChunkStage::LoadFromPostgres].start();
         let logs = conn
             .snapshots_creator_dal()
-            .get_storage_logs_chunk(miniblock_number, hashed_keys_range)
+            .get_modified_storage_logs_chunk(miniblock_range, hashed_keys_range)
             .await
             .context("Error fetching storage logs count")?;
         drop(conn);
@@ -143,7 +151,7 @@ impl SnapshotCreator {
 
     async fn process_factory_deps(
         &self,
-        miniblock_number: MiniblockNumber,
+        miniblock_range: ops::RangeInclusive<MiniblockNumber>,
         l1_batch_number: L1BatchNumber,
     ) -> anyhow::Result<String> {
         let mut conn = self.connect_to_replica().await?;
@@ -153,7 +161,7 @@ impl SnapshotCreator {
             METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
         let factory_deps = conn
             .snapshots_creator_dal()
-            .get_all_factory_deps(miniblock_number)
+            .get_factory_deps_in_range(miniblock_range)
             .await?;
         drop(conn);
         let latency = latency.observe();
@@ -187,6 +195,27 @@ impl SnapshotCreator {
         Ok(output_filepath)
     }
 
+    /// Returns the number of incremental snapshots in the chain ending with `snapshot` (including `snapshot` itself).
+    async fn incremental_chain_len(
+        snapshot: &SnapshotMetadata,
+        conn: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<u32> {
+        let mut chain_len = 0;
+        let mut base_l1_batch_number = snapshot.base_l1_batch_number;
+        while let Some(l1_batch_number) = base_l1_batch_number {
+            chain_len += 1;
+            let base_snapshot = conn
+                .snapshots_dal()
+                .get_snapshot_metadata(l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!("Base snapshot for L1 batch #{l1_batch_number} is missing")
+                })?;
+            base_l1_batch_number = base_snapshot.base_l1_batch_number;
+        }
+        Ok(chain_len)
+    }
+
     /// Returns `Ok(None)` if the created snapshot would coincide with `latest_snapshot`.
     async fn initialize_snapshot_progress(
         config: &SnapshotsCreatorConfig,
@@ -212,6 +241,25 @@ impl SnapshotCreator {
             return Ok(None);
         }
 
+        if let Some(latest_snapshot) = latest_snapshot {
+            let chain_len = Self::incremental_chain_len(latest_snapshot, conn).await?;
+            if chain_len < config.max_incremental_snapshots {
+                // Incremental snapshots reuse chunking of the base snapshot, so that chunks with the same ID
+                // in all snapshots of the chain cover the same range of hashed keys.
+                let chunk_count = latest_snapshot.storage_logs_filepaths.len() as u64;
+                tracing::info!(
+                    "Creating incremental snapshot for L1 batch {l1_batch_number} on top of snapshot \
+                     for L1 batch {} ({chain_len} incremental snapshot(s) in chain) with {chunk_count} chunks",
+                    latest_snapshot.l1_batch_number
+                );
+                return Ok(Some(SnapshotProgress::new(
+                    l1_batch_number,
+                    Some(latest_snapshot.l1_batch_number),
+                    chunk_count,
+                )));
+            }
+        }
+
         let distinct_storage_logs_keys_count = conn
             .snapshots_creator_dal()
             .get_distinct_storage_logs_keys_count(l1_batch_number)
@@ -226,7 +274,11 @@ impl SnapshotCreator {
             "Selected storage logs chunking for L1 batch {l1_batch_number}: \
             {chunk_count} chunks of expected size {chunk_size}"
         );
-        Ok(Some(SnapshotProgress::new(l1_batch_number, chunk_count)))
+        Ok(Some(SnapshotProgress::new(
+            l1_batch_number,
+            None,
+            chunk_count,
+        )))
     }
 
     /// Returns `Ok(None)` if a snapshot should not be created / resumed.
@@ -286,18 +338,29 @@ impl SnapshotCreator {
             .get_miniblock_range_of_l1_batch(progress.l1_batch_number)
             .await?
             .context("Error fetching last miniblock number")?;
+        let first_miniblock_number =
+            if let Some(base_l1_batch_number) = progress.base_l1_batch_number {
+                let (_, last_miniblock_in_base_batch) = conn
+                    .blocks_dal()
+                    .get_miniblock_range_of_l1_batch(base_l1_batch_number)
+                    .await?
+                    .context("Error fetching last miniblock number for the base snapshot")?;
+                last_miniblock_in_base_batch + 1
+            } else {
+                MiniblockNumber(0)
+            };
+        let miniblock_range = first_miniblock_number..=last_miniblock_number_in_batch;
         drop(conn);
 
         METRICS.storage_logs_chunks_count.set(progress.chunk_count);
         tracing::info!(
-            "Creating snapshot for storage logs up to miniblock {last_miniblock_number_in_batch}, \
-            L1 batch {}",
+            "Creating snapshot for storage logs in miniblocks {miniblock_range:?}, L1 batch {}",
             progress.l1_batch_number
         );
 
         if progress.is_new_snapshot {
             let factory_deps_output_file = self
-                .process_factory_deps(last_miniblock_number_in_batch, progress.l1_batch_number)
+                .process_factory_deps(miniblock_range.clone(), progress.l1_batch_number)
                 .await?;
 
             let mut master_conn = self
@@ -308,6 +371,7 @@ impl SnapshotCreator {
                 .snapshots_dal()
                 .add_snapshot(
                     progress.l1_batch_number,
+                    progress.base_l1_batch_number,
                     progress.chunk_count,
                     &factory_deps_output_file,
                 )
@@ -321,7 +385,7 @@ impl SnapshotCreator {
         let tasks = progress.remaining_chunk_ids.into_iter().map(|chunk_id| {
             self.process_storage_logs_single_chunk(
                 &semaphore,
-                last_miniblock_number_in_batch,
+                miniblock_range.clone(),
                 progress.l1_batch_number,
                 chunk_id,
                 progress.chunk_count,
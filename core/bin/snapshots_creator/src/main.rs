@@ -1,5 +1,9 @@
 //! Snapshot creator utility. Intended to run on a schedule, with each run creating a new snapshot.
 //!
+//! If `max_incremental_snapshots` is configured, snapshots may be incremental, i.e., only contain storage logs
+//! and factory deps changed since the previous snapshot. Incremental snapshots are created on top of the newest
+//! snapshot until the chain of incremental snapshots reaches the configured length; then, a full snapshot is created.
+//!
 //! # Assumptions
 //!
 //! The snapshot creator is fault-tolerant; if it stops in the middle of creating a snapshot,
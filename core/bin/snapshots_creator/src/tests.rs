@@ -2,7 +2,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
-    fmt,
+    fmt, ops,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
@@ -27,10 +27,12 @@ use super::*;
 const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     storage_logs_chunk_size: 1_000_000,
     concurrent_queries_count: 10,
+    max_incremental_snapshots: 0,
 };
 const SEQUENTIAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     storage_logs_chunk_size: 1_000_000,
     concurrent_queries_count: 1,
+    max_incremental_snapshots: 0,
 };
 
 #[derive(Debug)]
@@ -329,20 +331,31 @@ async fn persisting_snapshot_logs() {
     assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
 }
 
-async fn assert_storage_logs(
+async fn load_storage_logs(
     object_store: &dyn ObjectStore,
     snapshot_l1_batch_number: L1BatchNumber,
-    expected_outputs: &ExpectedOutputs,
-) {
-    let mut actual_logs = HashSet::new();
+) -> Vec<SnapshotStorageLog> {
+    let mut logs = vec![];
     for chunk_id in 0..MIN_CHUNK_COUNT {
         let key = SnapshotStorageLogsStorageKey {
             l1_batch_number: snapshot_l1_batch_number,
             chunk_id,
         };
         let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
-        actual_logs.extend(chunk.storage_logs.into_iter());
+        logs.extend(chunk.storage_logs);
     }
+    logs
+}
+
+async fn assert_storage_logs(
+    object_store: &dyn ObjectStore,
+    snapshot_l1_batch_number: L1BatchNumber,
+    expected_outputs: &ExpectedOutputs,
+) {
+    let actual_logs: HashSet<_> = load_storage_logs(object_store, snapshot_l1_batch_number)
+        .await
+        .into_iter()
+        .collect();
     assert_eq!(actual_logs, expected_outputs.storage_logs);
 }
 
@@ -456,3 +469,136 @@ async fn recovery_workflow_with_varying_chunk_size() {
     let object_store = object_store_factory.create_store().await;
     assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
 }
+
+/// Adds blocks to Postgres prepared with `prepare_postgres()`. Each block creates new storage slots
+/// and updates the specified existing ones.
+async fn extend_postgres(
+    rng: &mut impl Rng,
+    conn: &mut StorageProcessor<'_>,
+    block_numbers: ops::Range<u32>,
+    updated_keys: &[StorageKey],
+) {
+    for block_number in block_numbers {
+        let new_logs = gen_storage_logs(rng, 50);
+        let updated_logs = updated_keys
+            .iter()
+            .map(|&key| StorageLog::new_write_log(key, H256(rng.gen())));
+        let logs = new_logs.iter().copied().chain(updated_logs).collect();
+        create_miniblock(conn, MiniblockNumber(block_number), logs).await;
+
+        let factory_deps = gen_factory_deps(rng, 10);
+        conn.factory_deps_dal()
+            .insert_factory_deps(MiniblockNumber(block_number), &factory_deps)
+            .await
+            .unwrap();
+        create_l1_batch(conn, L1BatchNumber(block_number), &new_logs).await;
+    }
+}
+
+#[tokio::test]
+async fn creating_incremental_snapshots() {
+    const INCREMENTAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
+        max_incremental_snapshots: 2,
+        ..TEST_CONFIG
+    };
+
+    let pool = ConnectionPool::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store_factory = ObjectStoreFactory::mock();
+    let object_store = object_store_factory.create_store().await;
+    let mut conn = pool.access_storage().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(INCREMENTAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let base_snapshot = conn
+        .snapshots_dal()
+        .get_newest_snapshot_metadata()
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(base_snapshot.l1_batch_number, L1BatchNumber(8));
+    assert_eq!(base_snapshot.base_l1_batch_number, None);
+
+    let updated_keys: Vec<_> = expected_outputs
+        .storage_logs
+        .iter()
+        .take(20)
+        .map(|log| log.key)
+        .collect();
+    extend_postgres(&mut rng, &mut conn, 10..15, &updated_keys).await;
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(INCREMENTAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(13);
+    let snapshot = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot.is_complete());
+    assert_eq!(snapshot.base_l1_batch_number, Some(L1BatchNumber(8)));
+    assert_eq!(
+        snapshot.storage_logs_filepaths.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+
+    // Check that applying the incremental snapshot on top of the base one is equivalent to a full snapshot.
+    let incremental_logs = load_storage_logs(&*object_store, snapshot_l1_batch_number).await;
+    // Miniblocks #9..=13 create 100 + 4 * 50 new slots (miniblock #9 is created in `prepare_postgres()`)
+    // and update `updated_keys`.
+    assert_eq!(incremental_logs.len(), 100 + 4 * 50 + updated_keys.len());
+    let mut merged_logs: HashMap<_, _> = expected_outputs
+        .storage_logs
+        .iter()
+        .map(|log| (log.key, log.clone()))
+        .collect();
+    merged_logs.extend(incremental_logs.into_iter().map(|log| (log.key, log)));
+    let merged_logs: HashSet<_> = merged_logs.into_values().collect();
+    let full_logs: HashSet<_> = conn
+        .snapshots_creator_dal()
+        .get_storage_logs_chunk(MiniblockNumber(13), H256::zero()..=H256::repeat_byte(0xff))
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(merged_logs, full_logs);
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(factory_deps.len(), 5 * 10);
+
+    // The next snapshot should be incremental as well, and the one after that should be full.
+    extend_postgres(&mut rng, &mut conn, 15..17, &updated_keys).await;
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(INCREMENTAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot = conn
+        .snapshots_dal()
+        .get_newest_snapshot_metadata()
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(snapshot.l1_batch_number, L1BatchNumber(15));
+    assert_eq!(snapshot.base_l1_batch_number, Some(L1BatchNumber(13)));
+
+    extend_postgres(&mut rng, &mut conn, 17..19, &updated_keys).await;
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(INCREMENTAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot = conn
+        .snapshots_dal()
+        .get_newest_snapshot_metadata()
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(snapshot.l1_batch_number, L1BatchNumber(17));
+    assert_eq!(snapshot.base_l1_batch_number, None);
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use clap::Parser;
+use tokio::sync::watch;
+use zksync_config::{
+    configs::{
+        chain::{NetworkConfig, StateKeeperConfig},
+        ObservabilityConfig,
+    },
+    PostgresConfig,
+};
+use zksync_core::state_keeper::{
+    seal_criteria::NoopSealer, MainBatchExecutor, ReplayIO, ZkSyncStateKeeper,
+};
+use zksync_dal::ConnectionPool;
+use zksync_env_config::FromEnv;
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Replays sealed L1 batches using the state keeper and compares results with Postgres",
+    long_about = None
+)]
+struct Cli {
+    /// Number of the first L1 batch to replay.
+    #[arg(long)]
+    from_l1_batch: u32,
+    /// Number of the last L1 batch to replay (inclusive). If not specified, only `from_l1_batch` is replayed.
+    #[arg(long)]
+    to_l1_batch: Option<u32>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let cli = Cli::parse();
+    let first_l1_batch = L1BatchNumber(cli.from_l1_batch);
+    let last_l1_batch = L1BatchNumber(cli.to_l1_batch.unwrap_or(cli.from_l1_batch));
+
+    let state_keeper_config =
+        StateKeeperConfig::from_env().context("StateKeeperConfig::from_env()")?;
+    let network_config = NetworkConfig::from_env().context("NetworkConfig::from_env()")?;
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let connection_pool = ConnectionPool::builder(
+        postgres_config.replica_url()?,
+        postgres_config.max_connections()?,
+    )
+    .build()
+    .await
+    .context("failed to build a connection pool")?;
+
+    let (io, mut diffs_receiver) = ReplayIO::new(
+        connection_pool.clone(),
+        first_l1_batch,
+        last_l1_batch,
+        state_keeper_config.validation_computational_gas_limit,
+        network_config.zksync_network_id,
+    )
+    .await?;
+    let batch_executor = MainBatchExecutor::new(
+        String::new(), // RocksDB cache isn't used with historical storage
+        connection_pool,
+        state_keeper_config.max_allowed_l2_tx_gas_limit.into(),
+        state_keeper_config.save_call_traces,
+        false,
+        state_keeper_config.enum_index_migration_chunk_size(),
+        false,
+    )
+    .with_historical_storage();
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let state_keeper = ZkSyncStateKeeper::new(
+        stop_receiver,
+        Box::new(io),
+        Box::new(batch_executor),
+        Arc::new(NoopSealer),
+    );
+    let state_keeper_task = tokio::spawn(state_keeper.run());
+
+    let l1_batch_count = (last_l1_batch.0 - first_l1_batch.0 + 1) as usize;
+    let mut replayed_count = 0;
+    let mut mismatched_count = 0;
+    while replayed_count < l1_batch_count {
+        let Some(diff) = diffs_receiver.recv().await else {
+            break; // The state keeper has stopped; its error is returned below
+        };
+        replayed_count += 1;
+        if diff.is_empty() {
+            println!("L1 batch #{}: OK", diff.l1_batch_number);
+        } else {
+            mismatched_count += 1;
+            println!(
+                "L1 batch #{}: {} mismatch(es)",
+                diff.l1_batch_number,
+                diff.mismatches.len()
+            );
+            for mismatch in &diff.mismatches {
+                println!("  - {mismatch}");
+            }
+        }
+    }
+
+    stop_sender.send_replace(true);
+    state_keeper_task
+        .await
+        .context("state keeper panicked")?
+        .context("state keeper failed")?;
+    anyhow::ensure!(
+        replayed_count == l1_batch_count,
+        "State keeper stopped after replaying {replayed_count} out of {l1_batch_count} L1 batches"
+    );
+    anyhow::ensure!(
+        mismatched_count == 0,
+        "{mismatched_count} out of {l1_batch_count} replayed L1 batches differ from the stored ones"
+    );
+    Ok(())
+}
@@ -1,4 +1,10 @@
-use std::{net::SocketAddr, num::NonZeroU32, time::Duration};
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    str::FromStr,
+    time::Duration,
+};
 
 use serde::Deserialize;
 use zksync_basic_types::H256;
@@ -96,6 +102,28 @@ pub struct Web3JsonRpcConfig {
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
+    /// Quota of request weight units per minute for a single API client. The quota is shared by the HTTP and WebSocket servers.
+    /// Clients are identified by an API key from `api_keys` provided in the `api_key_header` header, or by the client IP address
+    /// (see `trusted_proxies`). If not set, per-client quotas are disabled.
+    pub requests_quota_per_minute: Option<NonZeroU32>,
+    /// Weights of RPC methods used for rate limiting, specified as `method=weight` entries. Methods not mentioned
+    /// in this list have the weight of 1.
+    pub method_weights: Option<Vec<MethodWeight>>,
+    /// Name of the HTTP header containing the API key used to identify API clients for per-client quotas.
+    pub api_key_header: Option<String>,
+    /// API keys recognized in the `api_key_header` header. Requests with other keys are identified by the client IP address.
+    pub api_keys: Option<Vec<String>>,
+    /// IP addresses of reverse proxies / load balancers trusted to append the client address to the `X-Forwarded-For` header.
+    /// For requests coming from other peers, the header is ignored and the peer address is used to identify the client.
+    pub trusted_proxies: Option<Vec<IpAddr>>,
+    /// Full names of RPC methods (e.g., `debug_traceTransaction`) allowed to be called. If set and non-empty,
+    /// only the listed methods from the enabled namespaces are exposed.
+    pub method_allowlist: Option<Vec<String>>,
+    /// Full names of RPC methods (e.g., `eth_getLogs`) that are not exposed even if their namespace is enabled.
+    pub method_denylist: Option<Vec<String>>,
+    /// Maximum number of VM instructions recorded by the `structLogger` tracer. Larger `maxSteps` values
+    /// requested by clients are clamped to this value. Default is 100,000.
+    pub struct_log_max_steps: Option<usize>,
 }
 
 impl Web3JsonRpcConfig {
@@ -131,6 +159,14 @@ impl Web3JsonRpcConfig {
             max_response_body_size_mb: Default::default(),
             websocket_requests_per_minute_limit: Default::default(),
             tree_api_url: None,
+            requests_quota_per_minute: None,
+            method_weights: None,
+            api_key_header: None,
+            api_keys: None,
+            trusted_proxies: None,
+            method_allowlist: None,
+            method_denylist: None,
+            struct_log_max_steps: None,
         }
     }
 
@@ -192,6 +228,10 @@ impl Web3JsonRpcConfig {
         self.fee_history_limit.unwrap_or(1024)
     }
 
+    pub fn struct_log_max_steps(&self) -> usize {
+        self.struct_log_max_steps.unwrap_or(100_000)
+    }
+
     pub fn max_batch_request_size(&self) -> usize {
         // The default limit is chosen to be reasonably permissive.
         self.max_batch_request_size.unwrap_or(500)
@@ -210,6 +250,52 @@ impl Web3JsonRpcConfig {
     pub fn tree_api_url(&self) -> Option<String> {
         self.tree_api_url.clone()
     }
+
+    pub fn method_weights(&self) -> Vec<MethodWeight> {
+        self.method_weights.clone().unwrap_or_default()
+    }
+}
+
+/// Weight of an RPC method used for rate limiting. Parsed from a `method=weight` string, e.g. `eth_call=10`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct MethodWeight {
+    pub method: String,
+    pub weight: NonZeroU32,
+}
+
+impl fmt::Display for MethodWeight {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}={}", self.method, self.weight)
+    }
+}
+
+impl FromStr for MethodWeight {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, weight) = s.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("method weight `{s}` is not in the `method=weight` format")
+        })?;
+        let method = method.trim();
+        anyhow::ensure!(!method.is_empty(), "method name in `{s}` is empty");
+        let weight = weight
+            .trim()
+            .parse()
+            .map_err(|err| anyhow::anyhow!("invalid weight in `{s}`: {err}"))?;
+        Ok(Self {
+            method: method.to_owned(),
+            weight,
+        })
+    }
+}
+
+impl TryFrom<String> for MethodWeight {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -1,7 +1,7 @@
-use std::{str::FromStr, time::Duration};
+use std::{fmt, num::NonZeroU32, str::FromStr, time::Duration};
 
 use serde::Deserialize;
-use zksync_basic_types::{network::Network, Address, L2ChainId};
+use zksync_basic_types::{network::Network, Address, L2ChainId, H256};
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct NetworkConfig {
@@ -45,6 +45,116 @@ impl Default for FeeModelVersion {
     }
 }
 
+/// Strategy used by the main node to derive the batch fee input from the L1 prices.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+pub enum FeeInputStrategyKind {
+    /// Fee input is derived from the current L1 prices as defined by the fee model version.
+    #[default]
+    Default,
+    /// L1 prices are smoothed using the exponential moving average before computing the fee input.
+    Ema,
+    /// Part of the L1 pubdata price exceeding the configured threshold is multiplied by the surge multiplier.
+    PubdataSurge,
+}
+
+/// Policy used by the state keeper mempool to order L2 transactions of different accounts.
+/// Transactions of a single account are always ordered by nonce.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+pub enum TxOrderingPolicyKind {
+    /// Transactions are ordered by the time they were received.
+    #[default]
+    Fifo,
+    /// Transactions with higher fees are prioritized.
+    FeePriority,
+    /// Accounts are served in the round-robin fashion.
+    RoundRobin,
+}
+
+/// Additional criterion for sealing L1 batches. Parsed from a `kind=value` string:
+///
+/// - `max_l1_txs=10`: [`Self::MaxL1Txs`]
+/// - `max_batch_age_ms=60000`: [`Self::MaxBatchAge`]
+/// - `event=0x…` or `event=0x…:0x…`: [`Self::Event`] with the emitter address and, optionally, the first topic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum SealCriterionConfig {
+    /// Seals the batch once it contains the specified number of L1 transactions.
+    MaxL1Txs { max_count: NonZeroU32 },
+    /// Seals the batch after a transaction executed when the specified time has passed since the first transaction
+    /// in the batch was received.
+    MaxBatchAge { max_age_ms: u64 },
+    /// Seals the batch after a transaction emitting an event with the specified emitter address
+    /// and, if specified, the first topic.
+    Event {
+        address: Address,
+        topic: Option<H256>,
+    },
+}
+
+impl fmt::Display for SealCriterionConfig {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxL1Txs { max_count } => write!(formatter, "max_l1_txs={max_count}"),
+            Self::MaxBatchAge { max_age_ms } => write!(formatter, "max_batch_age_ms={max_age_ms}"),
+            Self::Event {
+                address,
+                topic: None,
+            } => write!(formatter, "event={address:?}"),
+            Self::Event {
+                address,
+                topic: Some(topic),
+            } => write!(formatter, "event={address:?}:{topic:?}"),
+        }
+    }
+}
+
+impl FromStr for SealCriterionConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("seal criterion `{s}` is not in the `kind=value` format")
+        })?;
+        let value = value.trim();
+        Ok(match kind.trim() {
+            "max_l1_txs" => Self::MaxL1Txs {
+                max_count: value
+                    .parse()
+                    .map_err(|err| anyhow::anyhow!("invalid L1 tx count in `{s}`: {err}"))?,
+            },
+            "max_batch_age_ms" => Self::MaxBatchAge {
+                max_age_ms: value
+                    .parse()
+                    .map_err(|err| anyhow::anyhow!("invalid batch age in `{s}`: {err}"))?,
+            },
+            "event" => {
+                let (address, topic) = match value.split_once(':') {
+                    Some((address, topic)) => (address, Some(topic)),
+                    None => (value, None),
+                };
+                let address = address
+                    .trim()
+                    .parse()
+                    .map_err(|err| anyhow::anyhow!("invalid event address in `{s}`: {err}"))?;
+                let topic = topic
+                    .map(|topic| topic.trim().parse())
+                    .transpose()
+                    .map_err(|err| anyhow::anyhow!("invalid event topic in `{s}`: {err}"))?;
+                Self::Event { address, topic }
+            }
+            other => anyhow::bail!("unknown seal criterion kind `{other}` in `{s}`"),
+        })
+    }
+}
+
+impl TryFrom<String> for SealCriterionConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Default)]
 pub struct StateKeeperConfig {
     /// The max number of slots for txs in a block before it should be sealed by the slots sealer.
@@ -102,6 +212,15 @@ pub struct StateKeeperConfig {
 
     /// The version of the fee model to use.
     pub fee_model_version: FeeModelVersion,
+    /// Strategy used to derive the batch fee input from the L1 prices.
+    #[serde(default)]
+    pub fee_input_strategy: FeeInputStrategyKind,
+    /// Half-life of the exponential moving average used by the `Ema` fee input strategy, in seconds.
+    pub fee_input_ema_half_life_sec: Option<u64>,
+    /// L1 pubdata price (in wei per byte) starting from which the `PubdataSurge` fee input strategy applies the multiplier.
+    pub fee_input_pubdata_surge_threshold: Option<u64>,
+    /// Multiplier applied by the `PubdataSurge` fee input strategy to the part of the L1 pubdata price exceeding the threshold.
+    pub fee_input_pubdata_surge_multiplier: Option<f64>,
 
     /// Max number of computational gas that validation step is allowed to take.
     pub validation_computational_gas_limit: u32,
@@ -116,6 +235,25 @@ pub struct StateKeeperConfig {
 
     /// Number of keys that is processed by enum_index migration in State Keeper each L1 batch.
     pub enum_index_migration_chunk_size: Option<usize>,
+
+    /// Accounts allowed to send L2 transactions. If empty, all accounts not in `tx_sender_denylist` are allowed.
+    #[serde(default)]
+    pub tx_sender_allowlist: Vec<Address>,
+    /// Accounts not allowed to send L2 transactions.
+    #[serde(default)]
+    pub tx_sender_denylist: Vec<Address>,
+    /// Accounts allowed to deploy contracts. If empty, contract deployment is not restricted.
+    /// Only direct calls to the contract deployer are restricted, so this is a best-effort measure;
+    /// deployments via other contracts are not detected.
+    #[serde(default)]
+    pub contract_deployer_allowlist: Vec<Address>,
+    /// Path to a JSON file with additional transaction access lists. The file is periodically reloaded,
+    /// so the lists can be changed without restarting the node.
+    pub tx_access_policy_path: Option<String>,
+
+    /// Additional criteria for sealing L1 batches applied together with the built-in ones.
+    #[serde(default)]
+    pub custom_seal_criteria: Vec<SealCriterionConfig>,
 }
 
 impl StateKeeperConfig {
@@ -144,12 +282,21 @@ impl StateKeeperConfig {
             max_pubdata_per_batch: 100_000,
             minimal_l2_gas_price: 100000000,
             fee_model_version: FeeModelVersion::V2,
+            fee_input_strategy: FeeInputStrategyKind::Default,
+            fee_input_ema_half_life_sec: None,
+            fee_input_pubdata_surge_threshold: None,
+            fee_input_pubdata_surge_multiplier: None,
             validation_computational_gas_limit: 300000,
             save_call_traces: true,
             virtual_blocks_interval: 1,
             virtual_blocks_per_miniblock: 1,
             upload_witness_inputs_to_gcs: false,
             enum_index_migration_chunk_size: None,
+            tx_sender_allowlist: vec![],
+            tx_sender_denylist: vec![],
+            contract_deployer_allowlist: vec![],
+            tx_access_policy_path: None,
+            custom_seal_criteria: vec![],
         }
     }
 
@@ -196,9 +343,43 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Path to the RocksDB directory used to persist mempool snapshots. If not set, the mempool
+    /// is not persisted and is fully reloaded from Postgres on restart.
+    pub snapshot_path: Option<String>,
+    /// Interval between persisting mempool snapshots in milliseconds. The default value is 10 seconds.
+    pub snapshot_interval_ms: Option<u64>,
+    /// Maximum number of pending L2 transactions per account. If not set, the number of pending transactions
+    /// is only limited by the maximum nonce gap accepted by the API server.
+    pub max_pending_txs_per_account: Option<usize>,
+    /// Minimum increase (in percent) of both `max_fee_per_gas` and `max_priority_fee_per_gas` required to replace
+    /// a pending L2 transaction with the same nonce. If not set, pending transactions can be replaced without restrictions.
+    pub replacement_fee_bump_percent: Option<u32>,
+    /// Policy used to order L2 transactions of different accounts.
+    #[serde(default)]
+    pub ordering_policy: TxOrderingPolicyKind,
 }
 
 impl MempoolConfig {
+    const DEFAULT_SNAPSHOT_INTERVAL_MS: u64 = 10_000;
+
+    /// Creates a config object suitable for use in unit tests.
+    /// Values mostly repeat the values used in the localhost environment.
+    pub fn for_tests() -> Self {
+        Self {
+            sync_interval_ms: 10,
+            sync_batch_size: 1_000,
+            capacity: 10_000_000,
+            stuck_tx_timeout: 86_400,
+            remove_stuck_txs: true,
+            delay_interval: 100,
+            snapshot_path: None,
+            snapshot_interval_ms: None,
+            max_pending_txs_per_account: None,
+            replacement_fee_bump_percent: None,
+            ordering_policy: TxOrderingPolicyKind::Fifo,
+        }
+    }
+
     pub fn sync_interval(&self) -> Duration {
         Duration::from_millis(self.sync_interval_ms)
     }
@@ -210,4 +391,11 @@ impl MempoolConfig {
     pub fn delay_interval(&self) -> Duration {
         Duration::from_millis(self.delay_interval)
     }
+
+    pub fn snapshot_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.snapshot_interval_ms
+                .unwrap_or(Self::DEFAULT_SNAPSHOT_INTERVAL_MS),
+        )
+    }
 }
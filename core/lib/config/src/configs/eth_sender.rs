@@ -35,6 +35,7 @@ impl ETHSenderConfig {
                 l1_batch_min_age_before_execute_seconds: None,
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
+                pubdata_sending_mode: PubdataSendingMode::Calldata,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -45,6 +46,10 @@ impl ETHSenderConfig {
                 internal_enforced_l1_gas_price: None,
                 poll_period: 5,
                 max_l1_gas_price: None,
+                max_blob_base_fee_samples: 10,
+                internal_pubdata_pricing_multiplier: 1.0,
+                max_blob_base_fee: None,
+                min_blob_base_fee: None,
             },
         }
     }
@@ -63,6 +68,16 @@ pub enum ProofLoadingMode {
     FriProofFromGcs,
 }
 
+/// Data availability mode used to publish pubdata on L1.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum PubdataSendingMode {
+    /// Pubdata is sent as a part of the commit transaction calldata.
+    #[default]
+    Calldata,
+    /// Pubdata is sent in EIP-4844 blobs.
+    Blobs,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct SenderConfig {
     pub aggregated_proof_sizes: Vec<usize>,
@@ -96,6 +111,10 @@ pub struct SenderConfig {
 
     /// The mode in which proofs are loaded, either from DB/GCS for FRI/Old proof.
     pub proof_loading_mode: ProofLoadingMode,
+
+    /// The mode in which pubdata is sent to L1. Affects the pubdata price estimated by `GasAdjuster`.
+    #[serde(default)]
+    pub pubdata_sending_mode: PubdataSendingMode,
 }
 
 impl SenderConfig {
@@ -142,6 +161,17 @@ pub struct GasAdjusterConfig {
     pub poll_period: u64,
     /// Max number of l1 gas price that is allowed to be used in state keeper.
     pub max_l1_gas_price: Option<u64>,
+    /// Number of blocks collected by GasAdjuster from which `blob_base_fee` median is taken
+    #[serde(default = "GasAdjusterConfig::default_max_blob_base_fee_samples")]
+    pub max_blob_base_fee_samples: usize,
+    /// Parameter by which the pubdata price will be multiplied for internal purposes
+    #[serde(default = "GasAdjusterConfig::default_internal_pubdata_pricing_multiplier")]
+    pub internal_pubdata_pricing_multiplier: f64,
+    /// Max blob base fee that is allowed to be used.
+    pub max_blob_base_fee: Option<u64>,
+    /// Min blob base fee used to price pubdata published in blobs. If not set, the minimum blob base fee
+    /// defined by EIP-4844 (1 wei) is used.
+    pub min_blob_base_fee: Option<u64>,
 }
 
 impl GasAdjusterConfig {
@@ -153,4 +183,20 @@ impl GasAdjusterConfig {
     pub fn max_l1_gas_price(&self) -> u64 {
         self.max_l1_gas_price.unwrap_or(u64::MAX)
     }
+
+    pub fn max_blob_base_fee(&self) -> u64 {
+        self.max_blob_base_fee.unwrap_or(u64::MAX)
+    }
+
+    pub fn min_blob_base_fee(&self) -> u64 {
+        self.min_blob_base_fee.unwrap_or(1)
+    }
+
+    const fn default_max_blob_base_fee_samples() -> usize {
+        10
+    }
+
+    const fn default_internal_pubdata_pricing_multiplier() -> f64 {
+        1.0
+    }
 }
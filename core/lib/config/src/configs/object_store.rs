@@ -7,6 +7,10 @@ pub struct ObjectStoreConfig {
     pub mode: ObjectStoreMode,
     #[serde(default = "ObjectStoreConfig::default_max_retries")]
     pub max_retries: u16,
+    /// Whether to compress stored objects and verify their checksums on reads. Objects stored
+    /// without compression can still be read if this is enabled, but not vice versa.
+    #[serde(default)]
+    pub enable_compression: bool,
 }
 
 impl ObjectStoreConfig {
@@ -31,4 +35,14 @@ pub enum ObjectStoreMode {
     FileBacked {
         file_backed_base_path: String,
     },
+    /// S3-compatible storage (AWS S3, MinIO etc.). Credentials are obtained from the standard AWS sources
+    /// (environment variables, shared config / credential files, instance metadata etc.).
+    S3 {
+        s3_bucket: String,
+        /// AWS region. If not specified, the region is obtained from the standard AWS sources.
+        s3_region: Option<String>,
+        /// Custom endpoint URL, e.g. for a MinIO deployment. If specified, path-style addressing
+        /// is used for objects.
+        s3_endpoint: Option<String>,
+    },
 }
@@ -7,6 +7,12 @@ pub struct SnapshotsCreatorConfig {
 
     #[serde(default = "snapshots_creator_concurrent_queries_count")]
     pub concurrent_queries_count: u32,
+
+    /// Maximum number of consecutive incremental snapshots created on top of a full snapshot.
+    /// An incremental snapshot only contains storage logs and factory deps changed since the previous snapshot.
+    /// If set to 0 (the default), only full snapshots are created.
+    #[serde(default)]
+    pub max_incremental_snapshots: u32,
 }
 
 fn snapshots_creator_storage_logs_chunk_size_default() -> u64 {
@@ -112,6 +112,12 @@ impl RandomConfig for usize {
     }
 }
 
+impl RandomConfig for std::net::IpAddr {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        g.rng.gen::<[u8; 4]>().into()
+    }
+}
+
 impl RandomConfig for std::num::NonZeroU32 {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         g.rng.gen()
@@ -154,6 +160,41 @@ impl RandomConfig for configs::chain::FeeModelVersion {
     }
 }
 
+impl RandomConfig for configs::chain::FeeInputStrategyKind {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..3) {
+            0 => Self::Default,
+            1 => Self::Ema,
+            _ => Self::PubdataSurge,
+        }
+    }
+}
+
+impl RandomConfig for configs::chain::TxOrderingPolicyKind {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..3) {
+            0 => Self::Fifo,
+            1 => Self::FeePriority,
+            _ => Self::RoundRobin,
+        }
+    }
+}
+
+impl RandomConfig for configs::chain::SealCriterionConfig {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..3) {
+            0 => Self::MaxL1Txs { max_count: g.gen() },
+            1 => Self::MaxBatchAge {
+                max_age_ms: g.gen(),
+            },
+            _ => Self::Event {
+                address: g.gen(),
+                topic: g.gen(),
+            },
+        }
+    }
+}
+
 impl RandomConfig for configs::AlertsConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
@@ -204,6 +245,23 @@ impl RandomConfig for configs::api::Web3JsonRpcConfig {
             max_response_body_size_mb: g.gen(),
             websocket_requests_per_minute_limit: g.gen(),
             tree_api_url: g.gen(),
+            requests_quota_per_minute: g.gen(),
+            method_weights: g.gen(),
+            api_key_header: g.gen(),
+            api_keys: g.gen(),
+            trusted_proxies: g.gen(),
+            method_allowlist: g.gen(),
+            method_denylist: g.gen(),
+            struct_log_max_steps: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::api::MethodWeight {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            method: g.gen(),
+            weight: g.gen(),
         }
     }
 }
@@ -276,12 +334,21 @@ impl RandomConfig for configs::chain::StateKeeperConfig {
             max_gas_per_batch: g.gen(),
             max_pubdata_per_batch: g.gen(),
             fee_model_version: g.gen(),
+            fee_input_strategy: g.gen(),
+            fee_input_ema_half_life_sec: g.gen(),
+            fee_input_pubdata_surge_threshold: g.gen(),
+            fee_input_pubdata_surge_multiplier: g.gen(),
             validation_computational_gas_limit: g.gen(),
             save_call_traces: g.gen(),
             virtual_blocks_interval: g.gen(),
             virtual_blocks_per_miniblock: g.gen(),
             upload_witness_inputs_to_gcs: g.gen(),
             enum_index_migration_chunk_size: g.gen(),
+            tx_sender_allowlist: g.gen(),
+            tx_sender_denylist: g.gen(),
+            contract_deployer_allowlist: g.gen(),
+            tx_access_policy_path: g.gen(),
+            custom_seal_criteria: g.gen(),
         }
     }
 }
@@ -314,6 +381,11 @@ impl RandomConfig for configs::chain::MempoolConfig {
             stuck_tx_timeout: g.gen(),
             remove_stuck_txs: g.gen(),
             delay_interval: g.gen(),
+            snapshot_path: g.gen(),
+            snapshot_interval_ms: g.gen(),
+            max_pending_txs_per_account: g.gen(),
+            replacement_fee_bump_percent: g.gen(),
+            ordering_policy: g.gen(),
         }
     }
 }
@@ -461,6 +533,15 @@ impl RandomConfig for configs::eth_sender::ProofLoadingMode {
     }
 }
 
+impl RandomConfig for configs::eth_sender::PubdataSendingMode {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..2) {
+            0 => Self::Calldata,
+            _ => Self::Blobs,
+        }
+    }
+}
+
 impl RandomConfig for configs::eth_sender::SenderConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
@@ -481,6 +562,7 @@ impl RandomConfig for configs::eth_sender::SenderConfig {
             l1_batch_min_age_before_execute_seconds: g.gen(),
             max_acceptable_priority_fee_in_gwei: g.gen(),
             proof_loading_mode: g.gen(),
+            pubdata_sending_mode: g.gen(),
         }
     }
 }
@@ -496,6 +578,10 @@ impl RandomConfig for configs::eth_sender::GasAdjusterConfig {
             internal_enforced_l1_gas_price: g.gen(),
             poll_period: g.gen(),
             max_l1_gas_price: g.gen(),
+            max_blob_base_fee_samples: g.gen(),
+            internal_pubdata_pricing_multiplier: g.gen(),
+            max_blob_base_fee: g.gen(),
+            min_blob_base_fee: g.gen(),
         }
     }
 }
@@ -645,7 +731,7 @@ impl RandomConfig for configs::house_keeper::HouseKeeperConfig {
 
 impl RandomConfig for configs::object_store::ObjectStoreMode {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
-        match g.rng.gen_range(0..4) {
+        match g.rng.gen_range(0..5) {
             0 => Self::GCS {
                 bucket_base_url: g.gen(),
             },
@@ -656,6 +742,11 @@ impl RandomConfig for configs::object_store::ObjectStoreMode {
             2 => Self::FileBacked {
                 file_backed_base_path: g.gen(),
             },
+            3 => Self::S3 {
+                s3_bucket: g.gen(),
+                s3_region: g.gen(),
+                s3_endpoint: g.gen(),
+            },
             _ => Self::GCSAnonymousReadOnly {
                 bucket_base_url: g.gen(),
             },
@@ -668,6 +759,7 @@ impl RandomConfig for configs::ObjectStoreConfig {
         Self {
             mode: g.gen(),
             max_retries: g.gen(),
+            enable_compression: g.gen(),
         }
     }
 }
@@ -697,6 +789,7 @@ impl RandomConfig for configs::SnapshotsCreatorConfig {
         Self {
             storage_logs_chunk_size: g.gen(),
             concurrent_queries_count: g.gen(),
+            max_incremental_snapshots: g.gen(),
         }
     }
 }
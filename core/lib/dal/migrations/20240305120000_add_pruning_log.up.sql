@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS pruning_log
+(
+    pruned_l1_batch  BIGINT    NOT NULL,
+    pruned_miniblock BIGINT    NOT NULL,
+    -- Either 'Soft' (data is no longer served by the API, but is not removed yet) or 'Hard' (data is removed).
+    type             TEXT      NOT NULL,
+
+    created_at       TIMESTAMP NOT NULL,
+    updated_at       TIMESTAMP NOT NULL,
+    PRIMARY KEY (type, pruned_l1_batch)
+);
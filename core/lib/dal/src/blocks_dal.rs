@@ -12,6 +12,7 @@ use zksync_types::{
     block::{BlockGasCount, L1BatchHeader, L1BatchTreeData, MiniblockHeader},
     circuit::CircuitStatistic,
     commitment::{L1BatchCommitmentArtifacts, L1BatchWithMetadata},
+    fee_model::L1BatchFeeStats,
     zk_evm_types::LogQuery,
     Address, L1BatchNumber, MiniblockNumber, ProtocolVersionId, H256, U256,
 };
@@ -1892,6 +1893,88 @@ impl BlocksDal<'_, '_> {
             .context("Sum of predicted gas costs should fit into u32")
     }
 
+    /// Returns fee-related stats for L1 batches in the specified range, ordered by the batch number.
+    /// Stats are computed based on the execution info of the transactions in each batch.
+    pub async fn get_l1_batch_fee_stats(
+        &mut self,
+        number_range: ops::RangeInclusive<L1BatchNumber>,
+    ) -> sqlx::Result<Vec<L1BatchFeeStats>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.number,
+                l1_batches.timestamp,
+                COALESCE(
+                    SUM((transactions.execution_info ->> 'computational_gas_used')::BIGINT),
+                    0
+                )::BIGINT AS "computational_gas_used!",
+                COALESCE(
+                    SUM((transactions.execution_info ->> 'pubdata_published')::BIGINT),
+                    0
+                )::BIGINT AS "pubdata_published!"
+            FROM
+                l1_batches
+                LEFT JOIN transactions ON transactions.l1_batch_number = l1_batches.number
+            WHERE
+                l1_batches.number BETWEEN $1 AND $2
+            GROUP BY
+                l1_batches.number
+            ORDER BY
+                l1_batches.number
+            "#,
+            number_range.start().0 as i64,
+            number_range.end().0 as i64
+        )
+        .instrument("get_l1_batch_fee_stats")
+        .with_arg("number_range", &number_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchFeeStats {
+                number: L1BatchNumber(row.number as u32),
+                timestamp: row.timestamp as u64,
+                computational_gas_used: row.computational_gas_used as u64,
+                pubdata_published: row.pubdata_published as u64,
+            })
+            .collect())
+    }
+
+    /// Returns L1 gas prices used by the state keeper for L1 batches in the specified range, as
+    /// `(timestamp, l1_gas_price)` tuples ordered by L1 batch number. Prices are taken from the first miniblock
+    /// in each batch and include the L1 gas price scale factor applied by the state keeper.
+    pub async fn get_l1_gas_prices_for_l1_batches(
+        &mut self,
+        number_range: ops::RangeInclusive<L1BatchNumber>,
+    ) -> sqlx::Result<Vec<(u64, u64)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                ON (l1_batch_number) timestamp,
+                l1_gas_price
+            FROM
+                miniblocks
+            WHERE
+                l1_batch_number BETWEEN $1 AND $2
+            ORDER BY
+                l1_batch_number,
+                number
+            "#,
+            number_range.start().0 as i64,
+            number_range.end().0 as i64
+        )
+        .instrument("get_l1_gas_prices_for_l1_batches")
+        .with_arg("number_range", &number_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.timestamp as u64, row.l1_gas_price as u64))
+            .collect())
+    }
+
     pub async fn get_miniblock_range_of_l1_batch(
         &mut self,
         l1_batch_number: L1BatchNumber,
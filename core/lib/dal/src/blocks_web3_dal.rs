@@ -22,7 +22,8 @@ use crate::{
     StorageProcessor,
 };
 
-const BLOCK_GAS_LIMIT: u32 = u32::MAX;
+/// Gas limit reported for miniblocks in the Web3 API.
+pub const BLOCK_GAS_LIMIT: u32 = u32::MAX;
 
 #[derive(Debug)]
 pub struct BlocksWeb3Dal<'a, 'c> {
@@ -474,6 +475,39 @@ impl BlocksWeb3Dal<'_, '_> {
         .collect())
     }
 
+    /// Same as [`Self::get_traces_for_miniblock()`], but also returns hashes of the traced transactions.
+    /// Transactions without a persisted call trace are skipped.
+    pub async fn get_traces_with_hashes_for_miniblock(
+        &mut self,
+        block_number: MiniblockNumber,
+    ) -> sqlx::Result<Vec<(H256, Call)>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                transactions.hash AS tx_hash,
+                call_trace
+            FROM
+                call_traces
+                INNER JOIN transactions ON tx_hash = transactions.hash
+            WHERE
+                transactions.miniblock_number = $1
+            ORDER BY
+                transactions.index_in_block
+            "#,
+            block_number.0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?
+        .into_iter()
+        .map(|row| {
+            let call_trace = CallTrace {
+                call_trace: row.call_trace,
+            };
+            (H256::from_slice(&row.tx_hash), Call::from(call_trace))
+        })
+        .collect())
+    }
+
     /// Returns `base_fee_per_gas` for miniblock range [min(newest_block - block_count + 1, 0), newest_block]
     /// in descending order of miniblock numbers.
     pub async fn get_fee_history(
@@ -901,5 +935,16 @@ mod tests {
             let expected_trace = tx_result.call_trace().unwrap();
             assert_eq!(*trace, expected_trace);
         }
+
+        let traces = conn
+            .blocks_web3_dal()
+            .get_traces_with_hashes_for_miniblock(MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(traces.len(), 2);
+        for ((tx_hash, trace), tx_result) in traces.iter().zip(&tx_results) {
+            assert_eq!(*tx_hash, tx_result.hash);
+            assert_eq!(*trace, tx_result.call_trace().unwrap());
+        }
     }
 }
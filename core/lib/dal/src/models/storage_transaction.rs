@@ -10,18 +10,20 @@ use sqlx::{
 use zksync_types::{
     api,
     api::{TransactionDetails, TransactionReceipt, TransactionStatus},
-    fee::Fee,
+    fee::{Fee, FeeBreakdown},
     l1::{OpProcessingType, PriorityQueueType},
     l2::TransactionType,
     protocol_version::ProtocolUpgradeTxCommonData,
     transaction_request::PaymasterParams,
+    tx::ExecutionMetrics,
     vm_trace::Call,
     web3::types::U64,
     Address, Bytes, Execute, ExecuteTransactionCommon, L1TxCommonData, L2ChainId, L2TxCommonData,
-    Nonce, PackedEthSignature, PriorityOpId, Transaction, EIP_1559_TX_TYPE, EIP_2930_TX_TYPE,
-    EIP_712_TX_TYPE, H160, H256, PRIORITY_OPERATION_L2_TX_TYPE, PROTOCOL_UPGRADE_TX_TYPE, U256,
+    Nonce, PackedEthSignature, PriorityOpId, ProtocolVersionId, Transaction, EIP_1559_TX_TYPE,
+    EIP_2930_TX_TYPE, EIP_712_TX_TYPE, H160, H256, PRIORITY_OPERATION_L2_TX_TYPE,
+    PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
-use zksync_utils::{bigdecimal_to_u256, h256_to_account_address};
+use zksync_utils::{bigdecimal_to_u256, ceil_div_u256, h256_to_account_address};
 
 use crate::BigDecimal;
 
@@ -339,6 +341,63 @@ pub(crate) struct StorageTransactionReceipt {
     pub effective_gas_price: Option<BigDecimal>,
     pub contract_address: Option<Vec<u8>>,
     pub initiator_address: Vec<u8>,
+    pub execution_info: serde_json::Value,
+    pub fair_pubdata_price: Option<i64>,
+    pub protocol_version: Option<i32>,
+}
+
+/// Subset of [`ExecutionMetrics`] persisted in `transactions.execution_info` that is necessary
+/// to compute [`FeeBreakdown`] for a receipt.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct StoragePubdataMetrics {
+    published_bytecode_bytes: usize,
+    l2_l1_long_messages: usize,
+    l2_to_l1_logs: usize,
+    pubdata_published: u32,
+    // Only persisted by newer state keeper versions.
+    initial_storage_writes: Option<u64>,
+    repeated_storage_writes: Option<u64>,
+}
+
+impl StorageTransactionReceipt {
+    fn fee_breakdown(&self, gas_used: U256, effective_gas_price: U256) -> Option<FeeBreakdown> {
+        let fair_pubdata_price = self.fair_pubdata_price?;
+        if effective_gas_price.is_zero() {
+            return None;
+        }
+        let protocol_version = self
+            .protocol_version
+            .map_or(Some(ProtocolVersionId::last_potentially_undefined()), |v| {
+                ProtocolVersionId::try_from(v as u16).ok()
+            })?;
+        // VMs before the refunds enhancement don't track published pubdata, so it's recorded as 0.
+        if protocol_version < ProtocolVersionId::Version16 {
+            return None;
+        }
+        let metrics = StoragePubdataMetrics::deserialize(&self.execution_info).ok()?;
+        let execution_metrics = ExecutionMetrics {
+            published_bytecode_bytes: metrics.published_bytecode_bytes,
+            l2_l1_long_messages: metrics.l2_l1_long_messages,
+            l2_to_l1_logs: metrics.l2_to_l1_logs,
+            pubdata_published: metrics.pubdata_published,
+            ..ExecutionMetrics::default()
+        };
+        let gas_per_pubdata =
+            ceil_div_u256(U256::from(fair_pubdata_price as u64), effective_gas_price);
+        let breakdown = FeeBreakdown::new(gas_used, gas_per_pubdata, &execution_metrics);
+        Some(
+            match (
+                metrics.initial_storage_writes,
+                metrics.repeated_storage_writes,
+            ) {
+                (Some(initial_writes), Some(repeated_writes)) => {
+                    breakdown.with_storage_writes(initial_writes, repeated_writes)
+                }
+                _ => breakdown,
+            },
+        )
+    }
 }
 
 impl From<StorageTransactionReceipt> for TransactionReceipt {
@@ -352,6 +411,19 @@ impl From<StorageTransactionReceipt> for TransactionReceipt {
             .index_in_block
             .map_or_else(Default::default, U64::from);
 
+        let gas_used = storage_receipt.gas_limit.clone().map(|val| {
+            let gas_limit = bigdecimal_to_u256(val);
+            let refunded_gas: U256 = storage_receipt.refunded_gas.into();
+            gas_limit - refunded_gas
+        });
+        let effective_gas_price = storage_receipt
+            .effective_gas_price
+            .clone()
+            .map(bigdecimal_to_u256)
+            .unwrap_or_default();
+        let fee_breakdown = gas_used
+            .and_then(|gas_used| storage_receipt.fee_breakdown(gas_used, effective_gas_price));
+
         let block_hash = H256::from_slice(&storage_receipt.block_hash);
         TransactionReceipt {
             transaction_hash: H256::from_slice(&storage_receipt.tx_hash),
@@ -371,19 +443,8 @@ impl From<StorageTransactionReceipt> for TransactionReceipt {
                 // For better compatibility with various clients, we never return null.
                 .or_else(|| Some(Address::default())),
             cumulative_gas_used: Default::default(), // TODO: Should be actually calculated (SMA-1183).
-            gas_used: {
-                let refunded_gas: U256 = storage_receipt.refunded_gas.into();
-                storage_receipt.gas_limit.map(|val| {
-                    let gas_limit = bigdecimal_to_u256(val);
-                    gas_limit - refunded_gas
-                })
-            },
-            effective_gas_price: Some(
-                storage_receipt
-                    .effective_gas_price
-                    .map(bigdecimal_to_u256)
-                    .unwrap_or_default(),
-            ),
+            gas_used,
+            effective_gas_price: Some(effective_gas_price),
             contract_address: storage_receipt
                 .contract_address
                 .map(|addr| h256_to_account_address(&H256::from_slice(&addr))),
@@ -395,6 +456,7 @@ impl From<StorageTransactionReceipt> for TransactionReceipt {
             // Even though the Rust SDK recommends us to supply "None" for legacy transactions
             // we always supply some number anyway to have the same behavior as most popular RPCs
             transaction_type: Some(tx_type),
+            fee_breakdown,
         }
     }
 }
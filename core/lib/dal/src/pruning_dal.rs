@@ -0,0 +1,550 @@
+use zksync_types::{L1BatchNumber, MiniblockNumber};
+
+use crate::{instrument::InstrumentExt, StorageProcessor};
+
+#[derive(Debug)]
+pub struct PruningDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+/// Information about Postgres data pruning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruningInfo {
+    /// Last soft-pruned L1 batch. Data for this batch and all earlier batches must not be served by the API,
+    /// but may still be present in the storage.
+    pub last_soft_pruned_l1_batch: Option<L1BatchNumber>,
+    pub last_soft_pruned_miniblock: Option<MiniblockNumber>,
+    /// Last hard-pruned L1 batch. Data for this batch and all earlier batches is removed from the storage.
+    pub last_hard_pruned_l1_batch: Option<L1BatchNumber>,
+    pub last_hard_pruned_miniblock: Option<MiniblockNumber>,
+}
+
+/// Statistics about a single hard pruning operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HardPruningStats {
+    pub deleted_l1_batches: u64,
+    pub deleted_miniblocks: u64,
+    pub deleted_transactions: u64,
+    pub deleted_events: u64,
+    pub deleted_l2_to_l1_logs: u64,
+    pub deleted_storage_logs_from_past_batches: u64,
+    pub deleted_storage_logs_from_pruned_batches: u64,
+}
+
+impl PruningDal<'_, '_> {
+    pub async fn get_pruning_info(&mut self) -> sqlx::Result<PruningInfo> {
+        let row = sqlx::query!(
+            r#"
+            WITH
+                soft AS (
+                    SELECT
+                        pruned_l1_batch,
+                        pruned_miniblock
+                    FROM
+                        pruning_log
+                    WHERE
+                        TYPE = 'Soft'
+                    ORDER BY
+                        pruned_l1_batch DESC
+                    LIMIT
+                        1
+                ),
+                hard AS (
+                    SELECT
+                        pruned_l1_batch,
+                        pruned_miniblock
+                    FROM
+                        pruning_log
+                    WHERE
+                        TYPE = 'Hard'
+                    ORDER BY
+                        pruned_l1_batch DESC
+                    LIMIT
+                        1
+                )
+            SELECT
+                soft.pruned_l1_batch AS last_soft_pruned_l1_batch,
+                soft.pruned_miniblock AS last_soft_pruned_miniblock,
+                hard.pruned_l1_batch AS last_hard_pruned_l1_batch,
+                hard.pruned_miniblock AS last_hard_pruned_miniblock
+            FROM
+                soft
+                FULL JOIN hard ON TRUE
+            "#
+        )
+        .instrument("get_pruning_info")
+        .fetch_optional(self.storage)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(PruningInfo::default());
+        };
+        Ok(PruningInfo {
+            last_soft_pruned_l1_batch: row
+                .last_soft_pruned_l1_batch
+                .map(|number| L1BatchNumber(number as u32)),
+            last_soft_pruned_miniblock: row
+                .last_soft_pruned_miniblock
+                .map(|number| MiniblockNumber(number as u32)),
+            last_hard_pruned_l1_batch: row
+                .last_hard_pruned_l1_batch
+                .map(|number| L1BatchNumber(number as u32)),
+            last_hard_pruned_miniblock: row
+                .last_hard_pruned_miniblock
+                .map(|number| MiniblockNumber(number as u32)),
+        })
+    }
+
+    /// Marks all data up to and including the specified L1 batch / miniblock as soft-pruned, i.e., not served
+    /// by the API.
+    pub async fn soft_prune_batches_range(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                pruning_log (
+                    pruned_l1_batch,
+                    pruned_miniblock,
+                    TYPE,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, 'Soft', NOW(), NOW())
+            "#,
+            i64::from(last_l1_batch_to_prune.0),
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("soft_prune_batches_range")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes all data up to and including the specified L1 batch / miniblock. Storage logs are pruned
+    /// so that the latest value for each storage slot is retained; initial writes and factory deps are retained
+    /// as well, since they are necessary for executing new transactions.
+    ///
+    /// This method should be called in a DB transaction.
+    pub async fn hard_prune_batches_range(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<HardPruningStats> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(number) AS first_miniblock_to_prune
+            FROM
+                miniblocks
+            WHERE
+                number <= $1
+            "#,
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#get_first_miniblock")
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .fetch_one(self.storage)
+        .await?;
+
+        let mut stats = HardPruningStats::default();
+        // There may be no miniblocks to prune if the node was recovered from a snapshot.
+        if let Some(first_miniblock_to_prune) = row.first_miniblock_to_prune {
+            let first_miniblock_to_prune = MiniblockNumber(first_miniblock_to_prune as u32);
+            stats.deleted_storage_logs_from_past_batches = self
+                .prune_storage_logs_from_past_miniblocks(
+                    first_miniblock_to_prune,
+                    last_miniblock_to_prune,
+                )
+                .await?;
+            stats.deleted_storage_logs_from_pruned_batches = self
+                .prune_storage_logs_in_range(first_miniblock_to_prune, last_miniblock_to_prune)
+                .await?;
+            stats.deleted_events = self.delete_events(last_miniblock_to_prune).await?;
+            stats.deleted_l2_to_l1_logs =
+                self.delete_l2_to_l1_logs(last_miniblock_to_prune).await?;
+            stats.deleted_transactions = self.delete_transactions(last_miniblock_to_prune).await?;
+            stats.deleted_miniblocks = self.delete_miniblocks(last_miniblock_to_prune).await?;
+        }
+        stats.deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
+
+        self.insert_hard_pruning_log(last_l1_batch_to_prune, last_miniblock_to_prune)
+            .await?;
+        Ok(stats)
+    }
+
+    /// Removes storage logs from miniblocks preceding the pruned range that are overwritten
+    /// in the pruned range.
+    async fn prune_storage_logs_from_past_miniblocks(
+        &mut self,
+        first_miniblock_to_prune: MiniblockNumber,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM storage_logs
+            WHERE
+                storage_logs.miniblock_number < $1
+                AND hashed_key IN (
+                    SELECT
+                        hashed_key
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                )
+            "#,
+            i64::from(first_miniblock_to_prune.0),
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#prune_storage_logs_from_past_miniblocks")
+        .with_arg("first_miniblock_to_prune", &first_miniblock_to_prune)
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    /// Removes all storage logs in the pruned range except for the latest log for each storage slot.
+    async fn prune_storage_logs_in_range(
+        &mut self,
+        first_miniblock_to_prune: MiniblockNumber,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM storage_logs USING (
+                SELECT
+                    hashed_key,
+                    MAX(ARRAY[miniblock_number, operation_number::BIGINT]) AS op
+                FROM
+                    storage_logs
+                WHERE
+                    miniblock_number BETWEEN $1 AND $2
+                GROUP BY
+                    hashed_key
+            ) AS last_storage_logs
+            WHERE
+                storage_logs.miniblock_number BETWEEN $1 AND $2
+                AND last_storage_logs.hashed_key = storage_logs.hashed_key
+                AND (
+                    storage_logs.miniblock_number != last_storage_logs.op[1]
+                    OR storage_logs.operation_number != last_storage_logs.op[2]
+                )
+            "#,
+            i64::from(first_miniblock_to_prune.0),
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#prune_storage_logs_in_range")
+        .with_arg("first_miniblock_to_prune", &first_miniblock_to_prune)
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    async fn delete_events(
+        &mut self,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM events
+            WHERE
+                miniblock_number <= $1
+            "#,
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#delete_events")
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    async fn delete_l2_to_l1_logs(
+        &mut self,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM l2_to_l1_logs
+            WHERE
+                miniblock_number <= $1
+            "#,
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#delete_l2_to_l1_logs")
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    /// Deletes executed transactions. Call traces are removed automatically since they reference transactions.
+    async fn delete_transactions(
+        &mut self,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                miniblock_number <= $1
+            "#,
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#delete_transactions")
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    async fn delete_miniblocks(
+        &mut self,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM miniblocks
+            WHERE
+                number <= $1
+            "#,
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#delete_miniblocks")
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    async fn delete_l1_batches(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+    ) -> sqlx::Result<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM l1_batches
+            WHERE
+                number <= $1
+            "#,
+            i64::from(last_l1_batch_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#delete_l1_batches")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    async fn insert_hard_pruning_log(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_miniblock_to_prune: MiniblockNumber,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                pruning_log (
+                    pruned_l1_batch,
+                    pruned_miniblock,
+                    TYPE,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, 'Hard', NOW(), NOW())
+            "#,
+            i64::from(last_l1_batch_to_prune.0),
+            i64::from(last_miniblock_to_prune.0)
+        )
+        .instrument("hard_prune_batches_range#insert_hard_pruning_log")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("last_miniblock_to_prune", &last_miniblock_to_prune)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{
+        block::L1BatchHeader, AccountTreeId, Address, ProtocolVersion, ProtocolVersionId,
+        StorageKey, StorageLog, H256,
+    };
+
+    use super::*;
+    use crate::{tests::create_miniblock_header, ConnectionPool};
+
+    async fn insert_l1_batch(conn: &mut StorageProcessor<'_>, number: u32, logs: Vec<StorageLog>) {
+        let header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            number.into(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::default(),
+        );
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(number))
+            .await
+            .unwrap();
+        conn.storage_logs_dal()
+            .insert_storage_logs(MiniblockNumber(number), &[(H256::zero(), logs)])
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+    }
+
+    async fn storage_log_values(
+        conn: &mut StorageProcessor<'_>,
+        miniblock_number: u32,
+    ) -> Vec<H256> {
+        let logs = conn
+            .storage_logs_dal()
+            .get_miniblock_storage_logs_from_table(
+                MiniblockNumber(miniblock_number),
+                "storage_logs",
+            )
+            .await;
+        logs.into_iter().map(|(_, value, _)| value).collect()
+    }
+
+    #[tokio::test]
+    async fn pruning_basics() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        let account = AccountTreeId::new(Address::repeat_byte(1));
+        let first_key = StorageKey::new(account, H256::zero());
+        let second_key = StorageKey::new(account, H256::from_low_u64_be(1));
+        let third_key = StorageKey::new(account, H256::from_low_u64_be(2));
+        insert_l1_batch(&mut conn, 0, vec![]).await;
+        insert_l1_batch(
+            &mut conn,
+            1,
+            vec![
+                StorageLog::new_write_log(first_key, H256::repeat_byte(1)),
+                StorageLog::new_write_log(second_key, H256::repeat_byte(2)),
+            ],
+        )
+        .await;
+        insert_l1_batch(
+            &mut conn,
+            2,
+            vec![
+                StorageLog::new_write_log(first_key, H256::repeat_byte(3)),
+                StorageLog::new_write_log(first_key, H256::repeat_byte(4)),
+            ],
+        )
+        .await;
+        insert_l1_batch(
+            &mut conn,
+            3,
+            vec![
+                StorageLog::new_write_log(second_key, H256::repeat_byte(5)),
+                StorageLog::new_write_log(third_key, H256::repeat_byte(6)),
+            ],
+        )
+        .await;
+
+        let pruning_info = conn.pruning_dal().get_pruning_info().await.unwrap();
+        assert_eq!(pruning_info, PruningInfo::default());
+
+        conn.pruning_dal()
+            .soft_prune_batches_range(L1BatchNumber(2), MiniblockNumber(2))
+            .await
+            .unwrap();
+        let pruning_info = conn.pruning_dal().get_pruning_info().await.unwrap();
+        assert_eq!(
+            pruning_info,
+            PruningInfo {
+                last_soft_pruned_l1_batch: Some(L1BatchNumber(2)),
+                last_soft_pruned_miniblock: Some(MiniblockNumber(2)),
+                last_hard_pruned_l1_batch: None,
+                last_hard_pruned_miniblock: None,
+            }
+        );
+
+        let stats = conn
+            .pruning_dal()
+            .hard_prune_batches_range(L1BatchNumber(2), MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(stats.deleted_l1_batches, 3);
+        assert_eq!(stats.deleted_miniblocks, 3);
+        assert_eq!(stats.deleted_storage_logs_from_past_batches, 0);
+        assert_eq!(stats.deleted_storage_logs_from_pruned_batches, 2);
+
+        let pruning_info = conn.pruning_dal().get_pruning_info().await.unwrap();
+        assert_eq!(
+            pruning_info.last_hard_pruned_l1_batch,
+            Some(L1BatchNumber(2))
+        );
+        assert_eq!(
+            pruning_info.last_hard_pruned_miniblock,
+            Some(MiniblockNumber(2))
+        );
+
+        // Only the latest values for each key must be retained.
+        assert_eq!(
+            storage_log_values(&mut conn, 1).await,
+            [H256::repeat_byte(2)]
+        );
+        assert_eq!(
+            storage_log_values(&mut conn, 2).await,
+            [H256::repeat_byte(4)]
+        );
+        assert_eq!(storage_log_values(&mut conn, 3).await.len(), 2);
+        let value = conn.storage_web3_dal().get_value(&first_key).await.unwrap();
+        assert_eq!(value, H256::repeat_byte(4));
+
+        let earliest_l1_batch = conn
+            .blocks_dal()
+            .get_earliest_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(earliest_l1_batch, Some(L1BatchNumber(3)));
+
+        // Prune the remaining batch; the storage log for `second_key` from the past batches must be removed.
+        let stats = conn
+            .pruning_dal()
+            .hard_prune_batches_range(L1BatchNumber(3), MiniblockNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(stats.deleted_storage_logs_from_past_batches, 1);
+        assert_eq!(stats.deleted_storage_logs_from_pruned_batches, 0);
+        assert!(storage_log_values(&mut conn, 1).await.is_empty());
+        let value = conn
+            .storage_web3_dal()
+            .get_value(&second_key)
+            .await
+            .unwrap();
+        assert_eq!(value, H256::repeat_byte(5));
+    }
+}
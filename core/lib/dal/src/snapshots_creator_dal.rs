@@ -1,3 +1,5 @@
+use std::ops;
+
 use zksync_types::{
     snapshots::SnapshotStorageLog, AccountTreeId, Address, L1BatchNumber, MiniblockNumber,
     StorageKey, H256,
@@ -42,7 +44,18 @@ impl SnapshotsCreatorDal<'_, '_> {
     pub async fn get_storage_logs_chunk(
         &mut self,
         miniblock_number: MiniblockNumber,
-        hashed_keys_range: std::ops::RangeInclusive<H256>,
+        hashed_keys_range: ops::RangeInclusive<H256>,
+    ) -> sqlx::Result<Vec<SnapshotStorageLog>> {
+        self.get_modified_storage_logs_chunk(MiniblockNumber(0)..=miniblock_number, hashed_keys_range)
+            .await
+    }
+
+    /// Returns the latest values (as of the end of `miniblock_range`) of storage logs with hashed keys
+    /// in `hashed_keys_range` that were modified in `miniblock_range`. Used to create incremental snapshots.
+    pub async fn get_modified_storage_logs_chunk(
+        &mut self,
+        miniblock_range: ops::RangeInclusive<MiniblockNumber>,
+        hashed_keys_range: ops::RangeInclusive<H256>,
     ) -> sqlx::Result<Vec<SnapshotStorageLog>> {
         let storage_logs = sqlx::query!(
             r#"
@@ -61,9 +74,9 @@ impl SnapshotsCreatorDal<'_, '_> {
                     FROM
                         storage_logs
                     WHERE
-                        miniblock_number <= $1
-                        AND hashed_key >= $2
-                        AND hashed_key < $3
+                        miniblock_number BETWEEN $1 AND $2
+                        AND hashed_key >= $3
+                        AND hashed_key < $4
                     GROUP BY
                         hashed_key
                     ORDER BY
@@ -74,12 +87,13 @@ impl SnapshotsCreatorDal<'_, '_> {
                 AND storage_logs.operation_number = keys.op[2]
                 INNER JOIN initial_writes ON keys.hashed_key = initial_writes.hashed_key;
             "#,
-            miniblock_number.0 as i64,
+            miniblock_range.start().0 as i64,
+            miniblock_range.end().0 as i64,
             hashed_keys_range.start().0.as_slice(),
             hashed_keys_range.end().0.as_slice(),
         )
         .instrument("get_storage_logs_chunk")
-        .with_arg("miniblock_number", &miniblock_number)
+        .with_arg("miniblock_range", &miniblock_range)
         .with_arg("min_hashed_key", &hashed_keys_range.start())
         .with_arg("max_hashed_key", &hashed_keys_range.end())
         .report_latency()
@@ -103,6 +117,15 @@ impl SnapshotsCreatorDal<'_, '_> {
     pub async fn get_all_factory_deps(
         &mut self,
         miniblock_number: MiniblockNumber,
+    ) -> sqlx::Result<Vec<(H256, Vec<u8>)>> {
+        self.get_factory_deps_in_range(MiniblockNumber(0)..=miniblock_number)
+            .await
+    }
+
+    /// Returns factory dependencies added in the specified miniblock range.
+    pub async fn get_factory_deps_in_range(
+        &mut self,
+        miniblock_range: ops::RangeInclusive<MiniblockNumber>,
     ) -> sqlx::Result<Vec<(H256, Vec<u8>)>> {
         let rows = sqlx::query!(
             r#"
@@ -112,11 +135,13 @@ impl SnapshotsCreatorDal<'_, '_> {
             FROM
                 factory_deps
             WHERE
-                miniblock_number <= $1
+                miniblock_number BETWEEN $1 AND $2
             "#,
-            miniblock_number.0 as i64,
+            miniblock_range.start().0 as i64,
+            miniblock_range.end().0 as i64,
         )
-        .instrument("get_all_factory_deps")
+        .instrument("get_factory_deps_in_range")
+        .with_arg("miniblock_range", &miniblock_range)
         .report_latency()
         .fetch_all(self.storage)
         .await?;
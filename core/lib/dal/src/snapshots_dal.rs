@@ -8,6 +8,7 @@ use crate::{instrument::InstrumentExt, StorageProcessor};
 #[derive(Debug, sqlx::FromRow)]
 struct StorageSnapshotMetadata {
     l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
     storage_logs_filepaths: Vec<String>,
     factory_deps_filepath: String,
 }
@@ -16,6 +17,9 @@ impl From<StorageSnapshotMetadata> for SnapshotMetadata {
     fn from(row: StorageSnapshotMetadata) -> Self {
         Self {
             l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            base_l1_batch_number: row
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
             storage_logs_filepaths: row
                 .storage_logs_filepaths
                 .into_iter()
@@ -32,9 +36,12 @@ pub struct SnapshotsDal<'a, 'c> {
 }
 
 impl SnapshotsDal<'_, '_> {
+    /// Adds a new snapshot. If `base_l1_batch_number` is specified, the snapshot is incremental, i.e.,
+    /// it only contains data changed since the snapshot at this L1 batch.
     pub async fn add_snapshot(
         &mut self,
         l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
         storage_logs_chunk_count: u64,
         factory_deps_filepaths: &str,
     ) -> sqlx::Result<()> {
@@ -43,15 +50,17 @@ impl SnapshotsDal<'_, '_> {
             INSERT INTO
                 snapshots (
                     l1_batch_number,
+                    base_l1_batch_number,
                     storage_logs_filepaths,
                     factory_deps_filepath,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, ARRAY_FILL(''::TEXT, ARRAY[$2::INTEGER]), $3, NOW(), NOW())
+                ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, NOW(), NOW())
             "#,
             l1_batch_number.0 as i32,
+            base_l1_batch_number.map(|number| i64::from(number.0)),
             storage_logs_chunk_count as i32,
             factory_deps_filepaths,
         )
@@ -121,6 +130,7 @@ impl SnapshotsDal<'_, '_> {
             r#"
             SELECT
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             FROM
@@ -148,6 +158,7 @@ impl SnapshotsDal<'_, '_> {
             r#"
             SELECT
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             FROM
@@ -178,7 +189,7 @@ mod tests {
         let mut conn = pool.access_storage().await.unwrap();
         let mut dal = conn.snapshots_dal();
         let l1_batch_number = L1BatchNumber(100);
-        dal.add_snapshot(l1_batch_number, 2, "gs:///bucket/factory_deps.bin")
+        dal.add_snapshot(l1_batch_number, None, 2, "gs:///bucket/factory_deps.bin")
             .await
             .expect("Failed to add snapshot");
 
@@ -210,6 +221,36 @@ mod tests {
             .expect("Failed to retrieve snapshot")
             .unwrap();
         assert_eq!(snapshot_metadata.l1_batch_number, l1_batch_number);
+        assert_eq!(snapshot_metadata.base_l1_batch_number, None);
+    }
+
+    #[tokio::test]
+    async fn adding_incremental_snapshot() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        dal.add_snapshot(L1BatchNumber(100), None, 1, "gs:///bucket/factory_deps.bin")
+            .await
+            .unwrap();
+        dal.add_snapshot(
+            L1BatchNumber(150),
+            Some(L1BatchNumber(100)),
+            1,
+            "gs:///bucket/factory_deps_150.bin",
+        )
+        .await
+        .unwrap();
+
+        let snapshot_metadata = dal
+            .get_newest_snapshot_metadata()
+            .await
+            .unwrap()
+            .expect("No snapshot metadata");
+        assert_eq!(snapshot_metadata.l1_batch_number, L1BatchNumber(150));
+        assert_eq!(
+            snapshot_metadata.base_l1_batch_number,
+            Some(L1BatchNumber(100))
+        );
     }
 
     #[tokio::test]
@@ -218,7 +259,7 @@ mod tests {
         let mut conn = pool.access_storage().await.unwrap();
         let mut dal = conn.snapshots_dal();
         let l1_batch_number = L1BatchNumber(100);
-        dal.add_snapshot(l1_batch_number, 2, "gs:///bucket/factory_deps.bin")
+        dal.add_snapshot(l1_batch_number, None, 2, "gs:///bucket/factory_deps.bin")
             .await
             .expect("Failed to add snapshot");
 
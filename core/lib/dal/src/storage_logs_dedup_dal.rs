@@ -189,11 +189,16 @@ impl StorageLogsDedupDal<'_, '_> {
         .collect()
     }
 
-    pub async fn get_enumeration_index_for_key(&mut self, key: StorageKey) -> Option<u64> {
+    /// Returns the enumeration index of the specified key together with the L1 batch of its initial write.
+    pub async fn get_enumeration_index_for_key(
+        &mut self,
+        key: StorageKey,
+    ) -> Option<(u64, L1BatchNumber)> {
         sqlx::query!(
             r#"
             SELECT
-                INDEX
+                INDEX,
+                l1_batch_number
             FROM
                 initial_writes
             WHERE
@@ -204,7 +209,7 @@ impl StorageLogsDedupDal<'_, '_> {
         .fetch_optional(self.storage.conn())
         .await
         .unwrap()
-        .map(|row| row.index as u64)
+        .map(|row| (row.index as u64, L1BatchNumber(row.l1_batch_number as u32)))
     }
 
     /// Returns `hashed_keys` that are both present in the input and in `initial_writes` table.
@@ -3,6 +3,7 @@ use std::{collections::HashMap, ops};
 use zksync_types::{
     get_code_key, get_nonce_key,
     utils::{decompose_full_nonce, storage_key_for_standard_token_balance},
+    vm_trace::StorageAccess,
     AccountTreeId, Address, L1BatchNumber, MiniblockNumber, Nonce, StorageKey,
     FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
 };
@@ -295,6 +296,143 @@ impl StorageWeb3Dal<'_, '_> {
             .map(|option_row| option_row.map(|row| row.bytecode))
         }
     }
+
+    /// Returns storage slots written by the specified transaction, together with their values before and after
+    /// the transaction. Returns an empty list if the transaction is not executed.
+    pub async fn get_storage_accesses_for_tx(
+        &mut self,
+        tx_hash: H256,
+    ) -> sqlx::Result<Vec<StorageAccess>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                storage_logs.tx_hash,
+                storage_logs.address,
+                storage_logs.key,
+                storage_logs.value,
+                (
+                    SELECT
+                        prev.value
+                    FROM
+                        storage_logs prev
+                    WHERE
+                        prev.hashed_key = storage_logs.hashed_key
+                        AND (prev.miniblock_number, prev.operation_number) < (storage_logs.miniblock_number, storage_logs.operation_number)
+                    ORDER BY
+                        prev.miniblock_number DESC,
+                        prev.operation_number DESC
+                    LIMIT
+                        1
+                ) AS "previous_value?"
+            FROM
+                storage_logs
+                INNER JOIN transactions ON transactions.miniblock_number = storage_logs.miniblock_number
+            WHERE
+                transactions.hash = $1
+                AND storage_logs.tx_hash = $1
+            ORDER BY
+                storage_logs.operation_number
+            "#,
+            tx_hash.as_bytes()
+        )
+        .instrument("get_storage_accesses_for_tx")
+        .with_arg("tx_hash", &tx_hash)
+        .fetch_all(self.storage)
+        .await?;
+
+        let accesses = rows.into_iter().map(|row| {
+            let access =
+                Self::storage_access(&row.address, &row.key, &row.value, row.previous_value);
+            (H256::from_slice(&row.tx_hash), access)
+        });
+        Ok(merge_storage_accesses(accesses)
+            .into_iter()
+            .map(|(_, access)| access)
+            .collect())
+    }
+
+    /// Returns storage slots written by each transaction in the specified miniblock, together with their values
+    /// before and after the transaction. Transactions are returned in the order of their execution.
+    pub async fn get_storage_accesses_for_miniblock(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+    ) -> sqlx::Result<Vec<(H256, StorageAccess)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                storage_logs.tx_hash,
+                storage_logs.address,
+                storage_logs.key,
+                storage_logs.value,
+                (
+                    SELECT
+                        prev.value
+                    FROM
+                        storage_logs prev
+                    WHERE
+                        prev.hashed_key = storage_logs.hashed_key
+                        AND (prev.miniblock_number, prev.operation_number) < (storage_logs.miniblock_number, storage_logs.operation_number)
+                    ORDER BY
+                        prev.miniblock_number DESC,
+                        prev.operation_number DESC
+                    LIMIT
+                        1
+                ) AS "previous_value?"
+            FROM
+                storage_logs
+            WHERE
+                storage_logs.miniblock_number = $1
+            ORDER BY
+                storage_logs.operation_number
+            "#,
+            miniblock_number.0 as i64
+        )
+        .instrument("get_storage_accesses_for_miniblock")
+        .with_arg("miniblock_number", &miniblock_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        let accesses = rows.into_iter().map(|row| {
+            let access =
+                Self::storage_access(&row.address, &row.key, &row.value, row.previous_value);
+            (H256::from_slice(&row.tx_hash), access)
+        });
+        Ok(merge_storage_accesses(accesses))
+    }
+
+    fn storage_access(
+        address: &[u8],
+        key: &[u8],
+        value: &[u8],
+        previous_value: Option<Vec<u8>>,
+    ) -> StorageAccess {
+        let key = StorageKey::new(
+            AccountTreeId::new(Address::from_slice(address)),
+            H256::from_slice(key),
+        );
+        StorageAccess {
+            key,
+            value_before: previous_value.map_or_else(H256::zero, |value| H256::from_slice(&value)),
+            value_after: H256::from_slice(value),
+        }
+    }
+}
+
+/// Merges repeated writes to the same slot by the same transaction, retaining the original order of writes.
+fn merge_storage_accesses(
+    accesses: impl Iterator<Item = (H256, StorageAccess)>,
+) -> Vec<(H256, StorageAccess)> {
+    let mut merged: Vec<(H256, StorageAccess)> = vec![];
+    let mut indices = HashMap::new();
+    for (tx_hash, access) in accesses {
+        if let Some(&idx) = indices.get(&(tx_hash, access.key)) {
+            merged[idx].1.value_after = access.value_after;
+        } else {
+            indices.insert((tx_hash, access.key), merged.len());
+            merged.push((tx_hash, access));
+        }
+    }
+    merged
 }
 
 #[cfg(test)]
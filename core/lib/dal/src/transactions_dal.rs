@@ -1009,6 +1009,68 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Marks the specified transactions as present in the mempool, e.g. after restoring a mempool snapshot.
+    /// Transactions that are not pending anymore (i.e., are included into a miniblock, rejected or removed),
+    /// or are already in the mempool, are skipped. Returns hashes of the marked transactions.
+    pub async fn mark_txs_as_in_mempool(&mut self, tx_hashes: &[H256]) -> sqlx::Result<Vec<H256>> {
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        // Transactions are updated in order of their hashes to avoid deadlocks, similarly to `sync_mempool()`.
+        let rows = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                in_mempool = TRUE
+            FROM
+                (
+                    SELECT
+                        hash
+                    FROM
+                        transactions
+                    WHERE
+                        hash = ANY ($1)
+                        AND miniblock_number IS NULL
+                        AND in_mempool = FALSE
+                        AND error IS NULL
+                    ORDER BY
+                        hash
+                ) AS subquery
+            WHERE
+                transactions.hash = subquery.hash
+            RETURNING
+                transactions.hash
+            "#,
+            &tx_hashes as &[&[u8]]
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| H256::from_slice(&row.hash))
+            .collect())
+    }
+
+    /// Marks the specified transactions as absent from the mempool, e.g. if the mempool has previously rejected them
+    /// because of the per-account limits, and their accounts now have room for them. Such transactions will be loaded
+    /// into the mempool again during one of the following syncs.
+    pub async fn reset_txs_in_mempool(&mut self, tx_hashes: &[H256]) -> sqlx::Result<()> {
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                in_mempool = FALSE
+            WHERE
+                hash = ANY ($1)
+                AND in_mempool = TRUE
+            "#,
+            &tx_hashes as &[&[u8]]
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     pub async fn get_last_processed_l1_block(&mut self) -> Option<L1BlockNumber> {
         {
             sqlx::query!(
@@ -1,8 +1,12 @@
+use std::ops;
+
 use sqlx::types::chrono::NaiveDateTime;
 use zksync_types::{
-    api, api::TransactionReceipt, Address, L2ChainId, MiniblockNumber, Transaction,
-    ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
+    api, api::TransactionReceipt, fee::Fee, Address, L2ChainId, MiniblockNumber, Nonce,
+    Transaction, ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256,
+    U256,
 };
+use zksync_utils::bigdecimal_to_u256;
 
 use crate::{
     instrument::InstrumentExt,
@@ -58,8 +62,11 @@ impl TransactionsWeb3Dal<'_, '_> {
                 transactions.tx_format AS "tx_format?",
                 transactions.refunded_gas AS refunded_gas,
                 transactions.gas_limit AS gas_limit,
+                transactions.execution_info AS execution_info,
                 miniblocks.hash AS "block_hash",
                 miniblocks.l1_batch_number AS "l1_batch_number?",
+                miniblocks.fair_pubdata_price AS fair_pubdata_price,
+                miniblocks.protocol_version AS protocol_version,
                 sl.key AS "contract_address?"
             FROM
                 transactions
@@ -304,6 +311,79 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(U256::from(pending_nonce))
     }
 
+    /// Returns the number of pending (i.e., not included into a miniblock and not rejected) L2 transactions
+    /// for `initiator_address`. `committed_next_nonce` should equal the nonce for `initiator_address` in the storage.
+    pub async fn get_pending_txs_count(
+        &mut self,
+        initiator_address: Address,
+        committed_next_nonce: u64,
+    ) -> sqlx::Result<usize> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce >= $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes(),
+            committed_next_nonce as i64
+        )
+        .instrument("get_pending_txs_count")
+        .with_arg("initiator_address", &initiator_address)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.count as usize)
+    }
+
+    /// Returns fee parameters of the pending L2 transaction with the specified initiator and nonce, if any.
+    pub async fn get_pending_tx_fee(
+        &mut self,
+        initiator_address: Address,
+        nonce: Nonce,
+    ) -> sqlx::Result<Option<Fee>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                gas_per_pubdata_limit
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce = $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes(),
+            i64::from(nonce.0)
+        )
+        .instrument("get_pending_tx_fee")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| Fee {
+            gas_limit: bigdecimal_to_u256(row.gas_limit.unwrap_or_default()),
+            max_fee_per_gas: bigdecimal_to_u256(row.max_fee_per_gas.unwrap_or_default()),
+            max_priority_fee_per_gas: bigdecimal_to_u256(
+                row.max_priority_fee_per_gas.unwrap_or_default(),
+            ),
+            gas_per_pubdata_limit: bigdecimal_to_u256(
+                row.gas_per_pubdata_limit.unwrap_or_default(),
+            ),
+        }))
+    }
+
     /// Returns the server transactions (not API ones) from a certain miniblock.
     /// Returns an empty list if the miniblock doesn't exist.
     pub async fn get_raw_miniblock_transactions(
@@ -329,6 +409,63 @@ impl TransactionsWeb3Dal<'_, '_> {
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
+
+    /// Returns gas used and the effective priority fee per gas for each transaction included
+    /// into miniblocks in the specified range. The priority fee is computed as in EIP-1559, i.e.,
+    /// `min(max_priority_fee_per_gas, max_fee_per_gas - base_fee_per_gas)`. Transactions are
+    /// ordered by miniblock number and index in the miniblock.
+    pub async fn get_gas_usage_for_miniblocks(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<Vec<(MiniblockNumber, U256, U256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                transactions.miniblock_number AS "miniblock_number!",
+                transactions.gas_limit,
+                transactions.refunded_gas,
+                transactions.max_fee_per_gas,
+                transactions.max_priority_fee_per_gas,
+                miniblocks.base_fee_per_gas
+            FROM
+                transactions
+                INNER JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            WHERE
+                transactions.miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                transactions.miniblock_number,
+                transactions.index_in_block
+            "#,
+            miniblocks.start().0 as i64,
+            miniblocks.end().0 as i64
+        )
+        .instrument("get_gas_usage_for_miniblocks")
+        .with_arg("miniblocks", &miniblocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let gas_limit = row.gas_limit.map(bigdecimal_to_u256).unwrap_or_default();
+                let gas_used = gas_limit.saturating_sub((row.refunded_gas as u64).into());
+                let max_fee_per_gas = row.max_fee_per_gas.map(bigdecimal_to_u256);
+                let max_priority_fee_per_gas = row.max_priority_fee_per_gas.map(bigdecimal_to_u256);
+                let base_fee_per_gas = bigdecimal_to_u256(row.base_fee_per_gas);
+                let priority_fee = max_fee_per_gas.zip(max_priority_fee_per_gas).map_or(
+                    U256::zero(),
+                    |(max_fee, max_priority_fee)| {
+                        max_priority_fee.min(max_fee.saturating_sub(base_fee_per_gas))
+                    },
+                );
+                (
+                    MiniblockNumber(row.miniblock_number as u32),
+                    gas_used,
+                    priority_fee,
+                )
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -336,8 +473,10 @@ mod tests {
     use std::collections::HashMap;
 
     use zksync_types::{
-        block::MiniblockHasher, fee::TransactionExecutionMetrics, l2::L2Tx, Nonce, ProtocolVersion,
-        ProtocolVersionId,
+        block::MiniblockHasher,
+        fee::{PubdataCost, TransactionExecutionMetrics},
+        l2::L2Tx,
+        Nonce, ProtocolVersion, ProtocolVersionId,
     };
 
     use super::*;
@@ -467,6 +606,78 @@ mod tests {
         assert_eq!(receipts.len(), 2);
         assert_eq!(receipts[0].transaction_hash, tx1_hash);
         assert_eq!(receipts[1].transaction_hash, tx2_hash);
+
+        for receipt in &receipts {
+            let fee_breakdown = receipt.fee_breakdown.as_ref().unwrap();
+            assert!(!fee_breakdown.gas_per_pubdata.is_zero());
+            assert_eq!(fee_breakdown.total_pubdata.bytes, 0);
+            assert_eq!(Some(fee_breakdown.computational_gas), receipt.gas_used);
+            assert_eq!(fee_breakdown.initial_storage_writes, Some(0));
+            assert_eq!(fee_breakdown.repeated_storage_writes, Some(0));
+            assert_eq!(fee_breakdown.initial_writes, Some(PubdataCost::default()));
+            assert_eq!(fee_breakdown.repeated_writes, Some(PubdataCost::default()));
+        }
+    }
+
+    #[tokio::test]
+    async fn getting_receipts_for_legacy_protocol_version() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        prepare_transactions(&mut conn, vec![tx]).await;
+        // Miniblocks without a protocol version are treated as executed by a VM that didn't track published pubdata.
+        sqlx::query("UPDATE miniblocks SET protocol_version = NULL")
+            .execute(conn.conn())
+            .await
+            .unwrap();
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .unwrap();
+        assert_eq!(receipts.len(), 1);
+        assert!(receipts[0].fee_breakdown.is_none());
+        assert!(receipts[0].gas_used.is_some());
+    }
+
+    #[tokio::test]
+    async fn getting_gas_usage_for_miniblocks() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        let tx1 = mock_l2_transaction();
+        let mut tx2 = mock_l2_transaction();
+        tx2.common_data.fee.max_priority_fee_per_gas = 50.into();
+        prepare_transactions(&mut conn, vec![tx1, tx2]).await;
+
+        let gas_usage = conn
+            .transactions_web3_dal()
+            .get_gas_usage_for_miniblocks(MiniblockNumber(0)..=MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            gas_usage,
+            [
+                (MiniblockNumber(1), 1_000_000.into(), U256::zero()),
+                (MiniblockNumber(1), 1_000_000.into(), 50.into()),
+            ]
+        );
+
+        let gas_usage = conn
+            .transactions_web3_dal()
+            .get_gas_usage_for_miniblocks(MiniblockNumber(2)..=MiniblockNumber(3))
+            .await
+            .unwrap();
+        assert!(gas_usage.is_empty());
     }
 
     #[tokio::test]
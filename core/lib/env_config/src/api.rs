@@ -50,6 +50,8 @@ impl FromEnv for MerkleTreeApiConfig {
 mod tests {
     use std::num::NonZeroU32;
 
+    use zksync_config::configs::api::MethodWeight;
+
     use super::*;
     use crate::test_utils::{hash, EnvMutex};
 
@@ -88,6 +90,26 @@ mod tests {
                 max_response_body_size_mb: Some(10),
                 websocket_requests_per_minute_limit: Some(NonZeroU32::new(10).unwrap()),
                 tree_api_url: None,
+                requests_quota_per_minute: Some(NonZeroU32::new(1_000).unwrap()),
+                method_weights: Some(vec![
+                    MethodWeight {
+                        method: "eth_call".into(),
+                        weight: NonZeroU32::new(10).unwrap(),
+                    },
+                    MethodWeight {
+                        method: "debug_traceBlockByNumber".into(),
+                        weight: NonZeroU32::new(100).unwrap(),
+                    },
+                ]),
+                api_key_header: Some("x-api-key".into()),
+                api_keys: Some(vec!["key1".into(), "key2".into()]),
+                trusted_proxies: Some(vec![[10, 0, 0, 1].into()]),
+                method_allowlist: None,
+                method_denylist: Some(vec![
+                    "eth_getLogs".into(),
+                    "debug_traceBlockByNumber".into(),
+                ]),
+                struct_log_max_steps: Some(10_000),
             },
             contract_verification: ContractVerificationApiConfig {
                 port: 3070,
@@ -135,6 +157,13 @@ mod tests {
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_REQUESTS_QUOTA_PER_MINUTE=1000
+            API_WEB3_JSON_RPC_METHOD_WEIGHTS="eth_call=10,debug_traceBlockByNumber=100"
+            API_WEB3_JSON_RPC_API_KEY_HEADER="x-api-key"
+            API_WEB3_JSON_RPC_API_KEYS="key1,key2"
+            API_WEB3_JSON_RPC_TRUSTED_PROXIES="10.0.0.1"
+            API_WEB3_JSON_RPC_METHOD_DENYLIST="eth_getLogs,debug_traceBlockByNumber"
+            API_WEB3_JSON_RPC_STRUCT_LOG_MAX_STEPS=10000
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_MB=10
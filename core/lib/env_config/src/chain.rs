@@ -36,11 +36,15 @@ impl FromEnv for MempoolConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroU32;
+
     use zksync_basic_types::L2ChainId;
-    use zksync_config::configs::chain::FeeModelVersion;
+    use zksync_config::configs::chain::{
+        FeeInputStrategyKind, FeeModelVersion, SealCriterionConfig, TxOrderingPolicyKind,
+    };
 
     use super::*;
-    use crate::test_utils::{addr, EnvMutex};
+    use crate::test_utils::{addr, hash, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -88,12 +92,34 @@ mod tests {
             max_gas_per_batch: 200_000_000,
             max_pubdata_per_batch: 100_000,
             fee_model_version: FeeModelVersion::V2,
+            fee_input_strategy: FeeInputStrategyKind::PubdataSurge,
+            fee_input_ema_half_life_sec: None,
+            fee_input_pubdata_surge_threshold: Some(1_000_000_000),
+            fee_input_pubdata_surge_multiplier: Some(1.5),
             validation_computational_gas_limit: 10_000_000,
             save_call_traces: false,
             virtual_blocks_interval: 1,
             virtual_blocks_per_miniblock: 1,
             upload_witness_inputs_to_gcs: false,
             enum_index_migration_chunk_size: Some(2_000),
+            tx_sender_allowlist: vec![],
+            tx_sender_denylist: vec![
+                addr("0000000000000000000000000000000000000001"),
+                addr("0000000000000000000000000000000000000002"),
+            ],
+            contract_deployer_allowlist: vec![addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7")],
+            tx_access_policy_path: Some("/etc/zksync/tx_access_policy.json".to_owned()),
+            custom_seal_criteria: vec![
+                SealCriterionConfig::MaxL1Txs {
+                    max_count: NonZeroU32::new(10).unwrap(),
+                },
+                SealCriterionConfig::Event {
+                    address: addr("0000000000000000000000000000000000008008"),
+                    topic: Some(hash(
+                        "0x27fe8c0b49f49507b9d4fe5968c9f49edfe5c9df277d433a07a0717ede97638d",
+                    )),
+                },
+            ],
         }
     }
 
@@ -121,12 +147,19 @@ mod tests {
             CHAIN_STATE_KEEPER_MAX_GAS_PER_BATCH="200000000"
             CHAIN_STATE_KEEPER_MAX_PUBDATA_PER_BATCH="100000"
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
+            CHAIN_STATE_KEEPER_FEE_INPUT_STRATEGY="PubdataSurge"
+            CHAIN_STATE_KEEPER_FEE_INPUT_PUBDATA_SURGE_THRESHOLD="1000000000"
+            CHAIN_STATE_KEEPER_FEE_INPUT_PUBDATA_SURGE_MULTIPLIER="1.5"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
             CHAIN_STATE_KEEPER_UPLOAD_WITNESS_INPUTS_TO_GCS="false"
             CHAIN_STATE_KEEPER_ENUM_INDEX_MIGRATION_CHUNK_SIZE="2000"
             CHAIN_STATE_KEEPER_VIRTUAL_BLOCKS_PER_MINIBLOCK="1"
             CHAIN_STATE_KEEPER_VIRTUAL_BLOCKS_INTERVAL="1"
+            CHAIN_STATE_KEEPER_TX_SENDER_DENYLIST="0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002"
+            CHAIN_STATE_KEEPER_CONTRACT_DEPLOYER_ALLOWLIST="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+            CHAIN_STATE_KEEPER_TX_ACCESS_POLICY_PATH="/etc/zksync/tx_access_policy.json"
+            CHAIN_STATE_KEEPER_CUSTOM_SEAL_CRITERIA="max_l1_txs=10,event=0x0000000000000000000000000000000000008008:0x27fe8c0b49f49507b9d4fe5968c9f49edfe5c9df277d433a07a0717ede97638d"
         "#;
         lock.set_env(config);
 
@@ -160,6 +193,11 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            snapshot_path: Some("/db/mempool".to_owned()),
+            snapshot_interval_ms: Some(5000),
+            max_pending_txs_per_account: Some(64),
+            replacement_fee_bump_percent: Some(10),
+            ordering_policy: TxOrderingPolicyKind::RoundRobin,
         }
     }
 
@@ -173,6 +211,11 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_SNAPSHOT_PATH="/db/mempool"
+            CHAIN_MEMPOOL_SNAPSHOT_INTERVAL_MS="5000"
+            CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="64"
+            CHAIN_MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT="10"
+            CHAIN_MEMPOOL_ORDERING_POLICY="RoundRobin"
         "#;
         lock.set_env(config);
 
@@ -56,6 +56,7 @@ mod tests {
                 gcs_credential_file_path: "/path/to/credentials.json".to_owned(),
             },
             max_retries: 5,
+            enable_compression: false,
         }
     }
 
@@ -90,6 +91,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn s3_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_MODE="S3"
+            OBJECT_STORE_S3_BUCKET="zksync-artifacts"
+            OBJECT_STORE_S3_ENDPOINT="http://localhost:9000"
+            OBJECT_STORE_ENABLE_COMPRESSION="true"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert!(actual.enable_compression);
+        assert_eq!(
+            actual.mode,
+            ObjectStoreMode::S3 {
+                s3_bucket: "zksync-artifacts".to_owned(),
+                s3_region: None,
+                s3_endpoint: Some("http://localhost:9000".to_owned()),
+            }
+        );
+    }
+
     #[test]
     fn public_bucket_config_from_env() {
         let mut lock = MUTEX.lock();
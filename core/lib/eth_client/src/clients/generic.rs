@@ -13,8 +13,8 @@ use zksync_types::{
 };
 
 use crate::{
-    BoundEthInterface, ContractCall, Error, EthInterface, ExecutedTxStatus, FailureInfo, Options,
-    RawTransactionBytes, SignedCallResult,
+    BaseFees, BoundEthInterface, ContractCall, Error, EthInterface, ExecutedTxStatus, FailureInfo,
+    Options, RawTransactionBytes, SignedCallResult,
 };
 
 #[async_trait]
@@ -35,7 +35,7 @@ impl<C: EthInterface + ?Sized> EthInterface for Arc<C> {
         from_block: usize,
         block_count: usize,
         component: &'static str,
-    ) -> Result<Vec<u64>, Error> {
+    ) -> Result<Vec<BaseFees>, Error> {
         self.as_ref()
             .base_fee_history(from_block, block_count, component)
             .await
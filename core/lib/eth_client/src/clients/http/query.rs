@@ -1,24 +1,37 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use serde::Deserialize;
 use zksync_types::web3::{
     self,
     contract::Contract,
     ethabi,
+    helpers::{serialize, CallFuture},
     transports::Http,
     types::{
         Address, Block, BlockId, BlockNumber, Bytes, Filter, Log, Transaction, TransactionId,
         TransactionReceipt, H256, U256, U64,
     },
-    Web3,
+    Transport, Web3,
 };
 
 use crate::{
     clients::http::{Method, COUNTERS, LATENCIES},
     types::{Error, ExecutedTxStatus, FailureInfo, RawTokens},
-    ContractCall, EthInterface, RawTransactionBytes,
+    BaseFees, ContractCall, EthInterface, RawTransactionBytes,
 };
 
+/// Subset of the `eth_feeHistory` response. Unlike `web3::types::FeeHistory`, it contains
+/// blob base fees (EIP-4844), which L1 nodes compute based on the `excessBlobGas` in block headers.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeHistory {
+    base_fee_per_gas: Vec<U256>,
+    /// Absent for nodes / networks not supporting blobs.
+    #[serde(default)]
+    base_fee_per_blob_gas: Vec<U256>,
+}
+
 /// An "anonymous" Ethereum client that can invoke read-only methods that aren't
 /// tied to a particular account.
 #[derive(Debug, Clone)]
@@ -89,7 +102,7 @@ impl EthInterface for QueryClient {
         upto_block: usize,
         block_count: usize,
         component: &'static str,
-    ) -> Result<Vec<u64>, Error> {
+    ) -> Result<Vec<BaseFees>, Error> {
         const MAX_REQUEST_CHUNK: usize = 1024;
 
         COUNTERS.call[&(Method::BaseFeeHistory, component)].inc();
@@ -103,18 +116,30 @@ impl EthInterface for QueryClient {
         for chunk_start in (from_block..=upto_block).step_by(MAX_REQUEST_CHUNK) {
             let chunk_end = (chunk_start + MAX_REQUEST_CHUNK).min(upto_block);
             let chunk_size = chunk_end - chunk_start;
-            let chunk = self
-                .web3
-                .eth()
-                .fee_history(chunk_size.into(), chunk_end.into(), None)
-                .await?
-                .base_fee_per_gas;
-
+            let transport = self.web3.transport();
+            let params = vec![
+                serialize(&U256::from(chunk_size)),
+                serialize(&BlockNumber::from(chunk_end)),
+                serialize(&Vec::<f64>::new()),
+            ];
+            let chunk: FeeHistory =
+                CallFuture::new(transport.execute("eth_feeHistory", params)).await?;
+
+            let blob_base_fees = chunk
+                .base_fee_per_blob_gas
+                .into_iter()
+                .chain(std::iter::repeat(U256::zero()));
+            let chunk = chunk.base_fee_per_gas.into_iter().zip(blob_base_fees).map(
+                |(base_fee, blob_base_fee)| BaseFees {
+                    base_fee_per_gas: base_fee.as_u64(),
+                    base_fee_per_blob_gas: blob_base_fee,
+                },
+            );
             history.extend(chunk);
         }
 
         latency.observe();
-        Ok(history.into_iter().map(|fee| fee.as_u64()).collect())
+        Ok(history)
     }
 
     async fn get_pending_block_base_fee_per_gas(
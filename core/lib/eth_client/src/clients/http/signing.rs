@@ -21,7 +21,8 @@ use zksync_types::{
 use super::{query::QueryClient, Method, LATENCIES};
 use crate::{
     types::{encode_blob_tx_with_sidecar, Error, ExecutedTxStatus, FailureInfo, SignedCallResult},
-    BoundEthInterface, CallFunctionArgs, ContractCall, EthInterface, Options, RawTransactionBytes,
+    BaseFees, BoundEthInterface, CallFunctionArgs, ContractCall, EthInterface, Options,
+    RawTransactionBytes,
 };
 
 /// HTTP-based Ethereum client, backed by a private key to sign transactions.
@@ -160,7 +161,7 @@ impl<S: EthereumSigner> EthInterface for SigningClient<S> {
         upto_block: usize,
         block_count: usize,
         component: &'static str,
-    ) -> Result<Vec<u64>, Error> {
+    ) -> Result<Vec<BaseFees>, Error> {
         self.query_client
             .base_fee_history(upto_block, block_count, component)
             .await
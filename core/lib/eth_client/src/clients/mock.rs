@@ -17,7 +17,7 @@ use zksync_types::{
 
 use crate::{
     types::{Error, ExecutedTxStatus, FailureInfo, SignedCallResult},
-    BoundEthInterface, ContractCall, EthInterface, Options, RawTransactionBytes,
+    BaseFees, BoundEthInterface, ContractCall, EthInterface, Options, RawTransactionBytes,
 };
 
 #[derive(Debug, Clone)]
@@ -117,7 +117,7 @@ impl MockEthereumInner {
 pub struct MockEthereum {
     max_fee_per_gas: U256,
     max_priority_fee_per_gas: U256,
-    base_fee_history: Vec<u64>,
+    base_fee_history: Vec<BaseFees>,
     /// If true, the mock will not check the ordering nonces of the transactions.
     /// This is useful for testing the cases when the transactions are executed out of order.
     non_ordering_confirmations: bool,
@@ -202,7 +202,7 @@ impl MockEthereum {
         inner.block_number
     }
 
-    pub fn with_fee_history(self, history: Vec<u64>) -> Self {
+    pub fn with_fee_history(self, history: Vec<BaseFees>) -> Self {
         Self {
             base_fee_history: history,
             ..self
@@ -276,7 +276,7 @@ impl EthInterface for MockEthereum {
         from_block: usize,
         block_count: usize,
         _component: &'static str,
-    ) -> Result<Vec<u64>, Error> {
+    ) -> Result<Vec<BaseFees>, Error> {
         let start_block = from_block.saturating_sub(block_count - 1);
         Ok(self.base_fee_history[start_block..=from_block].to_vec())
     }
@@ -285,7 +285,9 @@ impl EthInterface for MockEthereum {
         &self,
         _component: &'static str,
     ) -> Result<U256, Error> {
-        Ok(U256::from(*self.base_fee_history.last().unwrap()))
+        Ok(U256::from(
+            self.base_fee_history.last().unwrap().base_fee_per_gas,
+        ))
     }
 
     async fn failure_reason(&self, tx_hash: H256) -> Result<Option<FailureInfo>, Error> {
@@ -21,6 +21,15 @@ pub use crate::types::{
 pub mod clients;
 mod types;
 
+/// Base fees of a single L1 block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BaseFees {
+    /// Base fee per execution gas (EIP-1559).
+    pub base_fee_per_gas: u64,
+    /// Base fee per blob gas (EIP-4844). Zero for blocks produced before blobs were enabled on L1.
+    pub base_fee_per_blob_gas: U256,
+}
+
 /// Contract Call/Query Options
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Options {
@@ -88,7 +97,7 @@ pub trait EthInterface: 'static + Sync + Send + fmt::Debug {
         component: &'static str,
     ) -> Result<U256, Error>;
 
-    /// Collects the base fee history for the specified block range.
+    /// Collects the base fee history (both for execution and blob gas) for the specified block range.
     ///
     /// Returns 1 value for each block in range, assuming that these blocks exist.
     /// Will return an error if the `from_block + block_count` is beyond the head block.
@@ -97,7 +106,7 @@ pub trait EthInterface: 'static + Sync + Send + fmt::Debug {
         from_block: usize,
         block_count: usize,
         component: &'static str,
-    ) -> Result<Vec<u64>, Error>;
+    ) -> Result<Vec<BaseFees>, Error>;
 
     /// Returns the `base_fee_per_gas` value for the currently pending L1 block.
     async fn get_pending_block_base_fee_per_gas(
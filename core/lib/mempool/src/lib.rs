@@ -4,6 +4,6 @@ mod tests;
 mod types;
 
 pub use crate::{
-    mempool_store::{MempoolInfo, MempoolStats, MempoolStore},
-    types::L2TxFilter,
+    mempool_store::{MempoolInfo, MempoolSnapshot, MempoolStats, MempoolStore},
+    types::{AccountTxPolicy, L2TxFilter, TxOrderingPolicy},
 };
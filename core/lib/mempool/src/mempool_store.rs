@@ -1,15 +1,20 @@
 use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
 
 use zksync_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
+    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction, H256,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{
+    AccountTransactions, AccountTxPolicy, L2TxFilter, MempoolScore, TxOrderingPolicy,
+};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// Hashes of L2 transactions previously rejected because their accounts had too many pending transactions,
+    /// which can be loaded into the mempool again since the accounts now have room for them.
+    pub released_transactions: Vec<H256>,
 }
 
 #[derive(Debug)]
@@ -19,6 +24,34 @@ pub struct MempoolStats {
     pub l2_priority_queue_size: usize,
 }
 
+/// Pending transactions in the mempool that can be persisted and later restored with [`MempoolStore::insert()`].
+#[derive(Debug, Default)]
+pub struct MempoolSnapshot {
+    /// Pending L1 transactions ordered by their priority ID (once [sorted](Self::sort())).
+    pub l1_transactions: Vec<L1Tx>,
+    /// Pending L2 transactions ordered by initiator address and nonce (once [sorted](Self::sort())).
+    pub l2_transactions: Vec<L2Tx>,
+}
+
+impl MempoolSnapshot {
+    pub fn len(&self) -> usize {
+        self.l1_transactions.len() + self.l2_transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.l1_transactions.is_empty() && self.l2_transactions.is_empty()
+    }
+
+    /// Sorts transactions in this snapshot. Snapshots assembled from parts (e.g., in order not to hold
+    /// a mempool lock while sorting) should be sorted before persisting.
+    pub fn sort(&mut self) {
+        self.l1_transactions
+            .sort_unstable_by_key(|tx| tx.common_data.serial_id);
+        self.l2_transactions
+            .sort_unstable_by_key(|tx| (tx.initiator_account(), tx.common_data.nonce));
+    }
+}
+
 #[derive(Debug)]
 pub struct MempoolStore {
     /// Pending L1 transactions
@@ -30,9 +63,17 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
+    /// L2 transactions rejected because their accounts had too many pending transactions. Such transactions
+    /// stay marked as present in the mempool in Postgres, so that they are not reloaded on each sync,
+    /// until the account has room for them.
+    deferred_transactions: HashMap<Address, Vec<H256>>,
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    account_policy: AccountTxPolicy,
+    ordering_policy: TxOrderingPolicy,
+    /// Number of L2 transactions returned by [`Self::next_transaction()`]. Used by round-robin ordering.
+    round: u64,
 }
 
 impl MempoolStore {
@@ -43,11 +84,35 @@ impl MempoolStore {
             l2_priority_queue: BTreeSet::new(),
             next_priority_id,
             stashed_accounts: vec![],
+            deferred_transactions: HashMap::new(),
             size: 0,
             capacity,
+            account_policy: AccountTxPolicy::default(),
+            ordering_policy: TxOrderingPolicy::default(),
+            round: 0,
         }
     }
 
+    /// Sets limits on pending transactions of a single account.
+    pub fn with_account_policy(mut self, policy: AccountTxPolicy) -> Self {
+        self.account_policy = policy;
+        self
+    }
+
+    /// Sets the policy used to order L2 transactions of different accounts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mempool already contains L2 transactions, since they are scored using the previous policy.
+    pub fn with_ordering_policy(mut self, policy: TxOrderingPolicy) -> Self {
+        assert!(
+            self.l2_transactions_per_account.is_empty(),
+            "ordering policy must be set before inserting L2 transactions"
+        );
+        self.ordering_policy = policy;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -101,14 +166,19 @@ impl MempoolStore {
         initial_nonces: &HashMap<Address, Nonce>,
     ) {
         let account = transaction.initiator_account();
+        let tx_hash = transaction.hash();
 
+        let policy = &self.account_policy;
         let metadata = match self.l2_transactions_per_account.entry(account) {
-            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
+            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction, policy),
             hash_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
                 entry
-                    .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
+                    .insert(AccountTransactions::new(
+                        account_nonce,
+                        self.ordering_policy,
+                    ))
+                    .insert(transaction, policy)
             }
         };
         if let Some(score) = metadata.previous_score {
@@ -120,6 +190,12 @@ impl MempoolStore {
         if metadata.is_new {
             self.size += 1;
         }
+        if metadata.is_rejected {
+            self.deferred_transactions
+                .entry(account)
+                .or_default()
+                .push(tx_hash);
+        }
     }
 
     /// Returns `true` if there is a transaction in the mempool satisfying the filter.
@@ -159,15 +235,18 @@ impl MempoolStore {
                 .remove(&stashed_pointer.account)
                 .expect("mempool: dangling pointer in priority queue")
                 .len();
+            // All transactions of stashed accounts are reloaded from Postgres, including deferred ones.
+            self.deferred_transactions.remove(&stashed_pointer.account);
 
             self.stashed_accounts.push(stashed_pointer.account);
         }
         // insert pointer to the next transaction if it exists
+        self.round += 1;
         let (transaction, score) = self
             .l2_transactions_per_account
             .get_mut(&tx_pointer.account)
             .expect("mempool: dangling pointer in priority queue")
-            .next();
+            .next(self.round);
 
         if let Some(score) = score {
             self.l2_priority_queue.insert(score);
@@ -206,12 +285,30 @@ impl MempoolStore {
     }
 
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
+        let purged_accounts = self.gc();
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
-            purged_accounts: self.gc(),
+            purged_accounts,
+            released_transactions: self.release_deferred_transactions(),
         }
     }
 
+    /// Releases deferred transactions of accounts that have room for new transactions.
+    fn release_deferred_transactions(&mut self) -> Vec<H256> {
+        let mut released_transactions = vec![];
+        self.deferred_transactions.retain(|account, tx_hashes| {
+            let has_room = self
+                .l2_transactions_per_account
+                .get(account)
+                .map_or(true, |txs| txs.has_room(&self.account_policy));
+            if has_room {
+                released_transactions.append(tx_hashes);
+            }
+            !has_room
+        });
+        released_transactions
+    }
+
     pub fn stats(&self) -> MempoolStats {
         MempoolStats {
             l1_transaction_count: self.l1_transactions.len(),
@@ -220,6 +317,40 @@ impl MempoolStore {
         }
     }
 
+    /// Returns a sorted snapshot of all pending transactions in the mempool. If the mempool is shared,
+    /// consider assembling the snapshot from [`Self::snapshot_l1_transactions()`] and [`Self::snapshot_accounts()`]
+    /// instead, so that the mempool isn't locked for the entire snapshot duration.
+    pub fn snapshot(&self) -> MempoolSnapshot {
+        let accounts = self.l2_accounts();
+        let mut snapshot = MempoolSnapshot {
+            l1_transactions: self.snapshot_l1_transactions(),
+            l2_transactions: self.snapshot_accounts(&accounts),
+        };
+        snapshot.sort();
+        snapshot
+    }
+
+    /// Returns accounts having pending L2 transactions in the mempool.
+    pub fn l2_accounts(&self) -> Vec<Address> {
+        self.l2_transactions_per_account.keys().copied().collect()
+    }
+
+    /// Returns pending L1 transactions in no particular order.
+    pub fn snapshot_l1_transactions(&self) -> Vec<L1Tx> {
+        self.l1_transactions.values().cloned().collect()
+    }
+
+    /// Returns pending L2 transactions of the specified accounts in no particular order. Accounts without
+    /// pending transactions are skipped.
+    pub fn snapshot_accounts(&self, accounts: &[Address]) -> Vec<L2Tx> {
+        accounts
+            .iter()
+            .filter_map(|account| self.l2_transactions_per_account.get(account))
+            .flat_map(AccountTransactions::transactions)
+            .cloned()
+            .collect()
+    }
+
     fn gc(&mut self) -> Vec<Address> {
         if self.size >= self.capacity {
             let index: HashSet<_> = self
@@ -232,6 +363,10 @@ impl MempoolStore {
                 .into_iter()
                 .partition(|(address, _)| index.contains(address));
             self.l2_transactions_per_account = kept;
+            // Transactions of purged accounts are removed from Postgres, including deferred ones.
+            for address in drained.keys() {
+                self.deferred_transactions.remove(address);
+            }
             self.size = self
                 .l2_transactions_per_account
                 .iter()
@@ -12,7 +12,10 @@ use zksync_types::{
     H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::{MempoolSnapshot, MempoolStore},
+    types::{AccountTxPolicy, L2TxFilter, TxOrderingPolicy},
+};
 
 #[test]
 fn basic_flow() {
@@ -365,6 +368,348 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn assembling_snapshot_from_parts() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l1_tx(PriorityOpId(0)),
+        gen_l2_tx_with_timestamp(account0, Nonce(0), 1),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), 2),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), 0),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let mut accounts = mempool.l2_accounts();
+    accounts.sort_unstable();
+    let mut expected_accounts = vec![account0, account1];
+    expected_accounts.sort_unstable();
+    assert_eq!(accounts, expected_accounts);
+
+    let account0_txs = mempool.snapshot_accounts(&[account0, Address::random()]);
+    assert_eq!(account0_txs.len(), 2);
+    assert!(account0_txs
+        .iter()
+        .all(|tx| tx.initiator_account() == account0));
+
+    let mut snapshot = MempoolSnapshot {
+        l1_transactions: mempool.snapshot_l1_transactions(),
+        l2_transactions: mempool.snapshot_accounts(&[account1]),
+    };
+    snapshot.l2_transactions.extend(account0_txs);
+    snapshot.sort();
+    let expected_snapshot = mempool.snapshot();
+    assert_eq!(snapshot.len(), expected_snapshot.len());
+    let l2_keys = |snapshot: &MempoolSnapshot| -> Vec<_> {
+        snapshot
+            .l2_transactions
+            .iter()
+            .map(|tx| (tx.initiator_account(), tx.nonce()))
+            .collect()
+    };
+    assert_eq!(l2_keys(&snapshot), l2_keys(&expected_snapshot));
+}
+
+#[test]
+fn restoring_mempool_from_snapshot() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l1_tx(PriorityOpId(0)),
+        gen_l1_tx(PriorityOpId(1)),
+        gen_l2_tx_with_timestamp(account0, Nonce(0), 1),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), 2),
+        gen_l2_tx_with_timestamp(account1, Nonce(3), 0),
+        gen_l2_tx_with_timestamp(account1, Nonce(4), 3),
+    ];
+    mempool.insert(transactions, HashMap::from([(account1, Nonce(3))]));
+    let transaction = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert!(matches!(
+        transaction.common_data,
+        ExecuteTransactionCommon::L1(data) if data.serial_id == PriorityOpId(0)
+    ));
+
+    let snapshot = mempool.snapshot();
+    assert_eq!(snapshot.len(), 5);
+    let l1_ids: Vec<_> = snapshot
+        .l1_transactions
+        .iter()
+        .map(|tx| tx.common_data.serial_id)
+        .collect();
+    assert_eq!(l1_ids, [PriorityOpId(1)]);
+
+    let mut restored_mempool = MempoolStore::new(PriorityOpId(1), 100);
+    let restored_transactions = snapshot
+        .l1_transactions
+        .into_iter()
+        .map(Transaction::from)
+        .chain(snapshot.l2_transactions.into_iter().map(Transaction::from))
+        .collect();
+    restored_mempool.insert(restored_transactions, HashMap::from([(account1, Nonce(3))]));
+    assert_eq!(restored_mempool.stats().l2_transaction_count, 4);
+
+    let transaction = restored_mempool
+        .next_transaction(&L2TxFilter::default())
+        .unwrap();
+    assert!(matches!(
+        transaction.common_data,
+        ExecuteTransactionCommon::L1(data) if data.serial_id == PriorityOpId(1)
+    ));
+    // The original ordering by receiving timestamp must be preserved.
+    for expected in [(account1, 3), (account0, 0), (account0, 1), (account1, 4)] {
+        assert_eq!(
+            view(restored_mempool.next_transaction(&L2TxFilter::default())),
+            expected
+        );
+    }
+}
+
+#[test]
+fn replacing_transactions_with_fee_bump() {
+    let policy = AccountTxPolicy {
+        max_pending_txs: None,
+        min_replacement_fee_bump_percent: Some(10),
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_account_policy(policy);
+    let account = Address::random();
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), 100, 10)],
+        HashMap::new(),
+    );
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), 110, 11)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    assert!(mempool.get_mempool_info().released_transactions.is_empty());
+
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    let ExecuteTransactionCommon::L2(data) = tx.common_data else {
+        panic!("Unexpected transaction type");
+    };
+    assert_eq!(data.fee.max_fee_per_gas, 110.into());
+    assert_eq!(data.fee.max_priority_fee_per_gas, 11.into());
+}
+
+#[test]
+fn underpriced_replacement_follows_storage() {
+    let policy = AccountTxPolicy {
+        max_pending_txs: None,
+        min_replacement_fee_bump_percent: Some(10),
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_account_policy(policy);
+    let account = Address::random();
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), 100, 10)],
+        HashMap::new(),
+    );
+    // An underpriced replacement can only be loaded if it has raced past the fee bump check in the API server
+    // and has replaced the pending transaction in storage, so the mempool must accept it as well.
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), 105, 10)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    assert!(mempool.get_mempool_info().released_transactions.is_empty());
+
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    let ExecuteTransactionCommon::L2(data) = tx.common_data else {
+        panic!("Unexpected transaction type");
+    };
+    assert_eq!(data.fee.max_fee_per_gas, 105.into());
+    assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
+}
+
+#[test]
+fn limiting_pending_transactions_per_account() {
+    let policy = AccountTxPolicy {
+        max_pending_txs: Some(2),
+        min_replacement_fee_bump_percent: None,
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_account_policy(policy);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account0, Nonce(0), 0),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), 1),
+        gen_l2_tx_with_timestamp(account0, Nonce(2), 2),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), 3),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+    // The rejected transaction must not be released while the account has no room for it.
+    assert!(mempool.get_mempool_info().released_transactions.is_empty());
+    // Replacing a pending transaction doesn't count towards the limit.
+    mempool.insert(
+        vec![gen_l2_tx_with_timestamp(account0, Nonce(1), 1)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+    assert!(mempool.get_mempool_info().released_transactions.is_empty());
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    // Once a transaction is taken from the mempool, the rejected transaction is released exactly once,
+    // so that it can be reloaded from storage.
+    assert_eq!(mempool.get_mempool_info().released_transactions.len(), 1);
+    assert!(mempool.get_mempool_info().released_transactions.is_empty());
+    mempool.insert(
+        vec![gen_l2_tx_with_timestamp(account0, Nonce(2), 2)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+    for expected in [(account0, 1), (account0, 2), (account1, 0)] {
+        assert_eq!(
+            view(mempool.next_transaction(&L2TxFilter::default())),
+            expected
+        );
+    }
+}
+
+#[test]
+fn fee_priority_ordering() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering_policy(TxOrderingPolicy::FeePriority);
+    let account0 = Address::repeat_byte(1);
+    let account1 = Address::repeat_byte(2);
+    let transactions = vec![
+        gen_l2_tx_with_fee(account0, Nonce(0), 10, 1),
+        gen_l2_tx_with_fee(account0, Nonce(1), 50, 1),
+        gen_l2_tx_with_fee(account1, Nonce(0), 30, 1),
+        gen_l2_tx_with_fee(account1, Nonce(1), 5, 1),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let filter = L2TxFilter::default();
+    // The high-fee transaction with nonce 1 must not overtake the pending transaction with nonce 0.
+    let expected_order = [(account1, 0), (account0, 0), (account0, 1), (account1, 1)];
+    for expected in expected_order {
+        assert_eq!(view(mempool.next_transaction(&filter)), expected);
+    }
+    assert_eq!(mempool.next_transaction(&filter), None);
+}
+
+#[test]
+fn fee_priority_ordering_uses_priority_fee_and_timestamp_as_tiebreakers() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering_policy(TxOrderingPolicy::FeePriority);
+    let accounts: Vec<_> = (1..=3).map(Address::repeat_byte).collect();
+    let mut later_tx = gen_l2_tx_with_fee(accounts[0], Nonce(0), 10, 2);
+    later_tx.received_timestamp_ms = 100;
+    let transactions = vec![
+        later_tx,
+        gen_l2_tx_with_fee(accounts[1], Nonce(0), 10, 2),
+        gen_l2_tx_with_fee(accounts[2], Nonce(0), 10, 3),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let filter = L2TxFilter::default();
+    let expected_order = [(accounts[2], 0), (accounts[1], 0), (accounts[0], 0)];
+    for expected in expected_order {
+        assert_eq!(view(mempool.next_transaction(&filter)), expected);
+    }
+}
+
+#[test]
+fn round_robin_ordering() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering_policy(TxOrderingPolicy::RoundRobin);
+    let account0 = Address::repeat_byte(1);
+    let account1 = Address::repeat_byte(2);
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account0, Nonce(0), 0),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), 1),
+        gen_l2_tx_with_timestamp(account0, Nonce(2), 2),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), 10),
+        gen_l2_tx_with_timestamp(account1, Nonce(1), 11),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let filter = L2TxFilter::default();
+    let expected_order = [
+        (account0, 0),
+        (account1, 0),
+        (account0, 1),
+        (account1, 1),
+        (account0, 2),
+    ];
+    for expected in expected_order {
+        assert_eq!(view(mempool.next_transaction(&filter)), expected);
+    }
+    assert_eq!(mempool.next_transaction(&filter), None);
+
+    // An account that wasn't served yet is prioritized over previously served accounts.
+    let account2 = Address::repeat_byte(3);
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_timestamp(account0, Nonce(3), 20),
+            gen_l2_tx_with_timestamp(account2, Nonce(0), 30),
+        ],
+        HashMap::new(),
+    );
+    assert_eq!(view(mempool.next_transaction(&filter)), (account2, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 3));
+}
+
+#[test]
+fn nonce_order_is_preserved_under_all_policies() {
+    const ACCOUNT_COUNT: u8 = 5;
+    const TXS_PER_ACCOUNT: u32 = 6;
+
+    let policies = [
+        TxOrderingPolicy::Fifo,
+        TxOrderingPolicy::FeePriority,
+        TxOrderingPolicy::RoundRobin,
+    ];
+    for policy in policies {
+        let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_ordering_policy(policy);
+        // Insert transactions in the reverse nonce order with pseudo-random fees and timestamps,
+        // so that later nonces are often more attractive than earlier ones.
+        for nonce in (0..TXS_PER_ACCOUNT).rev() {
+            for account_idx in 1..=ACCOUNT_COUNT {
+                let account = Address::repeat_byte(account_idx);
+                let fee = (u64::from(account_idx) * 7 + u64::from(nonce) * 13) % 17 + 1;
+                let mut tx = gen_l2_tx_with_fee(account, Nonce(nonce), fee, fee);
+                tx.received_timestamp_ms =
+                    (u64::from(nonce) * 31 + u64::from(account_idx) * 11) % 23;
+                mempool.insert(vec![tx], HashMap::new());
+            }
+        }
+
+        let filter = L2TxFilter::default();
+        let mut next_nonces = HashMap::new();
+        let (mut tx_count, mut rollback_count) = (0, 0);
+        while let Some(tx) = mempool.next_transaction(&filter) {
+            let account = tx.initiator_account();
+            let expected_nonce = next_nonces.entry(account).or_insert(0);
+            assert_eq!(tx.nonce(), Some(Nonce(*expected_nonce)), "{policy:?}");
+            *expected_nonce += 1;
+            tx_count += 1;
+
+            // Roll back some transactions; they must be returned again in the same nonce order.
+            if tx_count % 7 == 0 {
+                mempool.rollback(&tx);
+                mempool.insert(vec![tx], HashMap::new());
+                *expected_nonce -= 1;
+                rollback_count += 1;
+            }
+        }
+        assert_eq!(
+            tx_count,
+            u32::from(ACCOUNT_COUNT) * TXS_PER_ACCOUNT + rollback_count,
+            "{policy:?}"
+        );
+        assert!(
+            next_nonces.values().all(|&nonce| nonce == TXS_PER_ACCOUNT),
+            "{policy:?}: {next_nonces:?}"
+        );
+    }
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -417,6 +762,23 @@ fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     }
 }
 
+fn gen_l2_tx_with_fee(
+    address: Address,
+    nonce: Nonce,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+) -> Transaction {
+    let mut tx = gen_l2_tx_with_timestamp(address, nonce, 0);
+    match &mut tx.common_data {
+        ExecuteTransactionCommon::L2(data) => {
+            data.fee.max_fee_per_gas = max_fee_per_gas.into();
+            data.fee.max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+        }
+        _ => unreachable!(),
+    }
+    tx
+}
+
 fn view(transaction: Option<Transaction>) -> (Address, u32) {
     let tx = transaction.unwrap();
     (tx.initiator_account(), tx.nonce().unwrap().0)
@@ -1,4 +1,7 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::HashMap,
+};
 
 use zksync_types::{
     fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, Address, Nonce, Transaction, U256,
@@ -12,29 +15,55 @@ pub(crate) struct AccountTransactions {
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    /// policy used to score transactions of the account
+    ordering_policy: TxOrderingPolicy,
+    /// mempool round in which a transaction of this account was last sent to state keeper
+    /// (0 if no transactions were sent yet)
+    last_served_round: u64,
 }
 
 impl AccountTransactions {
-    pub fn new(nonce: Nonce) -> Self {
+    pub fn new(nonce: Nonce, ordering_policy: TxOrderingPolicy) -> Self {
         Self {
             transactions: HashMap::new(),
             nonce,
+            ordering_policy,
+            last_served_round: 0,
         }
     }
 
     /// Inserts new transaction for given account. Returns insertion metadata
-    pub fn insert(&mut self, transaction: L2Tx) -> InsertionMetadata {
+    pub fn insert(&mut self, transaction: L2Tx, policy: &AccountTxPolicy) -> InsertionMetadata {
         let mut metadata = InsertionMetadata::default();
         let nonce = transaction.common_data.nonce;
         // skip insertion if transaction is old
         if nonce < self.nonce {
             return metadata;
         }
-        let new_score = Self::score_for_transaction(&transaction);
+        if let Some(pending_tx) = self.transactions.get(&nonce) {
+            // The fee bump is enforced when the transaction is submitted. An underpriced replacement can still
+            // get here if concurrent submissions race past the check; by then, it has already replaced
+            // the pending transaction in Postgres, so the mempool must follow Postgres.
+            let old_fee = &pending_tx.common_data.fee;
+            if !policy.allows_replacement(old_fee, &transaction.common_data.fee) {
+                tracing::warn!(
+                    "accepting underpriced replacement of L2 transaction {nonce} for {:?} persisted in Postgres",
+                    transaction.initiator_account()
+                );
+            }
+        } else if !policy.allows_new_transaction(self.transactions.len()) {
+            tracing::debug!(
+                "rejecting L2 transaction {nonce} for {:?}: too many pending transactions",
+                transaction.initiator_account()
+            );
+            metadata.is_rejected = true;
+            return metadata;
+        }
+        let new_score = self.score_for_transaction(&transaction);
         let previous_score = self
             .transactions
             .insert(nonce, transaction)
-            .map(|tx| Self::score_for_transaction(&tx));
+            .map(|tx| self.score_for_transaction(&tx));
         metadata.is_new = previous_score.is_none();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
@@ -43,18 +72,25 @@ impl AccountTransactions {
         metadata
     }
 
-    /// Returns next transaction to be included in block and optional score of its successor
+    /// Checks whether the account can accept a new (i.e., not replacing) transaction under the `policy`.
+    pub fn has_room(&self, policy: &AccountTxPolicy) -> bool {
+        policy.allows_new_transaction(self.transactions.len())
+    }
+
+    /// Returns next transaction to be included in block and optional score of its successor.
+    /// `round` is the current mempool round; it's used by round-robin ordering to schedule the successor.
     /// Panics if no such transaction exists
-    pub fn next(&mut self) -> (L2Tx, Option<MempoolScore>) {
+    pub fn next(&mut self, round: u64) -> (L2Tx, Option<MempoolScore>) {
         let transaction = self
             .transactions
             .remove(&self.nonce)
             .expect("missing transaction in mempool");
         self.nonce += 1;
+        self.last_served_round = round;
         let score = self
             .transactions
             .get(&self.nonce)
-            .map(Self::score_for_transaction);
+            .map(|tx| self.score_for_transaction(tx));
         (transaction, score)
     }
 
@@ -67,24 +103,102 @@ impl AccountTransactions {
         self.nonce = self.nonce.min(tx_nonce);
         self.transactions
             .get(&(tx_nonce + 1))
-            .map(Self::score_for_transaction)
+            .map(|tx| self.score_for_transaction(tx))
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
+    pub fn transactions(&self) -> impl Iterator<Item = &L2Tx> + '_ {
+        self.transactions.values()
+    }
+
+    fn score_for_transaction(&self, transaction: &L2Tx) -> MempoolScore {
         MempoolScore {
             account: transaction.initiator_account(),
             received_at_ms: transaction.received_timestamp_ms,
             fee_data: transaction.common_data.fee.clone(),
+            priority: self
+                .ordering_policy
+                .priority(transaction, self.last_served_round),
+        }
+    }
+}
+
+/// Policy used to order L2 transactions of different accounts in the mempool. Transactions of a single account
+/// are always returned in the nonce order, so the policy only decides which account is served next;
+/// it is applied to the next transaction of each account. L1 transactions always take precedence over L2 ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TxOrderingPolicy {
+    /// Transactions are ordered by the time they were received by the node.
+    #[default]
+    Fifo,
+    /// Transactions with higher `max_fee_per_gas` (and then `max_priority_fee_per_gas`) are prioritized;
+    /// transactions with equal fees are ordered by the time they were received.
+    FeePriority,
+    /// Accounts are served in the round-robin fashion: the account that was served least recently is prioritized.
+    /// Accounts that were served equally recently are ordered by the time their transactions were received.
+    RoundRobin,
+}
+
+impl TxOrderingPolicy {
+    fn priority(self, transaction: &L2Tx, last_served_round: u64) -> ScorePriority {
+        match self {
+            Self::Fifo => ScorePriority::None,
+            Self::FeePriority => ScorePriority::Fee {
+                max_fee_per_gas: transaction.common_data.fee.max_fee_per_gas,
+                max_priority_fee_per_gas: transaction.common_data.fee.max_priority_fee_per_gas,
+            },
+            Self::RoundRobin => ScorePriority::LastServedRound(Reverse(last_served_round)),
         }
     }
 }
 
+/// Policy-dependent part of [`MempoolScore`]. Greater values are prioritized.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum ScorePriority {
+    None,
+    Fee {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+    LastServedRound(Reverse<u64>),
+}
+
+/// Limits on pending L2 transactions of a single account.
+///
+/// The default policy doesn't impose any limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountTxPolicy {
+    /// Maximum number of pending transactions per account.
+    pub max_pending_txs: Option<usize>,
+    /// Minimum increase (in percent) of both `max_fee_per_gas` and `max_priority_fee_per_gas`
+    /// required to replace a pending transaction with the same nonce. If not set, transactions
+    /// can be replaced without any restrictions.
+    pub min_replacement_fee_bump_percent: Option<u32>,
+}
+
+impl AccountTxPolicy {
+    /// Checks whether a pending transaction with `old_fee` may be replaced by a transaction with `new_fee`.
+    pub fn allows_replacement(&self, old_fee: &Fee, new_fee: &Fee) -> bool {
+        let Some(bump_percent) = self.min_replacement_fee_bump_percent else {
+            return true;
+        };
+        let min_fee = |fee: U256| fee + fee * bump_percent / 100;
+        new_fee.max_fee_per_gas >= min_fee(old_fee.max_fee_per_gas)
+            && new_fee.max_priority_fee_per_gas >= min_fee(old_fee.max_priority_fee_per_gas)
+    }
+
+    /// Checks whether a new transaction may be added for an account with `pending_count` pending transactions.
+    pub fn allows_new_transaction(&self, pending_count: usize) -> bool {
+        self.max_pending_txs
+            .map_or(true, |max_pending_txs| pending_count < max_pending_txs)
+    }
+}
+
 /// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Transactions are ordered by the priority assigned by [`TxOrderingPolicy`], then by received at timestamp
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
     pub account: Address,
@@ -93,6 +207,7 @@ pub struct MempoolScore {
     // transactions that have acceptable fee values (so transactions
     // with fee too low would be ignored until prices go down).
     pub fee_data: Fee,
+    pub(crate) priority: ScorePriority,
 }
 
 impl MempoolScore {
@@ -105,6 +220,10 @@ impl MempoolScore {
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        match self.priority.cmp(&other.priority) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -124,6 +243,8 @@ pub(crate) struct InsertionMetadata {
     pub new_score: Option<MempoolScore>,
     pub previous_score: Option<MempoolScore>,
     pub is_new: bool,
+    /// Set if the transaction was not inserted because the account has too many pending transactions.
+    pub is_rejected: bool,
 }
 
 /// Structure that can be used by state keeper to describe
@@ -166,6 +287,7 @@ mod tests {
                 max_priority_fee_per_gas: U256::from(MAX_PRIORITY_FEE_PER_GAS),
                 gas_per_pubdata_limit: U256::from(GAS_PER_PUBDATA_LIMIT),
             },
+            priority: ScorePriority::None,
         };
 
         let noop_filter = filter(0, 0);
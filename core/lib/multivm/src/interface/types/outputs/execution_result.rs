@@ -102,6 +102,8 @@ impl VmExecutionResultAndLogs {
             computational_gas_used: self.statistics.computational_gas_used,
             pubdata_published: self.statistics.pubdata_published,
             circuit_statistic: self.statistics.circuit_statistic,
+            initial_storage_writes: 0,
+            repeated_storage_writes: 0,
         }
     }
 }
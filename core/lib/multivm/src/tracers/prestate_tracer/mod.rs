@@ -0,0 +1,94 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    mem,
+    sync::Arc,
+};
+
+use once_cell::sync::OnceCell;
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_types::{
+    get_code_key, get_nonce_key,
+    utils::storage_key_for_eth_balance,
+    vm_trace::{StorageAccess, TouchedState},
+    AccountTreeId, Address, StorageKey, H256, U256,
+};
+use zksync_utils::u256_to_h256;
+
+use crate::glue::tracers::IntoOldVmTracer;
+
+pub mod vm_1_4_1;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer collecting accounts and storage slots touched during the VM execution, together with
+/// the slot values before and after the execution. Used to serve geth-compatible `prestateTracer`.
+#[derive(Debug, Clone)]
+pub struct PrestateTracer {
+    accounts: BTreeSet<Address>,
+    values_before: HashMap<StorageKey, H256>,
+    result: Arc<OnceCell<TouchedState>>,
+}
+
+impl PrestateTracer {
+    pub fn new(result: Arc<OnceCell<TouchedState>>) -> Self {
+        Self {
+            accounts: BTreeSet::new(),
+            values_before: HashMap::new(),
+            result,
+        }
+    }
+
+    fn touch_account(&mut self, address: Address) {
+        self.accounts.insert(address);
+    }
+
+    /// Records a storage slot accessed by a `StorageRead` / `StorageWrite` opcode. Must be called
+    /// before the opcode is executed, so that the recorded value is the one before the execution.
+    fn touch_slot<S: WriteStorage>(
+        &mut self,
+        address: Address,
+        key: U256,
+        storage: &StoragePtr<S>,
+    ) {
+        self.accounts.insert(address);
+        let key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(key));
+        self.values_before
+            .entry(key)
+            .or_insert_with(|| storage.borrow_mut().read_value(&key));
+    }
+
+    fn store_result<S: WriteStorage>(&mut self, storage: StoragePtr<S>) {
+        let mut storage = storage.borrow_mut();
+        for address in &self.accounts {
+            let account_keys = [
+                storage_key_for_eth_balance(address),
+                get_nonce_key(address),
+                get_code_key(address),
+            ];
+            for key in account_keys {
+                // Account fields not accessed during execution have the same value before and after it.
+                self.values_before
+                    .entry(key)
+                    .or_insert_with(|| storage.read_value(&key));
+            }
+        }
+
+        let accesses = mem::take(&mut self.values_before)
+            .into_iter()
+            .map(|(key, value_before)| StorageAccess {
+                key,
+                value_before,
+                value_after: storage.read_value(&key),
+            })
+            .collect();
+        let touched_state = TouchedState {
+            accounts: mem::take(&mut self.accounts),
+            storage: accesses,
+        };
+        self.result.set(touched_state).unwrap();
+    }
+}
+
+impl IntoOldVmTracer for PrestateTracer {}
@@ -0,0 +1,64 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    mem,
+    sync::Arc,
+};
+
+use once_cell::sync::OnceCell;
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_types::{vm_trace::StorageAccesses, AccountTreeId, Address, StorageKey, U256};
+use zksync_utils::u256_to_h256;
+
+use crate::glue::tracers::IntoOldVmTracer;
+
+pub mod vm_1_4_1;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer collecting storage slots read and written during the VM execution. For written slots, the tracer
+/// determines whether the write is initial, which defines the amount of pubdata spent on it.
+#[derive(Debug, Clone)]
+pub struct StorageAccessTracer {
+    reads: BTreeSet<StorageKey>,
+    writes: BTreeSet<StorageKey>,
+    result: Arc<OnceCell<StorageAccesses>>,
+}
+
+impl StorageAccessTracer {
+    pub fn new(result: Arc<OnceCell<StorageAccesses>>) -> Self {
+        Self {
+            reads: BTreeSet::new(),
+            writes: BTreeSet::new(),
+            result,
+        }
+    }
+
+    fn storage_key(address: Address, key: U256) -> StorageKey {
+        StorageKey::new(AccountTreeId::new(address), u256_to_h256(key))
+    }
+
+    fn record_read(&mut self, address: Address, key: U256) {
+        self.reads.insert(Self::storage_key(address, key));
+    }
+
+    fn record_write(&mut self, address: Address, key: U256) {
+        self.writes.insert(Self::storage_key(address, key));
+    }
+
+    fn store_result<S: WriteStorage>(&mut self, storage: StoragePtr<S>) {
+        let mut storage = storage.borrow_mut();
+        let writes: BTreeMap<_, _> = mem::take(&mut self.writes)
+            .into_iter()
+            .map(|key| (key, storage.is_write_initial(&key)))
+            .collect();
+        let accesses = StorageAccesses {
+            reads: mem::take(&mut self.reads),
+            writes,
+        };
+        self.result.set(accesses).unwrap();
+    }
+}
+
+impl IntoOldVmTracer for StorageAccessTracer {}
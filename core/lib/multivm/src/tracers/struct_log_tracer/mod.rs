@@ -0,0 +1,49 @@
+use std::{mem, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use zksync_types::vm_trace::StructLog;
+
+use crate::glue::tracers::IntoOldVmTracer;
+
+pub mod vm_1_4_1;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer recording every instruction executed by the VM together with a snapshot of the VM state
+/// before its execution. Instructions are only recorded for the latest VM version; for older versions,
+/// the tracer is a no-op.
+#[derive(Debug, Clone)]
+pub struct StructLogTracer {
+    max_steps: Option<usize>,
+    record_memory: bool,
+    steps: Vec<StructLog>,
+    result: Arc<OnceCell<Vec<StructLog>>>,
+}
+
+impl StructLogTracer {
+    pub fn new(
+        result: Arc<OnceCell<Vec<StructLog>>>,
+        max_steps: Option<usize>,
+        record_memory: bool,
+    ) -> Self {
+        Self {
+            max_steps,
+            record_memory,
+            steps: vec![],
+            result,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.max_steps
+            .map_or(false, |max_steps| self.steps.len() >= max_steps)
+    }
+
+    fn store_result(&mut self) {
+        self.result.set(mem::take(&mut self.steps)).unwrap();
+    }
+}
+
+impl IntoOldVmTracer for StructLogTracer {}
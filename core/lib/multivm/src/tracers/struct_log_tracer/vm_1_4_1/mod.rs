@@ -0,0 +1,11 @@
+use zksync_state::WriteStorage;
+
+use crate::{
+    interface::traits::tracers::dyn_tracers::vm_1_4_1::DynTracer,
+    tracers::struct_log_tracer::StructLogTracer,
+    vm_1_4_1::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogTracer {}
@@ -0,0 +1,66 @@
+use zk_evm_1_4_1::tracing::{BeforeExecutionData, VmLocalStateData};
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_types::vm_trace::{MemoryWrite, StructLog};
+
+use crate::{
+    interface::{tracer::VmExecutionStopReason, traits::tracers::dyn_tracers::vm_1_4_1::DynTracer},
+    tracers::struct_log_tracer::StructLogTracer,
+    vm_latest::{
+        tracers::utils::get_heap_write_location, BootloaderState, HistoryMode, SimpleMemory,
+        VmTracer, ZkSyncVmState,
+    },
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        if self.is_full() {
+            return;
+        }
+
+        let memory_writes = if self.record_memory {
+            get_heap_write_location(&state, &data)
+                .map(|(page, offset)| MemoryWrite {
+                    page: page.0,
+                    offset,
+                    value: data.src1_value.value,
+                })
+                .into_iter()
+                .collect()
+        } else {
+            vec![]
+        };
+        let callstack = &state.vm_local_state.callstack;
+        self.steps.push(StructLog {
+            pc: callstack.current.pc,
+            op: format!("{:?}", data.opcode.variant.opcode),
+            gas: callstack.current.ergs_remaining,
+            depth: callstack.depth(),
+            contract_address: callstack.current.this_address,
+            registers: state
+                .vm_local_state
+                .registers
+                .iter()
+                .map(|register| register.value)
+                .collect(),
+            sp: callstack.current.sp,
+            memory_writes,
+        });
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result();
+    }
+}
@@ -17,6 +17,8 @@ mod refunds;
 mod require_eip712;
 mod rollbacks;
 mod simple_execution;
+mod storage_access_tracer;
+mod struct_log_tracer;
 mod tester;
 mod tracing_execution_error;
 mod upgrade;
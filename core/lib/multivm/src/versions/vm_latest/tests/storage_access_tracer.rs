@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_types::{
+    utils::storage_key_for_eth_balance, vm_trace::StorageAccesses, AccountTreeId, Address, Execute,
+    StorageKey, H256,
+};
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::StorageAccessTracer,
+    vm_latest::{
+        constants::BLOCK_GAS_LIMIT,
+        tests::{
+            tester::{VmTester, VmTesterBuilder},
+            utils::read_test_contract,
+        },
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+fn trace_call(
+    vm: &mut VmTester<HistoryEnabled>,
+    address: Address,
+    calldata: &str,
+) -> StorageAccesses {
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let tracer = StorageAccessTracer::new(result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    Arc::try_unwrap(result).unwrap().take().unwrap()
+}
+
+#[test]
+fn collecting_storage_accesses() {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_gas_limit(BLOCK_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+    let account_address = vm.rich_accounts[0].address;
+    // The counter value is stored in the first slot of the contract.
+    let counter_slot = StorageKey::new(AccountTreeId::new(address), H256::zero());
+    let balance_key = storage_key_for_eth_balance(&account_address);
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let accesses = trace_call(&mut vm, address, increment_by_6_calldata);
+    assert!(accesses.reads.contains(&counter_slot), "{accesses:?}");
+    // The slot was never written before, so the write is initial.
+    assert_eq!(
+        accesses.writes.get(&counter_slot),
+        Some(&true),
+        "{accesses:?}"
+    );
+    // The fee is charged from the account balance.
+    assert!(accesses.writes.contains_key(&balance_key), "{accesses:?}");
+    // The counter contract doesn't touch any other slots; accesses made by system contracts must be attributed to them.
+    let other_contract_slots = accesses
+        .reads
+        .iter()
+        .chain(accesses.writes.keys())
+        .filter(|key| *key.address() == address && **key != counter_slot);
+    assert_eq!(other_contract_slots.count(), 0, "{accesses:?}");
+
+    let get_calldata = "6d4ce63c";
+    let accesses = trace_call(&mut vm, address, get_calldata);
+    assert!(accesses.reads.contains(&counter_slot), "{accesses:?}");
+    assert!(!accesses.writes.contains_key(&counter_slot), "{accesses:?}");
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_types::{vm_trace::StructLog, Address, Execute};
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::StructLogTracer,
+    vm_latest::{
+        constants::BLOCK_GAS_LIMIT,
+        tests::{tester::VmTesterBuilder, utils::read_test_contract},
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+fn trace_increment_call(
+    max_steps: Option<usize>,
+    record_memory: bool,
+) -> (Address, Vec<StructLog>) {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_gas_limit(BLOCK_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let tracer =
+        StructLogTracer::new(result.clone(), max_steps, record_memory).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let steps = Arc::try_unwrap(result).unwrap().take().unwrap();
+    (address, steps)
+}
+
+#[test]
+fn recording_struct_logs() {
+    let (address, steps) = trace_increment_call(None, true);
+
+    assert!(steps.len() > 100, "{}", steps.len());
+    // Instructions of the called contract must be recorded in a nested frame.
+    let contract_steps: Vec<_> = steps
+        .iter()
+        .filter(|step| step.contract_address == address)
+        .collect();
+    assert!(!contract_steps.is_empty());
+    let min_depth = steps.iter().map(|step| step.depth).min().unwrap();
+    assert!(contract_steps.iter().all(|step| step.depth > min_depth));
+    assert!(steps.iter().any(|step| !step.memory_writes.is_empty()));
+    assert!(steps.iter().all(|step| !step.registers.is_empty()));
+}
+
+#[test]
+fn struct_logs_without_memory() {
+    let (_, steps) = trace_increment_call(None, false);
+    assert!(!steps.is_empty());
+    assert!(steps.iter().all(|step| step.memory_writes.is_empty()));
+}
+
+#[test]
+fn limiting_struct_log_steps() {
+    let (_, steps) = trace_increment_call(Some(10), false);
+    assert_eq!(steps.len(), 10);
+}
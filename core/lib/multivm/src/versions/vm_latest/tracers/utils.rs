@@ -84,6 +84,22 @@ impl VmHook {
     }
 }
 
+/// Returns the memory page and the byte offset of the heap word written by a `UMA` opcode,
+/// or `None` if the opcode doesn't write to the heap.
+pub(crate) fn get_heap_write_location(
+    state: &VmLocalStateData<'_>,
+    data: &BeforeExecutionData,
+) -> Option<(MemoryPage, u32)> {
+    let base_page = state.vm_local_state.callstack.current.base_memory_page;
+    let page = match data.opcode.variant.opcode {
+        Opcode::UMA(UMAOpcode::HeapWrite) => heap_page_from_base(base_page),
+        Opcode::UMA(UMAOpcode::AuxHeapWrite) => aux_heap_page_from_base(base_page),
+        _ => return None,
+    };
+    let offset = FatPointer::from_u256(data.src0_value.value).offset;
+    Some((page, offset))
+}
+
 pub(crate) fn get_debug_log<H: HistoryMode>(
     state: &VmLocalStateData<'_>,
     memory: &SimpleMemory<H>,
@@ -0,0 +1,178 @@
+//! [`ObjectStore`] decorator that compresses stored objects and verifies their integrity.
+//!
+//! ## Object format
+//!
+//! | Bytes  | Description                                              |
+//! | ------ | -------------------------------------------------------- |
+//! | 0..4   | Magic bytes (`ZKSO`)                                     |
+//! | 4      | Format version (currently, 1)                            |
+//! | 5..9   | CRC32 checksum of the uncompressed object (big-endian)   |
+//! | 9..    | zstd-compressed object                                   |
+//!
+//! Objects not starting with the magic bytes are considered to be written before compression
+//! was enabled and are returned as-is.
+
+use async_trait::async_trait;
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+
+const MAGIC: [u8; 4] = *b"ZKSO";
+const FORMAT_VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+const COMPRESSION_LEVEL: i32 = 3;
+
+fn encode(value: &[u8]) -> Result<Vec<u8>, ObjectStoreError> {
+    let checksum = crc32fast::hash(value);
+    let mut encoded = Vec::with_capacity(HEADER_LEN + value.len() / 2);
+    encoded.extend_from_slice(&MAGIC);
+    encoded.push(FORMAT_VERSION);
+    encoded.extend_from_slice(&checksum.to_be_bytes());
+    zstd::stream::copy_encode(value, &mut encoded, COMPRESSION_LEVEL)
+        .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+    Ok(encoded)
+}
+
+fn decode(bytes: Vec<u8>) -> Result<Vec<u8>, ObjectStoreError> {
+    if !bytes.starts_with(&MAGIC) {
+        return Ok(bytes); // legacy uncompressed object
+    }
+    if bytes.len() < HEADER_LEN {
+        let err = format!("object is truncated to {} bytes", bytes.len());
+        return Err(ObjectStoreError::Corrupted(err.into()));
+    }
+
+    let version = bytes[MAGIC.len()];
+    if version != FORMAT_VERSION {
+        let err = format!("unsupported object format version: {version}");
+        return Err(ObjectStoreError::Corrupted(err.into()));
+    }
+    let checksum_bytes = bytes[MAGIC.len() + 1..HEADER_LEN].try_into().unwrap();
+    let expected_checksum = u32::from_be_bytes(checksum_bytes);
+    let decoded = zstd::stream::decode_all(&bytes[HEADER_LEN..])
+        .map_err(|err| ObjectStoreError::Corrupted(err.into()))?;
+    let actual_checksum = crc32fast::hash(&decoded);
+    if actual_checksum != expected_checksum {
+        let err = format!(
+            "checksum mismatch: expected {expected_checksum:#010x}, got {actual_checksum:#010x}"
+        );
+        return Err(ObjectStoreError::Corrupted(err.into()));
+    }
+    Ok(decoded)
+}
+
+/// [`ObjectStore`] wrapper transparently compressing objects using zstd and checking their integrity
+/// on reads.
+#[derive(Debug)]
+pub(crate) struct CompressingObjectStore<S> {
+    inner: S,
+}
+
+impl<S: ObjectStore> CompressingObjectStore<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<S: ObjectStore> ObjectStore for CompressingObjectStore<S> {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let bytes = self.inner.get_raw(bucket, key).await?;
+        tokio::task::spawn_blocking(move || decode(bytes))
+            .await
+            .map_err(|err| ObjectStoreError::Other(err.into()))?
+            .map_err(|err| {
+                tracing::warn!("Failed decoding object {key} from bucket {bucket}: {err}");
+                err
+            })
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let encoded = tokio::task::spawn_blocking(move || encode(&value))
+            .await
+            .map_err(|err| ObjectStoreError::Other(err.into()))??;
+        self.inner.put_raw(bucket, key, encoded).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::mock::MockStore;
+
+    #[tokio::test]
+    async fn compressing_and_decompressing_objects() {
+        let inner = Arc::new(MockStore::default());
+        let store = CompressingObjectStore::new(inner.clone());
+        let value = vec![42_u8; 10_000];
+        store
+            .put_raw(Bucket::ProverJobs, "test", value.clone())
+            .await
+            .unwrap();
+
+        let raw_value = inner.get_raw(Bucket::ProverJobs, "test").await.unwrap();
+        assert!(raw_value.starts_with(&MAGIC));
+        assert!(raw_value.len() < value.len());
+        let loaded_value = store.get_raw(Bucket::ProverJobs, "test").await.unwrap();
+        assert_eq!(loaded_value, value);
+
+        store.remove_raw(Bucket::ProverJobs, "test").await.unwrap();
+        let err = store.get_raw(Bucket::ProverJobs, "test").await.unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn reading_legacy_objects() {
+        let inner = Arc::new(MockStore::default());
+        let value = vec![1, 2, 3, 4, 5];
+        inner
+            .put_raw(Bucket::WitnessInput, "legacy", value.clone())
+            .await
+            .unwrap();
+
+        let store = CompressingObjectStore::new(inner);
+        let loaded_value = store.get_raw(Bucket::WitnessInput, "legacy").await.unwrap();
+        assert_eq!(loaded_value, value);
+    }
+
+    #[tokio::test]
+    async fn detecting_corrupted_objects() {
+        let inner = Arc::new(MockStore::default());
+        let store = CompressingObjectStore::new(inner.clone());
+        let value: Vec<u8> = (0..=255).cycle().take(1_000).collect();
+        store
+            .put_raw(Bucket::ProofsFri, "test", value)
+            .await
+            .unwrap();
+        let raw_value = inner.get_raw(Bucket::ProofsFri, "test").await.unwrap();
+
+        let mut with_wrong_checksum = raw_value.clone();
+        with_wrong_checksum[HEADER_LEN - 1] ^= 1;
+        let truncated = raw_value[..raw_value.len() - 10].to_vec();
+        let mut with_wrong_version = raw_value;
+        with_wrong_version[MAGIC.len()] = 100;
+
+        for corrupted_value in [with_wrong_checksum, truncated, with_wrong_version] {
+            inner
+                .put_raw(Bucket::ProofsFri, "test", corrupted_value)
+                .await
+                .unwrap();
+            let err = store.get_raw(Bucket::ProofsFri, "test").await.unwrap_err();
+            assert!(matches!(err, ObjectStoreError::Corrupted(_)), "{err}");
+        }
+    }
+}
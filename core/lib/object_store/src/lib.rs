@@ -3,6 +3,10 @@
 //!
 //! - File-based storage saving blobs as separate files in the local filesystem
 //! - GCS-based storage
+//! - S3-based storage (also works with S3-compatible stores, such as MinIO)
+//!
+//! Optionally, stored objects can be compressed with zstd and protected with a checksum;
+//! objects written without compression can still be read in this case.
 //!
 //! These implementations are not exposed externally. Instead, a store trait object
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
@@ -23,12 +27,14 @@
     clippy::doc_markdown
 )]
 
+mod compression;
 mod file;
 mod gcs;
 mod metrics;
 mod mock;
 mod objects;
 mod raw;
+mod s3;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
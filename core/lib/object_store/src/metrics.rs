@@ -8,16 +8,16 @@ use crate::Bucket;
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_object_store")]
-pub(crate) struct GcsMetrics {
-    /// Latency to fetch an object from GCS.
+pub(crate) struct ObjectStoreMetrics {
+    /// Latency to fetch an object from a remote store (GCS or S3).
     #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
     fetching_time: LabeledFamily<&'static str, Histogram<Duration>>,
-    /// Latency to store an object in GCS.
+    /// Latency to store an object in a remote store (GCS or S3).
     #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
     storing_time: LabeledFamily<&'static str, Histogram<Duration>>,
 }
 
-impl GcsMetrics {
+impl ObjectStoreMetrics {
     pub fn start_fetch(&self, bucket: Bucket) -> LatencyObserver<'_> {
         self.fetching_time[&bucket.as_str()].start()
     }
@@ -28,4 +28,4 @@ impl GcsMetrics {
 }
 
 #[vise::register]
-pub(crate) static GCS_METRICS: vise::Global<GcsMetrics> = vise::Global::new();
+pub(crate) static OBJECT_STORE_METRICS: vise::Global<ObjectStoreMetrics> = vise::Global::new();
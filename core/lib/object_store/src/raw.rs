@@ -4,9 +4,11 @@ use async_trait::async_trait;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
 use crate::{
+    compression::CompressingObjectStore,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStorage, GoogleCloudStorageAuthMode},
     mock::MockStore,
+    s3::S3Store,
 };
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
@@ -60,6 +62,8 @@ pub enum ObjectStoreError {
     KeyNotFound(BoxedError),
     /// Object (de)serialization failed.
     Serialization(BoxedError),
+    /// Stored object is corrupted (e.g., its checksum doesn't match its contents).
+    Corrupted(BoxedError),
     /// Other error has occurred when accessing the store (e.g., a network error).
     Other(BoxedError),
 }
@@ -69,6 +73,7 @@ impl fmt::Display for ObjectStoreError {
         match self {
             Self::KeyNotFound(err) => write!(formatter, "key not found: {err}"),
             Self::Serialization(err) => write!(formatter, "serialization error: {err}"),
+            Self::Corrupted(err) => write!(formatter, "object is corrupted: {err}"),
             Self::Other(err) => write!(formatter, "other error: {err}"),
         }
     }
@@ -77,9 +82,10 @@ impl fmt::Display for ObjectStoreError {
 impl error::Error for ObjectStoreError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
-            Self::KeyNotFound(err) | Self::Serialization(err) | Self::Other(err) => {
-                Some(err.as_ref())
-            }
+            Self::KeyNotFound(err)
+            | Self::Serialization(err)
+            | Self::Corrupted(err)
+            | Self::Other(err) => Some(err.as_ref()),
         }
     }
 }
@@ -190,6 +196,16 @@ impl ObjectStoreFactory {
     }
 
     async fn create_from_config(config: &ObjectStoreConfig) -> Arc<dyn ObjectStore> {
+        let store = Self::create_base_store(config).await;
+        if config.enable_compression {
+            tracing::trace!("Enabled compression for Object store");
+            Arc::new(CompressingObjectStore::new(store))
+        } else {
+            store
+        }
+    }
+
+    async fn create_base_store(config: &ObjectStoreConfig) -> Arc<dyn ObjectStore> {
         match &config.mode {
             ObjectStoreMode::GCS { bucket_base_url } => {
                 tracing::trace!(
@@ -235,6 +251,21 @@ impl ObjectStoreFactory {
                 .await;
                 Arc::new(store)
             }
+            ObjectStoreMode::S3 {
+                s3_bucket,
+                s3_region,
+                s3_endpoint,
+            } => {
+                tracing::trace!("Initialized S3 Object store");
+                let store = S3Store::new(
+                    s3_bucket.clone(),
+                    s3_region.clone(),
+                    s3_endpoint.clone(),
+                    config.max_retries,
+                )
+                .await;
+                Arc::new(store)
+            }
         }
     }
 }
@@ -0,0 +1,492 @@
+//! S3-based [`ObjectStore`] implementation. Works with AWS S3 and S3-compatible stores (e.g., MinIO).
+
+use std::{error, fmt};
+
+use async_trait::async_trait;
+use aws_sdk_s3::{
+    config::{http::HttpResponse, retry::RetryConfig, BehaviorVersion, Region},
+    error::SdkError,
+    primitives::ByteStream,
+    types::{CompletedMultipartUpload, CompletedPart},
+    Client,
+};
+
+use crate::{
+    metrics::OBJECT_STORE_METRICS,
+    raw::{Bucket, ObjectStore, ObjectStoreError},
+};
+
+/// Default size of a single part in multipart uploads. S3 requires all parts except for the last one
+/// to be at least 5 MiB.
+const DEFAULT_PART_SIZE: usize = 16 * 1_024 * 1_024;
+
+impl<E> From<SdkError<E, HttpResponse>> for ObjectStoreError
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    fn from(err: SdkError<E, HttpResponse>) -> Self {
+        let is_not_found = err
+            .raw_response()
+            .map_or(false, |response| response.status().as_u16() == 404);
+        if is_not_found {
+            ObjectStoreError::KeyNotFound(err.into())
+        } else {
+            ObjectStoreError::Other(err.into())
+        }
+    }
+}
+
+pub(crate) struct S3Store {
+    client: Client,
+    s3_bucket: String,
+    /// Base URL used in [`ObjectStore::storage_prefix_raw()`].
+    base_url: String,
+    part_size: usize,
+}
+
+impl fmt::Debug for S3Store {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("S3Store")
+            .field("s3_bucket", &self.s3_bucket)
+            .field("base_url", &self.base_url)
+            .field("part_size", &self.part_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl S3Store {
+    pub async fn new(
+        s3_bucket: String,
+        region: Option<String>,
+        endpoint: Option<String>,
+        max_retries: u16,
+    ) -> Self {
+        let retry_config = RetryConfig::standard().with_max_attempts(u32::from(max_retries) + 1);
+        let mut loader = aws_config::defaults(BehaviorVersion::latest()).retry_config(retry_config);
+        if let Some(region) = region {
+            loader = loader.region(Region::new(region));
+        }
+        if let Some(endpoint) = &endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let sdk_config = loader.load().await;
+        let config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            // Custom endpoints (e.g., MinIO) usually don't support virtual-hosted–style addressing.
+            .force_path_style(endpoint.is_some())
+            .build();
+
+        let base_url = if let Some(endpoint) = endpoint {
+            format!("{}/{s3_bucket}", endpoint.trim_end_matches('/'))
+        } else {
+            let region = sdk_config.region().map_or("us-east-1", Region::as_ref);
+            format!("https://{s3_bucket}.s3.{region}.amazonaws.com")
+        };
+        Self::from_client(Client::from_conf(config), s3_bucket, base_url)
+    }
+
+    fn from_client(client: Client, s3_bucket: String, base_url: String) -> Self {
+        Self {
+            client,
+            s3_bucket,
+            base_url,
+            part_size: DEFAULT_PART_SIZE,
+        }
+    }
+
+    fn filename(bucket: Bucket, key: &str) -> String {
+        format!("{bucket}/{key}")
+    }
+
+    /// Checks whether the value should be uploaded in parts. Only applies to the buckets
+    /// that can contain large blobs.
+    fn should_use_multipart(&self, bucket: Bucket, value_len: usize) -> bool {
+        matches!(bucket, Bucket::StorageSnapshot | Bucket::ProofsFri) && value_len > self.part_size
+    }
+
+    async fn put_multipart(&self, filename: &str, value: &[u8]) -> Result<(), ObjectStoreError> {
+        let upload = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.s3_bucket)
+            .key(filename)
+            .send()
+            .await?;
+        let upload_id = upload.upload_id().ok_or_else(|| {
+            ObjectStoreError::Other("S3 did not return ID for a multipart upload".into())
+        })?;
+
+        match self.upload_parts(filename, upload_id, value).await {
+            Ok(parts) => {
+                let upload = CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build();
+                self.client
+                    .complete_multipart_upload()
+                    .bucket(&self.s3_bucket)
+                    .key(filename)
+                    .upload_id(upload_id)
+                    .multipart_upload(upload)
+                    .send()
+                    .await?;
+                Ok(())
+            }
+            Err(err) => {
+                // Abort the upload so that uploaded parts don't linger in the bucket.
+                let abort_result = self
+                    .client
+                    .abort_multipart_upload()
+                    .bucket(&self.s3_bucket)
+                    .key(filename)
+                    .upload_id(upload_id)
+                    .send()
+                    .await;
+                if let Err(abort_err) = abort_result {
+                    tracing::warn!(
+                        "Failed aborting multipart upload {upload_id} for {filename}: {abort_err}"
+                    );
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn upload_parts(
+        &self,
+        filename: &str,
+        upload_id: &str,
+        value: &[u8],
+    ) -> Result<Vec<CompletedPart>, ObjectStoreError> {
+        let mut parts = vec![];
+        for (i, chunk) in value.chunks(self.part_size).enumerate() {
+            let part_number = i32::try_from(i + 1).map_err(|err| {
+                ObjectStoreError::Other(format!("too many parts in multipart upload: {err}").into())
+            })?;
+            let output = self
+                .client
+                .upload_part()
+                .bucket(&self.s3_bucket)
+                .key(filename)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from(chunk.to_vec()))
+                .send()
+                .await?;
+            let part = CompletedPart::builder()
+                .set_e_tag(output.e_tag().map(str::to_owned))
+                .part_number(part_number)
+                .build();
+            parts.push(part);
+        }
+        Ok(parts)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3Store {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let fetch_latency = OBJECT_STORE_METRICS.start_fetch(bucket);
+        let filename = Self::filename(bucket, key);
+        tracing::trace!(
+            "Fetching data from S3 for key {filename} from bucket {}",
+            self.s3_bucket
+        );
+
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.s3_bucket)
+            .key(&filename)
+            .send()
+            .await?;
+        let blob = output
+            .body
+            .collect()
+            .await
+            .map_err(|err| ObjectStoreError::Other(err.into()))?
+            .into_bytes();
+
+        let elapsed = fetch_latency.observe();
+        tracing::trace!(
+            "Fetched data from S3 for key {key} from bucket {bucket} and it took: {elapsed:?}"
+        );
+        Ok(blob.to_vec())
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let store_latency = OBJECT_STORE_METRICS.start_store(bucket);
+        let filename = Self::filename(bucket, key);
+        tracing::trace!(
+            "Storing data to S3 for key {filename} from bucket {}",
+            self.s3_bucket
+        );
+
+        if self.should_use_multipart(bucket, value.len()) {
+            self.put_multipart(&filename, &value).await?;
+        } else {
+            self.client
+                .put_object()
+                .bucket(&self.s3_bucket)
+                .key(&filename)
+                .body(ByteStream::from(value))
+                .send()
+                .await?;
+        }
+
+        let elapsed = store_latency.observe();
+        tracing::trace!(
+            "Stored data to S3 for key {key} from bucket {bucket} and it took: {elapsed:?}"
+        );
+        Ok(())
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let filename = Self::filename(bucket, key);
+        tracing::trace!(
+            "Removing data from S3 for key {filename} from bucket {}",
+            self.s3_bucket
+        );
+        self.client
+            .delete_object()
+            .bucket(&self.s3_bucket)
+            .key(filename)
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{bucket}", self.base_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        convert::Infallible,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use aws_sdk_s3::config::Credentials;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Method, Request, Response, Server, StatusCode,
+    };
+
+    use super::*;
+
+    /// In-memory stand-in for an S3-compatible server supporting the subset of API used by [`S3Store`].
+    #[derive(Debug, Default)]
+    struct MockS3State {
+        objects: HashMap<String, Vec<u8>>,
+        uploads: HashMap<String, BTreeMap<i32, Vec<u8>>>,
+        completed_multipart_uploads: usize,
+        next_upload_id: usize,
+    }
+
+    impl MockS3State {
+        fn handle(
+            &mut self,
+            method: &Method,
+            path: &str,
+            query: &HashMap<&str, &str>,
+            body: Vec<u8>,
+        ) -> Response<Body> {
+            let upload_id = query.get("uploadId").copied();
+            match (method, upload_id) {
+                (&Method::GET, None) => match self.objects.get(path) {
+                    Some(object) => Response::new(Body::from(object.clone())),
+                    None => Self::response(
+                        StatusCode::NOT_FOUND,
+                        "<Error><Code>NoSuchKey</Code><Message>not found</Message></Error>",
+                    ),
+                },
+                (&Method::PUT, None) => {
+                    self.objects.insert(path.to_owned(), body);
+                    Self::response(StatusCode::OK, "")
+                }
+                (&Method::DELETE, None) => {
+                    self.objects.remove(path);
+                    Self::response(StatusCode::NO_CONTENT, "")
+                }
+                (&Method::POST, None) if query.contains_key("uploads") => {
+                    let upload_id = format!("upload-{}", self.next_upload_id);
+                    self.next_upload_id += 1;
+                    self.uploads.insert(upload_id.clone(), BTreeMap::new());
+                    let body = format!(
+                        "<InitiateMultipartUploadResult><Key>{path}</Key>\
+                         <UploadId>{upload_id}</UploadId></InitiateMultipartUploadResult>"
+                    );
+                    Self::response(StatusCode::OK, &body)
+                }
+                (&Method::PUT, Some(upload_id)) => {
+                    let part_number = query["partNumber"].parse().unwrap();
+                    let parts = self.uploads.get_mut(upload_id).unwrap();
+                    parts.insert(part_number, body);
+                    let mut response = Self::response(StatusCode::OK, "");
+                    let etag = format!("\"part-{part_number}\"").parse().unwrap();
+                    response.headers_mut().insert("ETag", etag);
+                    response
+                }
+                (&Method::POST, Some(upload_id)) => {
+                    let parts = self.uploads.remove(upload_id).unwrap();
+                    self.objects
+                        .insert(path.to_owned(), parts.into_values().flatten().collect());
+                    self.completed_multipart_uploads += 1;
+                    let body = format!(
+                        "<CompleteMultipartUploadResult><Key>{path}</Key>\
+                         <ETag>\"object\"</ETag></CompleteMultipartUploadResult>"
+                    );
+                    Self::response(StatusCode::OK, &body)
+                }
+                (&Method::DELETE, Some(upload_id)) => {
+                    self.uploads.remove(upload_id);
+                    Self::response(StatusCode::NO_CONTENT, "")
+                }
+                _ => Self::response(StatusCode::NOT_IMPLEMENTED, ""),
+            }
+        }
+
+        fn response(status: StatusCode, body: &str) -> Response<Body> {
+            let mut response = Response::new(Body::from(body.to_owned()));
+            *response.status_mut() = status;
+            response
+        }
+    }
+
+    async fn handle_request(
+        state: Arc<Mutex<MockS3State>>,
+        request: Request<Body>,
+    ) -> Result<Response<Body>, Infallible> {
+        let (parts, body) = request.into_parts();
+        let body = hyper::body::to_bytes(body).await.unwrap().to_vec();
+        let query_str = parts.uri.query().unwrap_or_default();
+        let query: HashMap<_, _> = query_str
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+            .collect();
+        let response = state
+            .lock()
+            .unwrap()
+            .handle(&parts.method, parts.uri.path(), &query, body);
+        Ok(response)
+    }
+
+    async fn start_mock_s3_server() -> (SocketAddr, Arc<Mutex<MockS3State>>) {
+        let state = Arc::<Mutex<MockS3State>>::default();
+        let state_for_server = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = state_for_server.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    handle_request(state.clone(), request)
+                }))
+            }
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let local_addr = server.local_addr();
+        tokio::spawn(server);
+        (local_addr, state)
+    }
+
+    fn create_store(server_addr: SocketAddr) -> S3Store {
+        let endpoint = format!("http://{server_addr}");
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .endpoint_url(&endpoint)
+            .force_path_style(true)
+            .credentials_provider(Credentials::new("test", "test", None, None, "test"))
+            .build();
+        let base_url = format!("{endpoint}/test-bucket");
+        S3Store::from_client(
+            Client::from_conf(config),
+            "test-bucket".to_owned(),
+            base_url,
+        )
+    }
+
+    #[tokio::test]
+    async fn basic_s3_operations() {
+        let (server_addr, state) = start_mock_s3_server().await;
+        let store = create_store(server_addr);
+
+        let err = store
+            .get_raw(Bucket::ProverJobs, "missing")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+
+        store
+            .put_raw(Bucket::ProverJobs, "test-key.bin", vec![1, 2, 3])
+            .await
+            .unwrap();
+        let value = store
+            .get_raw(Bucket::ProverJobs, "test-key.bin")
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2, 3]);
+        assert!(state
+            .lock()
+            .unwrap()
+            .objects
+            .contains_key("/test-bucket/prover_jobs/test-key.bin"));
+
+        store
+            .remove_raw(Bucket::ProverJobs, "test-key.bin")
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::ProverJobs, "test-key.bin")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+
+        assert_eq!(
+            store.storage_prefix_raw(Bucket::ProverJobs),
+            format!("http://{server_addr}/test-bucket/prover_jobs")
+        );
+    }
+
+    #[tokio::test]
+    async fn multipart_uploads() {
+        let (server_addr, state) = start_mock_s3_server().await;
+        let mut store = create_store(server_addr);
+        store.part_size = 10;
+
+        let value: Vec<u8> = (0..=35).collect();
+        store
+            .put_raw(Bucket::StorageSnapshot, "snapshot", value.clone())
+            .await
+            .unwrap();
+        {
+            let state = state.lock().unwrap();
+            assert_eq!(state.completed_multipart_uploads, 1);
+            assert!(state.uploads.is_empty());
+        }
+        let loaded_value = store
+            .get_raw(Bucket::StorageSnapshot, "snapshot")
+            .await
+            .unwrap();
+        assert_eq!(loaded_value, value);
+
+        // Small values and values in other buckets should be uploaded in a single request.
+        store
+            .put_raw(Bucket::StorageSnapshot, "small", vec![0; 10])
+            .await
+            .unwrap();
+        store
+            .put_raw(Bucket::ProverJobs, "large", value)
+            .await
+            .unwrap();
+        assert_eq!(state.lock().unwrap().completed_multipart_uploads, 1);
+    }
+}
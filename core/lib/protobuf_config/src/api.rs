@@ -120,6 +120,46 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .transpose()
                 .context("websocket_requests_per_minute_limit")?,
             tree_api_url: self.tree_api_url.clone(),
+            requests_quota_per_minute: self
+                .requests_quota_per_minute
+                .map(|x| x.try_into())
+                .transpose()
+                .context("requests_quota_per_minute")?,
+            method_weights: if self.method_weights.is_empty() {
+                None
+            } else {
+                Some(
+                    self.method_weights
+                        .iter()
+                        .enumerate()
+                        .map(|(i, weight)| weight.read().context(i))
+                        .collect::<anyhow::Result<_>>()
+                        .context("method_weights")?,
+                )
+            },
+            api_key_header: self.api_key_header.clone(),
+            method_allowlist: (!self.method_allowlist.is_empty())
+                .then(|| self.method_allowlist.clone()),
+            method_denylist: (!self.method_denylist.is_empty())
+                .then(|| self.method_denylist.clone()),
+            struct_log_max_steps: self
+                .struct_log_max_steps
+                .map(|x| x.try_into())
+                .transpose()
+                .context("struct_log_max_steps")?,
+            api_keys: (!self.api_keys.is_empty()).then(|| self.api_keys.clone()),
+            trusted_proxies: if self.trusted_proxies.is_empty() {
+                None
+            } else {
+                Some(
+                    self.trusted_proxies
+                        .iter()
+                        .enumerate()
+                        .map(|(i, addr)| addr.parse().context(i))
+                        .collect::<anyhow::Result<_>>()
+                        .context("trusted_proxies")?,
+                )
+            },
         })
     }
     fn build(this: &Self::Type) -> Self {
@@ -169,6 +209,42 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .websocket_requests_per_minute_limit
                 .map(|x| x.into()),
             tree_api_url: this.tree_api_url.clone(),
+            requests_quota_per_minute: this.requests_quota_per_minute.map(|x| x.into()),
+            method_weights: this
+                .method_weights
+                .iter()
+                .flatten()
+                .map(ProtoRepr::build)
+                .collect(),
+            api_key_header: this.api_key_header.clone(),
+            method_allowlist: this.method_allowlist.clone().unwrap_or_default(),
+            method_denylist: this.method_denylist.clone().unwrap_or_default(),
+            struct_log_max_steps: this.struct_log_max_steps.map(|x| x.try_into().unwrap()),
+            api_keys: this.api_keys.clone().unwrap_or_default(),
+            trusted_proxies: this
+                .trusted_proxies
+                .iter()
+                .flatten()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::MethodWeight {
+    type Type = api::MethodWeight;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            method: required(&self.method).context("method")?.clone(),
+            weight: required(&self.weight)
+                .and_then(|&weight| Ok(weight.try_into()?))
+                .context("weight")?,
+        })
+    }
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            method: Some(this.method.clone()),
+            weight: Some(this.weight.into()),
         }
     }
 }
@@ -1,9 +1,9 @@
 use anyhow::Context as _;
-use zksync_basic_types::network::Network;
+use zksync_basic_types::{network::Network, Address};
 use zksync_config::configs;
 use zksync_protobuf::required;
 
-use crate::{parse_h160, proto, repr::ProtoRepr};
+use crate::{parse_h160, parse_h256, proto, repr::ProtoRepr};
 
 impl proto::Network {
     fn new(n: &Network) -> Self {
@@ -51,6 +51,61 @@ impl proto::FeeModelVersion {
     }
 }
 
+impl proto::FeeInputStrategy {
+    fn new(n: &configs::chain::FeeInputStrategyKind) -> Self {
+        use configs::chain::FeeInputStrategyKind as From;
+        match n {
+            From::Default => Self::Default,
+            From::Ema => Self::Ema,
+            From::PubdataSurge => Self::PubdataSurge,
+        }
+    }
+
+    fn parse(&self) -> configs::chain::FeeInputStrategyKind {
+        use configs::chain::FeeInputStrategyKind as To;
+        match self {
+            Self::Default => To::Default,
+            Self::Ema => To::Ema,
+            Self::PubdataSurge => To::PubdataSurge,
+        }
+    }
+}
+
+fn parse_h160_list(addresses: &[Vec<u8>]) -> anyhow::Result<Vec<Address>> {
+    addresses
+        .iter()
+        .enumerate()
+        .map(|(i, address)| parse_h160(address).with_context(|| i.to_string()))
+        .collect()
+}
+
+fn build_h160_list(addresses: &[Address]) -> Vec<Vec<u8>> {
+    addresses
+        .iter()
+        .map(|address| address.as_bytes().into())
+        .collect()
+}
+
+impl proto::TxOrderingPolicy {
+    fn new(n: &configs::chain::TxOrderingPolicyKind) -> Self {
+        use configs::chain::TxOrderingPolicyKind as From;
+        match n {
+            From::Fifo => Self::Fifo,
+            From::FeePriority => Self::FeePriority,
+            From::RoundRobin => Self::RoundRobin,
+        }
+    }
+
+    fn parse(&self) -> configs::chain::TxOrderingPolicyKind {
+        use configs::chain::TxOrderingPolicyKind as To;
+        match self {
+            Self::Fifo => To::Fifo,
+            Self::FeePriority => To::FeePriority,
+            Self::RoundRobin => To::RoundRobin,
+        }
+    }
+}
+
 impl ProtoRepr for proto::EthNetwork {
     type Type = configs::chain::NetworkConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -126,6 +181,15 @@ impl ProtoRepr for proto::StateKeeper {
                 .and_then(|x| Ok(proto::FeeModelVersion::try_from(*x)?))
                 .context("fee_model_version")?
                 .parse(),
+            fee_input_strategy: self
+                .fee_input_strategy
+                .map(proto::FeeInputStrategy::try_from)
+                .transpose()
+                .context("fee_input_strategy")?
+                .map_or_else(Default::default, |strategy| strategy.parse()),
+            fee_input_ema_half_life_sec: self.fee_input_ema_half_life_sec,
+            fee_input_pubdata_surge_threshold: self.fee_input_pubdata_surge_threshold,
+            fee_input_pubdata_surge_multiplier: self.fee_input_pubdata_surge_multiplier,
             validation_computational_gas_limit: *required(&self.validation_computational_gas_limit)
                 .context("validation_computational_gas_limit")?,
             save_call_traces: *required(&self.save_call_traces).context("save_call_traces")?,
@@ -140,6 +204,20 @@ impl ProtoRepr for proto::StateKeeper {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("enum_index_migration_chunk_size")?,
+            tx_sender_allowlist: parse_h160_list(&self.tx_sender_allowlist)
+                .context("tx_sender_allowlist")?,
+            tx_sender_denylist: parse_h160_list(&self.tx_sender_denylist)
+                .context("tx_sender_denylist")?,
+            contract_deployer_allowlist: parse_h160_list(&self.contract_deployer_allowlist)
+                .context("contract_deployer_allowlist")?,
+            tx_access_policy_path: self.tx_access_policy_path.clone(),
+            custom_seal_criteria: self
+                .custom_seal_criteria
+                .iter()
+                .enumerate()
+                .map(|(i, criterion)| criterion.read().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("custom_seal_criteria")?,
         })
     }
 
@@ -167,6 +245,10 @@ impl ProtoRepr for proto::StateKeeper {
             max_gas_per_batch: Some(this.max_gas_per_batch),
             max_pubdata_per_batch: Some(this.max_pubdata_per_batch),
             fee_model_version: Some(proto::FeeModelVersion::new(&this.fee_model_version).into()),
+            fee_input_strategy: Some(proto::FeeInputStrategy::new(&this.fee_input_strategy).into()),
+            fee_input_ema_half_life_sec: this.fee_input_ema_half_life_sec,
+            fee_input_pubdata_surge_threshold: this.fee_input_pubdata_surge_threshold,
+            fee_input_pubdata_surge_multiplier: this.fee_input_pubdata_surge_multiplier,
             validation_computational_gas_limit: Some(this.validation_computational_gas_limit),
             save_call_traces: Some(this.save_call_traces),
             virtual_blocks_interval: Some(this.virtual_blocks_interval),
@@ -176,10 +258,67 @@ impl ProtoRepr for proto::StateKeeper {
                 .enum_index_migration_chunk_size
                 .as_ref()
                 .map(|x| (*x).try_into().unwrap()),
+            tx_sender_allowlist: build_h160_list(&this.tx_sender_allowlist),
+            tx_sender_denylist: build_h160_list(&this.tx_sender_denylist),
+            contract_deployer_allowlist: build_h160_list(&this.contract_deployer_allowlist),
+            tx_access_policy_path: this.tx_access_policy_path.clone(),
+            custom_seal_criteria: this
+                .custom_seal_criteria
+                .iter()
+                .map(ProtoRepr::build)
+                .collect(),
         }
     }
 }
 
+impl ProtoRepr for proto::SealCriterion {
+    type Type = configs::chain::SealCriterionConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        use proto::seal_criterion::Kind;
+
+        Ok(match required(&self.kind).context("kind")? {
+            Kind::MaxL1Txs(criterion) => Self::Type::MaxL1Txs {
+                max_count: required(&criterion.max_count)
+                    .and_then(|&count| Ok(count.try_into()?))
+                    .context("max_count")?,
+            },
+            Kind::MaxBatchAge(criterion) => Self::Type::MaxBatchAge {
+                max_age_ms: *required(&criterion.max_age_ms).context("max_age_ms")?,
+            },
+            Kind::Event(criterion) => Self::Type::Event {
+                address: required(&criterion.address)
+                    .and_then(|address| parse_h160(address))
+                    .context("address")?,
+                topic: criterion
+                    .topic
+                    .as_ref()
+                    .map(|topic| parse_h256(topic))
+                    .transpose()
+                    .context("topic")?,
+            },
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        use proto::seal_criterion::{Event, Kind, MaxBatchAge, MaxL1Txs};
+
+        let kind = match this {
+            Self::Type::MaxL1Txs { max_count } => Kind::MaxL1Txs(MaxL1Txs {
+                max_count: Some(max_count.get()),
+            }),
+            Self::Type::MaxBatchAge { max_age_ms } => Kind::MaxBatchAge(MaxBatchAge {
+                max_age_ms: Some(*max_age_ms),
+            }),
+            Self::Type::Event { address, topic } => Kind::Event(Event {
+                address: Some(address.as_bytes().into()),
+                topic: topic.map(|topic| topic.as_bytes().into()),
+            }),
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
 impl ProtoRepr for proto::OperationsManager {
     type Type = configs::chain::OperationsManagerConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -207,6 +346,20 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: *required(&self.stuck_tx_timeout).context("stuck_tx_timeout")?,
             remove_stuck_txs: *required(&self.remove_stuck_txs).context("remove_stuck_txs")?,
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            snapshot_path: self.snapshot_path.clone(),
+            snapshot_interval_ms: self.snapshot_interval_ms,
+            max_pending_txs_per_account: self
+                .max_pending_txs_per_account
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_pending_txs_per_account")?,
+            replacement_fee_bump_percent: self.replacement_fee_bump_percent,
+            ordering_policy: self
+                .ordering_policy
+                .map(proto::TxOrderingPolicy::try_from)
+                .transpose()
+                .context("ordering_policy")?
+                .map_or_else(Default::default, |policy| policy.parse()),
         })
     }
 
@@ -218,6 +371,13 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: Some(this.stuck_tx_timeout),
             remove_stuck_txs: Some(this.remove_stuck_txs),
             delay_interval: Some(this.delay_interval),
+            snapshot_path: this.snapshot_path.clone(),
+            snapshot_interval_ms: this.snapshot_interval_ms,
+            max_pending_txs_per_account: this
+                .max_pending_txs_per_account
+                .map(|x| x.try_into().unwrap()),
+            replacement_fee_bump_percent: this.replacement_fee_bump_percent,
+            ordering_policy: Some(proto::TxOrderingPolicy::new(&this.ordering_policy).into()),
         }
     }
 }
@@ -45,6 +45,24 @@ impl proto::ProofLoadingMode {
     }
 }
 
+impl proto::PubdataSendingMode {
+    fn new(x: &configs::eth_sender::PubdataSendingMode) -> Self {
+        use configs::eth_sender::PubdataSendingMode as From;
+        match x {
+            From::Calldata => Self::Calldata,
+            From::Blobs => Self::Blobs,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_sender::PubdataSendingMode {
+        use configs::eth_sender::PubdataSendingMode as To;
+        match self {
+            Self::Calldata => To::Calldata,
+            Self::Blobs => To::Blobs,
+        }
+    }
+}
+
 impl ProtoRepr for proto::EthSender {
     type Type = configs::eth_sender::ETHSenderConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -109,6 +127,12 @@ impl ProtoRepr for proto::Sender {
                 .and_then(|x| Ok(proto::ProofLoadingMode::try_from(*x)?))
                 .context("proof_loading_mode")?
                 .parse(),
+            pubdata_sending_mode: self
+                .pubdata_sending_mode
+                .map(proto::PubdataSendingMode::try_from)
+                .transpose()
+                .context("pubdata_sending_mode")?
+                .map_or_else(Default::default, |mode| mode.parse()),
         })
     }
 
@@ -137,6 +161,9 @@ impl ProtoRepr for proto::Sender {
             l1_batch_min_age_before_execute_seconds: this.l1_batch_min_age_before_execute_seconds,
             max_acceptable_priority_fee_in_gwei: Some(this.max_acceptable_priority_fee_in_gwei),
             proof_loading_mode: Some(proto::ProofLoadingMode::new(&this.proof_loading_mode).into()),
+            pubdata_sending_mode: Some(
+                proto::PubdataSendingMode::new(&this.pubdata_sending_mode).into(),
+            ),
         }
     }
 }
@@ -159,6 +186,17 @@ impl ProtoRepr for proto::GasAdjuster {
             internal_enforced_l1_gas_price: self.internal_enforced_l1_gas_price,
             poll_period: *required(&self.poll_period).context("poll_period")?,
             max_l1_gas_price: self.max_l1_gas_price,
+            max_blob_base_fee_samples: self
+                .max_blob_base_fee_samples
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_blob_base_fee_samples")?
+                .unwrap_or(10),
+            internal_pubdata_pricing_multiplier: self
+                .internal_pubdata_pricing_multiplier
+                .unwrap_or(1.0),
+            max_blob_base_fee: self.max_blob_base_fee,
+            min_blob_base_fee: self.min_blob_base_fee,
         })
     }
 
@@ -172,6 +210,10 @@ impl ProtoRepr for proto::GasAdjuster {
             internal_enforced_l1_gas_price: this.internal_enforced_l1_gas_price,
             poll_period: Some(this.poll_period),
             max_l1_gas_price: this.max_l1_gas_price,
+            max_blob_base_fee_samples: Some(this.max_blob_base_fee_samples.try_into().unwrap()),
+            internal_pubdata_pricing_multiplier: Some(this.internal_pubdata_pricing_multiplier),
+            max_blob_base_fee: this.max_blob_base_fee,
+            min_blob_base_fee: this.min_blob_base_fee,
         }
     }
 }
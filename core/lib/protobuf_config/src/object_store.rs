@@ -37,6 +37,11 @@ impl ProtoRepr for proto::ObjectStore {
                     .context("file_backed_base_path")?
                     .clone(),
             },
+            proto::object_store::Mode::S3(mode) => ObjectStoreMode::S3 {
+                s3_bucket: required(&mode.bucket).context("bucket")?.clone(),
+                s3_region: mode.region.clone(),
+                s3_endpoint: mode.endpoint.clone(),
+            },
         };
 
         Ok(Self::Type {
@@ -44,6 +49,7 @@ impl ProtoRepr for proto::ObjectStore {
             max_retries: required(&self.max_retries)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
+            enable_compression: self.enable_compression.unwrap_or(false),
         })
     }
 
@@ -75,11 +81,21 @@ impl ProtoRepr for proto::ObjectStore {
             } => proto::object_store::Mode::FileBacked(proto::object_store::FileBacked {
                 file_backed_base_path: Some(file_backed_base_path.clone()),
             }),
+            ObjectStoreMode::S3 {
+                s3_bucket,
+                s3_region,
+                s3_endpoint,
+            } => proto::object_store::Mode::S3(proto::object_store::S3 {
+                bucket: Some(s3_bucket.clone()),
+                region: s3_region.clone(),
+                endpoint: s3_endpoint.clone(),
+            }),
         };
 
         Self {
             mode: Some(mode),
             max_retries: Some(this.max_retries.into()),
+            enable_compression: Some(this.enable_compression),
         }
     }
 }
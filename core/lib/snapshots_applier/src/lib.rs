@@ -53,7 +53,9 @@ enum SnapshotsApplierError {
 impl SnapshotsApplierError {
     fn object_store(err: ObjectStoreError, context: String) -> Self {
         match err {
-            ObjectStoreError::KeyNotFound(_) | ObjectStoreError::Serialization(_) => {
+            ObjectStoreError::KeyNotFound(_)
+            | ObjectStoreError::Serialization(_)
+            | ObjectStoreError::Corrupted(_) => {
                 Self::Fatal(anyhow::Error::from(err).context(context))
             }
             ObjectStoreError::Other(_) => {
@@ -98,6 +100,11 @@ pub trait SnapshotsApplierMainNodeClient: fmt::Debug + Send + Sync {
 
     async fn fetch_newest_snapshot(&self) -> EnrichedClientResult<Option<SnapshotHeader>>;
 
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>>;
+
     async fn fetch_tokens(
         &self,
         at_miniblock: MiniblockNumber,
@@ -124,9 +131,16 @@ impl SnapshotsApplierMainNodeClient for HttpClient {
         let Some(newest_snapshot) = snapshots.snapshots_l1_batch_numbers.first() else {
             return Ok(None);
         };
-        self.get_snapshot_by_l1_batch_number(*newest_snapshot)
+        self.fetch_snapshot(*newest_snapshot).await
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        self.get_snapshot_by_l1_batch_number(l1_batch_number)
             .rpc_context("get_snapshot_by_l1_batch_number")
-            .with_arg("number", newest_snapshot)
+            .with_arg("number", &l1_batch_number)
             .await
     }
 
@@ -238,6 +252,9 @@ struct SnapshotsApplier<'a> {
     main_node_client: &'a dyn SnapshotsApplierMainNodeClient,
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
+    /// L1 batches of snapshots that need to be applied, starting from a full snapshot and followed
+    /// by incremental snapshots. The last element corresponds to `applied_snapshot_status`.
+    snapshot_chain: Vec<L1BatchNumber>,
     health_updater: &'a HealthUpdater,
     factory_deps_recovered: bool,
     tokens_recovered: bool,
@@ -308,12 +325,15 @@ impl<'a> SnapshotsApplier<'a> {
         let (applied_snapshot_status, created_from_scratch) =
             Self::prepare_applied_snapshot_status(&mut storage_transaction, main_node_client)
                 .await?;
+        let snapshot_chain =
+            Self::fetch_snapshot_chain(main_node_client, &applied_snapshot_status).await?;
 
         let mut this = Self {
             connection_pool,
             main_node_client,
             blob_store,
             applied_snapshot_status,
+            snapshot_chain,
             health_updater,
             factory_deps_recovered: !created_from_scratch,
             tokens_recovered: false,
@@ -362,6 +382,7 @@ impl<'a> SnapshotsApplier<'a> {
 
         let snapshot = snapshot_response
             .context("no snapshots on main node; snapshot recovery is impossible")?;
+        snapshot.check_version()?;
         let l1_batch_number = snapshot.l1_batch_number;
         let miniblock_number = snapshot.miniblock_number;
         tracing::info!(
@@ -393,6 +414,53 @@ impl<'a> SnapshotsApplier<'a> {
         })
     }
 
+    /// Resolves the chain of snapshots necessary to recover the snapshot with the specified status.
+    async fn fetch_snapshot_chain(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        status: &SnapshotRecoveryStatus,
+    ) -> Result<Vec<L1BatchNumber>, SnapshotsApplierError> {
+        let chunk_count = status.storage_logs_chunks_processed.len();
+        let mut chain = vec![status.l1_batch_number];
+        let mut l1_batch_number = status.l1_batch_number;
+        loop {
+            let snapshot = main_node_client
+                .fetch_snapshot(l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!("snapshot for L1 batch #{l1_batch_number} is missing on main node")
+                })?;
+            snapshot.check_version()?;
+            if snapshot.storage_logs_chunks.len() != chunk_count {
+                let err = anyhow::anyhow!(
+                    "snapshot for L1 batch #{l1_batch_number} has {} storage logs chunks; expected {chunk_count}",
+                    snapshot.storage_logs_chunks.len()
+                );
+                return Err(err.into());
+            }
+
+            let Some(base_l1_batch_number) = snapshot.base_l1_batch_number else {
+                break;
+            };
+            if base_l1_batch_number >= l1_batch_number {
+                let err = anyhow::anyhow!(
+                    "snapshot for L1 batch #{l1_batch_number} has invalid base L1 batch #{base_l1_batch_number}"
+                );
+                return Err(err.into());
+            }
+            chain.push(base_l1_batch_number);
+            l1_batch_number = base_l1_batch_number;
+        }
+        chain.reverse();
+
+        if chain.len() > 1 {
+            tracing::info!(
+                "Snapshot for L1 batch #{} is incremental; recovering from snapshot chain {chain:?}",
+                status.l1_batch_number
+            );
+        }
+        Ok(chain)
+    }
+
     fn update_health(&self) {
         let details = SnapshotsApplierHealthDetails {
             snapshot_miniblock: self.applied_snapshot_status.miniblock_number,
@@ -417,24 +485,26 @@ impl<'a> SnapshotsApplier<'a> {
         let latency = METRICS.initial_stage_duration[&InitialStage::ApplyFactoryDeps].start();
 
         tracing::debug!("Fetching factory dependencies from object store");
-        let l1_batch_number = self.applied_snapshot_status.l1_batch_number;
-        let factory_deps: SnapshotFactoryDependencies =
-            self.blob_store.get(l1_batch_number).await.map_err(|err| {
-                let context = format!(
-                    "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
-                );
-                SnapshotsApplierError::object_store(err, context)
-            })?;
-        tracing::debug!(
-            "Fetched {} factory dependencies from object store",
-            factory_deps.factory_deps.len()
-        );
+        let mut all_deps_hashmap = HashMap::<H256, Vec<u8>>::new();
+        for &l1_batch_number in &self.snapshot_chain {
+            let factory_deps: SnapshotFactoryDependencies =
+                self.blob_store.get(l1_batch_number).await.map_err(|err| {
+                    let context = format!(
+                        "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
+                    );
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            tracing::debug!(
+                "Fetched {} factory dependencies for L1 batch #{l1_batch_number} from object store",
+                factory_deps.factory_deps.len()
+            );
 
-        let all_deps_hashmap: HashMap<H256, Vec<u8>> = factory_deps
-            .factory_deps
-            .into_iter()
-            .map(|dep| (hash_bytecode(&dep.bytecode.0), dep.bytecode.0))
-            .collect();
+            let deps = factory_deps
+                .factory_deps
+                .into_iter()
+                .map(|dep| (hash_bytecode(&dep.bytecode.0), dep.bytecode.0));
+            all_deps_hashmap.extend(deps);
+        }
         storage
             .factory_deps_dal()
             .insert_factory_deps(
@@ -503,17 +573,21 @@ impl<'a> SnapshotsApplier<'a> {
         let latency =
             METRICS.storage_logs_chunks_duration[&StorageLogsChunksStage::LoadFromGcs].start();
 
-        let storage_key = SnapshotStorageLogsStorageKey {
-            chunk_id,
-            l1_batch_number: self.applied_snapshot_status.l1_batch_number,
-        };
-        let storage_snapshot_chunk: SnapshotStorageLogsChunk =
-            self.blob_store.get(storage_key).await.map_err(|err| {
-                let context =
-                    format!("cannot fetch storage logs {storage_key:?} from object store");
-                SnapshotsApplierError::object_store(err, context)
-            })?;
-        let storage_logs = &storage_snapshot_chunk.storage_logs;
+        let mut chunks = Vec::with_capacity(self.snapshot_chain.len());
+        for &l1_batch_number in &self.snapshot_chain {
+            let storage_key = SnapshotStorageLogsStorageKey {
+                chunk_id,
+                l1_batch_number,
+            };
+            let storage_snapshot_chunk: SnapshotStorageLogsChunk =
+                self.blob_store.get(storage_key).await.map_err(|err| {
+                    let context =
+                        format!("cannot fetch storage logs {storage_key:?} from object store");
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            chunks.push(storage_snapshot_chunk);
+        }
+        let storage_logs = &merge_storage_logs_chunks(chunks);
         let latency = latency.observe();
         tracing::info!(
             "Loaded {} storage logs from GCS for chunk {chunk_id} in {latency:?}",
@@ -640,3 +714,19 @@ impl<'a> SnapshotsApplier<'a> {
         Ok(())
     }
 }
+
+/// Merges storage logs chunks with the same ID from a snapshot chain (ordered from the full snapshot
+/// to the newest incremental one). Logs from newer snapshots override logs for the same keys from older ones.
+fn merge_storage_logs_chunks(mut chunks: Vec<SnapshotStorageLogsChunk>) -> Vec<SnapshotStorageLog> {
+    if chunks.len() == 1 {
+        return chunks.pop().unwrap().storage_logs;
+    }
+
+    let mut logs_by_key = HashMap::new();
+    for chunk in chunks {
+        logs_by_key.extend(chunk.storage_logs.into_iter().map(|log| (log.key, log)));
+    }
+    let mut logs: Vec<_> = logs_by_key.into_values().collect();
+    logs.sort_unstable_by_key(|log| log.key.hashed_key());
+    logs
+}
@@ -6,7 +6,9 @@ use test_casing::test_casing;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{
     block::{L1BatchHeader, MiniblockHeader},
-    get_code_key, Address, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
+    get_code_key,
+    snapshots::SnapshotFactoryDependency,
+    Address, Bytes, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
 };
 
 use self::utils::{
@@ -96,6 +98,135 @@ async fn snapshots_creator_can_successfully_recover_db(
         .unwrap();
 }
 
+#[tokio::test]
+async fn recovering_from_incremental_snapshot() {
+    let pool = ConnectionPool::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let base_l1_batch_number = L1BatchNumber(100);
+    let base_storage_logs = random_storage_logs(base_l1_batch_number, 100);
+
+    // Incremental storage logs must be placed in the same chunks as the base logs for the same keys.
+    let mut updated_storage_logs = base_storage_logs.clone();
+    for log in &mut updated_storage_logs {
+        log.value = H256::random();
+    }
+    let new_storage_logs = random_storage_logs(expected_status.l1_batch_number, 30);
+    let incremental_storage_logs: Vec<_> = updated_storage_logs[..10]
+        .iter()
+        .chain(&new_storage_logs[..15])
+        .chain(&updated_storage_logs[50..60])
+        .chain(&new_storage_logs[15..])
+        .cloned()
+        .collect();
+    let (object_store, mut client) =
+        prepare_clients(&expected_status, &incremental_storage_logs).await;
+
+    let base_factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![SnapshotFactoryDependency {
+            bytecode: Bytes::from(vec![1; 64]),
+        }],
+    };
+    object_store
+        .put(base_l1_batch_number, &base_factory_deps)
+        .await
+        .unwrap();
+    for (chunk_id, chunk) in base_storage_logs.chunks(50).enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: base_l1_batch_number,
+            chunk_id: chunk_id as u64,
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: chunk.to_vec(),
+        };
+        object_store.put(key, &chunk).await.unwrap();
+    }
+
+    let newest_snapshot = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    let base_snapshot = SnapshotHeader {
+        l1_batch_number: base_l1_batch_number,
+        ..newest_snapshot.clone()
+    };
+    newest_snapshot.base_l1_batch_number = Some(base_l1_batch_number);
+    client
+        .fetch_snapshot_responses
+        .insert(base_l1_batch_number, base_snapshot);
+
+    SnapshotsApplierConfig::for_tests()
+        .run(&pool, &client, &object_store)
+        .await
+        .unwrap();
+
+    let mut storage = pool.access_storage().await.unwrap();
+    let current_db_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(current_db_status.unwrap(), expected_status);
+
+    let expected_storage_logs: HashMap<_, _> = base_storage_logs
+        .iter()
+        .chain(&incremental_storage_logs)
+        .map(|log| (log.key.hashed_key(), log))
+        .collect();
+    assert_eq!(expected_storage_logs.len(), 130);
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_storage_logs.len());
+    for db_log in all_storage_logs {
+        let expected_log = expected_storage_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.miniblock_number, expected_status.miniblock_number);
+    }
+
+    let base_factory_dep = &base_factory_deps.factory_deps[0].bytecode.0;
+    let recovered_dep = storage
+        .factory_deps_dal()
+        .get_factory_dep(hash_bytecode(base_factory_dep))
+        .await
+        .unwrap();
+    assert_eq!(recovered_dep.as_ref(), Some(base_factory_dep));
+}
+
+#[tokio::test]
+async fn applier_errors_on_missing_base_snapshot() {
+    let pool = ConnectionPool::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs(expected_status.l1_batch_number, 100);
+    let (object_store, mut client) = prepare_clients(&expected_status, &storage_logs).await;
+    let newest_snapshot = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    newest_snapshot.base_l1_batch_number = Some(L1BatchNumber(100));
+
+    let err = SnapshotsApplierConfig::for_tests()
+        .run(&pool, &client, &object_store)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("missing"), "{err}");
+}
+
+#[test_casing(2, [(SnapshotHeader::LATEST_VERSION + 1, None), (0, Some(L1BatchNumber(100)))])]
+#[tokio::test]
+async fn applier_errors_on_unsupported_snapshot_version(
+    version: u16,
+    base_l1_batch_number: Option<L1BatchNumber>,
+) {
+    let pool = ConnectionPool::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs(expected_status.l1_batch_number, 100);
+    let (object_store, mut client) = prepare_clients(&expected_status, &storage_logs).await;
+    let newest_snapshot = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    newest_snapshot.version = version;
+    newest_snapshot.base_l1_batch_number = base_l1_batch_number;
+
+    let err = SnapshotsApplierConfig::for_tests()
+        .run(&pool, &client, &object_store)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("version"), "{err}");
+}
+
 #[tokio::test]
 async fn applier_errors_after_genesis() {
     let pool = ConnectionPool::test_pool().await;
@@ -25,6 +25,8 @@ use crate::SnapshotsApplierMainNodeClient;
 pub(super) struct MockMainNodeClient {
     pub fetch_l2_block_responses: HashMap<MiniblockNumber, SyncBlock>,
     pub fetch_newest_snapshot_response: Option<SnapshotHeader>,
+    /// Responses for snapshots other than the newest one.
+    pub fetch_snapshot_responses: HashMap<L1BatchNumber, SnapshotHeader>,
     pub tokens_response: Vec<TokenInfo>,
 }
 
@@ -41,6 +43,19 @@ impl SnapshotsApplierMainNodeClient for MockMainNodeClient {
         Ok(self.fetch_newest_snapshot_response.clone())
     }
 
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        let newest_snapshot = self
+            .fetch_newest_snapshot_response
+            .as_ref()
+            .filter(|snapshot| snapshot.l1_batch_number == l1_batch_number);
+        let snapshot =
+            newest_snapshot.or_else(|| self.fetch_snapshot_responses.get(&l1_batch_number));
+        Ok(snapshot.cloned())
+    }
+
     async fn fetch_tokens(
         &self,
         _at_miniblock: MiniblockNumber,
@@ -244,8 +259,10 @@ pub(super) async fn prepare_clients(
     }
 
     let snapshot_header = SnapshotHeader {
+        version: SnapshotHeader::LATEST_VERSION,
         l1_batch_number: status.l1_batch_number,
         miniblock_number: status.miniblock_number,
+        base_l1_batch_number: None,
         last_l1_batch_with_metadata: l1_block_metadata(
             status.l1_batch_number,
             status.l1_batch_root_hash,
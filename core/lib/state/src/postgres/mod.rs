@@ -525,7 +525,10 @@ impl ReadStorage for PostgresStorage<'_> {
         let value = self
             .rt_handle
             .block_on(dal.get_enumeration_index_for_key(*key));
-
-        value
+        // Indices assigned in L1 batches after the one this storage is bound to must not be visible,
+        // e.g. when replaying a sealed batch on top of historical state.
+        value.and_then(|(index, l1_batch_number)| {
+            self.write_counts(l1_batch_number).then_some(index)
+        })
     }
 }
@@ -88,6 +88,7 @@ fn test_postgres_storage_basics(
     assert_eq!(storage.l1_batch_number_for_miniblock, L1BatchNumber(0));
     for log in &non_existing_logs {
         assert!(storage.is_write_initial(&log.key));
+        assert_eq!(storage.get_enumeration_index(&log.key), None);
     }
 
     // ...but should be seen by the new one
@@ -103,6 +104,7 @@ fn test_postgres_storage_basics(
     assert_eq!(storage.l1_batch_number_for_miniblock, L1BatchNumber(1));
     for log in &non_existing_logs {
         assert!(!storage.is_write_initial(&log.key));
+        assert!(storage.get_enumeration_index(&log.key).is_some());
     }
 
     // ...except if we set `consider_new_l1_batch` to `false`
@@ -118,9 +120,11 @@ fn test_postgres_storage_basics(
     assert_eq!(storage.l1_batch_number_for_miniblock, L1BatchNumber(1));
     for log in &non_existing_logs {
         assert!(storage.is_write_initial(&log.key));
+        assert_eq!(storage.get_enumeration_index(&log.key), None);
     }
     for log in &existing_logs {
         assert!(!storage.is_write_initial(&log.key));
+        assert!(storage.get_enumeration_index(&log.key).is_some());
     }
 }
 
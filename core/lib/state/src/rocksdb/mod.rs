@@ -168,9 +168,28 @@ impl RocksbStorageBuilder {
         storage: &mut StorageProcessor<'_>,
         stop_receiver: &watch::Receiver<bool>,
     ) -> anyhow::Result<Option<RocksdbStorage>> {
-        let mut inner = self.0;
-        match inner.update_from_postgres(storage, stop_receiver).await {
-            Ok(()) => Ok(Some(inner)),
+        let builder = self.catch_up(storage, stop_receiver).await?;
+        Ok(builder.map(|builder| builder.0))
+    }
+
+    /// Catches up this storage with Postgres using the provided connection, but doesn't make the storage
+    /// accessible. This allows to perform the bulk of synchronization in advance (e.g., in background),
+    /// so that the following [`Self::synchronize()`] call only needs to process L1 batches sealed in the meantime.
+    ///
+    /// # Return value
+    ///
+    /// Returns `Ok(None)` if the update is interrupted using `stop_receiver`.
+    ///
+    /// # Errors
+    ///
+    /// Has the same error conditions as [`Self::synchronize()`].
+    pub async fn catch_up(
+        mut self,
+        storage: &mut StorageProcessor<'_>,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<Option<Self>> {
+        match self.0.update_from_postgres(storage, stop_receiver).await {
+            Ok(()) => Ok(Some(self)),
             Err(RocksdbSyncError::Interrupted) => Ok(None),
             Err(RocksdbSyncError::Internal(err)) => Err(err),
         }
@@ -155,6 +155,42 @@ async fn rocksdb_storage_syncing_fault_tolerance() {
     }
 }
 
+#[tokio::test]
+async fn rocksdb_storage_catching_up_with_postgres() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut conn = pool.access_storage().await.unwrap();
+    prepare_postgres(&mut conn).await;
+    let storage_logs = gen_storage_logs(20..40);
+    create_miniblock(&mut conn, MiniblockNumber(1), storage_logs[..10].to_vec()).await;
+    create_l1_batch(&mut conn, L1BatchNumber(1), &storage_logs[..10]).await;
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let builder = RocksdbStorage::builder(dir.path())
+        .await
+        .expect("Failed initializing RocksDB")
+        .catch_up(&mut conn, &stop_receiver)
+        .await
+        .unwrap()
+        .expect("Storage synchronization unexpectedly stopped");
+    assert_eq!(builder.l1_batch_number().await, Some(L1BatchNumber(2)));
+
+    // Seal another L1 batch and check that it's picked up by the builder.
+    create_miniblock(&mut conn, MiniblockNumber(2), storage_logs[10..].to_vec()).await;
+    create_l1_batch(&mut conn, L1BatchNumber(2), &storage_logs[10..]).await;
+    let mut storage = builder
+        .synchronize(&mut conn, &stop_receiver)
+        .await
+        .unwrap()
+        .expect("Storage synchronization unexpectedly stopped");
+
+    assert_eq!(storage.l1_batch_number().await, Some(L1BatchNumber(3)));
+    for log in &storage_logs {
+        assert_eq!(storage.read_value(&log.key), log.value);
+        assert!(!storage.is_write_initial(&log.key));
+    }
+}
+
 async fn insert_factory_deps(
     conn: &mut StorageProcessor<'_>,
     miniblock_number: MiniblockNumber,
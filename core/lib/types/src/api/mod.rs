@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use strum::Display;
@@ -6,15 +8,19 @@ use zksync_basic_types::{
     L1BatchNumber,
 };
 use zksync_contracts::BaseSystemContractsHashes;
+use zksync_utils::h256_to_u256;
 
 pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    fee::FeeBreakdown,
+    get_code_key, get_nonce_key,
     protocol_version::L1VerifierConfig,
-    vm_trace::{Call, CallType},
-    web3::types::{AccessList, Index, H2048},
-    Address, MiniblockNumber, ProtocolVersionId,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    vm_trace::{Call, CallType, StorageAccess, StorageAccesses, StructLog, TouchedState},
+    web3::types::{AccessList, AccessListItem, Index, H2048},
+    Address, MiniblockNumber, ProtocolVersionId, StorageKey,
 };
 
 pub mod en;
@@ -258,6 +264,14 @@ pub struct TransactionReceipt {
     /// Effective gas price
     #[serde(rename = "effectiveGasPrice")]
     pub effective_gas_price: Option<U256>,
+    /// Breakdown of the gas used into computation and published pubdata. Not reported for transactions
+    /// executed by VMs that don't track published pubdata.
+    #[serde(
+        rename = "feeBreakdown",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_breakdown: Option<FeeBreakdown>,
 }
 
 /// The block type returned from RPC calls.
@@ -555,11 +569,20 @@ pub struct GetLogsFilter {
 }
 
 /// Result of debugging block
-/// For some reasons geth returns result as {result: DebugCall}
+/// For some reasons geth returns result as {result: TracerResult}
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ResultDebugCall {
-    pub result: DebugCall,
+    pub result: TracerResult,
+}
+
+/// Result of tracing a transaction. Its shape depends on the tracer requested in [`TracerConfig`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TracerResult {
+    CallTrace(DebugCall),
+    PrestateTrace(PrestateTrace),
+    StructLogTrace(StructLogTrace),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -622,24 +645,364 @@ pub struct ProtocolVersion {
     pub l2_system_upgrade_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
+    #[default]
     CallTracer,
+    PrestateTracer,
+    StructLogger,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
+    #[serde(default)]
     pub only_top_call: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateTracerConfig {
+    /// If set, the tracer returns the state before and after the transaction only for the modified accounts.
+    #[serde(default)]
+    pub diff_mode: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTracerConfig {
+    /// Maximum number of recorded instructions; instructions after the limit are not recorded.
+    #[serde(default)]
+    pub max_steps: Option<usize>,
+    /// If set, heap writes are not recorded.
+    #[serde(default)]
+    pub disable_memory: bool,
+}
+
+/// Options for all supported tracers. Each tracer only reads the options relevant to it.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerSpecificConfig {
+    #[serde(flatten)]
+    pub call_tracer: CallTracerConfig,
+    #[serde(flatten)]
+    pub prestate_tracer: PrestateTracerConfig,
+    #[serde(flatten)]
+    pub struct_logger: StructLogTracerConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
-    pub tracer_config: CallTracerConfig,
+    pub tracer_config: TracerSpecificConfig,
+}
+
+/// State of an account as reported by the `prestateTracer`. Balance, nonce and code hash are extracted
+/// from the storage of the corresponding system contracts; missing fields were not accessed during execution.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<H256>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+impl PrestateAccount {
+    fn new(
+        address: &Address,
+        slots: &HashMap<StorageKey, &StorageAccess>,
+        value: fn(&StorageAccess) -> H256,
+    ) -> Self {
+        let balance = slots.get(&storage_key_for_eth_balance(address));
+        let nonce = slots.get(&get_nonce_key(address));
+        let code_hash = slots.get(&get_code_key(address));
+        Self {
+            balance: balance.map(|&access| h256_to_u256(value(access))),
+            nonce: nonce.map(|&access| {
+                let (tx_nonce, _) = decompose_full_nonce(h256_to_u256(value(access)));
+                tx_nonce.low_u64()
+            }),
+            code_hash: code_hash.map(|&access| value(access)),
+            storage: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the parts of `self` and `after` that differ, or `None` if the account wasn't modified.
+    /// Following geth, the returned "before" state retains all account fields, but only the modified storage slots.
+    fn diff(&self, after: &Self) -> Option<(Self, Self)> {
+        let mut pre = Self {
+            storage: BTreeMap::new(),
+            ..self.clone()
+        };
+        let mut post = Self::default();
+        if self.balance != after.balance {
+            post.balance = after.balance;
+        }
+        if self.nonce != after.nonce {
+            post.nonce = after.nonce;
+        }
+        if self.code_hash != after.code_hash {
+            post.code_hash = after.code_hash;
+        }
+        for (slot, value) in &self.storage {
+            let new_value = after.storage.get(slot).copied().unwrap_or_default();
+            if new_value != *value {
+                pre.storage.insert(*slot, *value);
+                post.storage.insert(*slot, new_value);
+            }
+        }
+        (post != Self::default()).then_some((pre, post))
+    }
+}
+
+/// Output of the `prestateTracer` in the diff mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrestateDiff {
+    pub pre: BTreeMap<Address, PrestateAccount>,
+    pub post: BTreeMap<Address, PrestateAccount>,
+}
+
+impl PrestateDiff {
+    /// Builds the diff from the state touched during execution.
+    pub fn new(touched: &TouchedState) -> Self {
+        let (accounts_before, accounts_after) = PrestateTrace::touched_accounts(touched);
+        Self::from_accounts(&accounts_before, &accounts_after)
+    }
+
+    fn from_accounts(
+        accounts_before: &BTreeMap<Address, PrestateAccount>,
+        accounts_after: &BTreeMap<Address, PrestateAccount>,
+    ) -> Self {
+        let mut diff = Self {
+            pre: BTreeMap::new(),
+            post: BTreeMap::new(),
+        };
+        for (address, before) in accounts_before {
+            if let Some((pre, post)) = before.diff(&accounts_after[address]) {
+                diff.pre.insert(*address, pre);
+                diff.post.insert(*address, post);
+            }
+        }
+        diff
+    }
+}
+
+/// Output of the `prestateTracer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    Diff(PrestateDiff),
+    Prestate(BTreeMap<Address, PrestateAccount>),
+}
+
+impl PrestateTrace {
+    /// Builds the trace from the state touched during execution. Storage slots holding balances, nonces
+    /// and code hashes of touched accounts are reported as account fields; all other slots are reported
+    /// as the storage of the contract owning them.
+    pub fn new(touched: &TouchedState, diff_mode: bool) -> Self {
+        let (accounts_before, accounts_after) = Self::touched_accounts(touched);
+        if diff_mode {
+            Self::Diff(PrestateDiff::from_accounts(
+                &accounts_before,
+                &accounts_after,
+            ))
+        } else {
+            Self::Prestate(accounts_before)
+        }
+    }
+
+    /// Returns the state of touched accounts before and after the execution.
+    fn touched_accounts(
+        touched: &TouchedState,
+    ) -> (
+        BTreeMap<Address, PrestateAccount>,
+        BTreeMap<Address, PrestateAccount>,
+    ) {
+        let slots: HashMap<_, _> = touched
+            .storage
+            .iter()
+            .map(|access| (access.key, access))
+            .collect();
+        let mut account_keys = HashSet::with_capacity(touched.accounts.len() * 3);
+        let mut accounts_before = BTreeMap::new();
+        let mut accounts_after = BTreeMap::new();
+        for address in &touched.accounts {
+            account_keys.extend([
+                storage_key_for_eth_balance(address),
+                get_nonce_key(address),
+                get_code_key(address),
+            ]);
+            let before = PrestateAccount::new(address, &slots, |access| access.value_before);
+            accounts_before.insert(*address, before);
+            let after = PrestateAccount::new(address, &slots, |access| access.value_after);
+            accounts_after.insert(*address, after);
+        }
+
+        for access in &touched.storage {
+            if account_keys.contains(&access.key) {
+                continue;
+            }
+            let (address, slot) = (*access.key.address(), *access.key.key());
+            accounts_before
+                .entry(address)
+                .or_insert_with(PrestateAccount::default)
+                .storage
+                .insert(slot, access.value_before);
+            accounts_after
+                .entry(address)
+                .or_insert_with(PrestateAccount::default)
+                .storage
+                .insert(slot, access.value_after);
+        }
+
+        (accounts_before, accounts_after)
+    }
+}
+
+/// Result of simulating a single transaction from a bundle with `zks_simulateBundle`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleTransactionResult {
+    /// Whether the transaction succeeded. State changes made by reverted transactions are rolled back
+    /// and are not visible to the following transactions in the bundle. If a transaction halts,
+    /// the following transactions are not executed.
+    pub success: bool,
+    /// Return data of a successful transaction, or revert data of a reverted one.
+    pub output: Bytes,
+    /// Human-readable revert / halt reason for a failed transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    pub gas_used: U256,
+    /// Events emitted by the transaction. Block- and transaction-related fields are not set.
+    pub logs: Vec<Log>,
+    /// State changes made by the transaction, in the same format as returned by `prestateTracer` in the diff mode.
+    pub state_diff: PrestateDiff,
+}
+
+/// Storage slots of a single contract accessed by a call, as reported by `eth_createAccessList`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSlotsUsage {
+    pub reads: BTreeSet<H256>,
+    /// Slots written for the first time. Initial writes publish the full slot key as pubdata,
+    /// so they are more expensive than repeated writes.
+    pub initial_writes: BTreeSet<H256>,
+    /// Slots that were written before; repeated writes publish a compact enumeration index instead of the key.
+    pub repeated_writes: BTreeSet<H256>,
+}
+
+/// Result of `eth_createAccessList`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    /// Storage slots read or written by the call grouped by the contract address, in the geth-compatible format.
+    /// Unlike in geth, slots of system contracts (e.g., nonces and balances) are included as well.
+    pub access_list: AccessList,
+    pub gas_used: U256,
+    /// Revert / halt reason if the call failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Storage slots accessed by the call grouped by the contract address, with reads, initial
+    /// and repeated writes reported separately.
+    pub storage_usage: BTreeMap<Address, StorageSlotsUsage>,
+}
+
+impl AccessListResult {
+    pub fn new(accesses: &StorageAccesses, gas_used: U256, error: Option<String>) -> Self {
+        let mut storage_usage = BTreeMap::<_, StorageSlotsUsage>::new();
+        for key in &accesses.reads {
+            let usage = storage_usage.entry(*key.address()).or_default();
+            usage.reads.insert(*key.key());
+        }
+        for (key, &is_initial) in &accesses.writes {
+            let usage = storage_usage.entry(*key.address()).or_default();
+            if is_initial {
+                usage.initial_writes.insert(*key.key());
+            } else {
+                usage.repeated_writes.insert(*key.key());
+            }
+        }
+
+        let access_list = storage_usage
+            .iter()
+            .map(|(&address, usage)| {
+                let slots: BTreeSet<_> = usage
+                    .reads
+                    .iter()
+                    .chain(&usage.initial_writes)
+                    .chain(&usage.repeated_writes)
+                    .copied()
+                    .collect();
+                AccessListItem {
+                    address,
+                    storage_keys: slots.into_iter().collect(),
+                }
+            })
+            .collect();
+        Self {
+            access_list,
+            gas_used,
+            error,
+            storage_usage,
+        }
+    }
+}
+
+/// Overrides for the state of a single account applied when executing `eth_call` and `eth_estimateGas`.
+/// Follows the geth format; `state` and `stateDiff` are mutually exclusive.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+    /// Bytecode to deploy at the account address. An empty bytecode removes the account code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Replaces the entire account storage; slots not mentioned here are read as zero.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<H256, H256>>,
+    /// Overrides the specified storage slots, leaving the remaining account storage intact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<H256, H256>>,
+}
+
+/// Account state overrides keyed by the account address.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StateOverride(HashMap<Address, OverrideAccount>);
+
+impl StateOverride {
+    pub fn new(accounts: HashMap<Address, OverrideAccount>) -> Self {
+        Self(accounts)
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&OverrideAccount> {
+        self.0.get(address)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &OverrideAccount)> + '_ {
+        self.0.iter()
+    }
+}
+
+/// Output of the `structLogger` tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTrace {
+    pub gas: u64,
+    pub failed: bool,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -702,3 +1065,214 @@ pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
 }
+
+/// Account state with Merkle proofs returned by `eth_getProof`.
+///
+/// Follows the EIP-1186 response shape where possible. Unlike Ethereum, the entire zkSync state is
+/// stored in a single Merkle tree, so account fields are proven by proofs for the corresponding
+/// system contract storage slots, and `storageHash` is the root hash of the tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthProof {
+    pub address: Address,
+    pub balance: U256,
+    pub nonce: U256,
+    /// Bytecode hash of the account as stored in the account code storage; zero for EOAs.
+    pub code_hash: H256,
+    pub storage_hash: H256,
+    /// Proofs for the balance, nonce and bytecode hash slots of the account, in this order.
+    pub account_proof: Vec<StorageProof>,
+    pub storage_proof: Vec<StorageProof>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use zksync_utils::u256_to_h256;
+
+    use super::*;
+    use crate::{AccountTreeId, L2_ETH_TOKEN_ADDRESS};
+
+    fn touched_state(account: Address, contract: Address) -> TouchedState {
+        let contract_slot = StorageKey::new(AccountTreeId::new(contract), H256::repeat_byte(1));
+        let untouched_slot = StorageKey::new(AccountTreeId::new(contract), H256::repeat_byte(2));
+        TouchedState {
+            accounts: BTreeSet::from([account, contract]),
+            storage: vec![
+                StorageAccess {
+                    key: storage_key_for_eth_balance(&account),
+                    value_before: u256_to_h256(1_000.into()),
+                    value_after: u256_to_h256(900.into()),
+                },
+                StorageAccess {
+                    key: get_nonce_key(&account),
+                    value_before: u256_to_h256(3.into()),
+                    value_after: u256_to_h256(4.into()),
+                },
+                StorageAccess {
+                    key: get_code_key(&contract),
+                    value_before: H256::repeat_byte(0xc0),
+                    value_after: H256::repeat_byte(0xc0),
+                },
+                StorageAccess {
+                    key: contract_slot,
+                    value_before: H256::zero(),
+                    value_after: H256::repeat_byte(0xff),
+                },
+                StorageAccess {
+                    key: untouched_slot,
+                    value_before: H256::repeat_byte(0xee),
+                    value_after: H256::repeat_byte(0xee),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn building_prestate_trace() {
+        let (account, contract) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let trace = PrestateTrace::new(&touched_state(account, contract), false);
+        let PrestateTrace::Prestate(accounts) = trace else {
+            panic!("Unexpected trace: {trace:?}");
+        };
+
+        assert_eq!(
+            accounts[&account],
+            PrestateAccount {
+                balance: Some(1_000.into()),
+                nonce: Some(3),
+                code_hash: None,
+                storage: BTreeMap::new(),
+            }
+        );
+        let contract_state = &accounts[&contract];
+        assert_eq!(contract_state.code_hash, Some(H256::repeat_byte(0xc0)));
+        assert_eq!(
+            contract_state.storage,
+            BTreeMap::from([
+                (H256::repeat_byte(1), H256::zero()),
+                (H256::repeat_byte(2), H256::repeat_byte(0xee)),
+            ])
+        );
+        // Storage of system contracts is not duplicated for account fields.
+        assert!(!accounts.contains_key(&L2_ETH_TOKEN_ADDRESS));
+    }
+
+    #[test]
+    fn building_prestate_diff() {
+        let (account, contract) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let trace = PrestateTrace::new(&touched_state(account, contract), true);
+        let PrestateTrace::Diff(diff) = trace else {
+            panic!("Unexpected trace: {trace:?}");
+        };
+        assert_eq!(PrestateDiff::new(&touched_state(account, contract)), diff);
+
+        assert_eq!(
+            diff.post[&account],
+            PrestateAccount {
+                balance: Some(900.into()),
+                nonce: Some(4),
+                ..PrestateAccount::default()
+            }
+        );
+        assert_eq!(diff.pre[&contract].code_hash, Some(H256::repeat_byte(0xc0)));
+        assert_eq!(
+            diff.pre[&contract].storage,
+            BTreeMap::from([(H256::repeat_byte(1), H256::zero())])
+        );
+        assert_eq!(
+            diff.post[&contract],
+            PrestateAccount {
+                storage: BTreeMap::from([(H256::repeat_byte(1), H256::repeat_byte(0xff))]),
+                ..PrestateAccount::default()
+            }
+        );
+
+        let trace = TracerResult::PrestateTrace(PrestateTrace::Diff(diff));
+        let serialized = serde_json::to_value(&trace).unwrap();
+        let deserialized: TracerResult = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, trace);
+    }
+
+    #[test]
+    fn building_access_list() {
+        let contract = AccountTreeId::new(Address::repeat_byte(1));
+        let read_slot = StorageKey::new(contract, H256::repeat_byte(1));
+        let written_slot = StorageKey::new(contract, H256::repeat_byte(2));
+        let balance_slot = storage_key_for_eth_balance(&Address::repeat_byte(2));
+        let accesses = StorageAccesses {
+            reads: BTreeSet::from([read_slot, written_slot, balance_slot]),
+            writes: BTreeMap::from([(written_slot, true), (balance_slot, false)]),
+        };
+        let result = AccessListResult::new(&accesses, 100.into(), None);
+
+        assert_eq!(result.access_list.len(), 2);
+        let contract_item = result
+            .access_list
+            .iter()
+            .find(|item| item.address == Address::repeat_byte(1))
+            .unwrap();
+        assert_eq!(
+            contract_item.storage_keys,
+            [H256::repeat_byte(1), H256::repeat_byte(2)]
+        );
+        let contract_usage = &result.storage_usage[&Address::repeat_byte(1)];
+        assert_eq!(contract_usage.reads.len(), 2);
+        assert_eq!(
+            contract_usage.initial_writes,
+            BTreeSet::from([H256::repeat_byte(2)])
+        );
+        assert!(contract_usage.repeated_writes.is_empty());
+        let token_usage = &result.storage_usage[&L2_ETH_TOKEN_ADDRESS];
+        assert_eq!(
+            token_usage.repeated_writes,
+            BTreeSet::from([*balance_slot.key()])
+        );
+    }
+
+    #[test]
+    fn deserializing_state_override() {
+        let state_override: StateOverride = serde_json::from_value(serde_json::json!({
+            "0x0101010101010101010101010101010101010101": {
+                "balance": "0x100",
+                "nonce": "0x3",
+                "stateDiff": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x00000000000000000000000000000000000000000000000000000000000000ff",
+                },
+            },
+        }))
+        .unwrap();
+
+        let account = state_override.get(&Address::repeat_byte(1)).unwrap();
+        assert_eq!(account.balance, Some(256.into()));
+        assert_eq!(account.nonce, Some(3.into()));
+        assert_eq!(account.code, None);
+        assert_eq!(account.state, None);
+        assert_eq!(
+            account.state_diff,
+            Some(HashMap::from([(
+                H256::from_low_u64_be(1),
+                H256::from_low_u64_be(0xff)
+            )]))
+        );
+    }
+
+    #[test]
+    fn deserializing_struct_logger_config() {
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "structLogger",
+            "tracerConfig": { "maxSteps": 100, "disableMemory": true },
+        }))
+        .unwrap();
+        assert_eq!(config.tracer, SupportedTracers::StructLogger);
+        assert_eq!(config.tracer_config.struct_logger.max_steps, Some(100));
+        assert!(config.tracer_config.struct_logger.disable_memory);
+
+        let config: TracerConfig =
+            serde_json::from_value(serde_json::json!({ "tracer": "structLogger" })).unwrap();
+        assert_eq!(config.tracer_config.struct_logger.max_steps, None);
+        assert!(!config.tracer_config.struct_logger.disable_memory);
+    }
+}
@@ -1,7 +1,14 @@
 use serde::{Deserialize, Serialize};
 use zksync_utils::ceil_div;
 
-use crate::{circuit::CircuitStatistic, U256};
+use crate::{
+    circuit::CircuitStatistic,
+    commitment::SerializeCommitment,
+    l2_to_l1_log::L2ToL1Log,
+    storage::writes::{BYTES_PER_DERIVED_KEY, BYTES_PER_ENUMERATION_INDEX},
+    tx::ExecutionMetrics,
+    U256,
+};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "result")]
@@ -79,6 +86,130 @@ impl Fee {
     }
 }
 
+/// Amount of pubdata published for a certain purpose, together with the gas charged for it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PubdataCost {
+    pub bytes: u64,
+    pub gas: U256,
+}
+
+impl PubdataCost {
+    fn new(bytes: u64, gas_per_pubdata: U256) -> Self {
+        Self {
+            bytes,
+            gas: gas_per_pubdata * bytes,
+        }
+    }
+}
+
+/// Breakdown of the transaction cost into the computational part and pubdata.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+    /// Gas not attributed to pubdata, i.e. spent on computation and the fixed transaction overhead.
+    pub computational_gas: U256,
+    /// Gas charged per published pubdata byte.
+    pub gas_per_pubdata: U256,
+    /// Number of initial storage writes. Not reported for receipts of transactions executed before it was persisted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_storage_writes: Option<u64>,
+    /// Number of repeated storage writes. Not reported for receipts of transactions executed before it was persisted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeated_storage_writes: Option<u64>,
+    /// Pubdata spent on storage writes (both initial and repeated ones).
+    pub storage_writes: PubdataCost,
+    /// Part of `storage_writes` spent on initial writes. Estimated based on the number of writes: keys of initial
+    /// and repeated writes have fixed sizes, and the remaining bytes (updated values) are split proportionally
+    /// to the number of writes. Not reported if the number of writes is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_writes: Option<PubdataCost>,
+    /// Part of `storage_writes` spent on repeated writes; estimated in the same way as `initial_writes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeated_writes: Option<PubdataCost>,
+    /// Pubdata spent on L2-to-L1 logs and long messages.
+    pub l2_to_l1_messages: PubdataCost,
+    pub published_bytecodes: PubdataCost,
+    pub total_pubdata: PubdataCost,
+}
+
+impl FeeBreakdown {
+    /// Creates a breakdown for a transaction that used `gas_used` gas in total and published the pubdata
+    /// recorded in `metrics`.
+    pub fn new(gas_used: U256, gas_per_pubdata: U256, metrics: &ExecutionMetrics) -> Self {
+        // Each L2-to-L1 log is published together with the 4-byte length of the corresponding message
+        // or bytecode (see `ExecutionMetrics::size()`).
+        let l2_to_l1_messages =
+            metrics.l2_l1_long_messages + metrics.l2_to_l1_logs * (L2ToL1Log::SERIALIZED_SIZE + 4);
+        let published_bytecodes = metrics.published_bytecode_bytes;
+        let non_storage_pubdata = (l2_to_l1_messages + published_bytecodes) as u64;
+        let total_pubdata = u64::from(metrics.pubdata_published).max(non_storage_pubdata);
+        let total_pubdata = PubdataCost::new(total_pubdata, gas_per_pubdata);
+
+        Self {
+            computational_gas: gas_used.saturating_sub(total_pubdata.gas),
+            gas_per_pubdata,
+            initial_storage_writes: None,
+            repeated_storage_writes: None,
+            storage_writes: PubdataCost::new(
+                total_pubdata.bytes - non_storage_pubdata,
+                gas_per_pubdata,
+            ),
+            initial_writes: None,
+            repeated_writes: None,
+            l2_to_l1_messages: PubdataCost::new(l2_to_l1_messages as u64, gas_per_pubdata),
+            published_bytecodes: PubdataCost::new(published_bytecodes as u64, gas_per_pubdata),
+            total_pubdata,
+        }
+    }
+
+    /// Creates a breakdown based on metrics obtained from the API sandbox.
+    pub fn from_tx_metrics(
+        gas_used: U256,
+        gas_per_pubdata: U256,
+        tx_metrics: &TransactionExecutionMetrics,
+    ) -> Self {
+        let metrics = ExecutionMetrics::from_tx_metrics(tx_metrics);
+        Self::new(gas_used, gas_per_pubdata, &metrics).with_storage_writes(
+            tx_metrics.initial_storage_writes as u64,
+            tx_metrics.repeated_storage_writes as u64,
+        )
+    }
+
+    /// Sets the number of initial and repeated storage writes, and splits `storage_writes` pubdata between them.
+    pub fn with_storage_writes(mut self, initial_writes: u64, repeated_writes: u64) -> Self {
+        let storage_bytes = self.storage_writes.bytes;
+        let initial_key_bytes = u64::from(BYTES_PER_DERIVED_KEY) * initial_writes;
+        let repeated_key_bytes = u64::from(BYTES_PER_ENUMERATION_INDEX) * repeated_writes;
+        let value_bytes = storage_bytes.saturating_sub(initial_key_bytes + repeated_key_bytes);
+        let total_writes = initial_writes + repeated_writes;
+        let initial_value_bytes = if total_writes == 0 {
+            0
+        } else {
+            value_bytes * initial_writes / total_writes
+        };
+        let initial_bytes = (initial_key_bytes + initial_value_bytes).min(storage_bytes);
+
+        self.initial_storage_writes = Some(initial_writes);
+        self.repeated_storage_writes = Some(repeated_writes);
+        self.initial_writes = Some(PubdataCost::new(initial_bytes, self.gas_per_pubdata));
+        self.repeated_writes = Some(PubdataCost::new(
+            storage_bytes - initial_bytes,
+            self.gas_per_pubdata,
+        ));
+        self
+    }
+}
+
+/// Fee estimate together with its breakdown.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeWithBreakdown {
+    #[serde(flatten)]
+    pub fee: Fee,
+    pub breakdown: FeeBreakdown,
+}
+
 /// Returns how many slots would ABI-encoding of the transaction with such parameters take
 pub fn encoding_len(
     data_len: u64,
@@ -101,3 +232,67 @@ pub fn encoding_len(
 
     BASE_LEN + dynamic_len as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_breakdown_basics() {
+        let metrics = ExecutionMetrics {
+            published_bytecode_bytes: 100,
+            l2_l1_long_messages: 20,
+            l2_to_l1_logs: 1,
+            pubdata_published: 300,
+            ..ExecutionMetrics::default()
+        };
+        let breakdown = FeeBreakdown::new(1_000_000.into(), 10.into(), &metrics);
+
+        let l2_to_l1_messages = 20 + L2ToL1Log::SERIALIZED_SIZE as u64 + 4;
+        assert_eq!(breakdown.l2_to_l1_messages.bytes, l2_to_l1_messages);
+        assert_eq!(breakdown.published_bytecodes.bytes, 100);
+        assert_eq!(breakdown.published_bytecodes.gas, 1_000.into());
+        assert_eq!(
+            breakdown.storage_writes.bytes,
+            300 - 100 - l2_to_l1_messages
+        );
+        assert_eq!(breakdown.total_pubdata.bytes, 300);
+        assert_eq!(breakdown.total_pubdata.gas, 3_000.into());
+        assert_eq!(breakdown.computational_gas, 997_000.into());
+        assert_eq!(breakdown.initial_storage_writes, None);
+        assert_eq!(breakdown.initial_writes, None);
+
+        let serialized = serde_json::to_value(&breakdown).unwrap();
+        assert_eq!(serialized["totalPubdata"]["bytes"], 300);
+        assert!(serialized.get("initialStorageWrites").is_none());
+        assert!(serialized.get("initialWrites").is_none());
+    }
+
+    #[test]
+    fn splitting_storage_writes_pubdata() {
+        let metrics = ExecutionMetrics {
+            pubdata_published: 200,
+            ..ExecutionMetrics::default()
+        };
+        let breakdown = FeeBreakdown::new(1_000_000.into(), 10.into(), &metrics);
+        assert_eq!(breakdown.storage_writes.bytes, 200);
+        let breakdown = breakdown.with_storage_writes(2, 3);
+
+        // Keys take 2 * 32 + 3 * 4 = 76 bytes; the remaining 124 bytes are split as 2:3.
+        let initial_writes = breakdown.initial_writes.unwrap();
+        let repeated_writes = breakdown.repeated_writes.unwrap();
+        assert_eq!(initial_writes.bytes, 64 + 49);
+        assert_eq!(initial_writes.gas, 1_130.into());
+        assert_eq!(repeated_writes.bytes, 200 - 64 - 49);
+        assert_eq!(breakdown.initial_storage_writes, Some(2));
+        assert_eq!(breakdown.repeated_storage_writes, Some(3));
+
+        let serialized = serde_json::to_value(&breakdown).unwrap();
+        assert_eq!(serialized["initialWrites"]["bytes"], 113);
+        assert_eq!(serialized["repeatedWrites"]["bytes"], 87);
+
+        let breakdown = FeeBreakdown::default().with_storage_writes(0, 0);
+        assert_eq!(breakdown.initial_writes, Some(PubdataCost::default()));
+        assert_eq!(breakdown.repeated_writes, Some(PubdataCost::default()));
+    }
+}
@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
-use zksync_config::configs::chain::{FeeModelVersion, StateKeeperConfig};
+use zksync_config::configs::chain::{FeeInputStrategyKind, FeeModelVersion, StateKeeperConfig};
 use zksync_system_constants::L1_GAS_PER_PUBDATA_BYTE;
 
-use crate::ProtocolVersionId;
+use crate::{L1BatchNumber, ProtocolVersionId};
 
 /// Fee input to be provided into the VM. It contains two options:
 /// - `L1Pegged`: L1 gas price is provided to the VM, and the pubdata price is derived from it. Using this option is required for the
@@ -228,6 +228,57 @@ impl FeeModelConfig {
     }
 }
 
+/// Strategy used to derive the batch fee input from the fee model params. It complements [`FeeModelConfig`]:
+/// the fee model defines how the L1 prices and the config are translated into the fee input, while the strategy
+/// may adjust the L1 prices and the resulting fee input (e.g., to smooth out price spikes).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum FeeInputStrategyConfig {
+    /// Fee input is derived from the current L1 prices as-is.
+    #[default]
+    Default,
+    /// L1 prices are smoothed using the exponential moving average with the specified half-life.
+    Ema { half_life_sec: u64 },
+    /// Part of the L1 pubdata price exceeding `threshold` (in wei per byte) is multiplied by `multiplier`.
+    /// Has no effect for the `V1` fee model, in which the pubdata price is pegged to the L1 gas price.
+    PubdataSurge { threshold: u64, multiplier: f64 },
+}
+
+impl FeeInputStrategyConfig {
+    const DEFAULT_EMA_HALF_LIFE_SEC: u64 = 300;
+    const DEFAULT_PUBDATA_SURGE_MULTIPLIER: f64 = 2.0;
+
+    pub fn from_state_keeper_config(state_keeper_config: &StateKeeperConfig) -> Self {
+        match state_keeper_config.fee_input_strategy {
+            FeeInputStrategyKind::Default => Self::Default,
+            FeeInputStrategyKind::Ema => Self::Ema {
+                half_life_sec: state_keeper_config
+                    .fee_input_ema_half_life_sec
+                    .unwrap_or(Self::DEFAULT_EMA_HALF_LIFE_SEC),
+            },
+            FeeInputStrategyKind::PubdataSurge => Self::PubdataSurge {
+                threshold: state_keeper_config
+                    .fee_input_pubdata_surge_threshold
+                    .unwrap_or(0),
+                multiplier: state_keeper_config
+                    .fee_input_pubdata_surge_multiplier
+                    .unwrap_or(Self::DEFAULT_PUBDATA_SURGE_MULTIPLIER),
+            },
+        }
+    }
+}
+
+/// Resources used by an L1 batch which are relevant for fee calculations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1BatchFeeStats {
+    pub number: L1BatchNumber,
+    /// UNIX timestamp of the batch, in seconds.
+    pub timestamp: u64,
+    /// Computational gas used by all transactions in the batch.
+    pub computational_gas_used: u64,
+    /// Number of pubdata bytes published by all transactions in the batch.
+    pub pubdata_published: u64,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct FeeParamsV1 {
     pub config: FeeModelConfigV1,
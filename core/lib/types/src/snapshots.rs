@@ -24,6 +24,9 @@ pub struct AllSnapshots {
 pub struct SnapshotMetadata {
     /// L1 batch for the snapshot. The data in the snapshot captures node storage at the end of this batch.
     pub l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot for incremental snapshots. An incremental snapshot only contains
+    /// storage logs and factory deps changed after the base snapshot.
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Path to the factory dependencies blob.
     pub factory_deps_filepath: String,
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
@@ -43,14 +46,45 @@ impl SnapshotMetadata {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotHeader {
+    /// Version of the snapshot format. Headers without a version are treated as having version 0.
+    #[serde(default)]
+    pub version: u16,
     pub l1_batch_number: L1BatchNumber,
     pub miniblock_number: MiniblockNumber,
+    /// L1 batch of the base snapshot if this snapshot is incremental. To recover from an incremental snapshot,
+    /// the base snapshot (which may be incremental itself) must be applied first. Always `None` for
+    /// version 0 snapshots.
+    #[serde(default)]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
     pub last_l1_batch_with_metadata: L1BatchWithMetadata,
 }
 
+impl SnapshotHeader {
+    /// Latest supported snapshot format version. Version 1 introduced incremental snapshots, so that
+    /// nodes only supporting version 0 must not recover from a version 1 snapshot.
+    pub const LATEST_VERSION: u16 = 1;
+
+    /// Checks whether this header is consistent with its format version.
+    pub fn check_version(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.version <= Self::LATEST_VERSION,
+            "snapshot for L1 batch #{} has unsupported version {}; latest supported version is {}",
+            self.l1_batch_number,
+            self.version,
+            Self::LATEST_VERSION
+        );
+        anyhow::ensure!(
+            self.version > 0 || self.base_l1_batch_number.is_none(),
+            "version 0 snapshot for L1 batch #{} cannot be incremental",
+            self.l1_batch_number
+        );
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotStorageLogsChunkMetadata {
@@ -71,6 +71,11 @@ pub struct ExecutionMetrics {
     pub computational_gas_used: u32,
     pub pubdata_published: u32,
     pub circuit_statistic: CircuitStatistic,
+    /// Storage writes of this transaction alone, deduplicated within the transaction (writes of other transactions
+    /// in the L1 batch are ignored). A write is initial if the VM has marked it as such, i.e. if the slot was
+    /// not written to before the L1 batch. Not known to the VM; set by the state keeper.
+    pub initial_storage_writes: usize,
+    pub repeated_storage_writes: usize,
 }
 
 impl ExecutionMetrics {
@@ -89,6 +94,8 @@ impl ExecutionMetrics {
             computational_gas_used: tx_metrics.computational_gas_used,
             pubdata_published: tx_metrics.pubdata_published,
             circuit_statistic: tx_metrics.circuit_statistic,
+            initial_storage_writes: tx_metrics.initial_storage_writes,
+            repeated_storage_writes: tx_metrics.repeated_storage_writes,
         }
     }
 
@@ -123,6 +130,8 @@ impl Add for ExecutionMetrics {
             computational_gas_used: self.computational_gas_used + other.computational_gas_used,
             pubdata_published: self.pubdata_published + other.pubdata_published,
             circuit_statistic: self.circuit_statistic + other.circuit_statistic,
+            initial_storage_writes: self.initial_storage_writes + other.initial_storage_writes,
+            repeated_storage_writes: self.repeated_storage_writes + other.repeated_storage_writes,
         }
     }
 }
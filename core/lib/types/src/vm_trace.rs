@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt,
     fmt::Display,
 };
@@ -8,7 +8,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zksync_system_constants::BOOTLOADER_ADDRESS;
 use zksync_utils::u256_to_h256;
 
-use crate::{zk_evm_types::FarCallOpcode, Address, U256};
+use crate::{zk_evm_types::FarCallOpcode, Address, StorageKey, H256, U256};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum VmTrace {
@@ -200,6 +200,66 @@ impl fmt::Debug for Call {
     }
 }
 
+/// Storage slot accessed during the VM execution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StorageAccess {
+    /// Accessed storage key.
+    pub key: StorageKey,
+    /// Value of the slot before the execution.
+    pub value_before: H256,
+    /// Value of the slot after the execution.
+    pub value_after: H256,
+}
+
+/// Accounts and storage slots touched during the VM execution.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchedState {
+    /// Addresses of accounts called or having their storage accessed.
+    pub accounts: BTreeSet<Address>,
+    /// Accessed storage slots.
+    pub storage: Vec<StorageAccess>,
+}
+
+/// Storage slots read and written during the VM execution.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StorageAccesses {
+    /// Slots read by the `StorageRead` opcode.
+    pub reads: BTreeSet<StorageKey>,
+    /// Slots written by the `StorageWrite` opcode (including writes in reverted frames), mapped to whether
+    /// the write is initial, i.e. the slot was never written before execution.
+    pub writes: BTreeMap<StorageKey, bool>,
+}
+
+/// Instruction executed by the VM, as recorded by the struct-log tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u16,
+    pub op: String,
+    /// Ergs remaining in the current frame before the instruction is executed.
+    pub gas: u32,
+    /// Depth of the call stack, including near call frames.
+    pub depth: usize,
+    pub contract_address: Address,
+    /// Values of the general-purpose registers before the instruction is executed.
+    pub registers: Vec<U256>,
+    /// Stack pointer before the instruction is executed.
+    pub sp: u16,
+    /// Heap writes performed by the instruction. Empty if memory recording is disabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memory_writes: Vec<MemoryWrite>,
+}
+
+/// Write of a 32-byte word to a heap memory page.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryWrite {
+    pub page: u32,
+    /// Byte offset of the written word in the page.
+    pub offset: u32,
+    pub value: U256,
+}
+
 #[derive(Debug, Clone)]
 pub enum ViolatedValidationRule {
     TouchedUnallowedStorageSlots(Address, U256),
@@ -14,7 +14,9 @@ use std::{
 use jsonrpsee::core::ClientError;
 use pin_project_lite::pin_project;
 use thiserror::Error;
-use zksync_types::{api::SerializationTransactionError, L1BatchNumber, MiniblockNumber};
+use zksync_types::{
+    api::SerializationTransactionError, Address, L1BatchNumber, MiniblockNumber, H256,
+};
 
 #[derive(Debug, Error)]
 pub enum Web3Error {
@@ -26,6 +28,10 @@ pub enum Web3Error {
     PrunedL1Batch(L1BatchNumber),
     #[error("Request timeout")]
     RequestTimeout,
+    #[error(
+        "Transaction {0:?} was submitted, but was not included into a block within the timeout"
+    )]
+    TransactionInclusionTimeout(H256),
     #[error("Internal error")]
     InternalError,
     #[error("RLP decoding error: {0}")]
@@ -40,20 +46,30 @@ pub enum Web3Error {
     SerializationError(#[from] SerializationTransactionError),
     #[error("Invalid fee parameters: {0}")]
     InvalidFeeParams(String),
+    #[error("Invalid reward percentiles: {0}")]
+    InvalidRewardPercentiles(String),
     #[error("More than four topics in filter")]
     TooManyTopics,
+    #[error("Invalid state override for account {0:?}: {1}")]
+    InvalidStateOverride(Address, String),
+    #[error("Bundle contains {0} transactions, while at most {1} transactions are allowed")]
+    BundleTooLarge(usize, usize),
     #[error("Your connection time exceeded the limit")]
     PubSubTimeout,
     #[error("Filter not found")]
     FilterNotFound,
     #[error("Not implemented")]
     NotImplemented,
+    #[error("Unsupported tracer: {0}")]
+    UnsupportedTracer(&'static str),
     #[error("Query returned more than {0} results. Try with this block range [{1:#x}, {2:#x}].")]
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
     #[error("Tree API is not available")]
     TreeApiUnavailable,
+    #[error("L1 batch including block {0} is not sealed or not processed by the Merkle tree yet")]
+    UnprocessedL1Batch(MiniblockNumber),
 }
 
 /// Client RPC error with additional details: the method name and arguments of the called method.
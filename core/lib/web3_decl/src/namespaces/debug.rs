@@ -1,6 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{BlockId, BlockNumber, ResultDebugCall, TracerConfig, TracerResult},
     transaction_request::CallRequest,
 };
 
@@ -18,6 +18,9 @@ use crate::types::H256;
     all(not(feature = "client"), feature = "server"),
     rpc(server, namespace = "debug")
 )]
+/// Debug namespace. Traces of blocks and transactions are restored from persisted data, so the `structLogger` tracer
+/// is only supported by `traceCall` (for blocks executed by the latest VM version); other methods return
+/// an invalid params error for it.
 pub trait DebugNamespace {
     #[method(name = "traceBlockByNumber")]
     async fn trace_block_by_number(
@@ -37,11 +40,11 @@ pub trait DebugNamespace {
         request: CallRequest,
         block: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> RpcResult<DebugCall>;
+    ) -> RpcResult<TracerResult>;
     #[method(name = "traceTransaction")]
     async fn trace_transaction(
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Option<DebugCall>>;
+    ) -> RpcResult<Option<TracerResult>>;
 }
@@ -3,7 +3,10 @@ use jsonrpsee::{
     proc_macros::rpc,
 };
 use zksync_types::{
-    api::{BlockId, BlockIdVariant, BlockNumber, Transaction, TransactionVariant},
+    api::{
+        AccessListResult, BlockId, BlockIdVariant, BlockNumber, EthProof, StateOverride,
+        Transaction, TransactionVariant,
+    },
     transaction_request::CallRequest,
     Address, H256,
 };
@@ -33,14 +36,36 @@ pub trait EthNamespace {
     async fn chain_id(&self) -> RpcResult<U64>;
 
     #[method(name = "call")]
-    async fn call(&self, req: CallRequest, block: Option<BlockIdVariant>) -> RpcResult<Bytes>;
+    async fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Bytes>;
 
     #[method(name = "estimateGas")]
-    async fn estimate_gas(&self, req: CallRequest, _block: Option<BlockNumber>) -> RpcResult<U256>;
+    async fn estimate_gas(
+        &self,
+        req: CallRequest,
+        _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<U256>;
+
+    /// Executes a call and returns storage slots accessed during its execution, both in the geth-compatible
+    /// access list format and split into reads, initial writes and repeated writes.
+    #[method(name = "createAccessList")]
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<AccessListResult>;
 
     #[method(name = "gasPrice")]
     async fn gas_price(&self) -> RpcResult<U256>;
 
+    #[method(name = "maxPriorityFeePerGas")]
+    async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
+
     #[method(name = "newFilter")]
     async fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
 
@@ -106,6 +131,14 @@ pub trait EthNamespace {
         block: Option<BlockIdVariant>,
     ) -> RpcResult<H256>;
 
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<EthProof>;
+
     #[method(name = "getTransactionCount")]
     async fn get_transaction_count(
         &self,
@@ -139,6 +172,15 @@ pub trait EthNamespace {
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, tx_bytes: Bytes) -> RpcResult<H256>;
 
+    /// Submits a transaction and waits until it's included into a sealed block. `timeout_ms` is capped
+    /// by the server; if it's not specified, the default server timeout is used.
+    #[method(name = "sendRawTransactionSync")]
+    async fn send_raw_transaction_sync(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<U64>,
+    ) -> RpcResult<TransactionReceipt>;
+
     #[method(name = "syncing")]
     async fn syncing(&self) -> RpcResult<SyncState>;
 
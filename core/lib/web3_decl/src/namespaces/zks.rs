@@ -3,10 +3,10 @@ use std::collections::HashMap;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BlockId, BridgeAddresses, BundleTransactionResult, L1BatchDetails,
+        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
     },
-    fee::Fee,
+    fee::{Fee, FeeWithBreakdown},
     fee_model::FeeParams,
     transaction_request::CallRequest,
     Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
@@ -30,6 +30,9 @@ pub trait ZksNamespace {
     #[method(name = "estimateFee")]
     async fn estimate_fee(&self, req: CallRequest) -> RpcResult<Fee>;
 
+    #[method(name = "estimateFeeWithBreakdown")]
+    async fn estimate_fee_with_breakdown(&self, req: CallRequest) -> RpcResult<FeeWithBreakdown>;
+
     #[method(name = "estimateGasL1ToL2")]
     async fn estimate_gas_l1_to_l2(&self, req: CallRequest) -> RpcResult<U256>;
 
@@ -118,4 +121,14 @@ pub trait ZksNamespace {
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Proof>;
+
+    /// Executes an ordered list of calls on top of the state at the specified block (by default, the pending block).
+    /// State changes made by earlier calls are visible to later ones; changes are not persisted. Execution stops
+    /// after the first halted call, so the returned list may be shorter than the bundle.
+    #[method(name = "simulateBundle")]
+    async fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+    ) -> RpcResult<Vec<BundleTransactionResult>>;
 }
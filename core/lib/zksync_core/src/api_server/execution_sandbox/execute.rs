@@ -1,17 +1,20 @@
 //! Implementation of "executing" methods, e.g. `eth_call`.
 
+use std::sync::Arc;
+
 use anyhow::Context as _;
 use multivm::{
-    interface::{TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
+    interface::{ExecutionResult, TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
     tracers::StorageInvocations,
     vm_latest::constants::ETH_CALL_GAS_LIMIT,
     MultiVMTracer,
 };
+use once_cell::sync::OnceCell;
 use tracing::{span, Level};
 use zksync_dal::ConnectionPool;
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, ExecuteTransactionCommon, Nonce,
-    PackedEthSignature, Transaction, U256,
+    api::StateOverride, fee::TransactionExecutionMetrics, l2::L2Tx, vm_trace::TouchedState,
+    ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
 };
 
 #[cfg(test)]
@@ -25,6 +28,8 @@ pub(crate) struct TxExecutionArgs {
     pub added_balance: U256,
     pub enforced_base_fee: Option<u64>,
     pub missed_storage_invocation_limit: usize,
+    /// Overrides applied to the storage the transaction is executed on.
+    pub state_override: Option<StateOverride>,
 }
 
 impl TxExecutionArgs {
@@ -35,6 +40,7 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee: Some(tx.common_data.fee.max_fee_per_gas.as_u64()),
             missed_storage_invocation_limit: usize::MAX,
+            state_override: None,
         }
     }
 
@@ -49,6 +55,7 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee: Some(enforced_base_fee),
             missed_storage_invocation_limit,
+            state_override: None,
         }
     }
 
@@ -72,8 +79,14 @@ impl TxExecutionArgs {
             enforced_nonce: tx.nonce(),
             added_balance,
             enforced_base_fee: Some(base_fee),
+            state_override: None,
         }
     }
+
+    pub fn with_state_override(mut self, state_override: Option<StateOverride>) -> Self {
+        self.state_override = state_override;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +99,15 @@ pub(crate) struct TransactionExecutionOutput {
     pub are_published_bytecodes_ok: bool,
 }
 
+/// Output of executing a single transaction from a bundle.
+#[derive(Debug, Clone)]
+pub(crate) struct BundleTransactionOutput {
+    /// Output of the VM.
+    pub vm: VmExecutionResultAndLogs,
+    /// Accounts and storage slots touched by the transaction.
+    pub touched_state: TouchedState,
+}
+
 /// Executor of transactions.
 #[derive(Debug)]
 pub(crate) enum TransactionExecutor {
@@ -174,19 +196,14 @@ impl TransactionExecutor {
         block_args: BlockArgs,
         vm_execution_cache_misses_limit: Option<usize>,
         custom_tracers: Vec<ApiTracer>,
+        state_override: Option<StateOverride>,
     ) -> anyhow::Result<VmExecutionResultAndLogs> {
         let enforced_base_fee = tx.common_data.fee.max_fee_per_gas.as_u64();
         let execution_args =
-            TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit);
+            TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit)
+                .with_state_override(state_override);
 
-        if tx.common_data.signature.is_empty() {
-            tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
-        }
-
-        // Protection against infinite-loop eth_calls and alike:
-        // limiting the amount of gas the call can use.
-        // We can't use `BLOCK_ERGS_LIMIT` here since the VM itself has some overhead.
-        tx.common_data.fee.gas_limit = ETH_CALL_GAS_LIMIT.into();
+        Self::prepare_eth_call_tx(&mut tx);
         let output = self
             .execute_tx_in_sandbox(
                 vm_permit,
@@ -201,4 +218,107 @@ impl TransactionExecutor {
             .await?;
         Ok(output.vm)
     }
+
+    fn prepare_eth_call_tx(tx: &mut L2Tx) {
+        if tx.common_data.signature.is_empty() {
+            tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
+        }
+
+        // Protection against infinite-loop eth_calls and alike:
+        // limiting the amount of gas the call can use.
+        // We can't use `BLOCK_ERGS_LIMIT` here since the VM itself has some overhead.
+        tx.common_data.fee.gas_limit = ETH_CALL_GAS_LIMIT.into();
+    }
+
+    /// Executes an ordered bundle of `eth_call`-like transactions in a single VM instance, so that storage changes
+    /// made by earlier transactions are visible to the later ones. The base fee is enforced based on the first transaction
+    /// in the bundle, and the storage invocations limit applies to the bundle as a whole.
+    ///
+    /// Since the sandbox VM doesn't support rollbacks, execution stops after the first halted transaction;
+    /// the returned outputs only cover transactions up to and including the halted one.
+    #[tracing::instrument(skip_all)]
+    pub async fn execute_bundle_eth_call(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool,
+        mut txs: Vec<L2Tx>,
+        block_args: BlockArgs,
+        vm_execution_cache_misses_limit: Option<usize>,
+    ) -> anyhow::Result<Vec<BundleTransactionOutput>> {
+        #[cfg(test)]
+        if let Self::Mock(mock_executor) = self {
+            let mut outputs = vec![];
+            for tx in txs {
+                let output = mock_executor.execute_tx(&tx.into(), &block_args)?;
+                let is_halted = matches!(output.vm.result, ExecutionResult::Halt { .. });
+                outputs.push(BundleTransactionOutput {
+                    vm: output.vm,
+                    touched_state: TouchedState::default(),
+                });
+                if is_halted {
+                    break;
+                }
+            }
+            return Ok(outputs);
+        }
+
+        let Some(first_tx) = txs.first() else {
+            return Ok(vec![]);
+        };
+        let enforced_base_fee = first_tx.common_data.fee.max_fee_per_gas.as_u64();
+        let execution_args =
+            TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit);
+        for tx in &mut txs {
+            Self::prepare_eth_call_tx(tx);
+        }
+        let mut txs = txs.into_iter().map(Transaction::from);
+        let first_tx = txs.next().unwrap(); // Safe: checked that the bundle is not empty above
+
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "execute_bundle_in_sandbox").entered();
+            let result = apply::apply_vm_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx,
+                block_args,
+                |vm, first_tx| {
+                    // The tracer checks the number of missed storage invocations accumulated by the VM storage,
+                    // which is shared by all transactions in the bundle.
+                    let storage_invocation_tracer =
+                        StorageInvocations::new(execution_args.missed_storage_invocation_limit);
+                    let mut outputs = vec![];
+                    for tx in std::iter::once(first_tx).chain(txs) {
+                        let touched_state = Arc::new(OnceCell::new());
+                        let tracers = vec![
+                            ApiTracer::PrestateTracer(touched_state.clone()).into_boxed(),
+                            storage_invocation_tracer.clone().into_tracer_pointer(),
+                        ];
+                        let (_, vm_output) = vm.inspect_transaction_with_bytecode_compression(
+                            tracers.into(),
+                            tx,
+                            true,
+                        );
+                        // A halt may leave the VM in an inconsistent state, so the following transactions are not executed.
+                        let is_halted = matches!(vm_output.result, ExecutionResult::Halt { .. });
+                        outputs.push(BundleTransactionOutput {
+                            vm: vm_output,
+                            touched_state: touched_state.get().cloned().unwrap_or_default(),
+                        });
+                        if is_halted {
+                            break;
+                        }
+                    }
+                    outputs
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("bundle execution panicked")?
+    }
 }
@@ -1,7 +1,14 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use anyhow::Context as _;
-use tokio::runtime::Handle;
+use tokio::{runtime::Handle, sync::watch};
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_state::{PostgresStorage, PostgresStorageCaches, ReadStorage, StorageView};
 use zksync_system_constants::PUBLISH_BYTECODE_OVERHEAD;
@@ -13,7 +20,7 @@ use zksync_utils::bytecode::{compress_bytecode, hash_bytecode};
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{TransactionExecutor, TxExecutionArgs},
+    execute::{BundleTransactionOutput, TransactionExecutor, TxExecutionArgs},
     tracers::ApiTracer,
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
@@ -24,6 +31,7 @@ use super::tx_sender::MultiVMBaseSystemContracts;
 mod apply;
 mod error;
 mod execute;
+mod storage;
 #[cfg(test)]
 pub(super) mod testonly;
 #[cfg(test)]
@@ -240,44 +248,108 @@ impl TxSharedArgs {
     }
 }
 
-/// Information about first L1 batch / miniblock in the node storage.
-#[derive(Debug, Clone, Copy)]
+/// Information about first L1 batch / miniblock in the node storage. The first available block may advance
+/// over time because of data pruning; use [`Self::update_task()`] to keep the info up to date.
+#[derive(Debug, Clone)]
 pub(crate) struct BlockStartInfo {
     /// Number of the first locally available miniblock.
-    pub first_miniblock: MiniblockNumber,
+    first_miniblock: Arc<AtomicU32>,
     /// Number of the first locally available L1 batch.
-    pub first_l1_batch: L1BatchNumber,
+    first_l1_batch: Arc<AtomicU32>,
 }
 
 impl BlockStartInfo {
     pub async fn new(storage: &mut StorageProcessor<'_>) -> anyhow::Result<Self> {
+        let (first_miniblock, first_l1_batch) = Self::load(storage).await?;
+        Ok(Self {
+            first_miniblock: Arc::new(AtomicU32::new(first_miniblock.0)),
+            first_l1_batch: Arc::new(AtomicU32::new(first_l1_batch.0)),
+        })
+    }
+
+    async fn load(
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<(MiniblockNumber, L1BatchNumber)> {
         let snapshot_recovery = storage
             .snapshot_recovery_dal()
             .get_applied_snapshot_status()
             .await
             .context("failed getting snapshot recovery status")?;
         let snapshot_recovery = snapshot_recovery.as_ref();
-        Ok(Self {
-            first_miniblock: snapshot_recovery
-                .map_or(MiniblockNumber(0), |recovery| recovery.miniblock_number + 1),
-            first_l1_batch: snapshot_recovery
-                .map_or(L1BatchNumber(0), |recovery| recovery.l1_batch_number + 1),
-        })
+        let pruning_info = storage
+            .pruning_dal()
+            .get_pruning_info()
+            .await
+            .context("failed getting pruning info")?;
+
+        let first_miniblock = snapshot_recovery
+            .map(|recovery| recovery.miniblock_number)
+            .max(pruning_info.last_soft_pruned_miniblock)
+            .map_or(MiniblockNumber(0), |number| number + 1);
+        let first_l1_batch = snapshot_recovery
+            .map(|recovery| recovery.l1_batch_number)
+            .max(pruning_info.last_soft_pruned_l1_batch)
+            .map_or(L1BatchNumber(0), |number| number + 1);
+        Ok((first_miniblock, first_l1_batch))
+    }
+
+    /// Reloads the info from the storage. The first available block never moves backwards.
+    pub async fn update(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let (first_miniblock, first_l1_batch) = Self::load(storage).await?;
+        self.first_miniblock
+            .fetch_max(first_miniblock.0, Ordering::Relaxed);
+        self.first_l1_batch
+            .fetch_max(first_l1_batch.0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns a task that will update this info on a schedule.
+    pub fn update_task(
+        self,
+        connection_pool: ConnectionPool,
+        update_interval: Duration,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> impl Future<Output = anyhow::Result<()>> {
+        async move {
+            loop {
+                if *stop_receiver.borrow() {
+                    tracing::debug!("Stopping first available block updates");
+                    return Ok(());
+                }
+
+                let mut storage = connection_pool.access_storage_tagged("api").await?;
+                self.update(&mut storage).await?;
+                drop(storage);
+
+                tokio::time::timeout(update_interval, stop_receiver.changed())
+                    .await
+                    .ok();
+            }
+        }
+    }
+
+    pub fn first_miniblock(&self) -> MiniblockNumber {
+        MiniblockNumber(self.first_miniblock.load(Ordering::Relaxed))
+    }
+
+    pub fn first_l1_batch(&self) -> L1BatchNumber {
+        L1BatchNumber(self.first_l1_batch.load(Ordering::Relaxed))
     }
 
     /// Checks whether a block with the specified ID is pruned and returns an error if it is.
     /// The `Err` variant wraps the first non-pruned miniblock.
     pub fn ensure_not_pruned_block(&self, block: api::BlockId) -> Result<(), MiniblockNumber> {
+        let first_miniblock = self.first_miniblock();
         match block {
             api::BlockId::Number(api::BlockNumber::Number(number))
-                if number < self.first_miniblock.0.into() =>
+                if number < first_miniblock.0.into() =>
             {
-                Err(self.first_miniblock)
+                Err(first_miniblock)
             }
             api::BlockId::Number(api::BlockNumber::Earliest)
-                if self.first_miniblock > MiniblockNumber(0) =>
+                if first_miniblock > MiniblockNumber(0) =>
             {
-                Err(self.first_miniblock)
+                Err(first_miniblock)
             }
             _ => Ok(()),
         }
@@ -316,7 +388,7 @@ impl BlockArgs {
     pub async fn new(
         connection: &mut StorageProcessor<'_>,
         block_id: api::BlockId,
-        start_info: BlockStartInfo,
+        start_info: &BlockStartInfo,
     ) -> Result<Self, BlockArgsError> {
         // We need to check that `block_id` is present in Postgres or can be present in the future
         // (i.e., it does not refer to a pruned block). If called for a pruned block, the returned value
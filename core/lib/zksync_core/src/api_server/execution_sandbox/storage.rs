@@ -0,0 +1,185 @@
+//! VM storage functionality specifically used in the VM sandbox.
+
+use std::collections::{HashMap, HashSet};
+
+use zksync_state::ReadStorage;
+use zksync_types::{
+    api::StateOverride,
+    get_code_key, get_known_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, StorageKey, StorageValue, H256, U256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+/// [`ReadStorage`] implementation applying [`StateOverride`] on top of the wrapped storage.
+///
+/// Overridden bytecodes are marked as known and are returned by [`ReadStorage::load_factory_dep()`],
+/// so that the VM can decommit them without them being published.
+#[derive(Debug)]
+pub(super) struct StorageWithOverrides<S> {
+    storage_handle: S,
+    overridden_slots: HashMap<StorageKey, H256>,
+    /// Overridden transaction nonces. Kept separately from other slots since the deployment nonce
+    /// stored in the same slot needs to be read from the wrapped storage.
+    overridden_nonces: HashMap<StorageKey, U256>,
+    overridden_factory_deps: HashMap<H256, Vec<u8>>,
+    /// Accounts with the entire storage replaced; storage slots not overridden explicitly are read as zero.
+    overridden_accounts: HashSet<AccountTreeId>,
+}
+
+impl<S: ReadStorage> StorageWithOverrides<S> {
+    /// Wraps the provided storage. Overridden bytecodes are assumed to be validated beforehand.
+    pub fn new(storage: S, state_override: Option<&StateOverride>) -> Self {
+        let mut this = Self {
+            storage_handle: storage,
+            overridden_slots: HashMap::new(),
+            overridden_nonces: HashMap::new(),
+            overridden_factory_deps: HashMap::new(),
+            overridden_accounts: HashSet::new(),
+        };
+        for (address, account) in state_override.into_iter().flat_map(StateOverride::iter) {
+            if let Some(balance) = account.balance {
+                let balance_key = storage_key_for_eth_balance(address);
+                this.overridden_slots
+                    .insert(balance_key, u256_to_h256(balance));
+            }
+            if let Some(nonce) = account.nonce {
+                let nonce_key = get_nonce_key(address);
+                this.overridden_nonces
+                    .insert(nonce_key, U256::from(nonce.as_u64()));
+            }
+            if let Some(code) = &account.code {
+                let code_key = get_code_key(address);
+                if code.0.is_empty() {
+                    this.overridden_slots.insert(code_key, H256::zero());
+                } else {
+                    let bytecode_hash = hash_bytecode(&code.0);
+                    this.overridden_slots.insert(code_key, bytecode_hash);
+                    let known_code_key = get_known_code_key(&bytecode_hash);
+                    this.overridden_slots
+                        .insert(known_code_key, H256::from_low_u64_be(1));
+                    this.overridden_factory_deps
+                        .insert(bytecode_hash, code.0.clone());
+                }
+            }
+
+            let account_tree_id = AccountTreeId::new(*address);
+            if account.state.is_some() {
+                this.overridden_accounts.insert(account_tree_id);
+            }
+            let slots = account.state.iter().chain(&account.state_diff).flatten();
+            for (&slot, &value) in slots {
+                let key = StorageKey::new(account_tree_id, slot);
+                this.overridden_slots.insert(key, value);
+            }
+        }
+        this
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for StorageWithOverrides<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(&value) = self.overridden_slots.get(key) {
+            return value;
+        }
+        if let Some(&nonce) = self.overridden_nonces.get(key) {
+            let full_nonce = self.storage_handle.read_value(key);
+            let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+            return u256_to_h256(nonces_to_full_nonce(nonce, deployment_nonce));
+        }
+        if self.overridden_accounts.contains(key.account()) {
+            return H256::zero();
+        }
+        self.storage_handle.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.storage_handle.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.overridden_factory_deps
+            .get(&hash)
+            .cloned()
+            .or_else(|| self.storage_handle.load_factory_dep(hash))
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.storage_handle.get_enumeration_index(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_state::InMemoryStorage;
+    use zksync_types::{
+        api::{OverrideAccount, StateOverride},
+        Address,
+    };
+
+    use super::*;
+
+    #[test]
+    fn override_basics() {
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let slot = H256::repeat_byte(0xaa);
+        let other_slot = H256::repeat_byte(0xbb);
+
+        let mut storage = InMemoryStorage::default();
+        let nonce_key = get_nonce_key(&address);
+        // Transaction nonce 3, deployment nonce 5
+        let full_nonce = nonces_to_full_nonce(3.into(), 5.into());
+        storage.set_value(nonce_key, u256_to_h256(full_nonce));
+        storage.set_value(
+            StorageKey::new(AccountTreeId::new(address), other_slot),
+            H256::repeat_byte(0xff),
+        );
+        storage.set_value(
+            StorageKey::new(AccountTreeId::new(other_address), other_slot),
+            H256::repeat_byte(0xff),
+        );
+
+        let bytecode = vec![0xfe; 32];
+        let bytecode_hash = hash_bytecode(&bytecode);
+        let state_override = StateOverride::new(HashMap::from([
+            (
+                address,
+                OverrideAccount {
+                    balance: Some(1_000.into()),
+                    nonce: Some(10.into()),
+                    code: Some(bytecode.clone().into()),
+                    state: Some(HashMap::from([(slot, H256::repeat_byte(0x11))])),
+                    state_diff: None,
+                },
+            ),
+            (
+                other_address,
+                OverrideAccount {
+                    state_diff: Some(HashMap::from([(slot, H256::repeat_byte(0x22))])),
+                    ..OverrideAccount::default()
+                },
+            ),
+        ]));
+        let mut storage = StorageWithOverrides::new(storage, Some(&state_override));
+
+        let balance = storage.read_value(&storage_key_for_eth_balance(&address));
+        assert_eq!(h256_to_u256(balance), 1_000.into());
+        let full_nonce = h256_to_u256(storage.read_value(&nonce_key));
+        assert_eq!(decompose_full_nonce(full_nonce), (10.into(), 5.into()));
+        assert_eq!(storage.read_value(&get_code_key(&address)), bytecode_hash);
+        assert!(storage.is_bytecode_known(&bytecode_hash));
+        assert_eq!(storage.load_factory_dep(bytecode_hash), Some(bytecode));
+
+        // Storage of `address` is replaced completely.
+        let key = StorageKey::new(AccountTreeId::new(address), slot);
+        assert_eq!(storage.read_value(&key), H256::repeat_byte(0x11));
+        let key = StorageKey::new(AccountTreeId::new(address), other_slot);
+        assert_eq!(storage.read_value(&key), H256::zero());
+        // ...while storage of `other_address` is patched.
+        let key = StorageKey::new(AccountTreeId::new(other_address), slot);
+        assert_eq!(storage.read_value(&key), H256::repeat_byte(0x22));
+        let key = StorageKey::new(AccountTreeId::new(other_address), other_slot);
+        assert_eq!(storage.read_value(&key), H256::repeat_byte(0xff));
+    }
+}
@@ -1,6 +1,12 @@
 //! Tests for the VM execution sandbox.
 
+use std::collections::HashMap;
+
 use assert_matches::assert_matches;
+use multivm::interface::ExecutionResult;
+use zksync_contracts::{load_contract, read_bytecode};
+use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
+use zksync_types::{ethabi::Token, get_code_key, Address, StorageLog, H256, U256};
 
 use super::*;
 use crate::{
@@ -32,11 +38,11 @@ async fn creating_block_args() {
     assert_eq!(pending_block_args.l1_batch_timestamp_s, None);
 
     let start_info = BlockStartInfo::new(&mut storage).await.unwrap();
-    assert_eq!(start_info.first_miniblock, MiniblockNumber(0));
-    assert_eq!(start_info.first_l1_batch, L1BatchNumber(0));
+    assert_eq!(start_info.first_miniblock(), MiniblockNumber(0));
+    assert_eq!(start_info.first_l1_batch(), L1BatchNumber(0));
 
     let latest_block = api::BlockId::Number(api::BlockNumber::Latest);
-    let latest_block_args = BlockArgs::new(&mut storage, latest_block, start_info)
+    let latest_block_args = BlockArgs::new(&mut storage, latest_block, &start_info)
         .await
         .unwrap();
     assert_eq!(latest_block_args.block_id, latest_block);
@@ -47,7 +53,7 @@ async fn creating_block_args() {
     );
 
     let earliest_block = api::BlockId::Number(api::BlockNumber::Earliest);
-    let earliest_block_args = BlockArgs::new(&mut storage, earliest_block, start_info)
+    let earliest_block_args = BlockArgs::new(&mut storage, earliest_block, &start_info)
         .await
         .unwrap();
     assert_eq!(earliest_block_args.block_id, earliest_block);
@@ -58,7 +64,7 @@ async fn creating_block_args() {
     assert_eq!(earliest_block_args.l1_batch_timestamp_s, Some(0));
 
     let missing_block = api::BlockId::Number(100.into());
-    let err = BlockArgs::new(&mut storage, missing_block, start_info)
+    let err = BlockArgs::new(&mut storage, missing_block, &start_info)
         .await
         .unwrap_err();
     assert_matches!(err, BlockArgsError::Missing);
@@ -84,16 +90,16 @@ async fn creating_block_args_after_snapshot_recovery() {
 
     let start_info = BlockStartInfo::new(&mut storage).await.unwrap();
     assert_eq!(
-        start_info.first_miniblock,
+        start_info.first_miniblock(),
         snapshot_recovery.miniblock_number + 1
     );
     assert_eq!(
-        start_info.first_l1_batch,
+        start_info.first_l1_batch(),
         snapshot_recovery.l1_batch_number + 1
     );
 
     let latest_block = api::BlockId::Number(api::BlockNumber::Latest);
-    let err = BlockArgs::new(&mut storage, latest_block, start_info)
+    let err = BlockArgs::new(&mut storage, latest_block, &start_info)
         .await
         .unwrap_err();
     assert_matches!(err, BlockArgsError::Missing);
@@ -105,7 +111,7 @@ async fn creating_block_args_after_snapshot_recovery() {
     ];
     for pruned_block in pruned_blocks {
         let pruned_block = api::BlockId::Number(pruned_block);
-        let err = BlockArgs::new(&mut storage, pruned_block, start_info)
+        let err = BlockArgs::new(&mut storage, pruned_block, &start_info)
             .await
             .unwrap_err();
         assert_matches!(err, BlockArgsError::Pruned(_));
@@ -117,7 +123,7 @@ async fn creating_block_args_after_snapshot_recovery() {
     ];
     for missing_block in missing_blocks {
         let missing_block = api::BlockId::Number(missing_block);
-        let err = BlockArgs::new(&mut storage, missing_block, start_info)
+        let err = BlockArgs::new(&mut storage, missing_block, &start_info)
             .await
             .unwrap_err();
         assert_matches!(err, BlockArgsError::Missing);
@@ -130,7 +136,7 @@ async fn creating_block_args_after_snapshot_recovery() {
         .await
         .unwrap();
 
-    let latest_block_args = BlockArgs::new(&mut storage, latest_block, start_info)
+    let latest_block_args = BlockArgs::new(&mut storage, latest_block, &start_info)
         .await
         .unwrap();
     assert_eq!(latest_block_args.block_id, latest_block);
@@ -142,20 +148,61 @@ async fn creating_block_args_after_snapshot_recovery() {
 
     for pruned_block in pruned_blocks {
         let pruned_block = api::BlockId::Number(pruned_block);
-        let err = BlockArgs::new(&mut storage, pruned_block, start_info)
+        let err = BlockArgs::new(&mut storage, pruned_block, &start_info)
             .await
             .unwrap_err();
         assert_matches!(err, BlockArgsError::Pruned(_));
     }
     for missing_block in missing_blocks {
         let missing_block = api::BlockId::Number(missing_block);
-        let err = BlockArgs::new(&mut storage, missing_block, start_info)
+        let err = BlockArgs::new(&mut storage, missing_block, &start_info)
             .await
             .unwrap_err();
         assert_matches!(err, BlockArgsError::Missing);
     }
 }
 
+#[tokio::test]
+async fn updating_block_start_info_after_pruning() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+        .await
+        .unwrap();
+    for number in 1..=3 {
+        storage
+            .blocks_dal()
+            .insert_miniblock(&create_miniblock(number))
+            .await
+            .unwrap();
+    }
+
+    let start_info = BlockStartInfo::new(&mut storage).await.unwrap();
+    assert_eq!(start_info.first_miniblock(), MiniblockNumber(0));
+    let block = api::BlockId::Number(1.into());
+    BlockArgs::new(&mut storage, block, &start_info)
+        .await
+        .unwrap();
+
+    storage
+        .pruning_dal()
+        .soft_prune_batches_range(L1BatchNumber(0), MiniblockNumber(1))
+        .await
+        .unwrap();
+    start_info.update(&mut storage).await.unwrap();
+    assert_eq!(start_info.first_miniblock(), MiniblockNumber(2));
+    assert_eq!(start_info.first_l1_batch(), L1BatchNumber(1));
+
+    let err = BlockArgs::new(&mut storage, block, &start_info)
+        .await
+        .unwrap_err();
+    assert_matches!(err, BlockArgsError::Pruned(MiniblockNumber(2)));
+    let block = api::BlockId::Number(2.into());
+    BlockArgs::new(&mut storage, block, &start_info)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn instantiating_vm() {
     let pool = ConnectionPool::test_pool().await;
@@ -167,7 +214,7 @@ async fn instantiating_vm() {
     let block_args = BlockArgs::pending(&mut storage).await.unwrap();
     test_instantiating_vm(pool.clone(), block_args).await;
     let start_info = BlockStartInfo::new(&mut storage).await.unwrap();
-    let block_args = BlockArgs::new(&mut storage, api::BlockId::Number(0.into()), start_info)
+    let block_args = BlockArgs::new(&mut storage, api::BlockId::Number(0.into()), &start_info)
         .await
         .unwrap();
     test_instantiating_vm(pool.clone(), block_args).await;
@@ -196,3 +243,71 @@ async fn test_instantiating_vm(pool: ConnectionPool, block_args: BlockArgs) {
     .expect("VM instantiation panicked")
     .expect("VM instantiation errored");
 }
+
+const COUNTER_CONTRACT_PATH: &str =
+    "etc/contracts-test-data/artifacts-zk/contracts/counter/counter.sol/Counter.json";
+
+async fn store_counter_contract(storage: &mut StorageProcessor<'_>, address: Address) {
+    let bytecode = read_bytecode(COUNTER_CONTRACT_PATH);
+    let bytecode_hash = hash_bytecode(&bytecode);
+    let code_log = StorageLog::new_write_log(get_code_key(&address), bytecode_hash);
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(MiniblockNumber(0), &[(H256::zero(), vec![code_log])])
+        .await
+        .unwrap();
+    storage
+        .factory_deps_dal()
+        .insert_factory_deps(
+            MiniblockNumber(0),
+            &HashMap::from([(bytecode_hash, bytecode)]),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn executing_bundle_with_state_shared_between_transactions() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+        .await
+        .unwrap();
+    let counter_address = Address::repeat_byte(1);
+    store_counter_contract(&mut storage, counter_address).await;
+    let block_args = BlockArgs::pending(&mut storage).await.unwrap();
+    drop(storage);
+
+    let increment_fn = load_contract(COUNTER_CONTRACT_PATH)
+        .function("incrementWithRevert")
+        .unwrap()
+        .clone();
+    let txs = [(5_u64, false), (3, true), (2, false)].map(|(increment, should_revert)| {
+        let mut tx = create_l2_transaction(10, DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE);
+        tx.execute.contract_address = counter_address;
+        tx.execute.calldata = increment_fn
+            .encode_input(&[Token::Uint(increment.into()), Token::Bool(should_revert)])
+            .unwrap();
+        tx
+    });
+
+    let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+    let vm_permit = vm_concurrency_limiter.acquire().await.unwrap();
+    let shared_args = TxSharedArgs::mock(ApiContracts::load_from_disk().eth_call, pool.clone());
+    let outputs = TransactionExecutor::Real
+        .execute_bundle_eth_call(vm_permit, shared_args, pool, txs.into(), block_args, None)
+        .await
+        .unwrap();
+
+    let returned_values: Vec<_> = outputs
+        .iter()
+        .map(|output| match &output.vm.result {
+            ExecutionResult::Success { output } => Some(U256::from_big_endian(output)),
+            ExecutionResult::Revert { .. } => None,
+            ExecutionResult::Halt { reason } => panic!("Unexpected halt: {reason}"),
+        })
+        .collect();
+    // Each transaction observes the counter value written by the previous successful one; the reverted increment
+    // is not visible.
+    assert_eq!(returned_values, [Some(5.into()), None, Some(7.into())]);
+}
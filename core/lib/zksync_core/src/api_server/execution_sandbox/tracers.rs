@@ -1,14 +1,24 @@
 use std::sync::Arc;
 
-use multivm::{tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer};
+use multivm::{
+    tracers::{CallTracer, PrestateTracer, StorageAccessTracer, StructLogTracer},
+    vm_latest::HistoryMode,
+    MultiVMTracer, MultiVmTracerPointer,
+};
 use once_cell::sync::OnceCell;
 use zksync_state::WriteStorage;
-use zksync_types::vm_trace::Call;
+use zksync_types::{
+    api::StructLogTracerConfig,
+    vm_trace::{Call, StorageAccesses, StructLog, TouchedState},
+};
 
 /// Custom tracers supported by our API
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer(Arc<OnceCell<TouchedState>>),
+    StorageAccessTracer(Arc<OnceCell<StorageAccesses>>),
+    StructLogTracer(Arc<OnceCell<Vec<StructLog>>>, StructLogTracerConfig),
 }
 
 impl ApiTracer {
@@ -20,6 +30,16 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer(tracer) => {
+                PrestateTracer::new(tracer.clone()).into_tracer_pointer()
+            }
+            ApiTracer::StorageAccessTracer(tracer) => {
+                StorageAccessTracer::new(tracer.clone()).into_tracer_pointer()
+            }
+            ApiTracer::StructLogTracer(tracer, config) => {
+                StructLogTracer::new(tracer.clone(), config.max_steps, !config.disable_memory)
+                    .into_tracer_pointer()
+            }
         }
     }
 }
@@ -211,7 +211,7 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
-    fn create_api_server(
+    pub(crate) fn create_api_server(
         self,
         bind_address: &SocketAddr,
         mut stop_receiver: watch::Receiver<bool>,
@@ -263,7 +263,7 @@ impl AsyncTreeReader {
 
 /// `axum`-powered REST server for Merkle tree API.
 #[must_use = "Server must be `run()`"]
-struct MerkleTreeServer {
+pub(crate) struct MerkleTreeServer {
     local_addr: SocketAddr,
     server_future: Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>,
 }
@@ -283,7 +283,7 @@ impl MerkleTreeServer {
         &self.local_addr
     }
 
-    async fn run(self) -> anyhow::Result<()> {
+    pub(crate) async fn run(self) -> anyhow::Result<()> {
         self.server_future.await
     }
 }
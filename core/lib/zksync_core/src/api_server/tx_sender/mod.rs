@@ -1,6 +1,10 @@
 //! Helper module to submit transactions into the zkSync Network.
 
-use std::{cmp, sync::Arc, time::Instant};
+use std::{
+    cmp,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use multivm::{
@@ -8,37 +12,49 @@ use multivm::{
     utils::{adjust_pubdata_price_for_tx, derive_base_fee_and_gas_per_pubdata, derive_overhead},
     vm_latest::constants::{BLOCK_GAS_LIMIT, MAX_PUBDATA_PER_BLOCK},
 };
-use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
+use once_cell::sync::OnceCell;
+use tokio::sync::watch;
+use zksync_config::configs::{
+    api::Web3JsonRpcConfig,
+    chain::{MempoolConfig, StateKeeperConfig},
+};
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, StorageProcessor};
+use zksync_mempool::AccountTxPolicy;
 use zksync_state::PostgresStorageCaches;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
-    fee::{Fee, TransactionExecutionMetrics},
+    api::{self, StateOverride},
+    fee::{Fee, FeeBreakdown, FeeWithBreakdown, TransactionExecutionMetrics},
     fee_model::BatchFeeInput,
     get_code_key, get_intrinsic_constants,
     l1::is_l1_tx_type,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
     utils::storage_key_for_eth_balance,
+    vm_trace::StorageAccesses,
     AccountTreeId, Address, ExecuteTransactionCommon, L2ChainId, MiniblockNumber, Nonce,
     PackedEthSignature, ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
     MAX_NEW_FACTORY_DEPS, U256,
 };
-use zksync_utils::h256_to_u256;
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256};
 
 pub(super) use self::result::SubmitTxError;
 use self::tx_sink::TxSink;
 use crate::{
     api_server::{
         execution_sandbox::{
-            get_pubdata_for_factory_deps, BlockArgs, BlockStartInfo, SubmitTxStage,
-            TransactionExecutor, TxExecutionArgs, TxSharedArgs, VmConcurrencyLimiter, VmPermit,
-            SANDBOX_METRICS,
+            get_pubdata_for_factory_deps, ApiTracer, BlockArgs, BlockStartInfo,
+            BundleTransactionOutput, SubmitTxStage, TransactionExecutor, TxExecutionArgs,
+            TxSharedArgs, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
         },
         tx_sender::result::ApiCallResult,
     },
     fee_model::BatchFeeModelInputProvider,
-    state_keeper::seal_criteria::{ConditionalSealer, NoopSealer, SealData},
+    state_keeper::{
+        seal_criteria::{ConditionalSealer, NoopSealer, SealData},
+        types::account_tx_policy,
+        TxAccessPolicy,
+    },
     utils::pending_protocol_version,
 };
 
@@ -151,6 +167,8 @@ pub struct TxSenderBuilder {
     tx_sink: Arc<dyn TxSink>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Option<Arc<dyn ConditionalSealer>>,
+    /// Policy restricting which accounts may send transactions or deploy contracts.
+    access_policy: Arc<TxAccessPolicy>,
 }
 
 impl TxSenderBuilder {
@@ -164,6 +182,7 @@ impl TxSenderBuilder {
             replica_connection_pool,
             tx_sink,
             sealer: None,
+            access_policy: Arc::default(),
         }
     }
 
@@ -172,6 +191,12 @@ impl TxSenderBuilder {
         self
     }
 
+    /// Sets the access policy for submitted transactions. By default, transactions are not restricted.
+    pub fn with_access_policy(mut self, access_policy: Arc<TxAccessPolicy>) -> Self {
+        self.access_policy = access_policy;
+        self
+    }
+
     pub async fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -191,6 +216,7 @@ impl TxSenderBuilder {
             vm_concurrency_limiter,
             storage_caches,
             sealer,
+            access_policy: self.access_policy,
             executor: TransactionExecutor::Real,
         }))
     }
@@ -210,12 +236,15 @@ pub struct TxSenderConfig {
     pub validation_computational_gas_limit: u32,
     pub l1_to_l2_transactions_compatibility_mode: bool,
     pub chain_id: L2ChainId,
+    /// Limits on pending transactions of a single account; should be consistent with the mempool.
+    pub account_tx_policy: AccountTxPolicy,
 }
 
 impl TxSenderConfig {
     pub fn new(
         state_keeper_config: &StateKeeperConfig,
         web3_json_config: &Web3JsonRpcConfig,
+        mempool_config: &MempoolConfig,
         chain_id: L2ChainId,
     ) -> Self {
         Self {
@@ -229,6 +258,7 @@ impl TxSenderConfig {
             l1_to_l2_transactions_compatibility_mode: web3_json_config
                 .l1_to_l2_transactions_compatibility_mode,
             chain_id,
+            account_tx_policy: account_tx_policy(mempool_config),
         }
     }
 }
@@ -247,6 +277,8 @@ pub struct TxSenderInner {
     storage_caches: PostgresStorageCaches,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Arc<dyn ConditionalSealer>,
+    /// Policy restricting which accounts may send transactions or deploy contracts.
+    access_policy: Arc<TxAccessPolicy>,
     pub(super) executor: TransactionExecutor,
 }
 
@@ -279,6 +311,7 @@ impl TxSender {
     #[tracing::instrument(skip(self, tx))]
     pub async fn submit_tx(&self, tx: L2Tx) -> Result<L2TxSubmissionResult, SubmitTxError> {
         let stage_latency = SANDBOX_METRICS.submit_tx[&SubmitTxStage::Validate].start();
+        self.0.access_policy.check_l2_tx(&tx)?;
         self.validate_tx(&tx).await?;
         stage_latency.observe();
 
@@ -375,6 +408,37 @@ impl TxSender {
         }
     }
 
+    /// Waits until the transaction with the specified hash is included into a sealed miniblock and returns its receipt.
+    /// Inclusion is checked each time `sealed_miniblocks` is updated; to not miss updates, the receiver should be
+    /// created before the transaction is submitted. Returns `Ok(None)` if the transaction wasn't included
+    /// within `timeout`.
+    pub(crate) async fn wait_for_receipt(
+        &self,
+        tx_hash: H256,
+        mut sealed_miniblocks: watch::Receiver<MiniblockNumber>,
+        timeout: Duration,
+    ) -> anyhow::Result<Option<api::TransactionReceipt>> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let mut connection = self.acquire_replica_connection().await?;
+            let receipts = connection
+                .transactions_web3_dal()
+                .get_transaction_receipts(&[tx_hash])
+                .await
+                .context("get_transaction_receipts()")?;
+            drop(connection);
+            if let Some(receipt) = receipts.into_iter().next() {
+                return Ok(Some(receipt));
+            }
+
+            match tokio::time::timeout_at(deadline, sealed_miniblocks.changed()).await {
+                Ok(Ok(())) => { /* Check inclusion once again */ }
+                // The sender is dropped only if the server is shutting down.
+                Ok(Err(_)) | Err(_) => return Ok(None),
+            }
+        }
+    }
+
     async fn shared_args(&self) -> TxSharedArgs {
         TxSharedArgs {
             operator_account: AccountTreeId::new(self.0.sender_config.fee_account_addr),
@@ -444,14 +508,16 @@ impl TxSender {
 
         // We still double-check the nonce manually
         // to make sure that only the correct nonce is submitted and the transaction's hashes never repeat
-        self.validate_account_nonce(tx).await?;
+        let expected_nonce = self.validate_account_nonce(tx).await?;
+        self.validate_pending_txs(tx, expected_nonce).await?;
         // Even though without enough balance the tx will not pass anyway
         // we check the user for enough balance explicitly here for better DevEx.
         self.validate_enough_balance(tx).await?;
         Ok(())
     }
 
-    async fn validate_account_nonce(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+    /// Validates the transaction nonce. Returns the expected (i.e., committed) nonce for the transaction initiator.
+    async fn validate_account_nonce(&self, tx: &L2Tx) -> Result<Nonce, SubmitTxError> {
         let Nonce(expected_nonce) = self
             .get_expected_nonce(tx.initiator_account())
             .await
@@ -477,9 +543,52 @@ impl TxSender {
                     tx.nonce().0,
                 ))
             } else {
-                Ok(())
+                Ok(Nonce(expected_nonce))
+            }
+        }
+    }
+
+    /// Checks the transaction against limits on pending transactions of its initiator.
+    async fn validate_pending_txs(
+        &self,
+        tx: &L2Tx,
+        expected_nonce: Nonce,
+    ) -> Result<(), SubmitTxError> {
+        let policy = self.0.sender_config.account_tx_policy;
+        if policy == AccountTxPolicy::default() {
+            return Ok(());
+        }
+
+        let initiator_account = tx.initiator_account();
+        let mut storage = self.acquire_replica_connection().await?;
+        let pending_fee = storage
+            .transactions_web3_dal()
+            .get_pending_tx_fee(initiator_account, tx.nonce())
+            .await
+            .with_context(|| {
+                format!("failed getting pending transaction for {initiator_account:?}")
+            })?;
+        if let Some(pending_fee) = pending_fee {
+            if !policy.allows_replacement(&pending_fee, &tx.common_data.fee) {
+                let bump_percent = policy.min_replacement_fee_bump_percent.unwrap_or(0);
+                return Err(SubmitTxError::ReplacementUnderpriced(bump_percent));
             }
+            return Ok(());
         }
+
+        if let Some(max_pending_txs) = policy.max_pending_txs {
+            let pending_count = storage
+                .transactions_web3_dal()
+                .get_pending_txs_count(initiator_account, expected_nonce.0.into())
+                .await
+                .with_context(|| {
+                    format!("failed getting pending transactions count for {initiator_account:?}")
+                })?;
+            if !policy.allows_new_transaction(pending_count) {
+                return Err(SubmitTxError::TooManyPendingTransactions(max_pending_txs));
+            }
+        }
+        Ok(())
     }
 
     async fn get_expected_nonce(&self, initiator_account: Address) -> anyhow::Result<Nonce> {
@@ -494,7 +603,7 @@ impl TxSender {
             None => {
                 // We don't have miniblocks in the storage yet. Use the snapshot miniblock number instead.
                 let start = BlockStartInfo::new(&mut storage).await?;
-                MiniblockNumber(start.first_miniblock.saturating_sub(1))
+                MiniblockNumber(start.first_miniblock().saturating_sub(1))
             }
         };
 
@@ -558,6 +667,7 @@ impl TxSender {
         block_args: BlockArgs,
         base_fee: u64,
         vm_version: VmVersion,
+        state_override: Option<StateOverride>,
     ) -> anyhow::Result<(VmExecutionResultAndLogs, TransactionExecutionMetrics)> {
         let gas_limit_with_overhead = tx_gas_limit
             + derive_overhead(
@@ -591,7 +701,8 @@ impl TxSender {
         let shared_args = self.shared_args_for_gas_estimate(fee_model_params);
         let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
         let execution_args =
-            TxExecutionArgs::for_gas_estimate(vm_execution_cache_misses_limit, &tx, base_fee);
+            TxExecutionArgs::for_gas_estimate(vm_execution_cache_misses_limit, &tx, base_fee)
+                .with_state_override(state_override);
         let execution_output = self
             .0
             .executor
@@ -625,10 +736,31 @@ impl TxSender {
 
     pub async fn get_txs_fee_in_wei(
         &self,
-        mut tx: Transaction,
+        tx: Transaction,
         estimated_fee_scale_factor: f64,
         acceptable_overestimation: u32,
+        state_override: Option<StateOverride>,
     ) -> Result<Fee, SubmitTxError> {
+        let fee_with_breakdown = self
+            .get_txs_fee_with_breakdown(
+                tx,
+                estimated_fee_scale_factor,
+                acceptable_overestimation,
+                state_override,
+            )
+            .await?;
+        Ok(fee_with_breakdown.fee)
+    }
+
+    /// Estimates the fee for a transaction similarly to [`Self::get_txs_fee_in_wei()`], additionally providing
+    /// the breakdown of the gas used by the final estimation step into computation and pubdata.
+    pub async fn get_txs_fee_with_breakdown(
+        &self,
+        mut tx: Transaction,
+        estimated_fee_scale_factor: f64,
+        acceptable_overestimation: u32,
+        state_override: Option<StateOverride>,
+    ) -> Result<FeeWithBreakdown, SubmitTxError> {
         let estimation_started_at = Instant::now();
 
         let mut connection = self.acquire_replica_connection().await?;
@@ -670,26 +802,38 @@ impl TxSender {
             }
         }
 
-        let hashed_key = get_code_key(&tx.initiator_account());
         // If the default account does not have enough funds for transferring `tx.value`, without taking into account the fee,
-        // there is no sense to estimate the fee.
-        let account_code_hash = self
-            .acquire_replica_connection()
-            .await?
-            .storage_web3_dal()
-            .get_value(&hashed_key)
-            .await
-            .with_context(|| {
-                format!(
-                    "failed getting code hash for account {:?}",
-                    tx.initiator_account()
-                )
-            })?;
+        // there is no sense to estimate the fee. Overridden account code and balance take precedence over the stored ones.
+        let initiator_override = state_override
+            .as_ref()
+            .and_then(|state_override| state_override.get(&tx.initiator_account()));
+        let overridden_code = initiator_override.and_then(|account| account.code.as_ref());
+        let account_code_hash = if let Some(code) = overridden_code {
+            if code.0.is_empty() {
+                H256::zero()
+            } else {
+                hash_bytecode(&code.0)
+            }
+        } else {
+            let hashed_key = get_code_key(&tx.initiator_account());
+            self.acquire_replica_connection()
+                .await?
+                .storage_web3_dal()
+                .get_value(&hashed_key)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed getting code hash for account {:?}",
+                        tx.initiator_account()
+                    )
+                })?
+        };
+        let balance = match initiator_override.and_then(|account| account.balance) {
+            Some(balance) => balance,
+            None => self.get_balance(&tx.initiator_account()).await?,
+        };
 
-        if !tx.is_l1()
-            && account_code_hash == H256::zero()
-            && tx.execute.value > self.get_balance(&tx.initiator_account()).await?
-        {
+        if !tx.is_l1() && account_code_hash == H256::zero() && tx.execute.value > balance {
             tracing::info!(
                 "fee estimation failed on validation step.
                 account: {} does not have enough funds for for transferring tx.value: {}.",
@@ -768,6 +912,7 @@ impl TxSender {
                     block_args,
                     base_fee,
                     protocol_version.into(),
+                    state_override.clone(),
                 )
                 .await
                 .context("estimate_gas step failed")?;
@@ -808,6 +953,7 @@ impl TxSender {
                 block_args,
                 base_fee,
                 protocol_version.into(),
+                state_override,
             )
             .await
             .context("final estimate_gas step failed")?;
@@ -850,18 +996,25 @@ impl TxSender {
                 }
             };
 
-        Ok(Fee {
+        let fee = Fee {
             max_fee_per_gas: base_fee.into(),
             max_priority_fee_per_gas: 0u32.into(),
             gas_limit: full_gas_limit.into(),
             gas_per_pubdata_limit: gas_per_pubdata_byte.into(),
-        })
+        };
+        let breakdown = FeeBreakdown::from_tx_metrics(
+            tx_metrics.gas_used.into(),
+            gas_per_pubdata_byte.into(),
+            &tx_metrics,
+        );
+        Ok(FeeWithBreakdown { fee, breakdown })
     }
 
     pub(super) async fn eth_call(
         &self,
         block_args: BlockArgs,
         tx: L2Tx,
+        state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
@@ -877,11 +1030,66 @@ impl TxSender {
                 block_args,
                 vm_execution_cache_misses_limit,
                 vec![],
+                state_override,
             )
             .await?
             .into_api_call_result()
     }
 
+    /// Executes a call and returns storage slots accessed during its execution.
+    pub(super) async fn eth_call_with_storage_accesses(
+        &self,
+        block_args: BlockArgs,
+        tx: L2Tx,
+    ) -> Result<(VmExecutionResultAndLogs, StorageAccesses), SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let storage_accesses = Arc::new(OnceCell::new());
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let output = self
+            .0
+            .executor
+            .execute_tx_eth_call(
+                vm_permit,
+                self.shared_args().await,
+                self.0.replica_connection_pool.clone(),
+                tx,
+                block_args,
+                vm_execution_cache_misses_limit,
+                vec![ApiTracer::StorageAccessTracer(storage_accesses.clone())],
+                None,
+            )
+            .await?;
+        let storage_accesses = storage_accesses.get().cloned().unwrap_or_default();
+        Ok((output, storage_accesses))
+    }
+
+    /// Executes an ordered bundle of transactions on top of the state specified by `block_args`.
+    pub(super) async fn simulate_bundle(
+        &self,
+        block_args: BlockArgs,
+        txs: Vec<L2Tx>,
+    ) -> Result<Vec<BundleTransactionOutput>, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let outputs = self
+            .0
+            .executor
+            .execute_bundle_eth_call(
+                vm_permit,
+                self.shared_args().await,
+                self.0.replica_connection_pool.clone(),
+                txs,
+                block_args,
+                vm_execution_cache_misses_limit,
+            )
+            .await?;
+        Ok(outputs)
+    }
+
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
         let mut connection = self.acquire_replica_connection().await?;
         let protocol_version = pending_protocol_version(&mut connection)
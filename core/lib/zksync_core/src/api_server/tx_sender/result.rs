@@ -3,7 +3,10 @@ use thiserror::Error;
 use zksync_types::{l2::error::TxCheckError, U256};
 use zksync_web3_decl::error::EnrichedClientError;
 
-use crate::api_server::execution_sandbox::{SandboxExecutionError, ValidationError};
+use crate::{
+    api_server::execution_sandbox::{SandboxExecutionError, ValidationError},
+    state_keeper::TxAccessError,
+};
 
 /// Errors that con occur submitting a transaction or estimating gas for its execution.
 #[derive(Debug, Error)]
@@ -70,6 +73,18 @@ pub enum SubmitTxError {
     ProxyError(#[from] EnrichedClientError),
     #[error("not enough gas to publish compressed bytecodes")]
     FailedToPublishCompressedBytecodes,
+    /// Returned if the transaction replaces a pending transaction without a sufficient fee bump.
+    #[error(
+        "replacement transaction underpriced; max fee per gas and max priority fee per gas \
+         must be increased by at least {0}%"
+    )]
+    ReplacementUnderpriced(u32),
+    /// Returned if the transaction initiator has too many pending transactions.
+    #[error("too many pending transactions for the account; at most {0} are allowed")]
+    TooManyPendingTransactions(usize),
+    /// Returned if the transaction is not allowed by the sequencer access policy.
+    #[error("{0}")]
+    AccessDenied(#[from] TxAccessError),
     /// Catch-all internal error (e.g., database error) that should not be exposed to the caller.
     #[error("internal error")]
     Internal(#[from] anyhow::Error),
@@ -104,6 +119,9 @@ impl SubmitTxError {
             Self::IntrinsicGas => "intrinsic-gas",
             Self::ProxyError(_) => "proxy-error",
             Self::FailedToPublishCompressedBytecodes => "failed-to-publish-compressed-bytecodes",
+            Self::ReplacementUnderpriced(_) => "replacement-underpriced",
+            Self::TooManyPendingTransactions(_) => "too-many-pending-transactions",
+            Self::AccessDenied(_) => "access-denied",
             Self::Internal(_) => "internal",
         }
     }
@@ -1,22 +1,50 @@
 //! Tests for the transaction sender.
 
-use zksync_types::{get_nonce_key, L1BatchNumber, StorageLog};
+use assert_matches::assert_matches;
+use zksync_types::{fee::TransactionExecutionMetrics, get_nonce_key, L1BatchNumber, StorageLog};
 
 use super::*;
 use crate::{
-    api_server::execution_sandbox::{testonly::MockTransactionExecutor, VmConcurrencyBarrier},
+    api_server::{
+        execution_sandbox::{testonly::MockTransactionExecutor, VmConcurrencyBarrier},
+        tx_sender::master_pool_sink::MasterPoolSink,
+    },
     genesis::{ensure_genesis_state, GenesisParams},
-    utils::testonly::{create_miniblock, prepare_recovery_snapshot, MockBatchFeeParamsProvider},
+    state_keeper::TxAccessError,
+    utils::testonly::{
+        create_l2_transaction, create_miniblock, prepare_recovery_snapshot,
+        MockBatchFeeParamsProvider,
+    },
 };
 
 pub(crate) async fn create_test_tx_sender(
     pool: ConnectionPool,
     l2_chain_id: L2ChainId,
     tx_executor: TransactionExecutor,
+) -> (TxSender, VmConcurrencyBarrier) {
+    create_test_tx_sender_with_mempool_config(
+        pool,
+        l2_chain_id,
+        &MempoolConfig::for_tests(),
+        tx_executor,
+    )
+    .await
+}
+
+async fn create_test_tx_sender_with_mempool_config(
+    pool: ConnectionPool,
+    l2_chain_id: L2ChainId,
+    mempool_config: &MempoolConfig,
+    tx_executor: TransactionExecutor,
 ) -> (TxSender, VmConcurrencyBarrier) {
     let web3_config = Web3JsonRpcConfig::for_tests();
     let state_keeper_config = StateKeeperConfig::for_tests();
-    let tx_sender_config = TxSenderConfig::new(&state_keeper_config, &web3_config, l2_chain_id);
+    let tx_sender_config = TxSenderConfig::new(
+        &state_keeper_config,
+        &web3_config,
+        mempool_config,
+        l2_chain_id,
+    );
 
     let mut storage_caches = PostgresStorageCaches::new(1, 1);
     let cache_update_task = storage_caches.configure_storage_values_cache(
@@ -35,6 +63,7 @@ pub(crate) async fn create_test_tx_sender(
         pool,
         batch_fee_model_input_provider,
         storage_caches,
+        Arc::default(),
     )
     .await;
 
@@ -146,3 +175,164 @@ async fn getting_nonce_for_account_after_snapshot_recovery() {
     let nonce = tx_sender.get_expected_nonce(missing_address).await.unwrap();
     assert_eq!(nonce, Nonce(0));
 }
+
+#[tokio::test]
+async fn submitting_tx_from_denied_sender() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let tx = create_l2_transaction(10, 100);
+    let initiator = tx.initiator_account();
+    let state_keeper_config = StateKeeperConfig {
+        tx_sender_denylist: vec![initiator],
+        ..StateKeeperConfig::for_tests()
+    };
+    let access_policy = TxAccessPolicy::new(&state_keeper_config).unwrap();
+    let tx_sender_config = TxSenderConfig::new(
+        &state_keeper_config,
+        &Web3JsonRpcConfig::for_tests(),
+        &MempoolConfig::for_tests(),
+        L2ChainId::default(),
+    );
+    let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+    // The transaction must be rejected before it's executed or persisted.
+    let tx_sender = TxSenderBuilder::new(
+        tx_sender_config,
+        pool.clone(),
+        Arc::new(MasterPoolSink::new(pool)),
+    )
+    .with_access_policy(Arc::new(access_policy))
+    .build(
+        Arc::new(MockBatchFeeParamsProvider::default()),
+        Arc::new(vm_concurrency_limiter),
+        ApiContracts::load_from_disk(),
+        PostgresStorageCaches::new(1, 1),
+    )
+    .await;
+
+    let err = tx_sender.submit_tx(tx).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::AccessDenied(TxAccessError::SenderNotAllowed(address)) if address == initiator
+    );
+    assert_eq!(err.prom_error_code(), "access-denied");
+}
+
+fn create_pending_transaction(
+    initiator: Address,
+    nonce: Nonce,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+) -> L2Tx {
+    let mut tx = create_l2_transaction(max_fee_per_gas, 50);
+    tx.common_data.initiator_address = initiator;
+    tx.common_data.nonce = nonce;
+    tx.common_data.fee.max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+    tx
+}
+
+async fn create_tx_sender_with_account_limits(pool: &ConnectionPool) -> TxSender {
+    let mut storage = pool.access_storage().await.unwrap();
+    ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+        .await
+        .unwrap();
+    drop(storage);
+
+    let mempool_config = MempoolConfig {
+        max_pending_txs_per_account: Some(2),
+        replacement_fee_bump_percent: Some(10),
+        ..MempoolConfig::for_tests()
+    };
+    let tx_executor = MockTransactionExecutor::default().into();
+    let (tx_sender, _) = create_test_tx_sender_with_mempool_config(
+        pool.clone(),
+        L2ChainId::default(),
+        &mempool_config,
+        tx_executor,
+    )
+    .await;
+    tx_sender
+}
+
+#[tokio::test]
+async fn rejecting_underpriced_replacement() {
+    let pool = ConnectionPool::test_pool().await;
+    let tx_sender = create_tx_sender_with_account_limits(&pool).await;
+    let initiator = Address::repeat_byte(1);
+    let pending_tx = create_pending_transaction(initiator, Nonce(0), 100, 10);
+    let mut storage = pool.access_storage().await.unwrap();
+    storage
+        .transactions_dal()
+        .insert_transaction_l2(pending_tx, TransactionExecutionMetrics::default())
+        .await;
+    drop(storage);
+
+    for (max_fee_per_gas, max_priority_fee_per_gas) in [(109, 20), (200, 10), (100, 10)] {
+        let replacement = create_pending_transaction(
+            initiator,
+            Nonce(0),
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        );
+        let err = tx_sender
+            .validate_pending_txs(&replacement, Nonce(0))
+            .await
+            .unwrap_err();
+        assert_matches!(err, SubmitTxError::ReplacementUnderpriced(10));
+        assert_eq!(err.prom_error_code(), "replacement-underpriced");
+    }
+
+    let replacement = create_pending_transaction(initiator, Nonce(0), 110, 11);
+    tx_sender
+        .validate_pending_txs(&replacement, Nonce(0))
+        .await
+        .unwrap();
+    // Transactions with a new nonce are not affected by the replacement rules.
+    let new_tx = create_pending_transaction(initiator, Nonce(1), 1, 0);
+    tx_sender
+        .validate_pending_txs(&new_tx, Nonce(0))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejecting_transaction_over_pending_limit() {
+    let pool = ConnectionPool::test_pool().await;
+    let tx_sender = create_tx_sender_with_account_limits(&pool).await;
+    let initiator = Address::repeat_byte(1);
+    let mut storage = pool.access_storage().await.unwrap();
+    for nonce in [Nonce(0), Nonce(1)] {
+        let pending_tx = create_pending_transaction(initiator, nonce, 100, 10);
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(pending_tx, TransactionExecutionMetrics::default())
+            .await;
+    }
+    drop(storage);
+
+    let tx = create_pending_transaction(initiator, Nonce(2), 100, 10);
+    let err = tx_sender
+        .validate_pending_txs(&tx, Nonce(0))
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::TooManyPendingTransactions(2));
+    assert_eq!(err.prom_error_code(), "too-many-pending-transactions");
+
+    // Replacing a pending transaction doesn't count towards the limit.
+    let replacement = create_pending_transaction(initiator, Nonce(1), 110, 11);
+    tx_sender
+        .validate_pending_txs(&replacement, Nonce(0))
+        .await
+        .unwrap();
+    // Pending transactions of other accounts don't count towards the limit either.
+    let other_tx = create_pending_transaction(Address::repeat_byte(2), Nonce(0), 100, 10);
+    tx_sender
+        .validate_pending_txs(&other_tx, Nonce(0))
+        .await
+        .unwrap();
+    // Transactions with nonces below the expected one are not pending anymore.
+    tx_sender.validate_pending_txs(&tx, Nonce(1)).await.unwrap();
+}
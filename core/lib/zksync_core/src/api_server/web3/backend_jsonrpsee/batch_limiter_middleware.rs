@@ -1,4 +1,4 @@
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, sync::Arc, time::Duration};
 
 use governor::{
     clock::DefaultClock,
@@ -15,11 +15,13 @@ use zksync_web3_decl::jsonrpsee::{
     MethodResponse,
 };
 
+use super::rate_limits::{ClientKey, RateLimitError, RpcRateLimits};
 use crate::api_server::web3::metrics::API_METRICS;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "transport", rename_all = "snake_case")]
 pub(crate) enum Transport {
+    Http,
     Ws,
 }
 
@@ -33,31 +35,68 @@ struct LimitMiddlewareMetrics {
     size: Family<Transport, Histogram<usize>>,
     /// Number of requests rejected by the limiter.
     rejected: Family<Transport, Counter>,
+    /// Number of requests rejected because of exceeded per-client quotas.
+    quota_exceeded: Family<Transport, Counter>,
+    /// Total weight of requests accepted by the limiter.
+    consumed_weight: Family<Transport, Counter>,
 }
 
 #[vise::register]
 static METRICS: vise::Global<LimitMiddlewareMetrics> = vise::Global::new();
 
-/// A rate-limiting middleware.
+/// A rate-limiting middleware. Applies the per-connection limit (for WebSocket connections) and per-client quotas
+/// (for all transports); each request consumes the weight of the called method from both.
 ///
-/// `jsonrpsee` will allocate the instance of this struct once per session.
+/// `jsonrpsee` will allocate the instance of this struct once per session (for WebSocket connections)
+/// or once per request (for HTTP).
 pub(crate) struct LimitMiddleware<S> {
     inner: S,
     rate_limiter: Option<RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>,
+    rate_limits: Option<Arc<RpcRateLimits>>,
+    /// Client key captured when the middleware was created.
+    client_key: Option<ClientKey>,
     transport: Transport,
-    _guard: GaugeGuard,
+    _guard: Option<GaugeGuard>,
 }
 
 impl<S> LimitMiddleware<S> {
-    pub(crate) fn new(inner: S, requests_per_minute_limit: Option<NonZeroU32>) -> Self {
+    pub(crate) fn new(
+        inner: S,
+        transport: Transport,
+        requests_per_minute_limit: Option<NonZeroU32>,
+        rate_limits: Option<Arc<RpcRateLimits>>,
+    ) -> Self {
         Self {
             inner,
             rate_limiter: requests_per_minute_limit
                 .map(|limit| RateLimiter::direct(Quota::per_minute(limit))),
-            transport: Transport::Ws,
-            _guard: API_METRICS.ws_open_sessions.inc_guard(1),
+            rate_limits,
+            client_key: ClientKey::current(),
+            transport,
+            _guard: matches!(transport, Transport::Ws)
+                .then(|| API_METRICS.ws_open_sessions.inc_guard(1)),
         }
     }
+
+    fn rate_limited_response(
+        request: Request<'_>,
+        retry_after: Option<Duration>,
+    ) -> MethodResponse {
+        let data = retry_after.map(|duration| {
+            serde_json::json!({
+                "retryAfterMs": duration.as_millis(),
+            })
+        });
+        MethodResponse::error(
+            request.id,
+            ErrorObject::owned(
+                ErrorCode::ServerError(reqwest::StatusCode::TOO_MANY_REQUESTS.as_u16().into())
+                    .code(),
+                "Too many requests",
+                data,
+            ),
+        )
+    }
 }
 
 impl<'a, S> RpcServiceT<'a> for LimitMiddleware<S>
@@ -67,27 +106,35 @@ where
     type Future = ResponseFuture<S::Future>;
 
     fn call(&self, request: Request<'a>) -> Self::Future {
-        if let Some(rate_limiter) = &self.rate_limiter {
-            let num_requests = NonZeroU32::MIN; // 1 request, no batches possible
+        let weight = self.rate_limits.as_ref().map_or(NonZeroU32::MIN, |limits| {
+            limits.method_weight(request.method_name())
+        });
 
+        if let Some(rate_limiter) = &self.rate_limiter {
             // Note: if required, we can extract data on rate limiting from the error.
-            if rate_limiter.check_n(num_requests).is_err() {
+            if rate_limiter.check_n(weight).is_err() {
                 METRICS.rate_limited[&self.transport].inc();
+                return ResponseFuture::ready(Self::rate_limited_response(request, None));
+            }
+        }
 
-                let rp = MethodResponse::error(
-                    request.id,
-                    ErrorObject::borrowed(
-                        ErrorCode::ServerError(
-                            reqwest::StatusCode::TOO_MANY_REQUESTS.as_u16().into(),
-                        )
-                        .code(),
-                        "Too many requests",
-                        None,
-                    ),
-                );
-                return ResponseFuture::ready(rp);
+        if let Some(limits) = &self.rate_limits {
+            let client_key = self
+                .client_key
+                .clone()
+                .or_else(ClientKey::current)
+                .unwrap_or(ClientKey::Unknown);
+            if let Err(err) = limits.check_client_quota(&client_key, weight) {
+                METRICS.quota_exceeded[&self.transport].inc();
+                let retry_after = match err {
+                    RateLimitError::RetryAfter(duration) => Some(duration),
+                    RateLimitError::InsufficientCapacity => None,
+                };
+                return ResponseFuture::ready(Self::rate_limited_response(request, retry_after));
             }
         }
+
+        METRICS.consumed_weight[&self.transport].inc_by(weight.get().into());
         ResponseFuture::future(self.inner.call(request))
     }
 }
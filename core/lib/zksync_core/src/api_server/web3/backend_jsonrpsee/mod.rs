@@ -13,10 +13,12 @@ use crate::api_server::{tx_sender::SubmitTxError, web3::metrics::API_METRICS};
 
 pub mod batch_limiter_middleware;
 pub mod namespaces;
+pub mod rate_limits;
 
 pub(crate) fn into_jsrpc_error(err: Web3Error) -> ErrorObjectOwned {
     let data = match &err {
         Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
+        Web3Error::TransactionInclusionTimeout(tx_hash) => Some(format!("{tx_hash:?}")),
         _ => None,
     };
     ErrorObjectOwned::owned(
@@ -29,12 +31,17 @@ pub(crate) fn into_jsrpc_error(err: Web3Error) -> ErrorObjectOwned {
             | Web3Error::RLPError(_)
             | Web3Error::InvalidTransactionData(_)
             | Web3Error::TooManyTopics
+            | Web3Error::InvalidStateOverride(..)
+            | Web3Error::BundleTooLarge(..)
+            | Web3Error::UnsupportedTracer(_)
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFeeParams(_)
+            | Web3Error::InvalidRewardPercentiles(_)
             | Web3Error::InvalidFilterBlockHash
+            | Web3Error::UnprocessedL1Batch(_)
             | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _) | Web3Error::SerializationError(_) => 3,
-            Web3Error::PubSubTimeout => 4,
+            Web3Error::PubSubTimeout | Web3Error::TransactionInclusionTimeout(_) => 4,
             Web3Error::RequestTimeout => 5,
             Web3Error::TreeApiUnavailable => 6,
         },
@@ -1,7 +1,7 @@
 use zksync_types::{
     api::{
-        Block, BlockId, BlockIdVariant, BlockNumber, Log, Transaction, TransactionId,
-        TransactionReceipt, TransactionVariant,
+        AccessListResult, Block, BlockId, BlockIdVariant, BlockNumber, EthProof, Log,
+        StateOverride, Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
     transaction_request::CallRequest,
     web3::types::{FeeHistory, Index, SyncState},
@@ -25,14 +25,34 @@ impl EthNamespaceServer for EthNamespace {
         Ok(self.chain_id_impl())
     }
 
-    async fn call(&self, req: CallRequest, block: Option<BlockIdVariant>) -> RpcResult<Bytes> {
-        self.call_impl(req, block.map(Into::into))
+    async fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Bytes> {
+        self.call_impl(req, block.map(Into::into), state_override)
             .await
             .map_err(into_jsrpc_error)
     }
 
-    async fn estimate_gas(&self, req: CallRequest, block: Option<BlockNumber>) -> RpcResult<U256> {
-        self.estimate_gas_impl(req, block)
+    async fn estimate_gas(
+        &self,
+        req: CallRequest,
+        block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<U256> {
+        self.estimate_gas_impl(req, block, state_override)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<AccessListResult> {
+        self.create_access_list_impl(req, block.map(Into::into))
             .await
             .map_err(into_jsrpc_error)
     }
@@ -41,6 +61,12 @@ impl EthNamespaceServer for EthNamespace {
         self.gas_price_impl().await.map_err(into_jsrpc_error)
     }
 
+    async fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+        self.max_priority_fee_per_gas_impl()
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
         self.new_filter_impl(filter).await.map_err(into_jsrpc_error)
     }
@@ -148,6 +174,17 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(into_jsrpc_error)
     }
 
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<EthProof> {
+        self.get_proof_impl(address, keys, block.map(Into::into))
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn get_transaction_count(
         &self,
         address: Address,
@@ -200,6 +237,16 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(into_jsrpc_error)
     }
 
+    async fn send_raw_transaction_sync(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<U64>,
+    ) -> RpcResult<TransactionReceipt> {
+        self.send_raw_transaction_sync_impl(tx_bytes, timeout_ms)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn syncing(&self) -> RpcResult<SyncState> {
         Ok(self.syncing_impl())
     }
@@ -2,10 +2,10 @@ use std::collections::HashMap;
 
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BlockId, BridgeAddresses, BundleTransactionResult, L1BatchDetails,
+        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
     },
-    fee::Fee,
+    fee::{Fee, FeeWithBreakdown},
     fee_model::FeeParams,
     transaction_request::CallRequest,
     Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
@@ -24,6 +24,12 @@ impl ZksNamespaceServer for ZksNamespace {
         self.estimate_fee_impl(req).await.map_err(into_jsrpc_error)
     }
 
+    async fn estimate_fee_with_breakdown(&self, req: CallRequest) -> RpcResult<FeeWithBreakdown> {
+        self.estimate_fee_with_breakdown_impl(req)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn estimate_gas_l1_to_l2(&self, req: CallRequest) -> RpcResult<U256> {
         self.estimate_l1_to_l2_gas_impl(req)
             .await
@@ -165,4 +171,14 @@ impl ZksNamespaceServer for ZksNamespace {
             .await
             .map_err(into_jsrpc_error)
     }
+
+    async fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+    ) -> RpcResult<Vec<BundleTransactionResult>> {
+        self.simulate_bundle_impl(requests, block)
+            .await
+            .map_err(into_jsrpc_error)
+    }
 }
@@ -0,0 +1,461 @@
+//! Per-client quotas and per-method weights for the JSON-RPC servers.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+    num::NonZeroU32,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::http::{HeaderMap, HeaderName, Request};
+use governor::{
+    clock::{Clock, DefaultClock},
+    state::keyed::DefaultKeyedStateStore,
+    NegativeMultiDecision, Quota, RateLimiter,
+};
+use tokio::task::futures::TaskLocalFuture;
+use zksync_config::configs::api::MethodWeight;
+
+tokio::task_local! {
+    /// Client key for the currently processed HTTP request (including WebSocket upgrade requests).
+    static CLIENT_KEY: ClientKey;
+}
+
+/// Key identifying an API client for the purposes of per-client quotas.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ClientKey {
+    /// Client identified by one of the configured API keys provided in the API key HTTP header.
+    ApiKey(Arc<str>),
+    /// Client identified by its IP address. IPv6 addresses are truncated to the /64 prefix (see [`Self::ip()`]).
+    Ip(IpAddr),
+    /// Client cannot be identified because the request wasn't routed via [`ClientKeyService`]; all such requests
+    /// share a single quota.
+    Unknown,
+}
+
+impl ClientKey {
+    /// Length of the IPv6 prefix identifying a client. A single host can usually use any address in its /64 subnet,
+    /// so keying clients by full IPv6 addresses would allow to trivially bypass quotas.
+    const IPV6_PREFIX_LEN: u32 = 64;
+
+    /// Creates a key for a client with the specified IP address. IPv4-mapped IPv6 addresses are converted to IPv4,
+    /// and other IPv6 addresses are truncated to their /64 prefix.
+    pub(crate) fn ip(ip: IpAddr) -> Self {
+        let ip = match ip {
+            IpAddr::V4(_) => ip,
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ipv4) => IpAddr::V4(ipv4),
+                None => {
+                    let mask = u128::MAX << (128 - Self::IPV6_PREFIX_LEN);
+                    IpAddr::V6(Ipv6Addr::from(u128::from(ip) & mask))
+                }
+            },
+        };
+        Self::Ip(ip)
+    }
+
+    /// Returns the client key for the HTTP request being currently processed, if any.
+    pub(crate) fn current() -> Option<Self> {
+        CLIENT_KEY.try_with(Clone::clone).ok()
+    }
+}
+
+/// Error returned if a request exceeds a rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateLimitError {
+    /// Request can be retried after the specified duration.
+    RetryAfter(Duration),
+    /// Request weight exceeds the quota; the request will never succeed.
+    InsufficientCapacity,
+}
+
+/// Ensures that an action is performed at most once per the specified interval, even if it's attempted
+/// concurrently from multiple threads.
+#[derive(Debug)]
+struct IntervalThrottle {
+    interval: Duration,
+    /// Time of the last run relative to `start`, in milliseconds.
+    last_run_millis: AtomicU64,
+    start: Instant,
+}
+
+impl IntervalThrottle {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_run_millis: AtomicU64::new(0),
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns `true` if the action should be performed now. Only a single caller can get `true` per interval.
+    fn try_acquire(&self) -> bool {
+        self.try_acquire_at(self.start.elapsed())
+    }
+
+    fn try_acquire_at(&self, elapsed: Duration) -> bool {
+        let now_millis = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        let last_run_millis = self.last_run_millis.load(Ordering::Relaxed);
+        if now_millis.saturating_sub(last_run_millis) < self.interval.as_millis() as u64 {
+            return false;
+        }
+        self.last_run_millis
+            .compare_exchange(
+                last_run_millis,
+                now_millis,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+}
+
+type KeyedRateLimiter = RateLimiter<ClientKey, DefaultKeyedStateStore<ClientKey>, DefaultClock>;
+
+/// Rate limiting configuration for the JSON-RPC servers: per-method weights and per-client quotas.
+/// A single instance should be shared by HTTP and WebSocket servers so that quotas are shared as well.
+pub struct RpcRateLimits {
+    method_weights: HashMap<String, NonZeroU32>,
+    client_quotas: Option<KeyedRateLimiter>,
+    /// Throttles checking the number of tracked clients and evicting stale ones, which requires locking
+    /// all shards of the quota store.
+    eviction_throttle: IntervalThrottle,
+    clock: DefaultClock,
+    api_key_header: Option<HeaderName>,
+    api_keys: HashSet<Arc<str>>,
+    trusted_proxies: HashSet<IpAddr>,
+}
+
+impl fmt::Debug for RpcRateLimits {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RpcRateLimits")
+            .field("method_weights", &self.method_weights)
+            .field("has_client_quotas", &self.client_quotas.is_some())
+            .field("api_key_header", &self.api_key_header)
+            .field("api_keys_count", &self.api_keys.len())
+            .field("trusted_proxies", &self.trusted_proxies)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RpcRateLimits {
+    /// Maximum number of clients tracked by the quotas before stale entries are evicted.
+    const MAX_TRACKED_CLIENTS: usize = 100_000;
+    /// Minimum interval between checks whether stale clients should be evicted from the quotas.
+    const EVICTION_INTERVAL: Duration = Duration::from_secs(10);
+    /// Header set by reverse proxies / load balancers containing the chain of client IP addresses.
+    const FORWARDED_FOR_HEADER: &'static str = "x-forwarded-for";
+
+    /// Creates rate limits with the specified per-client quota (in request weight units per minute).
+    /// If the quota is not specified, only per-method weights will be applied (e.g., for per-connection WebSocket limits).
+    pub fn new(requests_quota_per_minute: Option<NonZeroU32>) -> Self {
+        let clock = DefaultClock::default();
+        let client_quotas = requests_quota_per_minute.map(|quota| {
+            RateLimiter::new(
+                Quota::per_minute(quota),
+                DefaultKeyedStateStore::default(),
+                &clock,
+            )
+        });
+        Self {
+            method_weights: HashMap::new(),
+            client_quotas,
+            eviction_throttle: IntervalThrottle::new(Self::EVICTION_INTERVAL),
+            clock,
+            api_key_header: None,
+            api_keys: HashSet::new(),
+            trusted_proxies: HashSet::new(),
+        }
+    }
+
+    /// Sets weights for RPC methods. Methods without a weight have the weight of 1.
+    #[must_use]
+    pub fn with_method_weights(mut self, weights: impl IntoIterator<Item = MethodWeight>) -> Self {
+        self.method_weights = weights
+            .into_iter()
+            .map(|weight| (weight.method, weight.weight))
+            .collect();
+        self
+    }
+
+    /// Sets the HTTP header containing API keys used to identify clients. Only keys set via [`Self::with_api_keys()`]
+    /// are recognized; requests with other keys are identified by the client IP address.
+    pub fn with_api_key_header(mut self, header: &str) -> anyhow::Result<Self> {
+        let header = HeaderName::from_bytes(header.as_bytes())
+            .map_err(|err| anyhow::anyhow!("invalid API key header `{header}`: {err}"))?;
+        self.api_key_header = Some(header);
+        Ok(self)
+    }
+
+    /// Sets API keys recognized in the API key header.
+    #[must_use]
+    pub fn with_api_keys(mut self, keys: impl IntoIterator<Item = String>) -> Self {
+        self.api_keys = keys.into_iter().map(Arc::from).collect();
+        self
+    }
+
+    /// Sets IP addresses of reverse proxies trusted to append the client address to the `X-Forwarded-For` header.
+    #[must_use]
+    pub fn with_trusted_proxies(mut self, proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+        self.trusted_proxies = proxies.into_iter().collect();
+        self
+    }
+
+    /// Identifies the client sending a request with the specified headers from the specified peer address.
+    fn client_key(&self, headers: &HeaderMap, peer_ip: IpAddr) -> ClientKey {
+        let api_key = self
+            .api_key_header
+            .as_ref()
+            .and_then(|name| headers.get(name))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| self.api_keys.get(value));
+        if let Some(api_key) = api_key {
+            return ClientKey::ApiKey(api_key.clone());
+        }
+        ClientKey::ip(self.client_ip(headers, peer_ip))
+    }
+
+    /// Determines the client IP address. `X-Forwarded-For` is only taken into account if the peer is a trusted proxy.
+    /// In this case, hops in the header are traversed from right to left (i.e., starting from the hop added by the peer)
+    /// until a hop not belonging to a trusted proxy is encountered; hops to the left of it may be forged by the client.
+    fn client_ip(&self, headers: &HeaderMap, peer_ip: IpAddr) -> IpAddr {
+        let mut client_ip = peer_ip;
+        if !self.trusted_proxies.contains(&client_ip) {
+            return client_ip;
+        }
+
+        'headers: for value in headers.get_all(Self::FORWARDED_FOR_HEADER).iter().rev() {
+            let Ok(value) = value.to_str() else {
+                break;
+            };
+            for hop in value.rsplit(',') {
+                let Ok(hop) = hop.trim().parse() else {
+                    break 'headers;
+                };
+                client_ip = hop;
+                if !self.trusted_proxies.contains(&client_ip) {
+                    break 'headers;
+                }
+            }
+        }
+        client_ip
+    }
+
+    pub(crate) fn method_weight(&self, method: &str) -> NonZeroU32 {
+        self.method_weights
+            .get(method)
+            .copied()
+            .unwrap_or(NonZeroU32::MIN)
+    }
+
+    /// Consumes `weight` units from the quota of the specified client.
+    pub(crate) fn check_client_quota(
+        &self,
+        client_key: &ClientKey,
+        weight: NonZeroU32,
+    ) -> Result<(), RateLimitError> {
+        let Some(client_quotas) = &self.client_quotas else {
+            return Ok(());
+        };
+        let result = client_quotas.check_key_n(client_key, weight);
+        if self.eviction_throttle.try_acquire() && client_quotas.len() > Self::MAX_TRACKED_CLIENTS {
+            client_quotas.retain_recent();
+        }
+        result.map_err(|err| match err {
+            NegativeMultiDecision::BatchNonConforming(_, not_until) => {
+                RateLimitError::RetryAfter(not_until.wait_time_from(self.clock.now()))
+            }
+            _ => RateLimitError::InsufficientCapacity,
+        })
+    }
+}
+
+/// HTTP service wrapper identifying API clients. Created for each accepted connection, so that the peer address
+/// is known. The client key is available to the RPC middleware via [`ClientKey::current()`].
+#[derive(Debug, Clone)]
+pub(crate) struct ClientKeyService<S> {
+    inner: S,
+    rate_limits: Option<Arc<RpcRateLimits>>,
+    peer_addr: SocketAddr,
+}
+
+impl<S> ClientKeyService<S> {
+    pub(crate) fn new(
+        inner: S,
+        rate_limits: Option<Arc<RpcRateLimits>>,
+        peer_addr: SocketAddr,
+    ) -> Self {
+        Self {
+            inner,
+            rate_limits,
+            peer_addr,
+        }
+    }
+}
+
+impl<S, B> tower::Service<Request<B>> for ClientKeyService<S>
+where
+    S: tower::Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<ClientKey, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let peer_ip = self.peer_addr.ip();
+        let client_key = match &self.rate_limits {
+            Some(limits) => limits.client_key(request.headers(), peer_ip),
+            None => ClientKey::ip(peer_ip),
+        };
+        // The RPC middleware may be instantiated synchronously when the request is received (e.g., for WebSocket connections),
+        // or when the response future is polled, so we provide the client key in both cases.
+        let inner_future = CLIENT_KEY.sync_scope(client_key.clone(), || self.inner.call(request));
+        CLIENT_KEY.scope(client_key, inner_future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn extracting_client_key() {
+        let limits = RpcRateLimits::new(NonZeroU32::new(10))
+            .with_api_key_header("x-api-key")
+            .unwrap()
+            .with_api_keys(["secret".to_owned()]);
+        let peer_ip = IpAddr::from([10, 0, 0, 1]);
+        let mut headers = HeaderMap::new();
+        assert_eq!(limits.client_key(&headers, peer_ip), ClientKey::Ip(peer_ip));
+
+        // Unknown API keys are ignored.
+        headers.insert("x-api-key", HeaderValue::from_static("forged"));
+        assert_eq!(limits.client_key(&headers, peer_ip), ClientKey::Ip(peer_ip));
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        assert_eq!(
+            limits.client_key(&headers, peer_ip),
+            ClientKey::ApiKey("secret".into())
+        );
+    }
+
+    #[test]
+    fn extracting_client_ip() {
+        let proxy_ip = IpAddr::from([10, 0, 0, 1]);
+        let other_proxy_ip = IpAddr::from([10, 0, 0, 2]);
+        let untrusted_ip = IpAddr::from([192, 168, 0, 1]);
+        let limits = RpcRateLimits::new(NonZeroU32::new(10))
+            .with_trusted_proxies([proxy_ip, other_proxy_ip]);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(limits.client_ip(&headers, proxy_ip), proxy_ip);
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("1.2.3.4, 192.168.0.1"),
+        );
+        // The header is ignored for untrusted peers.
+        assert_eq!(limits.client_ip(&headers, untrusted_ip), untrusted_ip);
+        // Only the rightmost hop is trusted; `1.2.3.4` may be forged by the client.
+        assert_eq!(limits.client_ip(&headers, proxy_ip), untrusted_ip);
+
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("1.2.3.4, 192.168.0.1, 10.0.0.2"),
+        );
+        assert_eq!(limits.client_ip(&headers, proxy_ip), untrusted_ip);
+        headers.append("x-forwarded-for", HeaderValue::from_static("10.0.0.1"));
+        assert_eq!(limits.client_ip(&headers, proxy_ip), untrusted_ip);
+
+        headers.insert("x-forwarded-for", HeaderValue::from_static("garbage"));
+        assert_eq!(limits.client_ip(&headers, proxy_ip), proxy_ip);
+    }
+
+    #[test]
+    fn ipv6_clients_are_keyed_by_prefix() {
+        let ip: IpAddr = "2001:db8:1:2:3:4:5:6".parse().unwrap();
+        let same_subnet_ip: IpAddr = "2001:db8:1:2:ffff::1".parse().unwrap();
+        let other_subnet_ip: IpAddr = "2001:db8:1:3:3:4:5:6".parse().unwrap();
+        let expected_key = ClientKey::Ip("2001:db8:1:2::".parse().unwrap());
+        assert_eq!(ClientKey::ip(ip), expected_key);
+        assert_eq!(ClientKey::ip(same_subnet_ip), expected_key);
+        assert_ne!(ClientKey::ip(other_subnet_ip), expected_key);
+
+        let mapped_ip: IpAddr = "::ffff:192.168.0.1".parse().unwrap();
+        assert_eq!(
+            ClientKey::ip(mapped_ip),
+            ClientKey::Ip([192, 168, 0, 1].into())
+        );
+        let ipv4 = IpAddr::from([10, 0, 0, 1]);
+        assert_eq!(ClientKey::ip(ipv4), ClientKey::Ip(ipv4));
+
+        let limits = RpcRateLimits::new(NonZeroU32::new(10));
+        assert_eq!(
+            limits.client_key(&HeaderMap::new(), same_subnet_ip),
+            expected_key
+        );
+    }
+
+    #[test]
+    fn throttling_eviction() {
+        let throttle = IntervalThrottle::new(Duration::from_secs(10));
+        assert!(throttle.try_acquire_at(Duration::from_secs(10)));
+        assert!(!throttle.try_acquire_at(Duration::from_secs(10)));
+        assert!(!throttle.try_acquire_at(Duration::from_secs(15)));
+        assert!(throttle.try_acquire_at(Duration::from_secs(20)));
+        assert!(!throttle.try_acquire_at(Duration::from_millis(29_999)));
+        assert!(throttle.try_acquire_at(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn client_quotas_with_method_weights() {
+        let limits = RpcRateLimits::new(NonZeroU32::new(10)).with_method_weights([MethodWeight {
+            method: "debug_traceCall".to_owned(),
+            weight: NonZeroU32::new(8).unwrap(),
+        }]);
+        assert_eq!(limits.method_weight("eth_blockNumber"), NonZeroU32::MIN);
+        let trace_weight = limits.method_weight("debug_traceCall");
+        assert_eq!(trace_weight.get(), 8);
+
+        let client = ClientKey::Ip([127, 0, 0, 1].into());
+        let other_client = ClientKey::ApiKey("key".into());
+        limits.check_client_quota(&client, trace_weight).unwrap();
+        limits
+            .check_client_quota(&client, NonZeroU32::new(2).unwrap())
+            .unwrap();
+        let err = limits
+            .check_client_quota(&client, NonZeroU32::MIN)
+            .unwrap_err();
+        assert!(matches!(err, RateLimitError::RetryAfter(_)), "{err:?}");
+
+        // Quotas are tracked separately for each client.
+        limits
+            .check_client_quota(&other_client, trace_weight)
+            .unwrap();
+        let err = limits
+            .check_client_quota(&other_client, NonZeroU32::new(11).unwrap())
+            .unwrap_err();
+        assert_eq!(err, RateLimitError::InsufficientCapacity);
+    }
+
+    #[test]
+    fn no_client_quotas() {
+        let limits = RpcRateLimits::new(None);
+        for _ in 0..100 {
+            limits
+                .check_client_quota(&ClientKey::Unknown, NonZeroU32::MAX)
+                .unwrap();
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Per-method allow / deny lists for the JSON-RPC servers.
+
+use std::collections::HashSet;
+
+use zksync_web3_decl::jsonrpsee::RpcModule;
+
+use super::Namespace;
+
+/// Allow / deny lists for RPC methods applied on top of enabled [`Namespace`]s.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MethodFilter {
+    allowlist: Option<HashSet<String>>,
+    denylist: HashSet<String>,
+}
+
+impl MethodFilter {
+    /// Creates a filter from the provided full method names (e.g., `debug_traceTransaction`). An empty `allowlist`
+    /// means that all methods from the enabled namespaces are allowed.
+    pub fn new(
+        allowlist: impl IntoIterator<Item = String>,
+        denylist: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let allowlist: HashSet<_> = allowlist.into_iter().collect();
+        Self {
+            allowlist: (!allowlist.is_empty()).then_some(allowlist),
+            denylist: denylist.into_iter().collect(),
+        }
+    }
+
+    fn is_allowed(&self, method: &str) -> bool {
+        let is_allowlisted = self
+            .allowlist
+            .as_ref()
+            .map_or(true, |allowlist| allowlist.contains(method));
+        is_allowlisted && !self.denylist.contains(method)
+    }
+
+    /// Checks that all methods mentioned in the filter are known. Methods from disabled namespaces cannot be checked
+    /// and are ignored.
+    fn validate(
+        &self,
+        rpc: &RpcModule<()>,
+        is_namespace_enabled: impl Fn(&Namespace) -> bool,
+    ) -> anyhow::Result<()> {
+        let registered_methods: HashSet<_> = rpc.method_names().collect();
+        let all_methods = self.allowlist.iter().flatten().chain(&self.denylist);
+        for method in all_methods {
+            let namespace = Namespace::for_method(method).ok_or_else(|| {
+                anyhow::anyhow!("RPC method `{method}` in allow / deny lists has unknown namespace")
+            })?;
+            if !is_namespace_enabled(&namespace) {
+                tracing::info!(
+                    "RPC method `{method}` in allow / deny lists belongs to disabled namespace {namespace:?}; ignoring"
+                );
+                continue;
+            }
+            anyhow::ensure!(
+                registered_methods.contains(method.as_str()),
+                "RPC method `{method}` in allow / deny lists is unknown"
+            );
+        }
+        Ok(())
+    }
+
+    /// Validates this filter and removes all disallowed methods from the provided RPC module.
+    pub(super) fn apply(
+        &self,
+        rpc: &mut RpcModule<()>,
+        is_namespace_enabled: impl Fn(&Namespace) -> bool,
+    ) -> anyhow::Result<()> {
+        if self.allowlist.is_none() && self.denylist.is_empty() {
+            return Ok(());
+        }
+        self.validate(rpc, is_namespace_enabled)?;
+
+        let disallowed_methods: Vec<_> = rpc
+            .method_names()
+            .filter(|method| !self.is_allowed(method))
+            .collect();
+        for &method in &disallowed_methods {
+            rpc.remove_method(method);
+        }
+        tracing::info!(
+            "Disabled {} RPC methods according to allow / deny lists: {disallowed_methods:?}",
+            disallowed_methods.len()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_module() -> RpcModule<()> {
+        let mut rpc = RpcModule::new(());
+        for method in [
+            "eth_chainId",
+            "eth_getLogs",
+            "debug_traceTransaction",
+            "debug_traceBlockByNumber",
+        ] {
+            rpc.register_method(method, |_params, _ctx| 0_u64).unwrap();
+        }
+        rpc
+    }
+
+    fn method_names(rpc: &RpcModule<()>) -> HashSet<&'static str> {
+        rpc.method_names().collect()
+    }
+
+    #[test]
+    fn applying_denylist() {
+        let mut rpc = test_module();
+        let filter = MethodFilter::new([], ["eth_getLogs".to_owned()]);
+        filter.apply(&mut rpc, |_| true).unwrap();
+        assert_eq!(
+            method_names(&rpc),
+            HashSet::from([
+                "eth_chainId",
+                "debug_traceTransaction",
+                "debug_traceBlockByNumber"
+            ])
+        );
+    }
+
+    #[test]
+    fn applying_allowlist_and_denylist() {
+        let mut rpc = test_module();
+        let filter = MethodFilter::new(
+            [
+                "eth_chainId".to_owned(),
+                "debug_traceTransaction".to_owned(),
+            ],
+            ["eth_chainId".to_owned()],
+        );
+        filter.apply(&mut rpc, |_| true).unwrap();
+        assert_eq!(
+            method_names(&rpc),
+            HashSet::from(["debug_traceTransaction"])
+        );
+    }
+
+    #[test]
+    fn unknown_methods_are_rejected() {
+        let mut rpc = test_module();
+        let filter = MethodFilter::new([], ["eth_unknownMethod".to_owned()]);
+        let err = filter.apply(&mut rpc, |_| true).unwrap_err().to_string();
+        assert!(err.contains("`eth_unknownMethod`"), "{err}");
+
+        let filter = MethodFilter::new(["foo_bar".to_owned()], []);
+        let err = filter.apply(&mut rpc, |_| true).unwrap_err().to_string();
+        assert!(err.contains("unknown namespace"), "{err}");
+    }
+
+    #[test]
+    fn methods_from_disabled_namespaces_are_ignored() {
+        let mut rpc = test_module();
+        let filter = MethodFilter::new(
+            [],
+            [
+                "snapshots_getAllSnapshots".to_owned(),
+                "eth_subscribe".to_owned(),
+            ],
+        );
+        filter
+            .apply(&mut rpc, |namespace| {
+                !matches!(namespace, Namespace::Snapshots | Namespace::Pubsub)
+            })
+            .unwrap();
+        assert_eq!(method_names(&rpc).len(), 4);
+    }
+}
@@ -1,8 +1,9 @@
-use std::{net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
+use std::{convert::Infallible, net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
 use futures::future;
+use hyper::{server::conn::AddrStream, service::make_service_fn};
 use serde::Deserialize;
 use tokio::{
     sync::{mpsc, oneshot, watch, Mutex},
@@ -14,8 +15,8 @@ use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::MiniblockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
-        server::{BatchRequestConfig, RpcServiceBuilder, ServerBuilder},
-        RpcModule,
+        server::{stop_channel, BatchRequestConfig, RpcServiceBuilder, ServerBuilder},
+        Methods, RpcModule,
     },
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
@@ -24,6 +25,7 @@ use zksync_web3_decl::{
     types::Filter,
 };
 
+pub use self::{backend_jsonrpsee::rate_limits::RpcRateLimits, method_filter::MethodFilter};
 use self::{
     metrics::API_METRICS,
     namespaces::{
@@ -38,13 +40,17 @@ use crate::{
         execution_sandbox::{BlockStartInfo, VmConcurrencyBarrier},
         tree::TreeApiHttpClient,
         tx_sender::TxSender,
-        web3::backend_jsonrpsee::batch_limiter_middleware::LimitMiddleware,
+        web3::backend_jsonrpsee::{
+            batch_limiter_middleware::{LimitMiddleware, Transport},
+            rate_limits::ClientKeyService,
+        },
     },
     sync_layer::SyncState,
     utils::wait_for_l1_batch,
 };
 
 pub mod backend_jsonrpsee;
+mod method_filter;
 mod metrics;
 pub mod namespaces;
 mod pubsub;
@@ -94,6 +100,24 @@ impl Namespace {
         Self::En,
         Self::Pubsub,
     ];
+
+    /// Returns the namespace of the specified RPC method based on its name, e.g. `eth_call` -> [`Self::Eth`].
+    pub fn for_method(method: &str) -> Option<Self> {
+        if matches!(method, "eth_subscribe" | "eth_unsubscribe") {
+            return Some(Self::Pubsub);
+        }
+        let (prefix, _) = method.split_once('_')?;
+        Some(match prefix {
+            "eth" => Self::Eth,
+            "net" => Self::Net,
+            "web3" => Self::Web3,
+            "debug" => Self::Debug,
+            "zks" => Self::Zks,
+            "en" => Self::En,
+            "snapshots" => Self::Snapshots,
+            _ => return None,
+        })
+    }
 }
 
 /// Handles to the initialized API server.
@@ -114,6 +138,8 @@ struct OptionalApiParams {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<usize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    rate_limits: Option<Arc<RpcRateLimits>>,
+    method_filter: MethodFilter,
     tree_api_url: Option<String>,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
@@ -219,6 +245,19 @@ impl ApiBuilder {
         self
     }
 
+    /// Sets per-method weights and per-client quotas. To share quotas among HTTP and WebSocket servers,
+    /// the same instance should be provided to both servers.
+    pub fn with_rate_limits(mut self, rate_limits: Arc<RpcRateLimits>) -> Self {
+        self.optional.rate_limits = Some(rate_limits);
+        self
+    }
+
+    /// Sets allow / deny lists for RPC methods exposed by the server.
+    pub fn with_method_filter(mut self, method_filter: MethodFilter) -> Self {
+        self.optional.method_filter = method_filter;
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -278,11 +317,8 @@ impl FullApiParams {
     async fn build_rpc_state(
         self,
         last_sealed_miniblock: SealedMiniblockNumber,
+        start_info: BlockStartInfo,
     ) -> anyhow::Result<RpcState> {
-        let mut storage = self.updaters_pool.access_storage_tagged("api").await?;
-        let start_info = BlockStartInfo::new(&mut storage).await?;
-        drop(storage);
-
         let installed_filters = if self.config.filters_disabled {
             None
         } else {
@@ -310,13 +346,18 @@ impl FullApiParams {
         self,
         pub_sub: Option<EthSubscribe>,
         last_sealed_miniblock: SealedMiniblockNumber,
+        start_info: BlockStartInfo,
     ) -> anyhow::Result<RpcModule<()>> {
         let namespaces = self.namespaces.clone();
+        let method_filter = self.optional.method_filter.clone();
         let zksync_network_id = self.config.l2_chain_id;
-        let rpc_state = self.build_rpc_state(last_sealed_miniblock).await?;
+        let rpc_state = self
+            .build_rpc_state(last_sealed_miniblock, start_info)
+            .await?;
 
         // Collect all the methods into a single RPC module.
         let mut rpc = RpcModule::new(());
+        let has_pub_sub = pub_sub.is_some();
         if let Some(pub_sub) = pub_sub {
             rpc.merge(pub_sub.into_rpc())
                 .expect("Can't merge eth pubsub namespace");
@@ -350,6 +391,13 @@ impl FullApiParams {
             rpc.merge(SnapshotsNamespace::new(rpc_state).into_rpc())
                 .expect("Can't merge snapshots namespace");
         }
+
+        method_filter
+            .apply(&mut rpc, |namespace| match namespace {
+                Namespace::Pubsub => has_pub_sub,
+                _ => namespaces.contains(namespace),
+            })
+            .context("invalid RPC method allow / deny lists")?;
         Ok(rpc)
     }
 
@@ -412,6 +460,8 @@ impl FullApiParams {
         // processes enough requests, information about the latest sealed miniblock will be updated
         // by reporting block difference metrics, so the actual update lag would be much smaller than this value.
         const SEALED_MINIBLOCK_UPDATE_INTERVAL: Duration = Duration::from_millis(25);
+        // The first available block only changes because of pruning, which happens much less frequently.
+        const BLOCK_START_INFO_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
 
         let transport = self.transport;
         let health_check_name = match transport {
@@ -426,7 +476,19 @@ impl FullApiParams {
             stop_receiver.clone(),
         );
 
-        let mut tasks = vec![tokio::spawn(update_task)];
+        let mut storage = self.updaters_pool.access_storage_tagged("api").await?;
+        let start_info = BlockStartInfo::new(&mut storage).await?;
+        drop(storage);
+        let start_info_update_task = start_info.clone().update_task(
+            self.updaters_pool.clone(),
+            BLOCK_START_INFO_UPDATE_INTERVAL,
+            stop_receiver.clone(),
+        );
+
+        let mut tasks = vec![
+            tokio::spawn(update_task),
+            tokio::spawn(start_info_update_task),
+        ];
         let pub_sub = if matches!(transport, ApiTransport::WebSocket(_))
             && self.namespaces.contains(&Namespace::Pubsub)
         {
@@ -451,6 +513,7 @@ impl FullApiParams {
             stop_receiver,
             pub_sub,
             last_sealed_miniblock,
+            start_info,
             local_addr_sender,
             health_updater,
         ));
@@ -468,6 +531,7 @@ impl FullApiParams {
         mut stop_receiver: watch::Receiver<bool>,
         pub_sub: Option<EthSubscribe>,
         last_sealed_miniblock: SealedMiniblockNumber,
+        start_info: BlockStartInfo,
         local_addr_sender: oneshot::Sender<SocketAddr>,
         health_updater: HealthUpdater,
     ) -> anyhow::Result<()> {
@@ -506,11 +570,12 @@ impl FullApiParams {
             .response_body_size_limit
             .map_or(u32::MAX, |limit| limit as u32);
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let rate_limits = self.optional.rate_limits.clone();
         let subscriptions_limit = self.optional.subscriptions_limit;
         let vm_barrier = self.vm_barrier.clone();
 
         let rpc = self
-            .build_rpc_module(pub_sub, last_sealed_miniblock)
+            .build_rpc_module(pub_sub, last_sealed_miniblock, start_info)
             .await?;
 
         // Setup CORS.
@@ -534,6 +599,20 @@ impl FullApiParams {
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
             .option_layer(cors);
+        let (middleware_transport, websocket_requests_per_minute_limit) = if is_http {
+            (Transport::Http, None)
+        } else {
+            (Transport::Ws, websocket_requests_per_minute_limit)
+        };
+        let middleware_rate_limits = rate_limits.clone();
+        let rpc_middleware = RpcServiceBuilder::new().layer_fn(move |a| {
+            LimitMiddleware::new(
+                a,
+                middleware_transport,
+                websocket_requests_per_minute_limit,
+                middleware_rate_limits.clone(),
+            )
+        });
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -543,32 +622,35 @@ impl FullApiParams {
         let server_builder = ServerBuilder::default()
             .max_connections(max_connections as u32)
             .set_http_middleware(middleware)
+            .set_rpc_middleware(rpc_middleware)
             .max_response_body_size(response_body_size_limit)
             .set_batch_request_config(batch_request_config);
-
-        let (local_addr, server_handle) = if is_http {
+        let server_builder = if is_http {
             // HTTP-specific settings
-            let server = server_builder
-                .http_only()
-                .build(addr)
-                .await
-                .context("Failed building HTTP JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+            server_builder.http_only()
         } else {
             // WS specific settings
-            let server = server_builder
-                .set_rpc_middleware(RpcServiceBuilder::new().layer_fn(move |a| {
-                    LimitMiddleware::new(a, websocket_requests_per_minute_limit)
-                }))
-                .set_id_provider(EthSubscriptionIdProvider)
-                .build(addr)
-                .await
-                .context("Failed building WS JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+            server_builder.set_id_provider(EthSubscriptionIdProvider)
         };
-        let local_addr = local_addr.with_context(|| {
-            format!("Failed getting local address for {transport_str} JSON-RPC server")
-        })?;
+
+        // The server is driven by `hyper` directly rather than via `ServerBuilder::build()`, so that the peer address
+        // of each connection is known and can be used to identify API clients.
+        let service_builder = server_builder.to_service_builder();
+        let methods = Methods::from(rpc);
+        let (stop_handle, server_handle) = stop_channel();
+        let connection_stop_handle = stop_handle.clone();
+        let make_service = make_service_fn(move |conn: &AddrStream| {
+            let service = service_builder
+                .clone()
+                .build(methods.clone(), connection_stop_handle.clone());
+            let service = ClientKeyService::new(service, rate_limits.clone(), conn.remote_addr());
+            future::ready(Ok::<_, Infallible>(service))
+        });
+        let server = hyper::Server::try_bind(&addr)
+            .with_context(|| format!("Failed binding {transport_str} JSON-RPC server to {addr}"))?
+            .serve(make_service);
+        let local_addr = server.local_addr();
+        let server = server.with_graceful_shutdown(stop_handle.shutdown());
         tracing::info!("Initialized {transport_str} API on {local_addr:?}");
         local_addr_sender.send(local_addr).ok();
         health_updater.update(HealthStatus::Ready.into());
@@ -597,10 +679,14 @@ impl FullApiParams {
             close_handle.stop().ok();
         });
 
+        let server_result = server.await;
+        // Signal the remaining connections (e.g., upgraded WebSocket ones) to stop if the server has stopped on its own,
+        // and wait for them to terminate.
+        server_handle.stop().ok();
         server_handle.stopped().await;
         drop(health_updater);
         tracing::info!("{transport_str} JSON-RPC server stopped");
         Self::wait_for_vm(vm_barrier, transport_str).await;
-        Ok(())
+        server_result.with_context(|| format!("{transport_str} JSON-RPC server failed"))
     }
 }
@@ -1,24 +1,38 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
 
 use multivm::{interface::ExecutionResult, vm_latest::constants::BLOCK_GAS_LIMIT};
 use once_cell::sync::OnceCell;
-use zksync_system_constants::MAX_ENCODED_TX_SIZE;
+use zksync_system_constants::{ACCOUNT_CODE_STORAGE_ADDRESS, MAX_ENCODED_TX_SIZE};
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, PrestateTrace, ResultDebugCall, StructLogTrace,
+        SupportedTracers, TracerConfig, TracerResult,
+    },
     fee_model::BatchFeeInput,
     l2::L2Tx,
     transaction_request::CallRequest,
-    vm_trace::Call,
-    AccountTreeId, H256,
+    vm_trace::{Call, StorageAccess, TouchedState},
+    AccountTreeId, Address, ProtocolVersionId, VmVersion, H256,
 };
+use zksync_utils::h256_to_account_address;
 use zksync_web3_decl::error::Web3Error;
 
-use crate::api_server::{
-    execution_sandbox::{ApiTracer, TxSharedArgs},
-    tx_sender::{ApiContracts, TxSenderConfig},
-    web3::{backend_jsonrpsee::internal_error, metrics::API_METRICS, state::RpcState},
+use crate::{
+    api_server::{
+        execution_sandbox::{ApiTracer, TxSharedArgs},
+        tx_sender::{ApiContracts, TxSenderConfig},
+        web3::{backend_jsonrpsee::internal_error, metrics::API_METRICS, state::RpcState},
+    },
+    utils::pending_protocol_version,
 };
 
+const STRUCT_LOGGER_CALL_ONLY: &str = "`structLogger` is only supported by `debug_traceCall`";
+const STRUCT_LOGGER_LATEST_VM_ONLY: &str =
+    "`structLogger` is only supported for blocks executed by the latest VM version";
+
 #[derive(Debug, Clone)]
 pub struct DebugNamespace {
     batch_fee_input: BatchFeeInput,
@@ -58,9 +72,7 @@ impl DebugNamespace {
         const METHOD_NAME: &str = "debug_trace_block";
 
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let options = options.unwrap_or_default();
         let mut connection = self
             .state
             .connection_pool
@@ -71,25 +83,77 @@ impl DebugNamespace {
             .state
             .resolve_block(&mut connection, block_id, METHOD_NAME)
             .await?;
-        let call_traces = connection
-            .blocks_web3_dal()
-            .get_traces_for_miniblock(block_number)
-            .await
-            .map_err(|err| internal_error(METHOD_NAME, err))?;
-        let call_trace = call_traces
-            .into_iter()
-            .map(|call_trace| {
-                let mut result: DebugCall = call_trace.into();
-                if only_top_call {
-                    result.calls = vec![];
+
+        let traces = match options.tracer {
+            SupportedTracers::CallTracer => {
+                let only_top_call = options.tracer_config.call_tracer.only_top_call;
+                let call_traces = connection
+                    .blocks_web3_dal()
+                    .get_traces_for_miniblock(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                call_traces
+                    .into_iter()
+                    .map(|call_trace| {
+                        let mut result: DebugCall = call_trace.into();
+                        if only_top_call {
+                            result.calls = vec![];
+                        }
+                        ResultDebugCall {
+                            result: TracerResult::CallTrace(result),
+                        }
+                    })
+                    .collect()
+            }
+            SupportedTracers::PrestateTracer => {
+                let diff_mode = options.tracer_config.prestate_tracer.diff_mode;
+                let transactions = connection
+                    .transactions_web3_dal()
+                    .get_raw_miniblock_transactions(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                let accesses = connection
+                    .storage_web3_dal()
+                    .get_storage_accesses_for_miniblock(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                let mut call_traces: HashMap<_, _> = connection
+                    .blocks_web3_dal()
+                    .get_traces_with_hashes_for_miniblock(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?
+                    .into_iter()
+                    .collect();
+
+                let mut accesses_by_tx = HashMap::<_, Vec<_>>::new();
+                for (tx_hash, access) in accesses {
+                    accesses_by_tx.entry(tx_hash).or_default().push(access);
                 }
-                ResultDebugCall { result }
-            })
-            .collect();
+                transactions
+                    .into_iter()
+                    .map(|tx| {
+                        let tx_hash = tx.hash();
+                        let call_trace = call_traces.remove(&tx_hash);
+                        let tx_accesses = accesses_by_tx.remove(&tx_hash).unwrap_or_default();
+                        let touched_state = stored_touched_state(call_trace.as_ref(), tx_accesses);
+                        ResultDebugCall {
+                            result: TracerResult::PrestateTrace(PrestateTrace::new(
+                                &touched_state,
+                                diff_mode,
+                            )),
+                        }
+                    })
+                    .collect()
+            }
+            // Struct logs are not persisted, and re-executing stored transactions is not supported.
+            SupportedTracers::StructLogger => {
+                return Err(Web3Error::UnsupportedTracer(STRUCT_LOGGER_CALL_ONLY));
+            }
+        };
 
         let block_diff = self.state.last_sealed_miniblock.diff(block_number);
         method_latency.observe(block_diff);
-        Ok(call_trace)
+        Ok(traces)
     }
 
     #[tracing::instrument(skip(self))]
@@ -97,12 +161,10 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<Option<DebugCall>, Web3Error> {
+    ) -> Result<Option<TracerResult>, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_transaction";
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let options = options.unwrap_or_default();
         let mut connection = self
             .state
             .connection_pool
@@ -114,13 +176,39 @@ impl DebugNamespace {
             .get_call_trace(tx_hash)
             .await
             .map_err(|err| internal_error(METHOD_NAME, err))?;
-        Ok(call_trace.map(|call_trace| {
-            let mut result: DebugCall = call_trace.into();
-            if only_top_call {
-                result.calls = vec![];
+
+        Ok(match options.tracer {
+            SupportedTracers::CallTracer => {
+                let only_top_call = options.tracer_config.call_tracer.only_top_call;
+                call_trace.map(|call_trace| {
+                    let mut result: DebugCall = call_trace.into();
+                    if only_top_call {
+                        result.calls = vec![];
+                    }
+                    TracerResult::CallTrace(result)
+                })
             }
-            result
-        }))
+            SupportedTracers::PrestateTracer => {
+                let accesses = connection
+                    .storage_web3_dal()
+                    .get_storage_accesses_for_tx(tx_hash)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                // Each executed transaction writes at least to its initiator's nonce,
+                // so no writes mean that the transaction is not executed.
+                if accesses.is_empty() {
+                    None
+                } else {
+                    let touched_state = stored_touched_state(call_trace.as_ref(), accesses);
+                    let diff_mode = options.tracer_config.prestate_tracer.diff_mode;
+                    let trace = PrestateTrace::new(&touched_state, diff_mode);
+                    Some(TracerResult::PrestateTrace(trace))
+                }
+            }
+            SupportedTracers::StructLogger => {
+                return Err(Web3Error::UnsupportedTracer(STRUCT_LOGGER_CALL_ONLY));
+            }
+        })
     }
 
     #[tracing::instrument(skip(self, request, block_id))]
@@ -129,14 +217,13 @@ impl DebugNamespace {
         request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<TracerResult, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_call";
 
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let options = options.unwrap_or_default();
+        let only_top_call = options.tracer_config.call_tracer.only_top_call;
 
         let mut connection = self
             .state
@@ -148,6 +235,28 @@ impl DebugNamespace {
             .state
             .resolve_block_args(&mut connection, block_id, METHOD_NAME)
             .await?;
+        if options.tracer == SupportedTracers::StructLogger {
+            // The struct-log tracer is only implemented for the latest VM version; it's a no-op for older versions.
+            // The pending miniblock is not persisted, so its version is taken from the last sealed miniblock.
+            let protocol_version = if block_args.is_pending_miniblock() {
+                pending_protocol_version(&mut connection)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?
+            } else {
+                let miniblock_header = connection
+                    .blocks_dal()
+                    .get_miniblock_header(block_args.resolved_block_number())
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?
+                    .ok_or(Web3Error::NoBlock)?;
+                miniblock_header
+                    .protocol_version
+                    .unwrap_or(ProtocolVersionId::last_potentially_undefined())
+            };
+            if protocol_version.into_api_vm_version() != VmVersion::latest() {
+                return Err(Web3Error::UnsupportedTracer(STRUCT_LOGGER_LATEST_VM_ONLY));
+            }
+        }
         drop(connection);
 
         let tx = L2Tx::from_request(request.into(), MAX_ENCODED_TX_SIZE)?;
@@ -163,10 +272,29 @@ impl DebugNamespace {
 
         // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
-        } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+        let prestate_tracer_result = Arc::new(OnceCell::default());
+        let struct_log_tracer_result = Arc::new(OnceCell::default());
+        let custom_tracers = match options.tracer {
+            SupportedTracers::CallTracer if only_top_call => vec![],
+            SupportedTracers::CallTracer => {
+                vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+            }
+            SupportedTracers::PrestateTracer => {
+                vec![ApiTracer::PrestateTracer(prestate_tracer_result.clone())]
+            }
+            SupportedTracers::StructLogger => {
+                let mut config = options.tracer_config.struct_logger.clone();
+                let max_steps_limit = self.state.api_config.struct_log_max_steps;
+                config.max_steps = Some(
+                    config
+                        .max_steps
+                        .map_or(max_steps_limit, |steps| steps.min(max_steps_limit)),
+                );
+                vec![ApiTracer::StructLogTracer(
+                    struct_log_tracer_result.clone(),
+                    config,
+                )]
+            }
         };
 
         let executor = &self.state.tx_sender.0.executor;
@@ -179,6 +307,7 @@ impl DebugNamespace {
                 block_args,
                 self.sender_config().vm_execution_cache_misses_limit,
                 custom_tracers,
+                None,
             )
             .await
             .map_err(|err| internal_error(METHOD_NAME, err))?;
@@ -194,27 +323,52 @@ impl DebugNamespace {
             }
         };
 
-        // We had only one copy of Arc this arc is already dropped it's safe to unwrap
-        let trace = Arc::try_unwrap(call_tracer_result)
-            .unwrap()
-            .take()
-            .unwrap_or_default();
-        let call = Call::new_high_level(
-            tx.common_data.fee.gas_limit.as_u32(),
-            result.statistics.gas_used,
-            tx.execute.value,
-            tx.execute.calldata,
-            output,
-            revert_reason,
-            trace,
-        );
+        let trace = match options.tracer {
+            SupportedTracers::CallTracer => {
+                // We had only one copy of Arc this arc is already dropped it's safe to unwrap
+                let trace = Arc::try_unwrap(call_tracer_result)
+                    .unwrap()
+                    .take()
+                    .unwrap_or_default();
+                let call = Call::new_high_level(
+                    tx.common_data.fee.gas_limit.as_u32(),
+                    result.statistics.gas_used,
+                    tx.execute.value,
+                    tx.execute.calldata,
+                    output,
+                    revert_reason,
+                    trace,
+                );
+                TracerResult::CallTrace(call.into())
+            }
+            SupportedTracers::PrestateTracer => {
+                let touched_state = Arc::try_unwrap(prestate_tracer_result)
+                    .unwrap()
+                    .take()
+                    .unwrap_or_default();
+                let diff_mode = options.tracer_config.prestate_tracer.diff_mode;
+                TracerResult::PrestateTrace(PrestateTrace::new(&touched_state, diff_mode))
+            }
+            SupportedTracers::StructLogger => {
+                let struct_logs = Arc::try_unwrap(struct_log_tracer_result)
+                    .unwrap()
+                    .take()
+                    .unwrap_or_default();
+                TracerResult::StructLogTrace(StructLogTrace {
+                    gas: result.statistics.gas_used.into(),
+                    failed: revert_reason.is_some(),
+                    return_value: output.into(),
+                    struct_logs,
+                })
+            }
+        };
 
         let block_diff = self
             .state
             .last_sealed_miniblock
             .diff_with_block_args(&block_args);
         method_latency.observe(block_diff);
-        Ok(call.into())
+        Ok(trace)
     }
 
     fn shared_args(&self) -> TxSharedArgs {
@@ -229,3 +383,33 @@ impl DebugNamespace {
         }
     }
 }
+
+/// Restores the state touched by an already executed transaction. Only storage writes are persisted, so unlike
+/// for `debug_traceCall`, slots that were only read by the transaction are not reported. Accounts are collected
+/// from the call trace of the transaction (if call traces are persisted) and from deployed bytecodes.
+fn stored_touched_state(
+    call_trace: Option<&Call>,
+    accesses: impl IntoIterator<Item = StorageAccess>,
+) -> TouchedState {
+    fn collect_call_accounts(call: &Call, accounts: &mut BTreeSet<Address>) {
+        accounts.extend([call.from, call.to]);
+        for child in &call.calls {
+            collect_call_accounts(child, accounts);
+        }
+    }
+
+    let storage: Vec<_> = accesses.into_iter().collect();
+    let mut accounts = BTreeSet::new();
+    if let Some(call_trace) = call_trace {
+        // The top-level call is synthetic, so it's skipped.
+        for call in &call_trace.calls {
+            collect_call_accounts(call, &mut accounts);
+        }
+    }
+    let deployed_accounts = storage
+        .iter()
+        .filter(|access| *access.key.address() == ACCOUNT_CODE_STORAGE_ADDRESS)
+        .map(|access| h256_to_account_address(access.key.key()));
+    accounts.extend(deployed_accounts);
+    TouchedState { accounts, storage }
+}
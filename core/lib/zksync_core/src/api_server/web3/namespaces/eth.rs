@@ -1,19 +1,24 @@
+use std::time::Duration;
+
+use multivm::interface::ExecutionResult;
+use zksync_dal::blocks_web3_dal::BLOCK_GAS_LIMIT;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        AccessListResult, BlockId, BlockNumber, EthProof, GetLogsFilter, StateOverride,
+        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
+    get_code_key, get_nonce_key,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
-    utils::decompose_full_nonce,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
     web3::{
         self,
         types::{FeeHistory, SyncInfo, SyncState},
     },
     AccountTreeId, Bytes, MiniblockNumber, StorageKey, H256, L2_ETH_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{bytecode::validate_bytecode, h256_to_u256, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
@@ -29,6 +34,15 @@ use crate::api_server::web3::{
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
 
+/// Number of latest miniblocks considered by `eth_maxPriorityFeePerGas`.
+const PRIORITY_FEE_BLOCK_COUNT: u32 = 20;
+/// Percentile of priority fees in the latest miniblocks returned by `eth_maxPriorityFeePerGas`.
+const PRIORITY_FEE_PERCENTILE: f32 = 60.0;
+/// Default timeout for `eth_sendRawTransactionSync` waiting for the transaction to be included into a miniblock.
+const DEFAULT_TX_INCLUSION_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum timeout for `eth_sendRawTransactionSync` that can be requested by the client.
+const MAX_TX_INCLUSION_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Debug)]
 pub struct EthNamespace {
     state: RpcState,
@@ -61,14 +75,19 @@ impl EthNamespace {
         Ok(block_number.0.into())
     }
 
-    #[tracing::instrument(skip(self, request, block_id))]
+    #[tracing::instrument(skip(self, request, block_id, state_override))]
     pub async fn call_impl(
         &self,
         request: CallRequest,
         block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> Result<Bytes, Web3Error> {
         const METHOD_NAME: &str = "call";
 
+        if let Some(state_override) = &state_override {
+            validate_state_override(state_override)?;
+        }
+
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
         let mut connection = self
@@ -86,7 +105,11 @@ impl EthNamespace {
 
         let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
 
-        let call_result = self.state.tx_sender.eth_call(block_args, tx).await;
+        let call_result = self
+            .state
+            .tx_sender
+            .eth_call(block_args, tx, state_override)
+            .await;
         let res_bytes = call_result.map_err(|err| err.into_web3_error(METHOD_NAME))?;
 
         let block_diff = self
@@ -97,16 +120,76 @@ impl EthNamespace {
         Ok(res_bytes.into())
     }
 
-    #[tracing::instrument(skip(self, request, _block))]
+    #[tracing::instrument(skip(self, request, block_id))]
+    pub async fn create_access_list_impl(
+        &self,
+        request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<AccessListResult, Web3Error> {
+        const METHOD_NAME: &str = "create_access_list";
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut connection = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id, METHOD_NAME)
+            .await?;
+        drop(connection);
+
+        let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+        let (output, storage_accesses) = self
+            .state
+            .tx_sender
+            .eth_call_with_storage_accesses(block_args, tx)
+            .await
+            .map_err(|err| err.into_web3_error(METHOD_NAME))?;
+        let error = match &output.result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output } => Some(output.to_user_friendly_string()),
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+        };
+        let gas_used = output.statistics.gas_used.into();
+
+        let block_diff = self
+            .state
+            .last_sealed_miniblock
+            .diff_with_block_args(&block_args);
+        method_latency.observe(block_diff);
+        Ok(AccessListResult::new(&storage_accesses, gas_used, error))
+    }
+
+    #[tracing::instrument(skip(self, request, _block, state_override))]
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
         _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
         const METHOD_NAME: &str = "estimate_gas";
 
+        if let Some(state_override) = &state_override {
+            validate_state_override(state_override)?;
+        }
         let method_latency = API_METRICS.start_call(METHOD_NAME);
         let mut request_with_gas_per_pubdata_overridden = request;
+        if request_with_gas_per_pubdata_overridden.nonce.is_none() {
+            // Take the overridden nonce of the sender into account, if any.
+            let from = request_with_gas_per_pubdata_overridden
+                .from
+                .unwrap_or_default();
+            let account_override = state_override
+                .as_ref()
+                .and_then(|state_override| state_override.get(&from));
+            request_with_gas_per_pubdata_overridden.nonce = account_override
+                .and_then(|account| account.nonce)
+                .map(|nonce| nonce.as_u64().into());
+        }
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
             .await?;
@@ -147,7 +230,12 @@ impl EthNamespace {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation)
+            .get_txs_fee_in_wei(
+                tx.into(),
+                scale_factor,
+                acceptable_overestimation,
+                state_override,
+            )
             .await
             .map_err(|err| err.into_web3_error(METHOD_NAME))?;
         method_latency.observe();
@@ -446,6 +534,102 @@ impl EthNamespace {
         Ok(value)
     }
 
+    /// Returns the account state with Merkle proofs as of the end of the L1 batch that includes
+    /// the requested block. `latest` and similar block tags resolve to the last L1 batch processed
+    /// by the Merkle tree.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: Option<BlockId>,
+    ) -> Result<EthProof, Web3Error> {
+        const METHOD_NAME: &str = "get_proof";
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut connection = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let (block_number, l1_batch_number) = match block_id {
+            // The latest state with proofs is the one of the last L1 batch processed by the Merkle tree;
+            // newer sealed batches don't have proofs yet.
+            BlockId::Number(
+                BlockNumber::Latest | BlockNumber::Pending | BlockNumber::Committed,
+            ) => {
+                let l1_batch_number = connection
+                    .blocks_dal()
+                    .get_last_l1_batch_number_with_metadata()
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?
+                    .ok_or(Web3Error::NoBlock)?;
+                let (_, last_miniblock) = connection
+                    .blocks_dal()
+                    .get_miniblock_range_of_l1_batch(l1_batch_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?
+                    .ok_or(Web3Error::NoBlock)?;
+                (last_miniblock, l1_batch_number)
+            }
+            _ => {
+                let block_number = self
+                    .state
+                    .resolve_block(&mut connection, block_id, METHOD_NAME)
+                    .await?;
+                let resolved_batch = connection
+                    .storage_web3_dal()
+                    .resolve_l1_batch_number_of_miniblock(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                let l1_batch_number = resolved_batch
+                    .miniblock_l1_batch
+                    .ok_or(Web3Error::UnprocessedL1Batch(block_number))?;
+                (block_number, l1_batch_number)
+            }
+        };
+        // The root hash is only persisted once the Merkle tree has processed the batch.
+        let storage_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .ok_or(Web3Error::UnprocessedL1Batch(block_number))?;
+        drop(connection);
+
+        let account_keys = [
+            storage_key_for_eth_balance(&address),
+            get_nonce_key(&address),
+            get_code_key(&address),
+        ];
+        let storage_keys = keys
+            .into_iter()
+            .map(|key| StorageKey::new(AccountTreeId::new(address), key));
+        let all_keys: Vec<_> = account_keys.into_iter().chain(storage_keys).collect();
+        let mut account_proof = self
+            .state
+            .get_storage_proofs(l1_batch_number, &all_keys, METHOD_NAME)
+            .await?;
+        let storage_proof = account_proof.split_off(account_keys.len());
+
+        let balance = h256_to_u256(account_proof[0].value);
+        let (nonce, _) = decompose_full_nonce(h256_to_u256(account_proof[1].value));
+        let code_hash = account_proof[2].value;
+        self.report_latency_with_block_id(method_latency, block_number);
+
+        Ok(EthProof {
+            address,
+            balance,
+            nonce,
+            code_hash,
+            storage_hash,
+            account_proof,
+            storage_proof,
+        })
+    }
+
     /// Account nonce.
     #[tracing::instrument(skip(self))]
     pub async fn get_transaction_count_impl(
@@ -698,25 +882,71 @@ impl EthNamespace {
         PROTOCOL_VERSION.to_string()
     }
 
-    #[tracing::instrument(skip(self, tx_bytes))]
-    pub async fn send_raw_transaction_impl(&self, tx_bytes: Bytes) -> Result<H256, Web3Error> {
-        const METHOD_NAME: &str = "send_raw_transaction";
-
-        let method_latency = API_METRICS.start_call(METHOD_NAME);
+    async fn submit_raw_transaction(
+        &self,
+        tx_bytes: Bytes,
+        method_name: &'static str,
+    ) -> Result<H256, Web3Error> {
         let (mut tx, hash) = self.state.parse_transaction_bytes(&tx_bytes.0)?;
         tx.set_input(tx_bytes.0, hash);
 
         let submit_result = self.state.tx_sender.submit_tx(tx).await;
-        let submit_result = submit_result.map(|_| hash).map_err(|err| {
+        submit_result.map(|_| hash).map_err(|err| {
             tracing::debug!("Send raw transaction error: {err}");
             API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
-            err.into_web3_error(METHOD_NAME)
-        });
+            err.into_web3_error(method_name)
+        })
+    }
+
+    #[tracing::instrument(skip(self, tx_bytes))]
+    pub async fn send_raw_transaction_impl(&self, tx_bytes: Bytes) -> Result<H256, Web3Error> {
+        const METHOD_NAME: &str = "send_raw_transaction";
 
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let submit_result = self.submit_raw_transaction(tx_bytes, METHOD_NAME).await;
         method_latency.observe();
         submit_result
     }
 
+    #[tracing::instrument(skip(self, tx_bytes))]
+    pub async fn send_raw_transaction_sync_impl(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<U64>,
+    ) -> Result<TransactionReceipt, Web3Error> {
+        const METHOD_NAME: &str = "send_raw_transaction_sync";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let result = self
+            .submit_raw_transaction_and_wait(tx_bytes, timeout_ms, METHOD_NAME)
+            .await;
+        // Latency is observed for failed calls as well, since they may wait for inclusion for a considerable time.
+        method_latency.observe();
+        result
+    }
+
+    async fn submit_raw_transaction_and_wait(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<U64>,
+        method_name: &'static str,
+    ) -> Result<TransactionReceipt, Web3Error> {
+        let timeout = timeout_ms.map_or(DEFAULT_TX_INCLUSION_TIMEOUT, |ms| {
+            Duration::from_millis(ms.as_u64()).min(MAX_TX_INCLUSION_TIMEOUT)
+        });
+        // Subscribe before submitting the transaction so that no miniblock updates are missed.
+        let sealed_miniblocks = self.state.last_sealed_miniblock.subscribe();
+        let tx_hash = self.submit_raw_transaction(tx_bytes, method_name).await?;
+
+        let receipt = self
+            .state
+            .tx_sender
+            .wait_for_receipt(tx_hash, sealed_miniblocks, timeout)
+            .await
+            .map_err(|err| internal_error(method_name, err))?;
+        receipt.ok_or(Web3Error::TransactionInclusionTimeout(tx_hash))
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn accounts_impl(&self) -> Vec<Address> {
         Vec::new()
@@ -752,6 +982,7 @@ impl EthNamespace {
 
         let method_latency =
             API_METRICS.start_block_call(METHOD_NAME, BlockId::Number(newest_block));
+        validate_reward_percentiles(&reward_percentiles)?;
         // Limit `block_count`.
         let block_count = block_count
             .as_u64()
@@ -778,13 +1009,25 @@ impl EthNamespace {
         base_fee_per_gas.reverse();
 
         let oldest_block = newest_miniblock.0 + 1 - base_fee_per_gas.len() as u32;
-        // We do not store gas used ratio for blocks, returns array of zeroes as a placeholder.
-        let gas_used_ratio = vec![0.0; base_fee_per_gas.len()];
-        // Effective priority gas price is currently 0.
-        let reward = Some(vec![
-            vec![U256::zero(); reward_percentiles.len()];
-            base_fee_per_gas.len()
-        ]);
+        let gas_usage = connection
+            .transactions_web3_dal()
+            .get_gas_usage_for_miniblocks(MiniblockNumber(oldest_block)..=newest_miniblock)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let mut gas_usage_by_block = vec![vec![]; base_fee_per_gas.len()];
+        for (miniblock_number, gas_used, priority_fee) in gas_usage {
+            let block_index = (miniblock_number.0 - oldest_block) as usize;
+            gas_usage_by_block[block_index].push((gas_used, priority_fee));
+        }
+
+        let gas_used_ratio = gas_usage_by_block
+            .iter()
+            .map(|block_gas_usage| compute_gas_used_ratio(block_gas_usage))
+            .collect();
+        let reward = gas_usage_by_block
+            .iter_mut()
+            .map(|block_gas_usage| compute_rewards(block_gas_usage, &reward_percentiles))
+            .collect();
 
         // `base_fee_per_gas` for next miniblock cannot be calculated, appending last fee as a placeholder.
         base_fee_per_gas.push(*base_fee_per_gas.last().unwrap());
@@ -794,10 +1037,48 @@ impl EthNamespace {
             oldest_block: web3::types::BlockNumber::Number(oldest_block.into()),
             base_fee_per_gas,
             gas_used_ratio,
-            reward,
+            reward: Some(reward),
         })
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn max_priority_fee_per_gas_impl(&self) -> Result<U256, Web3Error> {
+        const METHOD_NAME: &str = "max_priority_fee_per_gas";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut connection = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let newest_miniblock = self
+            .state
+            .resolve_block(
+                &mut connection,
+                BlockId::Number(BlockNumber::Latest),
+                METHOD_NAME,
+            )
+            .await?;
+        let oldest_miniblock = MiniblockNumber(
+            newest_miniblock
+                .0
+                .saturating_sub(PRIORITY_FEE_BLOCK_COUNT - 1),
+        );
+        let mut gas_usage: Vec<_> = connection
+            .transactions_web3_dal()
+            .get_gas_usage_for_miniblocks(oldest_miniblock..=newest_miniblock)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .into_iter()
+            .map(|(_, gas_used, priority_fee)| (gas_used, priority_fee))
+            .collect();
+
+        let priority_fee = compute_rewards(&mut gas_usage, &[PRIORITY_FEE_PERCENTILE])[0];
+        method_latency.observe();
+        Ok(priority_fee)
+    }
+
     #[tracing::instrument(skip(self, typed_filter))]
     async fn filter_changes(
         &self,
@@ -966,3 +1247,75 @@ impl EthNamespace {
     // - `compile_solidity`.
     // - `compile_serpent`.
 }
+
+/// Checks that the state override passed to `eth_call` or `eth_estimateGas` is well-formed.
+fn validate_state_override(state_override: &StateOverride) -> Result<(), Web3Error> {
+    for (&address, account) in state_override.iter() {
+        if account.state.is_some() && account.state_diff.is_some() {
+            let message = "`state` and `stateDiff` cannot be set simultaneously".to_owned();
+            return Err(Web3Error::InvalidStateOverride(address, message));
+        }
+        if let Some(code) = account.code.as_ref().filter(|code| !code.0.is_empty()) {
+            validate_bytecode(&code.0)
+                .map_err(|err| Web3Error::InvalidStateOverride(address, err.to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that reward percentiles are in the `[0, 100]` range and are sorted in the ascending order, like geth does.
+fn validate_reward_percentiles(percentiles: &[f32]) -> Result<(), Web3Error> {
+    for (i, &percentile) in percentiles.iter().enumerate() {
+        if !(0.0..=100.0).contains(&percentile) {
+            let err = format!("percentile #{i} ({percentile}) is not in the [0, 100] range");
+            return Err(Web3Error::InvalidRewardPercentiles(err));
+        }
+        if i > 0 && percentile < percentiles[i - 1] {
+            let err = format!(
+                "percentile #{i} ({percentile}) is less than the previous percentile ({})",
+                percentiles[i - 1]
+            );
+            return Err(Web3Error::InvalidRewardPercentiles(err));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the ratio of gas used by the transactions in a miniblock to the miniblock gas limit.
+/// Transactions are specified as `(gas_used, priority_fee)` tuples.
+fn compute_gas_used_ratio(transactions: &[(U256, U256)]) -> f64 {
+    let gas_used = transactions
+        .iter()
+        .fold(U256::zero(), |acc, &(gas_used, _)| acc + gas_used);
+    gas_used.low_u128() as f64 / f64::from(BLOCK_GAS_LIMIT)
+}
+
+/// Computes priority fees at the specified percentiles of gas used by transactions, in the same way as geth does:
+/// transactions are sorted by their priority fee, and the reward for a percentile is the fee of the first transaction
+/// at which the cumulative gas used reaches the percentile of the total gas used. Transactions are specified
+/// as `(gas_used, priority_fee)` tuples.
+fn compute_rewards(transactions: &mut [(U256, U256)], percentiles: &[f32]) -> Vec<U256> {
+    if transactions.is_empty() {
+        return vec![U256::zero(); percentiles.len()];
+    }
+
+    transactions.sort_unstable_by_key(|&(_, priority_fee)| priority_fee);
+    let total_gas_used = transactions
+        .iter()
+        .fold(U256::zero(), |acc, &(gas_used, _)| acc + gas_used);
+    percentiles
+        .iter()
+        .map(|&percentile| {
+            // Percentiles are converted to basis points to keep computations integer.
+            let basis_points = (f64::from(percentile) * 100.0) as u64;
+            let threshold = total_gas_used * basis_points / 10_000_u64;
+            let mut index = 0;
+            let mut cumulative_gas_used = transactions[0].0;
+            while cumulative_gas_used < threshold && index < transactions.len() - 1 {
+                index += 1;
+                cumulative_gas_used += transactions[index].0;
+            }
+            transactions[index].1
+        })
+        .collect()
+}
@@ -98,8 +98,10 @@ impl SnapshotsNamespace {
 
         method_latency.observe();
         Ok(Some(SnapshotHeader {
+            version: SnapshotHeader::LATEST_VERSION,
             l1_batch_number: snapshot_metadata.l1_batch_number,
             miniblock_number,
+            base_l1_batch_number: snapshot_metadata.base_l1_batch_number,
             last_l1_batch_with_metadata: l1_batch_with_metadata,
             storage_logs_chunks: chunks,
             factory_deps_filepath: snapshot_metadata.factory_deps_filepath,
@@ -1,14 +1,16 @@
 use std::{collections::HashMap, convert::TryInto};
 
+use multivm::interface::ExecutionResult;
 use zksync_dal::StorageProcessor;
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        self, BlockDetails, BlockId, BlockNumber, BridgeAddresses, BundleTransactionResult,
+        GetLogsFilter, L1BatchDetails, L2ToL1LogProof, PrestateDiff, Proof, ProtocolVersion,
+        TransactionDetails,
     },
-    fee::Fee,
+    fee::{Fee, FeeWithBreakdown},
     fee_model::FeeParams,
     l1::L1Tx,
     l2::L2Tx,
@@ -26,10 +28,13 @@ use zksync_web3_decl::{
 };
 
 use crate::api_server::{
-    tree::TreeApiClient,
+    execution_sandbox::BundleTransactionOutput,
     web3::{backend_jsonrpsee::internal_error, metrics::API_METRICS, RpcState},
 };
 
+/// Maximum number of transactions in a bundle accepted by `zks_simulateBundle`.
+const MAX_BUNDLE_SIZE: usize = 32;
+
 #[derive(Debug)]
 pub struct ZksNamespace {
     pub state: RpcState,
@@ -56,6 +61,27 @@ impl ZksNamespace {
         const METHOD_NAME: &str = "estimate_fee";
 
         let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let tx = self.l2_tx_for_fee_estimation(request).await?;
+        let fee = self.estimate_fee(tx.into(), METHOD_NAME).await?;
+        method_latency.observe();
+        Ok(fee.fee)
+    }
+
+    #[tracing::instrument(skip(self, request))]
+    pub async fn estimate_fee_with_breakdown_impl(
+        &self,
+        request: CallRequest,
+    ) -> Result<FeeWithBreakdown, Web3Error> {
+        const METHOD_NAME: &str = "estimate_fee_with_breakdown";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let tx = self.l2_tx_for_fee_estimation(request).await?;
+        let fee = self.estimate_fee(tx.into(), METHOD_NAME).await?;
+        method_latency.observe();
+        Ok(fee)
+    }
+
+    async fn l2_tx_for_fee_estimation(&self, request: CallRequest) -> Result<L2Tx, Web3Error> {
         let mut request_with_gas_per_pubdata_overridden = request;
 
         self.state
@@ -75,10 +101,7 @@ impl ZksNamespace {
         // not consider provided ones.
         tx.common_data.fee.max_priority_fee_per_gas = 0u64.into();
         tx.common_data.fee.gas_per_pubdata_limit = U256::from(DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE);
-
-        let fee = self.estimate_fee(tx.into(), METHOD_NAME).await?;
-        method_latency.observe();
-        Ok(fee)
+        Ok(tx)
     }
 
     #[tracing::instrument(skip(self, request))]
@@ -104,21 +127,21 @@ impl ZksNamespace {
 
         let fee = self.estimate_fee(tx.into(), METHOD_NAME).await?;
         method_latency.observe();
-        Ok(fee.gas_limit)
+        Ok(fee.fee.gas_limit)
     }
 
     async fn estimate_fee(
         &self,
         tx: Transaction,
         method_name: &'static str,
-    ) -> Result<Fee, Web3Error> {
+    ) -> Result<FeeWithBreakdown, Web3Error> {
         let scale_factor = self.state.api_config.estimate_gas_scale_factor;
         let acceptable_overestimation =
             self.state.api_config.estimate_gas_acceptable_overestimation;
 
         self.state
             .tx_sender
-            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation)
+            .get_txs_fee_with_breakdown(tx, scale_factor, acceptable_overestimation, None)
             .await
             .map_err(|err| err.into_web3_error(method_name))
     }
@@ -599,32 +622,100 @@ impl ZksNamespace {
     ) -> Result<Proof, Web3Error> {
         const METHOD_NAME: &str = "get_proofs";
 
-        self.state.start_info.ensure_not_pruned(l1_batch_number)?;
-        let hashed_keys = keys
-            .iter()
-            .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
+        let keys: Vec<_> = keys
+            .into_iter()
+            .map(|key| StorageKey::new(AccountTreeId::new(address), key))
             .collect();
         let storage_proof = self
             .state
-            .tree_api
-            .as_ref()
-            .ok_or(Web3Error::TreeApiUnavailable)?
-            .get_proofs(l1_batch_number, hashed_keys)
-            .await
-            .map_err(|err| internal_error(METHOD_NAME, err))?
-            .into_iter()
-            .zip(keys)
-            .map(|(proof, key)| StorageProof {
-                key,
-                proof: proof.merkle_path,
-                value: proof.value,
-                index: proof.index,
-            })
-            .collect();
+            .get_storage_proofs(l1_batch_number, &keys, METHOD_NAME)
+            .await?;
 
         Ok(Proof {
             address,
             storage_proof,
         })
     }
+
+    #[tracing::instrument(skip(self, requests))]
+    pub async fn simulate_bundle_impl(
+        &self,
+        requests: Vec<CallRequest>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<BundleTransactionResult>, Web3Error> {
+        const METHOD_NAME: &str = "simulate_bundle";
+
+        if requests.len() > MAX_BUNDLE_SIZE {
+            return Err(Web3Error::BundleTooLarge(requests.len(), MAX_BUNDLE_SIZE));
+        }
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut storage = self.access_storage(METHOD_NAME).await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut storage, block_id, METHOD_NAME)
+            .await?;
+        drop(storage);
+
+        let max_tx_size = self.state.api_config.max_tx_size;
+        let txs = requests
+            .into_iter()
+            .map(|request| L2Tx::from_request(request.into(), max_tx_size))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = self
+            .state
+            .tx_sender
+            .simulate_bundle(block_args, txs)
+            .await
+            .map_err(|err| err.into_web3_error(METHOD_NAME))?;
+
+        let block_diff = self
+            .state
+            .last_sealed_miniblock
+            .diff_with_block_args(&block_args);
+        method_latency.observe(block_diff);
+        Ok(outputs.into_iter().map(bundle_transaction_result).collect())
+    }
+}
+
+fn bundle_transaction_result(output: BundleTransactionOutput) -> BundleTransactionResult {
+    let (success, output_bytes, revert_reason) = match output.vm.result {
+        ExecutionResult::Success { output } => (true, output, None),
+        ExecutionResult::Revert { output } => (
+            false,
+            output.encoded_data(),
+            Some(output.to_user_friendly_string()),
+        ),
+        ExecutionResult::Halt { reason } => (false, vec![], Some(reason.to_string())),
+    };
+    let logs = output
+        .vm
+        .logs
+        .events
+        .into_iter()
+        .enumerate()
+        .map(|(i, event)| api::Log {
+            address: event.address,
+            topics: event.indexed_topics,
+            data: event.value.into(),
+            block_hash: None,
+            block_number: None,
+            l1_batch_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: Some(i.into()),
+            log_type: None,
+            removed: None,
+        })
+        .collect();
+
+    BundleTransactionResult {
+        success,
+        output: output_bytes.into(),
+        revert_reason,
+        gas_used: output.vm.statistics.gas_used.into(),
+        logs,
+        state_diff: PrestateDiff::new(&output.touched_state),
+    }
 }
@@ -1,5 +1,7 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
+use std::collections::VecDeque;
+
 use anyhow::Context as _;
 use futures::FutureExt;
 use tokio::{
@@ -28,6 +30,8 @@ use crate::api_server::execution_sandbox::BlockStartInfo;
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
 const SUBSCRIPTION_SINK_SEND_TIMEOUT: Duration = Duration::from_secs(1);
+/// Number of latest notified miniblocks retained by notifiers to detect miniblock reverts.
+const RECENT_MINIBLOCKS_CAPACITY: usize = 128;
 
 #[derive(Debug, Clone, Copy)]
 pub struct EthSubscriptionIdProvider;
@@ -45,6 +49,46 @@ pub(super) enum PubSubEvent {
     Subscribed(SubscriptionType),
     NotifyIterationFinished(SubscriptionType),
     MiniblockAdvanced(SubscriptionType, MiniblockNumber),
+    MiniblocksReverted(SubscriptionType, MiniblockNumber),
+}
+
+/// Miniblocks recently notified about, together with the data sent to subscribers for each of them.
+/// Used to detect miniblock reverts, which can happen on external nodes if a reorg is detected.
+#[derive(Debug)]
+struct RecentMiniblocks<T> {
+    miniblocks: VecDeque<(MiniblockNumber, H256, T)>,
+}
+
+impl<T: Default> RecentMiniblocks<T> {
+    fn new() -> Self {
+        Self {
+            miniblocks: VecDeque::with_capacity(RECENT_MINIBLOCKS_CAPACITY),
+        }
+    }
+
+    /// Returns data for the specified miniblock, inserting the miniblock if it's not the latest retained one.
+    fn latest_entry(&mut self, number: MiniblockNumber, hash: H256) -> &mut T {
+        let is_new = self
+            .miniblocks
+            .back()
+            .map_or(true, |(last_number, ..)| *last_number != number);
+        if is_new {
+            if self.miniblocks.len() == RECENT_MINIBLOCKS_CAPACITY {
+                self.miniblocks.pop_front();
+            }
+            self.miniblocks.push_back((number, hash, T::default()));
+        }
+        &mut self.miniblocks.back_mut().unwrap().2
+    }
+}
+
+/// Miniblocks reverted since the last notifier iteration.
+#[derive(Debug)]
+struct RevertedMiniblocks<T> {
+    /// Number of the last miniblock that was not reverted. Notifications should resume after it.
+    last_retained_miniblock: MiniblockNumber,
+    /// Data sent to subscribers for the reverted miniblocks, starting from the newest miniblock.
+    data: Vec<T>,
 }
 
 /// Manager of notifications for a certain type of subscriptions.
@@ -73,11 +117,63 @@ impl PubSubNotifier {
             None => {
                 // We don't have miniblocks in the storage yet. Use the snapshot miniblock number instead.
                 let start_info = BlockStartInfo::new(&mut storage).await?;
-                MiniblockNumber(start_info.first_miniblock.saturating_sub(1))
+                MiniblockNumber(start_info.first_miniblock().saturating_sub(1))
             }
         })
     }
 
+    /// Checks whether any of the recently notified miniblocks were reverted, and removes reverted miniblocks
+    /// from `recent_miniblocks`.
+    async fn detect_revert<T: Default>(
+        &self,
+        recent_miniblocks: &mut RecentMiniblocks<T>,
+    ) -> anyhow::Result<Option<RevertedMiniblocks<T>>> {
+        if recent_miniblocks.miniblocks.is_empty() {
+            return Ok(None);
+        }
+
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?;
+        let mut reverted_data = vec![];
+        let mut last_reverted_miniblock = None;
+        while let Some(&(number, hash, _)) = recent_miniblocks.miniblocks.back() {
+            let header = storage
+                .blocks_dal()
+                .get_miniblock_header(number)
+                .await
+                .with_context(|| format!("get_miniblock_header({number})"))?;
+            if header.map_or(false, |header| header.hash == hash) {
+                break;
+            }
+            let (_, _, data) = recent_miniblocks.miniblocks.pop_back().unwrap();
+            reverted_data.push(data);
+            last_reverted_miniblock = Some(number);
+        }
+        drop(storage);
+
+        let Some(last_reverted_miniblock) = last_reverted_miniblock else {
+            return Ok(None);
+        };
+        let last_retained_miniblock = match recent_miniblocks.miniblocks.back() {
+            Some(&(number, ..)) => number,
+            None => {
+                // The revert is deeper than retained miniblocks; we cannot know which miniblocks are retained.
+                let sealed_miniblock = self.get_starting_miniblock_number().await?;
+                sealed_miniblock.min(MiniblockNumber(last_reverted_miniblock.0.saturating_sub(1)))
+            }
+        };
+        tracing::info!(
+            "Detected revert of miniblocks after #{last_retained_miniblock}; notifying subscribers"
+        );
+        Ok(Some(RevertedMiniblocks {
+            last_retained_miniblock,
+            data: reverted_data,
+        }))
+    }
+
     fn emit_event(&self, event: PubSubEvent) {
         if let Some(sender) = &self.events_sender {
             sender.send(event).ok();
@@ -88,6 +184,7 @@ impl PubSubNotifier {
 impl PubSubNotifier {
     async fn notify_blocks(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut last_block_number = self.get_starting_miniblock_number().await?;
+        let mut recent_miniblocks = RecentMiniblocks::<()>::new();
         let mut timer = interval(self.polling_interval);
         loop {
             if *stop_receiver.borrow() {
@@ -97,11 +194,23 @@ impl PubSubNotifier {
             timer.tick().await;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Blocks].start();
+            if let Some(reverted) = self.detect_revert(&mut recent_miniblocks).await? {
+                // Headers of the new miniblocks replacing the reverted ones will be sent as usual.
+                last_block_number = reverted.last_retained_miniblock;
+                self.emit_event(PubSubEvent::MiniblocksReverted(
+                    SubscriptionType::Blocks,
+                    last_block_number,
+                ));
+            }
             let new_blocks = self.new_blocks(last_block_number).await?;
             db_latency.observe();
 
             if let Some(last_block) = new_blocks.last() {
                 last_block_number = MiniblockNumber(last_block.number.unwrap().as_u32());
+                for block in &new_blocks {
+                    let number = MiniblockNumber(block.number.unwrap().as_u32());
+                    recent_miniblocks.latest_entry(number, block.hash.unwrap());
+                }
                 let new_blocks = new_blocks.into_iter().map(PubSubResult::Header).collect();
                 self.send_pub_sub_results(new_blocks, SubscriptionType::Blocks);
                 self.emit_event(PubSubEvent::MiniblockAdvanced(
@@ -176,6 +285,7 @@ impl PubSubNotifier {
 
     async fn notify_logs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut last_block_number = self.get_starting_miniblock_number().await?;
+        let mut recent_miniblocks = RecentMiniblocks::<Vec<Log>>::new();
 
         let mut timer = interval(self.polling_interval);
         loop {
@@ -186,11 +296,40 @@ impl PubSubNotifier {
             timer.tick().await;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
+            let reverted = self.detect_revert(&mut recent_miniblocks).await?;
+            if let Some(reverted) = &reverted {
+                last_block_number = reverted.last_retained_miniblock;
+            }
             let new_logs = self.new_logs(last_block_number).await?;
             db_latency.observe();
 
+            if let Some(reverted) = reverted {
+                // Logs from reverted miniblocks are sent in the reverse order, like in Ethereum clients.
+                let removed_logs = reverted
+                    .data
+                    .into_iter()
+                    .flat_map(|logs| logs.into_iter().rev())
+                    .map(|log| {
+                        PubSubResult::Log(Log {
+                            removed: Some(true),
+                            ..log
+                        })
+                    })
+                    .collect();
+                self.send_pub_sub_results(removed_logs, SubscriptionType::Logs);
+                self.emit_event(PubSubEvent::MiniblocksReverted(
+                    SubscriptionType::Logs,
+                    reverted.last_retained_miniblock,
+                ));
+            }
             if let Some(last_log) = new_logs.last() {
                 last_block_number = MiniblockNumber(last_log.block_number.unwrap().as_u32());
+                for log in &new_logs {
+                    let number = MiniblockNumber(log.block_number.unwrap().as_u32());
+                    recent_miniblocks
+                        .latest_entry(number, log.block_hash.unwrap())
+                        .push(log.clone());
+                }
                 let new_logs = new_logs.into_iter().map(PubSubResult::Log).collect();
                 self.send_pub_sub_results(new_logs, SubscriptionType::Logs);
                 self.emit_event(PubSubEvent::MiniblockAdvanced(
@@ -14,7 +14,7 @@ use zksync_config::configs::{api::Web3JsonRpcConfig, chain::NetworkConfig, Contr
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     api, l2::L2Tx, transaction_request::CallRequest, Address, L1BatchNumber, L1ChainId, L2ChainId,
-    MiniblockNumber, H256, U256, U64,
+    MiniblockNumber, StorageKey, H256, U256, U64,
 };
 use zksync_web3_decl::{error::Web3Error, types::Filter};
 
@@ -22,7 +22,7 @@ use super::metrics::{FilterType, FILTER_METRICS};
 use crate::{
     api_server::{
         execution_sandbox::{BlockArgs, BlockArgsError, BlockStartInfo},
-        tree::TreeApiHttpClient,
+        tree::{TreeApiClient, TreeApiHttpClient},
         tx_sender::TxSender,
         web3::{backend_jsonrpsee::internal_error, TypedFilter},
     },
@@ -60,8 +60,9 @@ impl BlockStartInfo {
                 .ensure_not_pruned_block(id)
                 .map_err(Web3Error::PrunedBlock),
             PruneQuery::L1Batch(number) => {
-                if number < self.first_l1_batch {
-                    return Err(Web3Error::PrunedL1Batch(self.first_l1_batch));
+                let first_l1_batch = self.first_l1_batch();
+                if number < first_l1_batch {
+                    return Err(Web3Error::PrunedL1Batch(first_l1_batch));
                 }
                 Ok(())
             }
@@ -87,6 +88,7 @@ pub struct InternalApiConfig {
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
     pub filters_disabled: bool,
+    pub struct_log_max_steps: usize,
 }
 
 impl InternalApiConfig {
@@ -114,6 +116,7 @@ impl InternalApiConfig {
             req_entities_limit: web3_config.req_entities_limit(),
             fee_history_limit: web3_config.fee_history_limit(),
             filters_disabled: web3_config.filters_disabled,
+            struct_log_max_steps: web3_config.struct_log_max_steps(),
         }
     }
 }
@@ -122,9 +125,12 @@ impl InternalApiConfig {
 ///
 /// The information may be temporarily outdated and thus should only be used where this is OK
 /// (e.g., for metrics reporting). The value is updated by [`Self::diff()`] and [`Self::diff_with_block_args()`]
-/// and on an interval specified when creating an instance.
+/// and on an interval specified when creating an instance. Updates can be subscribed to using [`Self::subscribe()`].
 #[derive(Debug, Clone)]
-pub(crate) struct SealedMiniblockNumber(Arc<AtomicU32>);
+pub(crate) struct SealedMiniblockNumber {
+    number: Arc<AtomicU32>,
+    notifier: Arc<watch::Sender<MiniblockNumber>>,
+}
 
 impl SealedMiniblockNumber {
     /// Creates a handle to the last sealed miniblock number together with a task that will update
@@ -134,7 +140,11 @@ impl SealedMiniblockNumber {
         update_interval: Duration,
         stop_receiver: watch::Receiver<bool>,
     ) -> (Self, impl Future<Output = anyhow::Result<()>>) {
-        let this = Self(Arc::default());
+        let (notifier, _) = watch::channel(MiniblockNumber(0));
+        let this = Self {
+            number: Arc::default(),
+            notifier: Arc::new(notifier),
+        };
         let number_updater = this.clone();
 
         let update_task = async move {
@@ -169,11 +179,25 @@ impl SealedMiniblockNumber {
     /// Returns the last sealed miniblock number after the update.
     fn update(&self, maybe_newer_miniblock_number: MiniblockNumber) -> MiniblockNumber {
         let prev_value = self
-            .0
+            .number
             .fetch_max(maybe_newer_miniblock_number.0, Ordering::Relaxed);
+        if maybe_newer_miniblock_number.0 > prev_value {
+            self.notifier.send_if_modified(|number| {
+                let is_newer = maybe_newer_miniblock_number > *number;
+                if is_newer {
+                    *number = maybe_newer_miniblock_number;
+                }
+                is_newer
+            });
+        }
         MiniblockNumber(prev_value).max(maybe_newer_miniblock_number)
     }
 
+    /// Subscribes to updates of the last sealed miniblock number.
+    pub fn subscribe(&self) -> watch::Receiver<MiniblockNumber> {
+        self.notifier.subscribe()
+    }
+
     pub fn diff(&self, miniblock_number: MiniblockNumber) -> u32 {
         let sealed_miniblock_number = self.update(miniblock_number);
         sealed_miniblock_number.0.saturating_sub(miniblock_number.0)
@@ -203,7 +227,7 @@ pub struct RpcState {
     pub sync_state: Option<SyncState>,
     pub(super) api_config: InternalApiConfig,
     /// Number of the first locally available miniblock / L1 batch. May differ from 0 if the node state was recovered
-    /// from a snapshot.
+    /// from a snapshot or old data was pruned.
     pub(super) start_info: BlockStartInfo,
     pub(super) last_sealed_miniblock: SealedMiniblockNumber,
 }
@@ -246,7 +270,7 @@ impl RpcState {
         block: api::BlockId,
         method_name: &'static str,
     ) -> Result<BlockArgs, Web3Error> {
-        BlockArgs::new(connection, block, self.start_info)
+        BlockArgs::new(connection, block, &self.start_info)
             .await
             .map_err(|err| match err {
                 BlockArgsError::Pruned(number) => Web3Error::PrunedBlock(number),
@@ -255,6 +279,35 @@ impl RpcState {
             })
     }
 
+    /// Loads Merkle tree proofs for the specified storage keys as of the end of the specified L1 batch.
+    pub(crate) async fn get_storage_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[StorageKey],
+        method_name: &'static str,
+    ) -> Result<Vec<api::StorageProof>, Web3Error> {
+        self.start_info.ensure_not_pruned(l1_batch_number)?;
+        let hashed_keys = keys.iter().map(StorageKey::hashed_key_u256).collect();
+        let proofs = self
+            .tree_api
+            .as_ref()
+            .ok_or(Web3Error::TreeApiUnavailable)?
+            .get_proofs(l1_batch_number, hashed_keys)
+            .await
+            .map_err(|err| internal_error(method_name, err))?;
+
+        Ok(proofs
+            .into_iter()
+            .zip(keys)
+            .map(|(proof, key)| api::StorageProof {
+                key: *key.key(),
+                proof: proof.merkle_path,
+                value: proof.value,
+                index: proof.index,
+            })
+            .collect())
+    }
+
     pub async fn resolve_filter_block_number(
         &self,
         block_number: Option<api::BlockNumber>,
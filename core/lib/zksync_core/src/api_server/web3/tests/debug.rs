@@ -1,5 +1,7 @@
 //! Tests for the `debug` Web3 namespace.
 
+use std::collections::BTreeMap;
+
 use zksync_types::{tx::TransactionExecutionResult, vm_trace::Call, BOOTLOADER_ADDRESS};
 use zksync_web3_decl::namespaces::DebugNamespaceClient;
 
@@ -54,7 +56,12 @@ impl HttpTest for TraceBlockTest {
 
             assert_eq!(block_traces.len(), tx_results.len()); // equals to the number of transactions in the block
             for (trace, tx_result) in block_traces.iter().zip(&tx_results) {
-                let api::ResultDebugCall { result } = trace;
+                let api::ResultDebugCall {
+                    result: api::TracerResult::CallTrace(result),
+                } = trace
+                else {
+                    panic!("Unexpected trace: {trace:?}");
+                };
                 assert_eq!(result.from, Address::zero());
                 assert_eq!(result.to, BOOTLOADER_ADDRESS);
                 assert_eq!(result.gas, tx_result.transaction.gas_limit());
@@ -113,6 +120,9 @@ impl HttpTest for TraceTransactionTest {
             .trace_transaction(tx_results[0].hash, None)
             .await?
             .context("no transaction traces")?;
+        let api::TracerResult::CallTrace(result) = result else {
+            panic!("Unexpected trace: {result:?}");
+        };
         assert_eq!(result.from, Address::zero());
         assert_eq!(result.to, BOOTLOADER_ADDRESS);
         assert_eq!(result.gas, tx_results[0].transaction.gas_limit());
@@ -127,6 +137,111 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug)]
+struct PrestateTraceTransactionTest;
+
+#[async_trait]
+impl HttpTest for PrestateTraceTransactionTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let tx_hash = tx_results[0].hash;
+        let account = tx_results[0].transaction.initiator_account();
+        let contract = Address::repeat_byte(0xab);
+        let nonce_key = get_nonce_key(&account);
+        let contract_slot = StorageKey::new(AccountTreeId::new(contract), H256::repeat_byte(1));
+
+        let mut storage = pool.access_storage().await?;
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(
+                MiniblockNumber(0),
+                &[(
+                    H256::zero(),
+                    vec![StorageLog::new_write_log(nonce_key, u256_to_h256(1.into()))],
+                )],
+            )
+            .await?;
+        store_miniblock(&mut storage, MiniblockNumber(1), &tx_results).await?;
+        let tx_logs = vec![
+            StorageLog::new_write_log(nonce_key, u256_to_h256(2.into())),
+            StorageLog::new_write_log(contract_slot, H256::repeat_byte(0xff)),
+        ];
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(MiniblockNumber(1), &[(tx_hash, tx_logs)])
+            .await?;
+        drop(storage);
+
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::TracerSpecificConfig::default(),
+        };
+        let trace = client
+            .trace_transaction(tx_hash, Some(options.clone()))
+            .await?
+            .context("no transaction traces")?;
+        let api::TracerResult::PrestateTrace(api::PrestateTrace::Prestate(accounts)) = trace else {
+            panic!("Unexpected trace: {trace:?}");
+        };
+        assert_eq!(accounts[&account].nonce, Some(1));
+        assert_eq!(
+            accounts[&contract].storage,
+            BTreeMap::from([(H256::repeat_byte(1), H256::zero())])
+        );
+
+        let mut diff_options = options.clone();
+        diff_options.tracer_config.prestate_tracer.diff_mode = true;
+        let trace = client
+            .trace_transaction(tx_hash, Some(diff_options.clone()))
+            .await?
+            .context("no transaction traces")?;
+        let api::TracerResult::PrestateTrace(api::PrestateTrace::Diff(diff)) = trace else {
+            panic!("Unexpected trace: {trace:?}");
+        };
+        assert_eq!(diff.pre[&account].nonce, Some(1));
+        assert_eq!(diff.post[&account].nonce, Some(2));
+        assert_eq!(
+            diff.post[&contract].storage,
+            BTreeMap::from([(H256::repeat_byte(1), H256::repeat_byte(0xff))])
+        );
+
+        let block_traces = client
+            .trace_block_by_number(1_u32.into(), Some(diff_options))
+            .await?;
+        assert_eq!(block_traces.len(), 1);
+        assert_eq!(
+            block_traces[0].result,
+            api::TracerResult::PrestateTrace(api::PrestateTrace::Diff(diff))
+        );
+
+        let missing_trace = client
+            .trace_transaction(H256::repeat_byte(0x01), Some(options))
+            .await?;
+        assert!(missing_trace.is_none());
+
+        let struct_logger_options = api::TracerConfig {
+            tracer: api::SupportedTracers::StructLogger,
+            tracer_config: api::TracerSpecificConfig::default(),
+        };
+        let error = client
+            .trace_transaction(tx_hash, Some(struct_logger_options))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+            assert!(error.message().contains("structLogger"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn prestate_tracing_transaction() {
+    test_http_server(PrestateTraceTransactionTest).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -1,17 +1,20 @@
-use std::{collections::HashMap, pin::Pin, slice, time::Instant};
+use std::{collections::HashMap, net::Ipv4Addr, pin::Pin, slice, time::Instant};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use jsonrpsee::core::ClientError;
 use multivm::zk_evm_latest::ethereum_types::U256;
+use tempfile::TempDir;
 use tokio::sync::watch;
 use zksync_config::configs::{
-    api::Web3JsonRpcConfig,
+    api::{MethodWeight, Web3JsonRpcConfig},
     chain::{NetworkConfig, StateKeeperConfig},
     ContractsConfig,
 };
+use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, StorageProcessor};
 use zksync_health_check::CheckHealth;
+use zksync_merkle_tree::TreeEntry;
 use zksync_types::{
     api,
     block::MiniblockHeader,
@@ -40,6 +43,9 @@ use crate::{
         tx_sender::tests::create_test_tx_sender,
     },
     genesis::{ensure_genesis_state, GenesisParams},
+    metadata_calculator::tests::{
+        gen_storage_logs, reset_db_state, run_calculator, setup_calculator,
+    },
     utils::testonly::{
         create_l1_batch, create_l1_batch_metadata, create_l2_transaction, create_miniblock,
         l1_batch_metadata_to_commitment_artifacts, prepare_recovery_snapshot,
@@ -110,6 +116,7 @@ pub(crate) async fn spawn_http_server(
         api_config,
         pool,
         None,
+        None,
         tx_executor,
         stop_receiver,
     )
@@ -122,12 +129,14 @@ async fn spawn_ws_server(
     pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    rate_limits: Option<RpcRateLimits>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     spawn_server(
         ApiTransportLabel::Ws,
         api_config,
         pool,
         websocket_requests_per_minute_limit,
+        rate_limits,
         MockTransactionExecutor::default(),
         stop_receiver,
     )
@@ -139,6 +148,7 @@ async fn spawn_server(
     api_config: InternalApiConfig,
     pool: ConnectionPool,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    rate_limits: Option<RpcRateLimits>,
     tx_executor: MockTransactionExecutor,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
@@ -162,6 +172,11 @@ async fn spawn_server(
             builder
         }
     };
+    let server_builder = if let Some(rate_limits) = rate_limits {
+        server_builder.with_rate_limits(Arc::new(rate_limits))
+    } else {
+        server_builder
+    };
     let server_handles = server_builder
         .with_polling_interval(POLL_INTERVAL)
         .with_tx_sender(tx_sender, vm_barrier)
@@ -190,6 +205,10 @@ trait HttpTest: Send + Sync {
     fn filters_disabled(&self) -> bool {
         false
     }
+
+    fn rate_limits(&self) -> Option<RpcRateLimits> {
+        None
+    }
 }
 
 /// Storage initialization strategy.
@@ -266,9 +285,12 @@ async fn test_http_server(test: impl HttpTest) {
     let web3_config = Web3JsonRpcConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&network_config, &web3_config, &contracts_config);
     api_config.filters_disabled = test.filters_disabled();
-    let mut server_handles = spawn_http_server(
+    let (mut server_handles, _) = spawn_server(
+        ApiTransportLabel::Http,
         api_config,
         pool.clone(),
+        None,
+        test.rate_limits(),
         test.transaction_executor(),
         stop_receiver,
     )
@@ -933,3 +955,282 @@ impl HttpTest for AllAccountBalancesTest {
 async fn getting_all_account_balances() {
     test_http_server(AllAccountBalancesTest).await;
 }
+
+#[derive(Debug)]
+struct FeeHistoryTest;
+
+impl FeeHistoryTest {
+    fn create_transaction(max_fee_per_gas: u64, max_priority_fee_per_gas: u64) -> L2Tx {
+        let mut tx = create_l2_transaction(max_fee_per_gas, 200);
+        tx.common_data.fee.max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+        tx
+    }
+}
+
+#[async_trait]
+impl HttpTest for FeeHistoryTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        // Miniblock base fee is 100, so the priority fees of these transactions are 0, 50 and 20 respectively.
+        let tx_results = [
+            TransactionExecutionResult {
+                refunded_gas: 200,
+                ..execute_l2_transaction(Self::create_transaction(1_000, 0))
+            },
+            execute_l2_transaction(Self::create_transaction(1_000, 50)),
+            execute_l2_transaction(Self::create_transaction(120, 50)),
+        ];
+        store_miniblock(&mut storage, MiniblockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let fee_history = client
+            .fee_history(2_u64.into(), 1_u32.into(), vec![0.0, 25.0, 50.0, 100.0])
+            .await?;
+        assert_eq!(
+            fee_history.oldest_block,
+            zksync_types::web3::types::BlockNumber::Number(0.into())
+        );
+        assert_eq!(fee_history.base_fee_per_gas.len(), 3);
+        assert_eq!(fee_history.base_fee_per_gas[1], 100.into());
+        let expected_ratio = 2_800.0 / f64::from(u32::MAX);
+        assert_eq!(fee_history.gas_used_ratio, [0.0, expected_ratio]);
+        let expected_rewards = vec![
+            vec![U256::zero(); 4],
+            vec![U256::zero(), U256::zero(), 20.into(), 50.into()],
+        ];
+        assert_eq!(fee_history.reward, Some(expected_rewards));
+
+        let priority_fee = client.max_priority_fee_per_gas().await?;
+        assert_eq!(priority_fee, 20.into());
+
+        let invalid_percentiles = [vec![-1.0], vec![100.5], vec![50.0, 25.0]];
+        for percentiles in invalid_percentiles {
+            let err = client
+                .fee_history(2_u64.into(), 1_u32.into(), percentiles.clone())
+                .await
+                .unwrap_err();
+            if let ClientError::Call(err) = err {
+                assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+                assert!(err.message().contains("percentile"), "{err:?}");
+            } else {
+                panic!("Unexpected error for {percentiles:?}: {err:?}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_fee_history() {
+    test_http_server(FeeHistoryTest).await;
+}
+
+#[derive(Debug)]
+struct ProofForUnprocessedBatchTest;
+
+#[async_trait]
+impl HttpTest for ProofForUnprocessedBatchTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        store_miniblock(&mut storage, MiniblockNumber(1), &[]).await?;
+        drop(storage);
+
+        let address = Address::repeat_byte(1);
+        let block_id = api::BlockIdVariant::BlockNumber(api::BlockNumber::Number(1.into()));
+        let error = client
+            .get_proof(address, vec![H256::zero()], Some(block_id))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+            assert!(error.message().contains("not sealed"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+
+        // The genesis L1 batch is processed, but the test server doesn't have the tree API.
+        let block_id = api::BlockIdVariant::BlockNumber(api::BlockNumber::Number(0.into()));
+        let error = client
+            .get_proof(address, vec![], Some(block_id))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert!(error.message().contains("Tree API"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_proof_for_unprocessed_l1_batch() {
+    test_http_server(ProofForUnprocessedBatchTest).await;
+}
+
+fn verify_storage_proof(key: StorageKey, proof: &api::StorageProof, trusted_root_hash: H256) {
+    assert_eq!(proof.key, *key.key());
+    let mut merkle_path = proof.proof.clone();
+    merkle_path.reverse(); // The API uses the root-to-leaf ordering, while the tree uses the opposite one
+    let entry = TreeEntry::new(key.hashed_key_u256(), proof.index, proof.value);
+    zksync_merkle_tree::TreeEntryWithProof {
+        base: entry,
+        merkle_path,
+    }
+    .verify(&Blake2Hasher, trusted_root_hash);
+}
+
+#[tokio::test]
+async fn getting_proofs_for_processed_l1_batches() {
+    let pool = ConnectionPool::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    reset_db_state(&pool, 2).await;
+    let tree_reader = calculator.tree_reader();
+    let calculator_task = tokio::spawn(run_calculator(calculator, pool.clone()));
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_api_server = tree_reader
+        .await
+        .create_api_server(&(Ipv4Addr::LOCALHOST, 0).into(), stop_receiver.clone())
+        .unwrap();
+    let tree_api_url = format!("http://{}", tree_api_server.local_addr());
+    let tree_api_task = tokio::spawn(tree_api_server.run());
+    // Wait until the calculator processes all L1 batches.
+    calculator_task.await.unwrap();
+
+    let network_config = NetworkConfig::for_tests();
+    let api_config = InternalApiConfig::new(
+        &network_config,
+        &Web3JsonRpcConfig::for_tests(),
+        &ContractsConfig::for_tests(),
+    );
+    let (tx_sender, vm_barrier) = create_test_tx_sender(
+        pool.clone(),
+        api_config.l2_chain_id,
+        MockTransactionExecutor::default().into(),
+    )
+    .await;
+    let mut server_handles = ApiBuilder::jsonrpsee_backend(api_config, pool.clone())
+        .http(0)
+        .with_polling_interval(POLL_INTERVAL)
+        .with_tx_sender(tx_sender, vm_barrier)
+        .with_tree_api(Some(tree_api_url))
+        .enable_api_namespaces(Namespace::DEFAULT.to_vec())
+        .build(stop_receiver)
+        .await
+        .expect("Failed spawning JSON-RPC server");
+    let local_addr = server_handles.wait_until_ready().await;
+    let client = <HttpClient>::builder()
+        .build(format!("http://{local_addr}/"))
+        .unwrap();
+
+    let batch_logs = gen_storage_logs(0..100, 2);
+    let mut storage = pool.access_storage().await.unwrap();
+    // Seal an L1 batch not processed by the tree; it must be ignored when resolving the latest block.
+    store_miniblock(&mut storage, MiniblockNumber(3), &[])
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&create_l1_batch(3))
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(3))
+        .await
+        .unwrap();
+
+    for (l1_batch_number, logs) in (1..).map(L1BatchNumber).zip(&batch_logs) {
+        let expected_root_hash = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("L1 batch is not processed by the tree");
+        let block_id = if l1_batch_number == L1BatchNumber(2) {
+            // The latest block should resolve to the last L1 batch processed by the tree.
+            None
+        } else {
+            let block_number = api::BlockNumber::Number(l1_batch_number.0.into());
+            Some(api::BlockIdVariant::BlockNumber(block_number))
+        };
+
+        let log = &logs[0];
+        let address = *log.key.address();
+        let proof = client
+            .get_proof(
+                address,
+                vec![*log.key.key(), H256::repeat_byte(0xff)],
+                block_id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(proof.address, address);
+        assert_eq!(proof.storage_hash, expected_root_hash);
+
+        let account_keys = [
+            storage_key_for_eth_balance(&address),
+            get_nonce_key(&address),
+            get_code_key(&address),
+        ];
+        assert_eq!(proof.account_proof.len(), account_keys.len());
+        for (key, account_proof) in account_keys.into_iter().zip(&proof.account_proof) {
+            verify_storage_proof(key, account_proof, expected_root_hash);
+        }
+
+        assert_eq!(proof.storage_proof.len(), 2);
+        assert_eq!(proof.storage_proof[0].value, log.value);
+        assert_ne!(proof.storage_proof[0].index, 0);
+        verify_storage_proof(log.key, &proof.storage_proof[0], expected_root_hash);
+        let missing_key = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(0xff));
+        assert_eq!(proof.storage_proof[1].value, H256::zero());
+        assert_eq!(proof.storage_proof[1].index, 0);
+        verify_storage_proof(missing_key, &proof.storage_proof[1], expected_root_hash);
+    }
+    drop(storage);
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+    tree_api_task.await.unwrap().unwrap();
+}
+
+#[derive(Debug)]
+struct ClientQuotaTest;
+
+#[async_trait]
+impl HttpTest for ClientQuotaTest {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        client.chain_id().await?;
+        client.chain_id().await?;
+        // The default method weight is 1, so this call should fit into the remaining quota.
+        client.get_block_number().await?;
+
+        let err = client.chain_id().await.unwrap_err();
+        let ClientError::Call(err) = err else {
+            panic!("Unexpected error returned: {err}");
+        };
+        assert_eq!(
+            err.code() as u16,
+            reqwest::StatusCode::TOO_MANY_REQUESTS.as_u16()
+        );
+        assert_eq!(err.message(), "Too many requests");
+        let data: serde_json::Value = serde_json::from_str(err.data().unwrap().get())?;
+        assert!(data["retryAfterMs"].as_u64().unwrap() > 0, "{data}");
+        Ok(())
+    }
+
+    fn rate_limits(&self) -> Option<RpcRateLimits> {
+        let limits = RpcRateLimits::new(NonZeroU32::new(5)).with_method_weights([MethodWeight {
+            method: "eth_chainId".to_owned(),
+            weight: NonZeroU32::new(2).unwrap(),
+        }]);
+        Some(limits)
+    }
+}
+
+#[tokio::test]
+async fn client_quotas_with_method_weights() {
+    test_http_server(ClientQuotaTest).await;
+}
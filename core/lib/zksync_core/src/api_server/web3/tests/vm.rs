@@ -2,9 +2,13 @@
 
 use std::sync::atomic::{AtomicU32, Ordering};
 
-use multivm::interface::{ExecutionResult, VmRevertReason};
+use multivm::interface::{ExecutionResult, Halt, VmRevertReason};
 use zksync_types::{
-    get_intrinsic_constants, transaction_request::CallRequest, L2ChainId, PackedEthSignature, U256,
+    api::{OverrideAccount, StateOverride},
+    fee::PubdataCost,
+    get_intrinsic_constants,
+    transaction_request::CallRequest,
+    L2ChainId, PackedEthSignature, U256,
 };
 use zksync_utils::u256_to_h256;
 use zksync_web3_decl::namespaces::DebugNamespaceClient;
@@ -51,7 +55,9 @@ impl HttpTest for CallTest {
     }
 
     async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
-        let call_result = client.call(Self::call_request(b"pending"), None).await?;
+        let call_result = client
+            .call(Self::call_request(b"pending"), None, None)
+            .await?;
         assert_eq!(call_result.0, b"output");
 
         let valid_block_numbers_and_calldata = [
@@ -62,7 +68,7 @@ impl HttpTest for CallTest {
         for (number, calldata) in valid_block_numbers_and_calldata {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(Self::call_request(calldata), Some(number))
+                .call(Self::call_request(calldata), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -70,7 +76,7 @@ impl HttpTest for CallTest {
         let invalid_block_number = api::BlockNumber::from(100);
         let number = api::BlockIdVariant::BlockNumber(invalid_block_number);
         let error = client
-            .call(Self::call_request(b"100"), Some(number))
+            .call(Self::call_request(b"100"), Some(number), None)
             .await
             .unwrap_err();
         if let ClientError::Call(error) = error {
@@ -104,7 +110,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
 
     async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
         let call_result = client
-            .call(CallTest::call_request(b"pending"), None)
+            .call(CallTest::call_request(b"pending"), None, None)
             .await?;
         assert_eq!(call_result.0, b"output");
         let pending_block_number = api::BlockIdVariant::BlockNumber(api::BlockNumber::Pending);
@@ -112,6 +118,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
             .call(
                 CallTest::call_request(b"pending"),
                 Some(pending_block_number),
+                None,
             )
             .await?;
         assert_eq!(call_result.0, b"output");
@@ -121,7 +128,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_miniblock);
@@ -131,7 +138,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in first_miniblock_numbers {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(CallTest::call_request(b"first"), Some(number))
+                .call(CallTest::call_request(b"first"), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -254,11 +261,121 @@ async fn send_raw_transaction_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct SendRawTransactionSyncTest {
+    seal_transaction: bool,
+}
+
+impl SendRawTransactionSyncTest {
+    async fn wait_and_seal_transaction(pool: &ConnectionPool, tx_bytes: &[u8], tx_hash: H256) {
+        let tx_id = api::TransactionId::Hash(tx_hash);
+        loop {
+            let mut storage = pool.access_storage().await.unwrap();
+            let tx = storage
+                .transactions_web3_dal()
+                .get_transaction(tx_id, L2ChainId::default())
+                .await
+                .unwrap();
+            if tx.is_some() {
+                break;
+            }
+            drop(storage);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let (tx_request, _) =
+            api::TransactionRequest::from_bytes(tx_bytes, L2ChainId::default()).unwrap();
+        let mut tx = L2Tx::from_request(tx_request, usize::MAX).unwrap();
+        tx.set_input(tx_bytes.to_vec(), tx_hash);
+        let mut storage = pool.access_storage().await.unwrap();
+        let new_miniblock = create_miniblock(1);
+        storage
+            .blocks_dal()
+            .insert_miniblock(&new_miniblock)
+            .await
+            .unwrap();
+        storage
+            .transactions_dal()
+            .mark_txs_as_executed_in_miniblock(
+                new_miniblock.number,
+                &[execute_l2_transaction(tx)],
+                1.into(),
+            )
+            .await;
+    }
+}
+
+#[async_trait]
+impl HttpTest for SendRawTransactionSyncTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        SendRawTransactionTest {
+            snapshot_recovery: false,
+        }
+        .transaction_executor()
+    }
+
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(
+                MiniblockNumber(0),
+                &[(
+                    H256::zero(),
+                    vec![SendRawTransactionTest::balance_storage_log()],
+                )],
+            )
+            .await?;
+        drop(storage);
+
+        let (tx_bytes, tx_hash) = SendRawTransactionTest::transaction_bytes_and_hash();
+        if self.seal_transaction {
+            let send_future = client.send_raw_transaction_sync(tx_bytes.clone().into(), None);
+            let seal_future = Self::wait_and_seal_transaction(pool, &tx_bytes, tx_hash);
+            let (receipt, ()) = tokio::join!(send_future, seal_future);
+            let receipt = receipt?;
+            assert_eq!(receipt.transaction_hash, tx_hash);
+            assert_eq!(receipt.block_number, 1.into());
+        } else {
+            let err = client
+                .send_raw_transaction_sync(tx_bytes.into(), Some(100.into()))
+                .await
+                .unwrap_err();
+            let ClientError::Call(err) = err else {
+                panic!("Unexpected error: {err:?}");
+            };
+            assert_eq!(err.code(), 4);
+            let data = err.data().map(|data| data.get());
+            assert_eq!(data, Some(format!("\"{tx_hash:?}\"").as_str()));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn send_raw_transaction_sync() {
+    test_http_server(SendRawTransactionSyncTest {
+        seal_transaction: true,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn send_raw_transaction_sync_timeout() {
+    test_http_server(SendRawTransactionSyncTest {
+        seal_transaction: false,
+    })
+    .await;
+}
+
 #[derive(Debug)]
 struct TraceCallTest;
 
 impl TraceCallTest {
-    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugCall) {
+    fn assert_debug_call(call_request: &CallRequest, call_result: &api::TracerResult) {
+        let api::TracerResult::CallTrace(call_result) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
         assert_eq!(call_result.from, Address::zero());
         assert_eq!(call_result.gas, call_request.gas.unwrap());
         assert_eq!(call_result.value, call_request.value.unwrap());
@@ -307,6 +424,31 @@ impl HttpTest for TraceCallTest {
             Self::assert_debug_call(&call_request, &call_result);
         }
 
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::StructLogger,
+            tracer_config: api::TracerSpecificConfig::default(),
+        };
+        // The pending block is not persisted, so it must be checked separately from the sealed ones.
+        let struct_log_cases = [
+            (None, b"pending" as &[_]),
+            (Some(pending_block_number), b"pending"),
+            (Some(api::BlockId::Number(0.into())), b"first"),
+        ];
+        for (block_id, calldata) in struct_log_cases {
+            let call_result = client
+                .trace_call(
+                    CallTest::call_request(calldata),
+                    block_id,
+                    Some(options.clone()),
+                )
+                .await?;
+            let api::TracerResult::StructLogTrace(trace) = call_result else {
+                panic!("Unexpected trace for {block_id:?}: {call_result:?}");
+            };
+            assert!(!trace.failed);
+            assert_eq!(trace.return_value.0, b"output");
+        }
+
         let invalid_block_number = api::BlockNumber::from(100);
         let error = client
             .trace_call(
@@ -360,7 +502,7 @@ impl HttpTest for TraceCallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_miniblock);
@@ -436,7 +578,7 @@ impl HttpTest for EstimateGasTest {
         for threshold in [10_000, 50_000, 100_000, 1_000_000] {
             self.gas_limit_threshold.store(threshold, Ordering::Relaxed);
             let output = client
-                .estimate_gas(l2_transaction.clone().into(), None)
+                .estimate_gas(l2_transaction.clone().into(), None, None)
                 .await?;
             assert!(
                 output >= U256::from(threshold),
@@ -448,6 +590,19 @@ impl HttpTest for EstimateGasTest {
             );
         }
 
+        let fee = client.estimate_fee(l2_transaction.clone().into()).await?;
+        let fee_with_breakdown = client
+            .estimate_fee_with_breakdown(l2_transaction.clone().into())
+            .await?;
+        assert_eq!(fee_with_breakdown.fee, fee);
+        let breakdown = fee_with_breakdown.breakdown;
+        assert_eq!(breakdown.gas_per_pubdata, fee.gas_per_pubdata_limit);
+        assert_eq!(breakdown.initial_storage_writes, Some(0));
+        assert_eq!(breakdown.repeated_storage_writes, Some(0));
+        assert_eq!(breakdown.initial_writes, Some(PubdataCost::default()));
+        assert_eq!(breakdown.repeated_writes, Some(PubdataCost::default()));
+        assert_eq!(breakdown.total_pubdata.bytes, 0);
+
         // Check transaction with value.
         if !self.snapshot_recovery {
             // Manually set sufficient balance for the transaction account.
@@ -461,10 +616,15 @@ impl HttpTest for EstimateGasTest {
         let mut call_request = CallRequest::from(l2_transaction);
         call_request.from = Some(SendRawTransactionTest::private_key_and_address().1);
         call_request.value = Some(1_000_000.into());
-        client.estimate_gas(call_request.clone(), None).await?;
+        client
+            .estimate_gas(call_request.clone(), None, None)
+            .await?;
 
         call_request.value = Some(U256::max_value());
-        let error = client.estimate_gas(call_request, None).await.unwrap_err();
+        let error = client
+            .estimate_gas(call_request.clone(), None, None)
+            .await
+            .unwrap_err();
         if let ClientError::Call(error) = error {
             let error_msg = error.message();
             assert!(
@@ -474,6 +634,38 @@ impl HttpTest for EstimateGasTest {
         } else {
             panic!("Unexpected error: {error:?}");
         }
+
+        // Check that the overridden balance is taken into account.
+        let sender = call_request.from.unwrap();
+        let state_override = StateOverride::new(HashMap::from([(
+            sender,
+            OverrideAccount {
+                balance: Some(U256::max_value()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        client
+            .estimate_gas(call_request.clone(), None, Some(state_override))
+            .await?;
+
+        let invalid_override = StateOverride::new(HashMap::from([(
+            sender,
+            OverrideAccount {
+                state: Some(HashMap::new()),
+                state_diff: Some(HashMap::new()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let error = client
+            .estimate_gas(call_request, None, Some(invalid_override))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+            assert!(error.message().contains("stateDiff"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
         Ok(())
     }
 }
@@ -487,3 +679,119 @@ async fn estimate_gas_basics() {
 async fn estimate_gas_after_snapshot_recovery() {
     test_http_server(EstimateGasTest::new(true)).await;
 }
+
+#[derive(Debug)]
+struct SimulateBundleTest;
+
+#[async_trait]
+impl HttpTest for SimulateBundleTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, block_args| {
+            assert_eq!(block_args.resolved_block_number(), MiniblockNumber(1));
+            match tx.execute.calldata() {
+                b"success" => ExecutionResult::Success {
+                    output: b"output".to_vec(),
+                },
+                b"revert" => ExecutionResult::Revert {
+                    output: VmRevertReason::General {
+                        msg: "oops".to_owned(),
+                        data: vec![],
+                    },
+                },
+                b"halt" => ExecutionResult::Halt {
+                    reason: Halt::TracerCustom("oops".to_owned()),
+                },
+                data => panic!("Unexpected calldata: {data:?}"),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let requests = vec![
+            CallTest::call_request(b"success"),
+            CallTest::call_request(b"revert"),
+            CallTest::call_request(b"success"),
+        ];
+        let results = client.simulate_bundle(requests, None).await?;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].success);
+        assert_eq!(results[0].output.0, b"output");
+        assert_eq!(results[0].revert_reason, None);
+        assert!(!results[1].success);
+        assert_eq!(results[1].revert_reason.as_deref(), Some("oops"));
+        assert!(results[2].success);
+
+        // Execution should stop after the first halted transaction.
+        let requests = vec![
+            CallTest::call_request(b"success"),
+            CallTest::call_request(b"halt"),
+            CallTest::call_request(b"success"),
+        ];
+        let results = client.simulate_bundle(requests, None).await?;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert!(!results[1].success);
+
+        let results = client.simulate_bundle(vec![], None).await?;
+        assert!(results.is_empty());
+
+        let requests = vec![CallTest::call_request(b"success"); 100];
+        let err = client.simulate_bundle(requests, None).await.unwrap_err();
+        let ClientError::Call(err) = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulate_bundle() {
+    test_http_server(SimulateBundleTest).await;
+}
+
+#[derive(Debug)]
+struct CreateAccessListTest;
+
+#[async_trait]
+impl HttpTest for CreateAccessListTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, _| match tx.execute.calldata() {
+            b"success" => ExecutionResult::Success {
+                output: b"output".to_vec(),
+            },
+            b"revert" => ExecutionResult::Revert {
+                output: VmRevertReason::General {
+                    msg: "oops".to_owned(),
+                    data: vec![],
+                },
+            },
+            data => panic!("Unexpected calldata: {data:?}"),
+        });
+        tx_executor
+    }
+
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let result = client
+            .create_access_list(CallTest::call_request(b"success"), None)
+            .await?;
+        assert_eq!(result.error, None);
+        // The mock executor doesn't run tracers, so no storage accesses are reported.
+        assert!(result.access_list.is_empty());
+        assert!(result.storage_usage.is_empty());
+
+        let result = client
+            .create_access_list(CallTest::call_request(b"revert"), None)
+            .await?;
+        assert_eq!(result.error.as_deref(), Some("oops"));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_access_list() {
+    test_http_server(CreateAccessListTest).await;
+}
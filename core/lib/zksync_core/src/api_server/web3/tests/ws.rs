@@ -94,6 +94,33 @@ async fn wait_for_notifier_miniblock(
     wait_future.await.expect("Timed out waiting for notifier");
 }
 
+#[allow(clippy::needless_pass_by_ref_mut)] // false positive
+async fn wait_for_miniblocks_revert(
+    events: &mut mpsc::UnboundedReceiver<PubSubEvent>,
+    sub_types: &[SubscriptionType],
+) {
+    let mut sub_types: HashSet<_> = sub_types.iter().copied().collect();
+    let wait_future = tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            let event = events
+                .recv()
+                .await
+                .expect("Events emitter unexpectedly dropped");
+            if let PubSubEvent::MiniblocksReverted(ty, _) = event {
+                sub_types.remove(&ty);
+                if sub_types.is_empty() {
+                    break;
+                }
+            } else {
+                tracing::trace!(?event, "Skipping event");
+            }
+        }
+    });
+    wait_future
+        .await
+        .expect("Timed out waiting for miniblocks revert");
+}
+
 #[tokio::test]
 async fn notifiers_start_after_snapshot_recovery() {
     let pool = ConnectionPool::test_pool().await;
@@ -159,6 +186,10 @@ trait WsTest: Send + Sync {
     fn websocket_requests_per_minute_limit(&self) -> Option<NonZeroU32> {
         None
     }
+
+    fn rate_limits(&self) -> Option<RpcRateLimits> {
+        None
+    }
 }
 
 async fn test_ws_server(test: impl WsTest) {
@@ -180,6 +211,7 @@ async fn test_ws_server(test: impl WsTest) {
         pool.clone(),
         stop_receiver,
         test.websocket_requests_per_minute_limit(),
+        test.rate_limits(),
     )
     .await;
 
@@ -627,6 +659,93 @@ async fn log_subscriptions_with_delay() {
     test_ws_server(LogSubscriptionsWithDelayTest).await;
 }
 
+#[derive(Debug)]
+struct SubscriptionsWithRevertTest;
+
+#[async_trait]
+impl WsTest for SubscriptionsWithRevertTest {
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Blocks]).await;
+        let params = rpc_params!["newHeads"];
+        let mut blocks_subscription = client
+            .subscribe::<BlockHeader, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Blocks).await;
+        let LogSubscriptions {
+            mut all_logs_subscription,
+            mut address_subscription,
+            ..
+        } = LogSubscriptions::new(client, &mut pub_sub_events).await?;
+
+        let mut storage = pool.access_storage().await?;
+        let (_, events) = store_events(&mut storage, 1, 0).await?;
+        drop(storage);
+        let events: Vec<_> = events.iter().collect();
+
+        let all_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert_logs_match(&all_logs, &events);
+        assert!(all_logs.iter().all(|log| !log.is_removed()));
+        let address_logs = collect_logs(&mut address_subscription, 2).await?;
+        assert_logs_match(&address_logs, &[events[0], events[3]]);
+        let block_header = tokio::time::timeout(TEST_TIMEOUT, blocks_subscription.next())
+            .await
+            .context("Timed out waiting for new block header")?
+            .context("New blocks subscription terminated")??;
+        assert_eq!(block_header.number, Some(1.into()));
+
+        // Revert the miniblock in the same way as `BlockReverter` does.
+        let mut storage = pool.access_storage().await?;
+        storage
+            .events_dal()
+            .rollback_events(MiniblockNumber(0))
+            .await;
+        storage
+            .blocks_dal()
+            .delete_miniblocks(MiniblockNumber(0))
+            .await?;
+        drop(storage);
+
+        let removed_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        let reversed_events: Vec<_> = events.iter().rev().copied().collect();
+        assert_logs_match(&removed_logs, &reversed_events);
+        assert!(removed_logs.iter().all(api::Log::is_removed));
+        let removed_address_logs = collect_logs(&mut address_subscription, 2).await?;
+        assert_logs_match(&removed_address_logs, &[events[3], events[0]]);
+        assert!(removed_address_logs.iter().all(api::Log::is_removed));
+        wait_for_miniblocks_revert(
+            &mut pub_sub_events,
+            &[SubscriptionType::Blocks, SubscriptionType::Logs],
+        )
+        .await;
+
+        // Store a replacement miniblock with other events.
+        let mut storage = pool.access_storage().await?;
+        let (_, new_events) = store_events(&mut storage, 1, 4).await?;
+        drop(storage);
+        let new_events: Vec<_> = new_events.iter().collect();
+
+        let all_new_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert_logs_match(&all_new_logs, &new_events);
+        assert!(all_new_logs.iter().all(|log| !log.is_removed()));
+        let block_header = tokio::time::timeout(TEST_TIMEOUT, blocks_subscription.next())
+            .await
+            .context("Timed out waiting for re-emitted block header")?
+            .context("New blocks subscription terminated")??;
+        assert_eq!(block_header.number, Some(1.into()));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn subscriptions_with_revert() {
+    test_ws_server(SubscriptionsWithRevertTest).await;
+}
+
 #[derive(Debug)]
 struct RateLimitingTest;
 
@@ -707,3 +826,46 @@ impl WsTest for BatchGetsRateLimitedTest {
 async fn batch_rate_limiting() {
     test_ws_server(BatchGetsRateLimitedTest).await;
 }
+
+#[derive(Debug)]
+struct WeightedRateLimitingTest;
+
+#[async_trait]
+impl WsTest for WeightedRateLimitingTest {
+    async fn test(
+        &self,
+        client: &WsClient,
+        _pool: &ConnectionPool,
+        _pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        client.chain_id().await.unwrap();
+        let expected_err = client.chain_id().await.unwrap_err();
+
+        if let ClientError::Call(error) = expected_err {
+            assert_eq!(error.code() as u16, StatusCode::TOO_MANY_REQUESTS.as_u16());
+            assert_eq!(error.message(), "Too many requests");
+        } else {
+            panic!("Unexpected error returned: {expected_err}");
+        }
+        // Cheaper methods should still fit into the limit.
+        client.get_block_number().await.unwrap();
+        Ok(())
+    }
+
+    fn websocket_requests_per_minute_limit(&self) -> Option<NonZeroU32> {
+        Some(NonZeroU32::new(3).unwrap())
+    }
+
+    fn rate_limits(&self) -> Option<RpcRateLimits> {
+        let limits = RpcRateLimits::new(None).with_method_weights([MethodWeight {
+            method: "eth_chainId".to_owned(),
+            weight: NonZeroU32::new(2).unwrap(),
+        }]);
+        Some(limits)
+    }
+}
+
+#[tokio::test]
+async fn weighted_rate_limiting() {
+    test_ws_server(WeightedRateLimitingTest).await;
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use zksync_dal::pruning_dal::HardPruningStats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "prune_type", rename_all = "snake_case")]
+pub(super) enum PruneType {
+    Soft,
+    Hard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "type", rename_all = "snake_case")]
+enum PrunedEntityType {
+    L1Batch,
+    Miniblock,
+    Transaction,
+    Event,
+    L2ToL1Log,
+    StorageLogFromPrunedBatch,
+    StorageLogFromPastBatch,
+}
+
+/// Metrics for the Postgres pruner.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "db_pruner")]
+pub(super) struct DbPrunerMetrics {
+    /// Total latency of pruning a chunk of L1 batches.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub pruning_chunk_duration: Family<PruneType, Histogram<Duration>>,
+    /// Last pruned L1 batch number.
+    pub last_pruned_l1_batch: Family<PruneType, Gauge<u64>>,
+    /// Number of rows deleted per pruned chunk of L1 batches.
+    #[metrics(buckets = Buckets::exponential(1.0..=1_000_000.0, 10.0))]
+    deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
+}
+
+impl DbPrunerMetrics {
+    pub fn observe_hard_pruning(&self, stats: HardPruningStats) {
+        let HardPruningStats {
+            deleted_l1_batches,
+            deleted_miniblocks,
+            deleted_transactions,
+            deleted_events,
+            deleted_l2_to_l1_logs,
+            deleted_storage_logs_from_past_batches,
+            deleted_storage_logs_from_pruned_batches,
+        } = stats;
+        tracing::info!(
+            "Performed pruning of database, deleted {deleted_l1_batches} L1 batches, {deleted_miniblocks} miniblocks, \
+             {deleted_transactions} transactions, {deleted_events} events, {deleted_l2_to_l1_logs} L2-to-L1 logs, \
+             {deleted_storage_logs_from_past_batches} storage logs from past batches and \
+             {deleted_storage_logs_from_pruned_batches} storage logs from pruned batches"
+        );
+
+        self.deleted_entities[&PrunedEntityType::L1Batch].observe(deleted_l1_batches);
+        self.deleted_entities[&PrunedEntityType::Miniblock].observe(deleted_miniblocks);
+        self.deleted_entities[&PrunedEntityType::Transaction].observe(deleted_transactions);
+        self.deleted_entities[&PrunedEntityType::Event].observe(deleted_events);
+        self.deleted_entities[&PrunedEntityType::L2ToL1Log].observe(deleted_l2_to_l1_logs);
+        self.deleted_entities[&PrunedEntityType::StorageLogFromPastBatch]
+            .observe(deleted_storage_logs_from_past_batches);
+        self.deleted_entities[&PrunedEntityType::StorageLogFromPrunedBatch]
+            .observe(deleted_storage_logs_from_pruned_batches);
+    }
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<DbPrunerMetrics> = vise::Global::new();
@@ -0,0 +1,273 @@
+//! Postgres pruning component.
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_dal::{pruning_dal::PruningInfo, ConnectionPool, StorageProcessor};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::L1BatchNumber;
+
+use self::{
+    metrics::{PruneType, METRICS},
+    prune_conditions::{
+        L1BatchExecutedCondition, L1BatchOlderThanPruneCondition, NextL1BatchHasMetadataCondition,
+        PruneCondition, RetainedL1BatchesPruneCondition,
+    },
+};
+
+mod metrics;
+mod prune_conditions;
+#[cfg(test)]
+mod tests;
+
+/// Configuration for [`DbPruner`].
+#[derive(Debug)]
+pub struct DbPrunerConfig {
+    /// Delta between soft- and hard-removing data from Postgres. Should be greater than the update interval
+    /// of the API server, so that API servers stop serving data before it is removed.
+    pub removal_delay: Duration,
+    /// Sleep interval between pruning iterations if there is nothing to prune.
+    pub next_iterations_delay: Duration,
+    /// Maximum number of L1 batches pruned in a single iteration.
+    pub pruned_batch_chunk_size: u32,
+    /// Minimum age of an L1 batch for it to be pruned.
+    pub minimum_l1_batch_age: Option<Duration>,
+    /// Minimum number of L1 batches retained after a pruned batch.
+    pub retained_l1_batches: Option<u32>,
+}
+
+/// Health details reported by [`DbPruner`].
+#[derive(Debug, Serialize)]
+struct DbPrunerHealth {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_soft_pruned_l1_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_hard_pruned_l1_batch: Option<L1BatchNumber>,
+}
+
+impl From<PruningInfo> for DbPrunerHealth {
+    fn from(info: PruningInfo) -> Self {
+        Self {
+            last_soft_pruned_l1_batch: info.last_soft_pruned_l1_batch,
+            last_hard_pruned_l1_batch: info.last_hard_pruned_l1_batch,
+        }
+    }
+}
+
+/// Outcome of a single pruning iteration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PruningIterationOutcome {
+    NoOp,
+    Pruned,
+    Interrupted,
+}
+
+/// Postgres pruner, periodically removing old data from the storage.
+///
+/// Pruning is performed in two stages. First, a chunk of L1 batches is *soft-pruned*; this only marks
+/// the batches as pruned, so that API servers stop serving data for them. After [`DbPrunerConfig::removal_delay`],
+/// the soft-pruned batches are *hard-pruned*, i.e. their data is actually removed from Postgres.
+/// Data necessary to execute new transactions (the latest storage logs for each key, initial writes
+/// and factory deps) is never removed.
+#[derive(Debug)]
+pub struct DbPruner {
+    config: DbPrunerConfig,
+    pool: ConnectionPool,
+    health_updater: HealthUpdater,
+    prune_conditions: Vec<Arc<dyn PruneCondition>>,
+}
+
+impl DbPruner {
+    pub fn new(config: DbPrunerConfig, pool: ConnectionPool) -> Self {
+        let mut conditions: Vec<Arc<dyn PruneCondition>> = vec![
+            Arc::new(L1BatchExecutedCondition { pool: pool.clone() }),
+            Arc::new(NextL1BatchHasMetadataCondition { pool: pool.clone() }),
+        ];
+        if let Some(minimum_age) = config.minimum_l1_batch_age {
+            conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
+                minimum_age,
+                pool: pool.clone(),
+            }));
+        }
+        if let Some(retained_l1_batches) = config.retained_l1_batches {
+            conditions.push(Arc::new(RetainedL1BatchesPruneCondition {
+                retained_l1_batches,
+                pool: pool.clone(),
+            }));
+        }
+        Self::with_conditions(config, pool, conditions)
+    }
+
+    fn with_conditions(
+        config: DbPrunerConfig,
+        pool: ConnectionPool,
+        prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    ) -> Self {
+        Self {
+            config,
+            pool,
+            health_updater: ReactiveHealthCheck::new("db_pruner").1,
+            prune_conditions,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn is_l1_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        for condition in &self.prune_conditions {
+            if !condition.is_batch_prunable(l1_batch_number).await? {
+                tracing::debug!(
+                    "L1 batch #{l1_batch_number} is not prunable: condition `{condition}` is not met"
+                );
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn update_health(&self, info: PruningInfo) {
+        let health = Health::from(HealthStatus::Ready).with_details(DbPrunerHealth::from(info));
+        self.health_updater.update(health);
+    }
+
+    async fn soft_prune(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let next_l1_batch_to_prune =
+            if let Some(l1_batch) = current_pruning_info.last_soft_pruned_l1_batch {
+                l1_batch + 1
+            } else {
+                let earliest_l1_batch = storage.blocks_dal().get_earliest_l1_batch_number().await?;
+                let Some(earliest_l1_batch) = earliest_l1_batch else {
+                    return Ok(false);
+                };
+                earliest_l1_batch
+            };
+        // Conditions acquire connections from the same pool, which may be a singleton one.
+        drop(storage);
+
+        let last_l1_batch_to_prune = L1BatchNumber(
+            next_l1_batch_to_prune.0 + self.config.pruned_batch_chunk_size.max(1) - 1,
+        );
+        if !self.is_l1_batch_prunable(last_l1_batch_to_prune).await? {
+            return Ok(false);
+        }
+
+        // The pruner is the only component modifying pruning info, so it cannot change after the check above.
+        let latency = METRICS.pruning_chunk_duration[&PruneType::Soft].start();
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        let mut transaction = storage.start_transaction().await?;
+        let (_, last_miniblock_to_prune) = transaction
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(last_l1_batch_to_prune)
+            .await?
+            .with_context(|| format!("L1 batch #{last_l1_batch_to_prune} is ready to be pruned, but has no miniblocks"))?;
+        transaction
+            .pruning_dal()
+            .soft_prune_batches_range(last_l1_batch_to_prune, last_miniblock_to_prune)
+            .await?;
+        transaction.commit().await?;
+
+        let latency = latency.observe();
+        METRICS.last_pruned_l1_batch[&PruneType::Soft].set(last_l1_batch_to_prune.0.into());
+        tracing::info!(
+            "Soft pruned DB up to and including L1 batch #{last_l1_batch_to_prune}, miniblock #{last_miniblock_to_prune}, took {latency:?}"
+        );
+        Ok(true)
+    }
+
+    async fn hard_prune(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let latency = METRICS.pruning_chunk_duration[&PruneType::Hard].start();
+        let mut transaction = storage.start_transaction().await?;
+
+        let current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
+        let last_pruned_l1_batch = current_pruning_info
+            .last_soft_pruned_l1_batch
+            .context("no soft-pruned L1 batches to hard-prune")?;
+        let last_pruned_miniblock = current_pruning_info
+            .last_soft_pruned_miniblock
+            .context("no soft-pruned miniblocks to hard-prune")?;
+
+        let stats = transaction
+            .pruning_dal()
+            .hard_prune_batches_range(last_pruned_l1_batch, last_pruned_miniblock)
+            .await?;
+        transaction.commit().await?;
+
+        let latency = latency.observe();
+        METRICS.last_pruned_l1_batch[&PruneType::Hard].set(last_pruned_l1_batch.0.into());
+        METRICS.observe_hard_pruning(stats);
+        tracing::info!(
+            "Hard pruned DB up to and including L1 batch #{last_pruned_l1_batch}, miniblock #{last_pruned_miniblock}, took {latency:?}"
+        );
+        Ok(())
+    }
+
+    async fn run_single_iteration(
+        &self,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<PruningIterationOutcome> {
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        drop(storage);
+        self.update_health(current_pruning_info);
+
+        // If this `if` is not entered, it means that the node has restarted after soft pruning.
+        if current_pruning_info.last_soft_pruned_l1_batch
+            == current_pruning_info.last_hard_pruned_l1_batch
+        {
+            let pruning_done = self.soft_prune().await?;
+            if !pruning_done {
+                return Ok(PruningIterationOutcome::NoOp);
+            }
+        }
+
+        if tokio::time::timeout(self.config.removal_delay, stop_receiver.changed())
+            .await
+            .is_ok()
+        {
+            return Ok(PruningIterationOutcome::Interrupted);
+        }
+
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        self.hard_prune(&mut storage).await?;
+        let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        self.update_health(current_pruning_info);
+        Ok(PruningIterationOutcome::Pruned)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            let outcome = match self.run_single_iteration(&mut stop_receiver).await {
+                Err(err) => {
+                    // As this component is not really mission-critical, all errors are generally ignored
+                    tracing::warn!(
+                        "Pruning error, retrying in {:?}, error was: {err:?}",
+                        self.config.next_iterations_delay
+                    );
+                    let health =
+                        Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+                            "error": err.to_string(),
+                        }));
+                    self.health_updater.update(health);
+                    PruningIterationOutcome::NoOp
+                }
+                Ok(outcome) => outcome,
+            };
+
+            if matches!(outcome, PruningIterationOutcome::NoOp)
+                && tokio::time::timeout(self.config.next_iterations_delay, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, shutting down DB pruning");
+        Ok(())
+    }
+}
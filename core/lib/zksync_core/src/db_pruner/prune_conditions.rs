@@ -0,0 +1,118 @@
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use zksync_dal::ConnectionPool;
+use zksync_types::L1BatchNumber;
+
+/// Condition that must hold for an L1 batch to be pruned.
+#[async_trait]
+pub(super) trait PruneCondition: fmt::Debug + fmt::Display + Send + Sync + 'static {
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool>;
+}
+
+/// Checks that the L1 batch is older than the configured retention age.
+#[derive(Debug)]
+pub(super) struct L1BatchOlderThanPruneCondition {
+    pub minimum_age: Duration,
+    pub pool: ConnectionPool,
+}
+
+impl fmt::Display for L1BatchOlderThanPruneCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "L1 batch is older than {:?}", self.minimum_age)
+    }
+}
+
+#[async_trait]
+impl PruneCondition for L1BatchOlderThanPruneCondition {
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        let l1_batch_header = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await?;
+        let is_old_enough = l1_batch_header.is_some_and(|header| {
+            let age = (Utc::now().timestamp() as u64).saturating_sub(header.timestamp);
+            age >= self.minimum_age.as_secs()
+        });
+        Ok(is_old_enough)
+    }
+}
+
+/// Checks that at least the configured number of L1 batches is retained after the L1 batch.
+#[derive(Debug)]
+pub(super) struct RetainedL1BatchesPruneCondition {
+    pub retained_l1_batches: u32,
+    pub pool: ConnectionPool,
+}
+
+impl fmt::Display for RetainedL1BatchesPruneCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "at least {} newer L1 batches are retained",
+            self.retained_l1_batches
+        )
+    }
+}
+
+#[async_trait]
+impl PruneCondition for RetainedL1BatchesPruneCondition {
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        let sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+        Ok(sealed_l1_batch.is_some_and(|sealed| {
+            sealed.0.saturating_sub(l1_batch_number.0) >= self.retained_l1_batches
+        }))
+    }
+}
+
+/// Checks that the next L1 batch has metadata, i.e. the L1 batch and all its predecessors are processed
+/// by the Merkle tree and don't need to be accessed by it again.
+#[derive(Debug)]
+pub(super) struct NextL1BatchHasMetadataCondition {
+    pub pool: ConnectionPool,
+}
+
+impl fmt::Display for NextL1BatchHasMetadataCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("next L1 batch has metadata")
+    }
+}
+
+#[async_trait]
+impl PruneCondition for NextL1BatchHasMetadataCondition {
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        let last_l1_batch_with_metadata = storage
+            .blocks_dal()
+            .get_last_l1_batch_number_with_metadata()
+            .await?;
+        Ok(last_l1_batch_with_metadata.is_some_and(|number| number > l1_batch_number))
+    }
+}
+
+/// Checks that the L1 batch is executed on L1, so that it cannot be reverted.
+#[derive(Debug)]
+pub(super) struct L1BatchExecutedCondition {
+    pub pool: ConnectionPool,
+}
+
+impl fmt::Display for L1BatchExecutedCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("L1 batch is executed on L1")
+    }
+}
+
+#[async_trait]
+impl PruneCondition for L1BatchExecutedCondition {
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage_tagged("db_pruner").await?;
+        let last_executed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?;
+        Ok(last_executed_l1_batch.is_some_and(|number| number >= l1_batch_number))
+    }
+}
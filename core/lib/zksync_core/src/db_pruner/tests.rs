@@ -0,0 +1,364 @@
+use std::{collections::HashMap, fmt};
+
+use test_casing::test_casing;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, MiniblockNumber, ProtocolVersion, H256,
+};
+
+use super::*;
+use crate::utils::testonly::{create_l1_batch, create_l1_batch_metadata, create_miniblock};
+
+#[derive(Debug)]
+struct ConditionMock {
+    name: &'static str,
+    is_batch_prunable_responses: HashMap<L1BatchNumber, bool>,
+}
+
+impl ConditionMock {
+    fn name(name: &'static str) -> Self {
+        Self {
+            name,
+            is_batch_prunable_responses: HashMap::default(),
+        }
+    }
+
+    fn with_response(mut self, l1_batch_number: L1BatchNumber, value: bool) -> Self {
+        self.is_batch_prunable_responses
+            .insert(l1_batch_number, value);
+        self
+    }
+}
+
+impl fmt::Display for ConditionMock {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.name)
+    }
+}
+
+#[async_trait::async_trait]
+impl PruneCondition for ConditionMock {
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        self.is_batch_prunable_responses
+            .get(&l1_batch_number)
+            .copied()
+            .with_context(|| format!("unexpected L1 batch #{l1_batch_number}"))
+    }
+}
+
+fn test_config(pruned_batch_chunk_size: u32) -> DbPrunerConfig {
+    DbPrunerConfig {
+        removal_delay: Duration::ZERO,
+        next_iterations_delay: Duration::ZERO,
+        pruned_batch_chunk_size,
+        minimum_l1_batch_age: None,
+        retained_l1_batches: None,
+    }
+}
+
+async fn insert_l1_batches(storage: &mut StorageProcessor<'_>, count: u32) {
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(ProtocolVersion::default())
+        .await;
+    for number in 0..count {
+        storage
+            .blocks_dal()
+            .insert_miniblock(&create_miniblock(number))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(number))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn is_l1_batch_prunable_works() {
+    let failing_condition: Arc<dyn PruneCondition> = Arc::new(
+        ConditionMock::name("some failing some passing")
+            .with_response(L1BatchNumber(1), true)
+            .with_response(L1BatchNumber(2), true)
+            .with_response(L1BatchNumber(3), false)
+            .with_response(L1BatchNumber(4), true),
+    );
+    let other_failing_condition: Arc<dyn PruneCondition> = Arc::new(
+        ConditionMock::name("some failing some passing2")
+            .with_response(L1BatchNumber(1), true)
+            .with_response(L1BatchNumber(2), false)
+            .with_response(L1BatchNumber(4), true),
+    );
+    let pruner = DbPruner::with_conditions(
+        test_config(1),
+        ConnectionPool::test_pool().await,
+        vec![failing_condition, other_failing_condition],
+    );
+
+    assert!(pruner.is_l1_batch_prunable(L1BatchNumber(1)).await.unwrap());
+    assert!(!pruner.is_l1_batch_prunable(L1BatchNumber(2)).await.unwrap());
+    // Conditions are checked in order, so the second condition is not queried for L1 batch #3.
+    assert!(!pruner.is_l1_batch_prunable(L1BatchNumber(3)).await.unwrap());
+    assert!(pruner.is_l1_batch_prunable(L1BatchNumber(4)).await.unwrap());
+    pruner
+        .is_l1_batch_prunable(L1BatchNumber(5))
+        .await
+        .unwrap_err();
+}
+
+#[test_casing(3, [1, 2, 3])]
+#[tokio::test]
+async fn pruning_chunks(chunk_size: u32) {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    insert_l1_batches(&mut storage, 10).await;
+
+    let condition: Arc<dyn PruneCondition> = Arc::new(
+        ConditionMock::name("all passing")
+            .with_response(L1BatchNumber(chunk_size - 1), true)
+            .with_response(L1BatchNumber(2 * chunk_size - 1), true),
+    );
+    let pruner = DbPruner::with_conditions(test_config(chunk_size), pool.clone(), vec![condition]);
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+
+    for iteration in 1..=2 {
+        let outcome = pruner
+            .run_single_iteration(&mut stop_receiver)
+            .await
+            .unwrap();
+        assert_eq!(outcome, PruningIterationOutcome::Pruned);
+
+        let last_pruned_number = iteration * chunk_size - 1;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+        assert_eq!(
+            pruning_info,
+            PruningInfo {
+                last_soft_pruned_l1_batch: Some(L1BatchNumber(last_pruned_number)),
+                last_soft_pruned_miniblock: Some(MiniblockNumber(last_pruned_number)),
+                last_hard_pruned_l1_batch: Some(L1BatchNumber(last_pruned_number)),
+                last_hard_pruned_miniblock: Some(MiniblockNumber(last_pruned_number)),
+            }
+        );
+
+        let earliest_l1_batch = storage
+            .blocks_dal()
+            .get_earliest_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(
+            earliest_l1_batch,
+            Some(L1BatchNumber(last_pruned_number + 1))
+        );
+        let pruned_miniblock = storage
+            .blocks_dal()
+            .get_miniblock_header(MiniblockNumber(last_pruned_number))
+            .await
+            .unwrap();
+        assert!(pruned_miniblock.is_none());
+    }
+
+    // The next chunk is not prunable since the condition has no response for it.
+    pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test]
+async fn pruning_is_noop_if_condition_is_not_met() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    insert_l1_batches(&mut storage, 5).await;
+
+    let condition: Arc<dyn PruneCondition> =
+        Arc::new(ConditionMock::name("not passing").with_response(L1BatchNumber(1), false));
+    let pruner = DbPruner::with_conditions(test_config(2), pool.clone(), vec![condition]);
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(outcome, PruningIterationOutcome::NoOp);
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(pruning_info, PruningInfo::default());
+}
+
+#[tokio::test]
+async fn hard_pruning_is_resumed_after_restart() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    insert_l1_batches(&mut storage, 5).await;
+    storage
+        .pruning_dal()
+        .soft_prune_batches_range(L1BatchNumber(2), MiniblockNumber(2))
+        .await
+        .unwrap();
+
+    // The condition must not be queried since the pruner should hard-prune the already soft-pruned batches.
+    let condition: Arc<dyn PruneCondition> = Arc::new(ConditionMock::name("unused"));
+    let pruner = DbPruner::with_conditions(test_config(2), pool.clone(), vec![condition]);
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(outcome, PruningIterationOutcome::Pruned);
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_hard_pruned_l1_batch,
+        Some(L1BatchNumber(2))
+    );
+    assert_eq!(
+        pruning_info.last_hard_pruned_miniblock,
+        Some(MiniblockNumber(2))
+    );
+}
+
+#[tokio::test]
+async fn pruner_is_interrupted_during_removal_delay() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    insert_l1_batches(&mut storage, 5).await;
+
+    let condition: Arc<dyn PruneCondition> =
+        Arc::new(ConditionMock::name("passing").with_response(L1BatchNumber(1), true));
+    let config = DbPrunerConfig {
+        removal_delay: Duration::from_secs(3_600),
+        ..test_config(2)
+    };
+    let pruner = DbPruner::with_conditions(config, pool.clone(), vec![condition]);
+    let (stop_sender, mut stop_receiver) = watch::channel(false);
+    stop_sender.send_replace(true);
+
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(outcome, PruningIterationOutcome::Interrupted);
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_soft_pruned_l1_batch,
+        Some(L1BatchNumber(1))
+    );
+    assert_eq!(pruning_info.last_hard_pruned_l1_batch, None);
+}
+
+async fn mark_l1_batches_as_processed(
+    storage: &mut StorageProcessor<'_>,
+    numbers_with_metadata: impl IntoIterator<Item = u32>,
+    executed_numbers: impl IntoIterator<Item = u32>,
+) {
+    for number in numbers_with_metadata {
+        storage
+            .blocks_dal()
+            .save_l1_batch_tree_data(
+                L1BatchNumber(number),
+                &create_l1_batch_metadata(number).tree_data(),
+            )
+            .await
+            .unwrap();
+    }
+    for number in executed_numbers {
+        storage
+            .eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(number),
+                AggregatedActionType::Execute,
+                H256::from_low_u64_be(number.into()),
+                chrono::Utc::now(),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn real_conditions_on_singleton_pool() {
+    let pool = ConnectionPool::constrained_test_pool(1).await;
+    let mut storage = pool.access_storage().await.unwrap();
+    insert_l1_batches(&mut storage, 5).await;
+    mark_l1_batches_as_processed(&mut storage, 0..4, 0..2).await;
+    drop(storage);
+
+    let condition = L1BatchExecutedCondition { pool: pool.clone() };
+    assert!(condition.is_batch_prunable(L1BatchNumber(1)).await.unwrap());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(2)).await.unwrap());
+
+    let condition = NextL1BatchHasMetadataCondition { pool: pool.clone() };
+    assert!(condition.is_batch_prunable(L1BatchNumber(2)).await.unwrap());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(3)).await.unwrap());
+
+    let condition = RetainedL1BatchesPruneCondition {
+        retained_l1_batches: 2,
+        pool: pool.clone(),
+    };
+    assert!(condition.is_batch_prunable(L1BatchNumber(2)).await.unwrap());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(3)).await.unwrap());
+
+    // Mock L1 batches have timestamps close to the UNIX epoch; non-existing batches are not prunable.
+    let condition = L1BatchOlderThanPruneCondition {
+        minimum_age: Duration::from_secs(3_600),
+        pool: pool.clone(),
+    };
+    assert!(condition.is_batch_prunable(L1BatchNumber(4)).await.unwrap());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(5)).await.unwrap());
+}
+
+#[tokio::test]
+async fn pruning_with_real_conditions_on_singleton_pool() {
+    let pool = ConnectionPool::constrained_test_pool(1).await;
+    let mut storage = pool.access_storage().await.unwrap();
+    insert_l1_batches(&mut storage, 5).await;
+    mark_l1_batches_as_processed(&mut storage, 0..4, 0..2).await;
+    drop(storage);
+
+    let config = DbPrunerConfig {
+        minimum_l1_batch_age: Some(Duration::from_secs(3_600)),
+        retained_l1_batches: Some(1),
+        ..test_config(2)
+    };
+    let pruner = DbPruner::new(config, pool.clone());
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(outcome, PruningIterationOutcome::Pruned);
+    let mut storage = pool.access_storage().await.unwrap();
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_hard_pruned_l1_batch,
+        Some(L1BatchNumber(1))
+    );
+    drop(storage);
+
+    // L1 batch #3 is not executed yet.
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(outcome, PruningIterationOutcome::NoOp);
+
+    let mut storage = pool.access_storage().await.unwrap();
+    mark_l1_batches_as_processed(&mut storage, [4], 2..4).await;
+    drop(storage);
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(outcome, PruningIterationOutcome::Pruned);
+    let mut storage = pool.access_storage().await.unwrap();
+    let pruning_info = storage.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(
+        pruning_info.last_hard_pruned_l1_batch,
+        Some(L1BatchNumber(3))
+    );
+}
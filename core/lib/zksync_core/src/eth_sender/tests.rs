@@ -8,7 +8,7 @@ use zksync_config::{
     ContractsConfig, ETHSenderConfig, GasAdjusterConfig,
 };
 use zksync_dal::{ConnectionPool, StorageProcessor};
-use zksync_eth_client::{clients::MockEthereum, EthInterface};
+use zksync_eth_client::{clients::MockEthereum, BaseFees, EthInterface};
 use zksync_l1_contract_interface::i_executor::methods::{
     CommitBatches, ExecuteBatches, ProveBatches,
 };
@@ -76,6 +76,10 @@ impl EthSenderTester {
                     std::iter::repeat(0)
                         .take(Self::WAIT_CONFIRMATIONS as usize)
                         .chain(history)
+                        .map(|base_fee_per_gas| BaseFees {
+                            base_fee_per_gas,
+                            base_fee_per_blob_gas: 0.into(),
+                        })
                         .collect(),
                 )
                 .with_non_ordering_confirmation(non_ordering_confirmations)
@@ -88,10 +92,12 @@ impl EthSenderTester {
                 gateway.clone(),
                 GasAdjusterConfig {
                     max_base_fee_samples: Self::MAX_BASE_FEE_SAMPLES,
+                    max_blob_base_fee_samples: Self::MAX_BASE_FEE_SAMPLES,
                     pricing_formula_parameter_a: 3.0,
                     pricing_formula_parameter_b: 2.0,
                     ..eth_sender_config.gas_adjuster
                 },
+                eth_sender_config.sender.pubdata_sending_mode,
             )
             .await
             .unwrap(),
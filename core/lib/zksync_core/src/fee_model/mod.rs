@@ -8,10 +8,17 @@ use zksync_types::{
     },
     U256,
 };
-use zksync_utils::ceil_div_u256;
+use zksync_utils::{ceil_div_u256, time::seconds_since_epoch};
 
+pub use self::strategy::{
+    new_fee_input_strategy, DefaultFeeInputStrategy, EmaFeeInputStrategy, FeeInputStrategy,
+    PubdataSurgeFeeInputStrategy,
+};
 use crate::l1_gas_price::GasAdjuster;
 
+pub mod simulator;
+mod strategy;
+
 /// Trait responsible for providing fee info for a batch
 #[async_trait::async_trait]
 pub trait BatchFeeModelInputProvider: fmt::Debug + 'static + Send + Sync {
@@ -23,20 +30,11 @@ pub trait BatchFeeModelInputProvider: fmt::Debug + 'static + Send + Sync {
         l1_pubdata_price_scale_factor: f64,
     ) -> BatchFeeInput {
         let params = self.get_fee_model_params();
-
-        match params {
-            FeeParams::V1(params) => BatchFeeInput::L1Pegged(compute_batch_fee_model_input_v1(
-                params,
-                l1_gas_price_scale_factor,
-            )),
-            FeeParams::V2(params) => {
-                BatchFeeInput::PubdataIndependent(compute_batch_fee_model_input_v2(
-                    params,
-                    l1_gas_price_scale_factor,
-                    l1_pubdata_price_scale_factor,
-                ))
-            }
-        }
+        compute_batch_fee_input(
+            params,
+            l1_gas_price_scale_factor,
+            l1_pubdata_price_scale_factor,
+        )
     }
 
     /// Returns the batch fee input as-is, i.e. without any scaling for the L1 gas and pubdata prices.
@@ -55,9 +53,24 @@ pub trait BatchFeeModelInputProvider: fmt::Debug + 'static + Send + Sync {
 pub struct MainNodeFeeInputProvider {
     provider: Arc<GasAdjuster>,
     config: FeeModelConfig,
+    strategy: Arc<dyn FeeInputStrategy>,
 }
 
+#[async_trait::async_trait]
 impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
+    async fn get_batch_fee_input_scaled(
+        &self,
+        l1_gas_price_scale_factor: f64,
+        l1_pubdata_price_scale_factor: f64,
+    ) -> BatchFeeInput {
+        self.strategy.compute_batch_fee_input(
+            self.get_fee_model_params(),
+            l1_gas_price_scale_factor,
+            l1_pubdata_price_scale_factor,
+            seconds_since_epoch(),
+        )
+    }
+
     fn get_fee_model_params(&self) -> FeeParams {
         match self.config {
             FeeModelConfig::V1(config) => FeeParams::V1(FeeParamsV1 {
@@ -75,7 +88,18 @@ impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
 
 impl MainNodeFeeInputProvider {
     pub fn new(provider: Arc<GasAdjuster>, config: FeeModelConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            strategy: Arc::new(DefaultFeeInputStrategy),
+        }
+    }
+
+    /// Sets the strategy used to derive the batch fee input from the fee model params.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: Arc<dyn FeeInputStrategy>) -> Self {
+        self.strategy = strategy;
+        self
     }
 }
 
@@ -131,6 +155,28 @@ impl BatchFeeModelInputProvider for ApiFeeInputProvider {
     }
 }
 
+/// Calculates the batch fee input based on the fee model params as prescribed by the fee model version.
+/// This is the default way to compute the fee input; [`FeeInputStrategy`] implementations may use it as a building block.
+pub fn compute_batch_fee_input(
+    params: FeeParams,
+    l1_gas_price_scale_factor: f64,
+    l1_pubdata_price_scale_factor: f64,
+) -> BatchFeeInput {
+    match params {
+        FeeParams::V1(params) => BatchFeeInput::L1Pegged(compute_batch_fee_model_input_v1(
+            params,
+            l1_gas_price_scale_factor,
+        )),
+        FeeParams::V2(params) => {
+            BatchFeeInput::PubdataIndependent(compute_batch_fee_model_input_v2(
+                params,
+                l1_gas_price_scale_factor,
+                l1_pubdata_price_scale_factor,
+            ))
+        }
+    }
+}
+
 /// Calculates the batch fee input based on the main node parameters.
 /// This function uses the `V1` fee model, i.e. where the pubdata price does not include the proving costs.
 fn compute_batch_fee_model_input_v1(
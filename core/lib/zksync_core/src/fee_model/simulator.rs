@@ -0,0 +1,245 @@
+//! Offline fee simulator allowing to estimate how a fee input strategy would have performed on historical data.
+
+use serde::{Deserialize, Serialize};
+use zksync_config::configs::{chain::StateKeeperConfig, eth_sender::PubdataSendingMode};
+use zksync_system_constants::L1_GAS_PER_PUBDATA_BYTE;
+use zksync_types::{
+    fee_model::{
+        FeeInputStrategyConfig, FeeModelConfig, FeeParams, FeeParamsV1, FeeParamsV2,
+        L1BatchFeeStats,
+    },
+    L1BatchNumber,
+};
+
+use super::new_fee_input_strategy;
+
+/// L1 fee sample used as an input for the simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct L1FeeSample {
+    /// UNIX timestamp of the sample, in seconds.
+    pub timestamp: u64,
+    pub base_fee_per_gas: u64,
+    /// Base fee per blob gas. Only used if pubdata is sent using blobs.
+    #[serde(default)]
+    pub base_fee_per_blob_gas: u64,
+}
+
+/// Simulation results for a single L1 batch. All prices and amounts are in wei.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchFeeSimulation {
+    pub number: L1BatchNumber,
+    pub timestamp: u64,
+    pub l1_gas_price: u64,
+    pub l1_pubdata_price: u64,
+    pub fair_l2_gas_price: u64,
+    pub fair_pubdata_price: u64,
+    /// Fees charged from users for the batch.
+    pub revenue: u128,
+    /// Estimated operator costs for the batch (batch overhead, publishing pubdata and proving).
+    pub cost: u128,
+    /// Difference between `revenue` and `cost`.
+    pub margin: i128,
+}
+
+/// Aggregated results of the fee simulation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FeeSimulationReport {
+    pub batches: Vec<BatchFeeSimulation>,
+    /// Numbers of batches skipped because there were no L1 fee samples preceding them.
+    pub skipped_batches: Vec<L1BatchNumber>,
+    pub total_revenue: u128,
+    pub total_cost: u128,
+    pub total_margin: i128,
+}
+
+/// Replays historical L1 fees and L1 batch stats through a fee input strategy to estimate revenue, costs and margins
+/// that the operator would have had if the strategy was used.
+#[derive(Debug, Clone)]
+pub struct FeeSimulator {
+    fee_model_config: FeeModelConfig,
+    strategy_config: FeeInputStrategyConfig,
+    pubdata_sending_mode: PubdataSendingMode,
+    batch_overhead_l1_gas: u64,
+    minimal_l2_gas_price: u64,
+}
+
+impl FeeSimulator {
+    pub fn new(
+        state_keeper_config: &StateKeeperConfig,
+        pubdata_sending_mode: PubdataSendingMode,
+    ) -> Self {
+        Self {
+            fee_model_config: FeeModelConfig::from_state_keeper_config(state_keeper_config),
+            strategy_config: FeeInputStrategyConfig::from_state_keeper_config(state_keeper_config),
+            pubdata_sending_mode,
+            batch_overhead_l1_gas: state_keeper_config.batch_overhead_l1_gas,
+            minimal_l2_gas_price: state_keeper_config.minimal_l2_gas_price,
+        }
+    }
+
+    /// Overrides the fee input strategy taken from the state keeper config.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy_config: FeeInputStrategyConfig) -> Self {
+        self.strategy_config = strategy_config;
+        self
+    }
+
+    /// Runs the simulation. `l1_fees` must be sorted by timestamp; each batch is priced using the latest sample
+    /// not newer than the batch. The strategy state is reset for each call.
+    pub fn simulate(
+        &self,
+        l1_fees: &[L1FeeSample],
+        batches: &[L1BatchFeeStats],
+    ) -> FeeSimulationReport {
+        let strategy = new_fee_input_strategy(self.strategy_config);
+        let mut report = FeeSimulationReport::default();
+
+        for batch in batches {
+            let sample_count =
+                l1_fees.partition_point(|sample| sample.timestamp <= batch.timestamp);
+            let Some(sample) = sample_count.checked_sub(1).map(|idx| &l1_fees[idx]) else {
+                report.skipped_batches.push(batch.number);
+                continue;
+            };
+
+            let l1_gas_price = sample.base_fee_per_gas;
+            let l1_pubdata_price = match self.pubdata_sending_mode {
+                PubdataSendingMode::Calldata => {
+                    l1_gas_price.saturating_mul(L1_GAS_PER_PUBDATA_BYTE.into())
+                }
+                PubdataSendingMode::Blobs => sample.base_fee_per_blob_gas,
+            };
+            let params = match self.fee_model_config {
+                FeeModelConfig::V1(config) => FeeParams::V1(FeeParamsV1 {
+                    config,
+                    l1_gas_price,
+                }),
+                FeeModelConfig::V2(config) => FeeParams::V2(FeeParamsV2 {
+                    config,
+                    l1_gas_price,
+                    l1_pubdata_price,
+                }),
+            };
+            let fee_input = strategy
+                .compute_batch_fee_input(params, 1.0, 1.0, batch.timestamp)
+                .into_pubdata_independent();
+
+            let compute_gas = u128::from(batch.computational_gas_used);
+            let pubdata = u128::from(batch.pubdata_published);
+            let revenue = compute_gas * u128::from(fee_input.fair_l2_gas_price)
+                + pubdata * u128::from(fee_input.fair_pubdata_price);
+            let cost = u128::from(self.batch_overhead_l1_gas) * u128::from(l1_gas_price)
+                + pubdata * u128::from(l1_pubdata_price)
+                + compute_gas * u128::from(self.minimal_l2_gas_price);
+            let margin = revenue as i128 - cost as i128;
+
+            report.total_revenue += revenue;
+            report.total_cost += cost;
+            report.total_margin += margin;
+            report.batches.push(BatchFeeSimulation {
+                number: batch.number,
+                timestamp: batch.timestamp,
+                l1_gas_price,
+                l1_pubdata_price,
+                fair_l2_gas_price: fee_input.fair_l2_gas_price,
+                fair_pubdata_price: fee_input.fair_pubdata_price,
+                revenue,
+                cost,
+                margin,
+            });
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::chain::{FeeInputStrategyKind, FeeModelVersion};
+
+    use super::*;
+
+    fn state_keeper_config() -> StateKeeperConfig {
+        StateKeeperConfig {
+            fee_model_version: FeeModelVersion::V2,
+            minimal_l2_gas_price: 100,
+            compute_overhead_part: 0.0,
+            pubdata_overhead_part: 0.0,
+            batch_overhead_l1_gas: 1_000,
+            ..StateKeeperConfig::for_tests()
+        }
+    }
+
+    fn batch(number: u32, timestamp: u64) -> L1BatchFeeStats {
+        L1BatchFeeStats {
+            number: L1BatchNumber(number),
+            timestamp,
+            computational_gas_used: 1_000,
+            pubdata_published: 100,
+        }
+    }
+
+    const L1_FEES: [L1FeeSample; 2] = [
+        L1FeeSample {
+            timestamp: 100,
+            base_fee_per_gas: 10,
+            base_fee_per_blob_gas: 1,
+        },
+        L1FeeSample {
+            timestamp: 200,
+            base_fee_per_gas: 20,
+            base_fee_per_blob_gas: 2,
+        },
+    ];
+
+    #[test]
+    fn simulating_with_calldata() {
+        let simulator = FeeSimulator::new(&state_keeper_config(), PubdataSendingMode::Calldata);
+        let report = simulator.simulate(&L1_FEES, &[batch(1, 50), batch(2, 150), batch(3, 200)]);
+
+        assert_eq!(report.skipped_batches, [L1BatchNumber(1)]);
+        assert_eq!(report.batches.len(), 2);
+        let first = &report.batches[0];
+        assert_eq!(first.number, L1BatchNumber(2));
+        assert_eq!(first.l1_gas_price, 10);
+        assert_eq!(first.l1_pubdata_price, 170);
+        assert_eq!(first.fair_l2_gas_price, 100);
+        assert_eq!(first.fair_pubdata_price, 170);
+        assert_eq!(first.revenue, 1_000 * 100 + 100 * 170);
+        assert_eq!(first.cost, 1_000 * 10 + 100 * 170 + 1_000 * 100);
+        assert_eq!(first.margin, -10_000);
+
+        let second = &report.batches[1];
+        assert_eq!(second.l1_gas_price, 20);
+        assert_eq!(second.margin, -20_000);
+        assert_eq!(report.total_margin, -30_000);
+        assert_eq!(
+            report.total_revenue as i128 - report.total_cost as i128,
+            report.total_margin
+        );
+    }
+
+    #[test]
+    fn simulating_with_blobs_and_surge_strategy() {
+        let config = StateKeeperConfig {
+            fee_input_strategy: FeeInputStrategyKind::PubdataSurge,
+            fee_input_pubdata_surge_threshold: Some(1),
+            fee_input_pubdata_surge_multiplier: Some(101.0),
+            ..state_keeper_config()
+        };
+        let simulator = FeeSimulator::new(&config, PubdataSendingMode::Blobs);
+        let report = simulator.simulate(&L1_FEES, &[batch(1, 250)]);
+
+        assert!(report.skipped_batches.is_empty());
+        let simulated = &report.batches[0];
+        assert_eq!(simulated.l1_pubdata_price, 2);
+        assert_eq!(simulated.fair_pubdata_price, 102);
+        assert_eq!(simulated.revenue, 1_000 * 100 + 100 * 102);
+        assert_eq!(simulated.cost, 1_000 * 20 + 100 * 2 + 1_000 * 100);
+        assert_eq!(simulated.margin, -10_000);
+
+        let report = simulator
+            .with_strategy(FeeInputStrategyConfig::Default)
+            .simulate(&L1_FEES, &[batch(1, 250)]);
+        assert_eq!(report.batches[0].fair_pubdata_price, 2);
+    }
+}
@@ -0,0 +1,299 @@
+//! Strategies deriving the batch fee input from the fee model params.
+
+use std::{fmt, sync::Arc};
+
+use zksync_types::fee_model::{
+    BatchFeeInput, FeeInputStrategyConfig, FeeParams, FeeParamsV1, FeeParamsV2,
+};
+
+use super::compute_batch_fee_input;
+
+/// Strategy used by [`MainNodeFeeInputProvider`](super::MainNodeFeeInputProvider) to derive the batch fee input
+/// from the fee model params. Strategies may be stateful (e.g., to smooth out L1 price spikes over time).
+pub trait FeeInputStrategy: fmt::Debug + Send + Sync + 'static {
+    /// Computes the batch fee input for the provided `params` at the specified UNIX `timestamp` (in seconds).
+    fn compute_batch_fee_input(
+        &self,
+        params: FeeParams,
+        l1_gas_price_scale_factor: f64,
+        l1_pubdata_price_scale_factor: f64,
+        timestamp: u64,
+    ) -> BatchFeeInput;
+}
+
+/// Creates a fee input strategy based on the provided config.
+pub fn new_fee_input_strategy(config: FeeInputStrategyConfig) -> Arc<dyn FeeInputStrategy> {
+    match config {
+        FeeInputStrategyConfig::Default => Arc::new(DefaultFeeInputStrategy),
+        FeeInputStrategyConfig::Ema { half_life_sec } => {
+            Arc::new(EmaFeeInputStrategy::new(half_life_sec))
+        }
+        FeeInputStrategyConfig::PubdataSurge {
+            threshold,
+            multiplier,
+        } => Arc::new(PubdataSurgeFeeInputStrategy::new(threshold, multiplier)),
+    }
+}
+
+/// Strategy using the current L1 prices as-is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFeeInputStrategy;
+
+impl FeeInputStrategy for DefaultFeeInputStrategy {
+    fn compute_batch_fee_input(
+        &self,
+        params: FeeParams,
+        l1_gas_price_scale_factor: f64,
+        l1_pubdata_price_scale_factor: f64,
+        _timestamp: u64,
+    ) -> BatchFeeInput {
+        compute_batch_fee_input(
+            params,
+            l1_gas_price_scale_factor,
+            l1_pubdata_price_scale_factor,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EmaState {
+    timestamp: u64,
+    l1_gas_price: f64,
+    l1_pubdata_price: f64,
+}
+
+/// Strategy smoothing the L1 prices using the exponential moving average (EMA). The weight of a new observation
+/// depends on the time elapsed since the previous one, so that the weight of an observation halves every `half_life_sec` seconds.
+#[derive(Debug)]
+pub struct EmaFeeInputStrategy {
+    half_life_sec: u64,
+    state: std::sync::Mutex<Option<EmaState>>,
+}
+
+impl EmaFeeInputStrategy {
+    pub fn new(half_life_sec: u64) -> Self {
+        Self {
+            half_life_sec,
+            state: std::sync::Mutex::default(),
+        }
+    }
+
+    /// Updates the EMA with the provided observation and returns the updated `(l1_gas_price, l1_pubdata_price)`.
+    fn update(&self, timestamp: u64, l1_gas_price: u64, l1_pubdata_price: u64) -> (u64, u64) {
+        let mut state = self.state.lock().expect("EMA state is poisoned");
+        let new_state = match *state {
+            None => EmaState {
+                timestamp,
+                l1_gas_price: l1_gas_price as f64,
+                l1_pubdata_price: l1_pubdata_price as f64,
+            },
+            // Observations with non-increasing timestamps don't affect the average; this makes the strategy
+            // robust to several fee inputs being requested within the same second.
+            Some(prev) if timestamp <= prev.timestamp => prev,
+            Some(prev) => {
+                let weight = if self.half_life_sec == 0 {
+                    1.0
+                } else {
+                    let elapsed = (timestamp - prev.timestamp) as f64;
+                    1.0 - 0.5_f64.powf(elapsed / self.half_life_sec as f64)
+                };
+                EmaState {
+                    timestamp,
+                    l1_gas_price: prev.l1_gas_price
+                        + weight * (l1_gas_price as f64 - prev.l1_gas_price),
+                    l1_pubdata_price: prev.l1_pubdata_price
+                        + weight * (l1_pubdata_price as f64 - prev.l1_pubdata_price),
+                }
+            }
+        };
+        *state = Some(new_state);
+        (
+            new_state.l1_gas_price.round() as u64,
+            new_state.l1_pubdata_price.round() as u64,
+        )
+    }
+}
+
+impl FeeInputStrategy for EmaFeeInputStrategy {
+    fn compute_batch_fee_input(
+        &self,
+        params: FeeParams,
+        l1_gas_price_scale_factor: f64,
+        l1_pubdata_price_scale_factor: f64,
+        timestamp: u64,
+    ) -> BatchFeeInput {
+        let params = match params {
+            FeeParams::V1(params) => {
+                let (l1_gas_price, _) = self.update(timestamp, params.l1_gas_price, 0);
+                FeeParams::V1(FeeParamsV1 {
+                    l1_gas_price,
+                    ..params
+                })
+            }
+            FeeParams::V2(params) => {
+                let (l1_gas_price, l1_pubdata_price) =
+                    self.update(timestamp, params.l1_gas_price, params.l1_pubdata_price);
+                FeeParams::V2(FeeParamsV2 {
+                    l1_gas_price,
+                    l1_pubdata_price,
+                    ..params
+                })
+            }
+        };
+        compute_batch_fee_input(
+            params,
+            l1_gas_price_scale_factor,
+            l1_pubdata_price_scale_factor,
+        )
+    }
+}
+
+/// Strategy applying a surcharge to the L1 pubdata price once it exceeds the configured threshold: the part of the price
+/// above `threshold` is multiplied by `multiplier`. Only affects the `V2` fee model.
+#[derive(Debug, Clone, Copy)]
+pub struct PubdataSurgeFeeInputStrategy {
+    threshold: u64,
+    multiplier: f64,
+}
+
+impl PubdataSurgeFeeInputStrategy {
+    pub fn new(threshold: u64, multiplier: f64) -> Self {
+        Self {
+            threshold,
+            multiplier,
+        }
+    }
+
+    fn adjust_pubdata_price(&self, l1_pubdata_price: u64) -> u64 {
+        let Some(excess) = l1_pubdata_price.checked_sub(self.threshold) else {
+            return l1_pubdata_price;
+        };
+        let adjusted_excess = (excess as f64 * self.multiplier) as u64; // saturates on overflow
+        self.threshold.saturating_add(adjusted_excess)
+    }
+}
+
+impl FeeInputStrategy for PubdataSurgeFeeInputStrategy {
+    fn compute_batch_fee_input(
+        &self,
+        params: FeeParams,
+        l1_gas_price_scale_factor: f64,
+        l1_pubdata_price_scale_factor: f64,
+        _timestamp: u64,
+    ) -> BatchFeeInput {
+        let params = match params {
+            FeeParams::V1(_) => params,
+            FeeParams::V2(params) => FeeParams::V2(FeeParamsV2 {
+                l1_pubdata_price: self.adjust_pubdata_price(params.l1_pubdata_price),
+                ..params
+            }),
+        };
+        compute_batch_fee_input(
+            params,
+            l1_gas_price_scale_factor,
+            l1_pubdata_price_scale_factor,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::fee_model::{FeeModelConfigV1, FeeModelConfigV2};
+
+    use super::*;
+
+    const CONFIG_V2: FeeModelConfigV2 = FeeModelConfigV2 {
+        minimal_l2_gas_price: 100_000_000,
+        compute_overhead_part: 0.0,
+        pubdata_overhead_part: 0.0,
+        batch_overhead_l1_gas: 800_000,
+        max_gas_per_batch: 200_000_000,
+        max_pubdata_per_batch: 100_000,
+    };
+
+    fn params_v2(l1_gas_price: u64, l1_pubdata_price: u64) -> FeeParams {
+        FeeParams::V2(FeeParamsV2 {
+            config: CONFIG_V2,
+            l1_gas_price,
+            l1_pubdata_price,
+        })
+    }
+
+    #[test]
+    fn default_strategy_matches_fee_model() {
+        let params = params_v2(1_000, 17_000);
+        let input = DefaultFeeInputStrategy.compute_batch_fee_input(params, 1.5, 2.0, 0);
+        assert_eq!(input, compute_batch_fee_input(params, 1.5, 2.0));
+    }
+
+    #[test]
+    fn ema_strategy_smooths_prices() {
+        let strategy = EmaFeeInputStrategy::new(100);
+        let input = strategy.compute_batch_fee_input(params_v2(1_000, 2_000), 1.0, 1.0, 1_000);
+        assert_eq!(input.l1_gas_price(), 1_000);
+        assert_eq!(input.fair_pubdata_price(), 2_000);
+
+        // After one half-life, the average should be halfway between the previous value and the new observation.
+        let input = strategy.compute_batch_fee_input(params_v2(3_000, 4_000), 1.0, 1.0, 1_100);
+        assert_eq!(input.l1_gas_price(), 2_000);
+        assert_eq!(input.fair_pubdata_price(), 3_000);
+
+        // Observations with the same or older timestamp should not affect the average.
+        let input = strategy.compute_batch_fee_input(params_v2(100_000, 100_000), 1.0, 1.0, 1_100);
+        assert_eq!(input.l1_gas_price(), 2_000);
+        let input = strategy.compute_batch_fee_input(params_v2(100_000, 100_000), 1.0, 1.0, 1_000);
+        assert_eq!(input.l1_gas_price(), 2_000);
+
+        // After a long time, the average should converge to the new observation.
+        let input = strategy.compute_batch_fee_input(params_v2(5_000, 6_000), 1.0, 1.0, 100_000);
+        assert_eq!(input.l1_gas_price(), 5_000);
+        assert_eq!(input.fair_pubdata_price(), 6_000);
+    }
+
+    #[test]
+    fn ema_strategy_with_v1_params() {
+        let strategy = EmaFeeInputStrategy::new(10);
+        let params = |l1_gas_price| {
+            FeeParams::V1(FeeParamsV1 {
+                config: FeeModelConfigV1 {
+                    minimal_l2_gas_price: 100,
+                },
+                l1_gas_price,
+            })
+        };
+        strategy.compute_batch_fee_input(params(1_000), 1.0, 1.0, 0);
+        let input = strategy.compute_batch_fee_input(params(5_000), 2.0, 1.0, 10);
+        assert_eq!(input.l1_gas_price(), 6_000);
+        assert_eq!(input.fair_l2_gas_price(), 100);
+    }
+
+    #[test]
+    fn pubdata_surge_strategy() {
+        let strategy = PubdataSurgeFeeInputStrategy::new(10_000, 3.0);
+        let input = strategy.compute_batch_fee_input(params_v2(1_000, 8_000), 1.0, 1.0, 0);
+        assert_eq!(input.fair_pubdata_price(), 8_000);
+        let input = strategy.compute_batch_fee_input(params_v2(1_000, 10_000), 1.0, 1.0, 0);
+        assert_eq!(input.fair_pubdata_price(), 10_000);
+        let input = strategy.compute_batch_fee_input(params_v2(1_000, 12_000), 1.0, 1.0, 0);
+        assert_eq!(input.fair_pubdata_price(), 16_000);
+        assert_eq!(input.l1_gas_price(), 1_000);
+
+        let input = strategy.compute_batch_fee_input(params_v2(1_000, u64::MAX), 1.0, 1.0, 0);
+        assert_eq!(input.fair_pubdata_price(), u64::MAX);
+    }
+
+    #[test]
+    fn creating_strategies_from_config() {
+        let params = params_v2(1_000, 20_000);
+        let strategy = new_fee_input_strategy(FeeInputStrategyConfig::PubdataSurge {
+            threshold: 10_000,
+            multiplier: 2.0,
+        });
+        let input = strategy.compute_batch_fee_input(params, 1.0, 1.0, 0);
+        assert_eq!(input.fair_pubdata_price(), 30_000);
+
+        let strategy = new_fee_input_strategy(FeeInputStrategyConfig::default());
+        let input = strategy.compute_batch_fee_input(params, 1.0, 1.0, 0);
+        assert_eq!(input.fair_pubdata_price(), 20_000);
+    }
+}
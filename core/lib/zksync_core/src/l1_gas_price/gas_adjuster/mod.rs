@@ -6,9 +6,10 @@ use std::{
 };
 
 use tokio::sync::watch;
-use zksync_config::GasAdjusterConfig;
+use zksync_config::{configs::eth_sender::PubdataSendingMode, GasAdjusterConfig};
 use zksync_eth_client::{Error, EthInterface};
 use zksync_system_constants::L1_GAS_PER_PUBDATA_BYTE;
+use zksync_types::U256;
 
 use self::metrics::METRICS;
 use super::L1TxParamsProvider;
@@ -18,12 +19,19 @@ mod metrics;
 #[cfg(test)]
 mod tests;
 
-/// This component keeps track of the median base_fee from the last `max_base_fee_samples` blocks.
-/// It is used to adjust the base_fee of transactions sent to L1.
+/// Number of blob gas units spent per byte of pubdata; `GAS_PER_BLOB` and `BYTES_PER_BLOB` are both 2^17.
+const BLOB_GAS_PER_BYTE: u64 = 1;
+
+/// This component keeps track of the median base_fee from the last `max_base_fee_samples` blocks,
+/// and of the median blob base fee from the last `max_blob_base_fee_samples` blocks.
+/// It is used to adjust the base_fee of transactions sent to L1 and to price pubdata.
 #[derive(Debug)]
 pub struct GasAdjuster {
-    pub(super) statistics: GasStatistics,
+    pub(super) base_fee_statistics: GasStatistics<u64>,
+    // Blob base fee is not bounded by EIP-4844, so it's stored as `U256`.
+    pub(super) blob_base_fee_statistics: GasStatistics<U256>,
     pub(super) config: GasAdjusterConfig,
+    pubdata_sending_mode: PubdataSendingMode,
     eth_client: Arc<dyn EthInterface>,
 }
 
@@ -31,6 +39,7 @@ impl GasAdjuster {
     pub async fn new(
         eth_client: Arc<dyn EthInterface>,
         config: GasAdjusterConfig,
+        pubdata_sending_mode: PubdataSendingMode,
     ) -> Result<Self, Error> {
         // Subtracting 1 from the "latest" block number to prevent errors in case
         // the info about the latest block is not yet present on the node.
@@ -40,13 +49,32 @@ impl GasAdjuster {
             .await?
             .as_usize()
             .saturating_sub(1);
+        let max_samples = config
+            .max_base_fee_samples
+            .max(config.max_blob_base_fee_samples);
         let history = eth_client
-            .base_fee_history(current_block, config.max_base_fee_samples, "gas_adjuster")
+            .base_fee_history(current_block, max_samples, "gas_adjuster")
             .await?;
+
+        let base_fee_history: Vec<_> = history.iter().map(|fees| fees.base_fee_per_gas).collect();
+        let blob_base_fee_history: Vec<_> = history
+            .iter()
+            .map(|fees| fees.base_fee_per_blob_gas)
+            .collect();
         Ok(Self {
-            statistics: GasStatistics::new(config.max_base_fee_samples, current_block, &history),
+            base_fee_statistics: GasStatistics::new(
+                config.max_base_fee_samples,
+                current_block,
+                &base_fee_history,
+            ),
+            blob_base_fee_statistics: GasStatistics::new(
+                config.max_blob_base_fee_samples,
+                current_block,
+                &blob_base_fee_history,
+            ),
             eth_client,
             config,
+            pubdata_sending_mode,
         })
     }
 
@@ -63,7 +91,8 @@ impl GasAdjuster {
             .as_usize()
             .saturating_sub(1);
 
-        let last_processed_block = self.statistics.last_processed_block();
+        // Both statistics are always updated together, so it's enough to check one of them.
+        let last_processed_block = self.base_fee_statistics.last_processed_block();
 
         if current_block > last_processed_block {
             // Report the current price to be gathered by the statistics module.
@@ -76,10 +105,22 @@ impl GasAdjuster {
                 )
                 .await?;
 
+            let base_fee_history: Vec<_> =
+                history.iter().map(|fees| fees.base_fee_per_gas).collect();
+            let blob_base_fee_history: Vec<_> = history
+                .iter()
+                .map(|fees| fees.base_fee_per_blob_gas)
+                .collect();
+
             METRICS
                 .current_base_fee_per_gas
-                .set(*history.last().unwrap());
-            self.statistics.add_samples(&history);
+                .set(*base_fee_history.last().unwrap());
+            METRICS
+                .current_blob_base_fee
+                .set(saturating_u64(*blob_base_fee_history.last().unwrap()));
+            self.base_fee_statistics.add_samples(&base_fee_history);
+            self.blob_base_fee_statistics
+                .add_samples(&blob_base_fee_history);
         }
         Ok(())
     }
@@ -97,6 +138,17 @@ impl GasAdjuster {
         gas_price
     }
 
+    fn bound_blob_base_fee(&self, blob_base_fee: f64) -> u64 {
+        let max_blob_base_fee = self.config.max_blob_base_fee();
+        if blob_base_fee > max_blob_base_fee as f64 {
+            tracing::warn!(
+                "Blob base fee is too high: {blob_base_fee}, using max allowed: {max_blob_base_fee}"
+            );
+            return max_blob_base_fee;
+        }
+        blob_base_fee as u64
+    }
+
     pub async fn run(self: Arc<Self>, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         loop {
             if *stop_receiver.borrow() {
@@ -129,9 +181,34 @@ impl GasAdjuster {
         self.bound_gas_price(calculated_price)
     }
 
+    /// Returns the price of publishing a single byte of pubdata on L1, in wei, depending on the data availability mode.
     pub(crate) fn estimate_effective_pubdata_price(&self) -> u64 {
-        // For now, pubdata is only sent via calldata, so its price is pegged to the L1 gas price.
-        self.estimate_effective_gas_price() * L1_GAS_PER_PUBDATA_BYTE as u64
+        match self.pubdata_sending_mode {
+            PubdataSendingMode::Blobs => {
+                // Blob base fees not fitting into `u64` are unrealistic; they are bounded below anyway.
+                let blob_base_fee_median = saturating_u64(self.blob_base_fee_statistics.median());
+                METRICS.median_blob_base_fee.set(blob_base_fee_median);
+                // The blob base fee may be reported as zero (e.g., by L1 nodes not supporting EIP-4844),
+                // which would make pubdata free; floor it with the configured minimum.
+                let blob_base_fee = blob_base_fee_median.max(self.config.min_blob_base_fee());
+                let calculated_price = blob_base_fee as f64
+                    * BLOB_GAS_PER_BYTE as f64
+                    * self.config.internal_pubdata_pricing_multiplier;
+                self.bound_blob_base_fee(calculated_price)
+            }
+            PubdataSendingMode::Calldata => {
+                // Pubdata sent via calldata is priced based on the L1 gas price.
+                self.estimate_effective_gas_price() * L1_GAS_PER_PUBDATA_BYTE as u64
+            }
+        }
+    }
+}
+
+fn saturating_u64(value: U256) -> u64 {
+    if value > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        value.as_u64()
     }
 }
 
@@ -150,14 +227,14 @@ impl L1TxParamsProvider for GasAdjuster {
         // The alternative is a linear one:
         // `let scale_factor = a + b * time_in_mempool as f64;`
         let scale_factor = a * b.powf(time_in_mempool as f64);
-        let median = self.statistics.median();
+        let median = self.base_fee_statistics.median();
         METRICS.median_base_fee_per_gas.set(median);
         let new_fee = median as f64 * scale_factor;
         new_fee as u64
     }
 
     fn get_next_block_minimal_base_fee(&self) -> u64 {
-        let last_block_base_fee = self.statistics.last_added_value();
+        let last_block_base_fee = self.base_fee_statistics.last_added_value();
 
         // The next block's base fee will decrease by a maximum of 12.5%.
         last_block_base_fee * 875 / 1000
@@ -176,21 +253,21 @@ impl L1TxParamsProvider for GasAdjuster {
 }
 
 /// Helper structure responsible for collecting the data about recent transactions,
-/// calculating the median base fee.
+/// calculating the median base fee (either for execution or blob gas).
 #[derive(Debug, Clone, Default)]
-pub(super) struct GasStatisticsInner {
-    samples: VecDeque<u64>,
-    median_cached: u64,
+pub(super) struct GasStatisticsInner<T> {
+    samples: VecDeque<T>,
+    median_cached: T,
     max_samples: usize,
     last_processed_block: usize,
 }
 
-impl GasStatisticsInner {
-    fn new(max_samples: usize, block: usize, fee_history: &[u64]) -> Self {
+impl<T: Ord + Copy + Default> GasStatisticsInner<T> {
+    fn new(max_samples: usize, block: usize, fee_history: &[T]) -> Self {
         let mut statistics = Self {
             max_samples,
             samples: VecDeque::with_capacity(max_samples),
-            median_cached: 0,
+            median_cached: T::default(),
             last_processed_block: 0,
         };
 
@@ -202,15 +279,15 @@ impl GasStatisticsInner {
         }
     }
 
-    fn median(&self) -> u64 {
+    fn median(&self) -> T {
         self.median_cached
     }
 
-    fn last_added_value(&self) -> u64 {
+    fn last_added_value(&self) -> T {
         self.samples.back().copied().unwrap_or(self.median_cached)
     }
 
-    fn add_samples(&mut self, fees: &[u64]) {
+    fn add_samples(&mut self, fees: &[T]) {
         self.samples.extend(fees);
         self.last_processed_block += fees.len();
 
@@ -225,10 +302,10 @@ impl GasStatisticsInner {
 }
 
 #[derive(Debug, Default)]
-pub(super) struct GasStatistics(RwLock<GasStatisticsInner>);
+pub(super) struct GasStatistics<T>(RwLock<GasStatisticsInner<T>>);
 
-impl GasStatistics {
-    pub fn new(max_samples: usize, block: usize, fee_history: &[u64]) -> Self {
+impl<T: Ord + Copy + Default> GasStatistics<T> {
+    pub fn new(max_samples: usize, block: usize, fee_history: &[T]) -> Self {
         Self(RwLock::new(GasStatisticsInner::new(
             max_samples,
             block,
@@ -236,15 +313,15 @@ impl GasStatistics {
         )))
     }
 
-    pub fn median(&self) -> u64 {
+    pub fn median(&self) -> T {
         self.0.read().unwrap().median()
     }
 
-    pub fn last_added_value(&self) -> u64 {
+    pub fn last_added_value(&self) -> T {
         self.0.read().unwrap().last_added_value()
     }
 
-    pub fn add_samples(&self, fees: &[u64]) {
+    pub fn add_samples(&self, fees: &[T]) {
         self.0.write().unwrap().add_samples(fees)
     }
 
@@ -1,7 +1,10 @@
 use std::{collections::VecDeque, sync::Arc};
 
-use zksync_config::GasAdjusterConfig;
-use zksync_eth_client::clients::MockEthereum;
+use test_casing::test_casing;
+use zksync_config::{configs::eth_sender::PubdataSendingMode, GasAdjusterConfig};
+use zksync_eth_client::{clients::MockEthereum, BaseFees};
+use zksync_system_constants::L1_GAS_PER_PUBDATA_BYTE;
+use zksync_types::U256;
 
 use super::{GasAdjuster, GasStatisticsInner};
 
@@ -26,35 +29,153 @@ fn samples_queue() {
     assert_eq!(stats.samples, VecDeque::from([4, 5, 18, 18, 18]));
 }
 
+/// Check that we compute the median of blob base fees, which may not fit into `u64`
+#[test]
+fn median_for_u256_samples() {
+    let samples = [
+        U256::MAX,
+        U256::from(3),
+        U256::MAX - 1,
+        U256::from(1),
+        U256::from(2),
+    ];
+    assert_eq!(
+        GasStatisticsInner::new(5, 5, &samples).median(),
+        U256::from(3)
+    );
+}
+
+const TEST_BLOCK_FEES: [u64; 10] = [0, 4, 6, 8, 7, 5, 5, 8, 10, 9];
+const TEST_BLOB_FEES: [u64; 10] = [
+    0,
+    393216,
+    393216,
+    393216 * 2,
+    393216,
+    393216 * 2,
+    393216 * 2,
+    393216 * 3,
+    393216 * 4,
+    393216,
+];
+
+fn test_base_fees() -> Vec<BaseFees> {
+    TEST_BLOCK_FEES
+        .into_iter()
+        .zip(TEST_BLOB_FEES)
+        .map(|(block_fee, blob_fee)| BaseFees {
+            base_fee_per_gas: block_fee,
+            base_fee_per_blob_gas: blob_fee.into(),
+        })
+        .collect()
+}
+
+fn test_config() -> GasAdjusterConfig {
+    GasAdjusterConfig {
+        default_priority_fee_per_gas: 5,
+        max_base_fee_samples: 5,
+        pricing_formula_parameter_a: 1.5,
+        pricing_formula_parameter_b: 1.0005,
+        internal_l1_pricing_multiplier: 0.8,
+        internal_enforced_l1_gas_price: None,
+        poll_period: 5,
+        max_l1_gas_price: None,
+        max_blob_base_fee_samples: 3,
+        internal_pubdata_pricing_multiplier: 1.0,
+        max_blob_base_fee: None,
+        min_blob_base_fee: None,
+    }
+}
+
 /// Check that we properly fetch base fees as block are mined
 #[tokio::test]
 async fn kept_updated() {
-    let eth_client =
-        Arc::new(MockEthereum::default().with_fee_history(vec![0, 4, 6, 8, 7, 5, 5, 8, 10, 9]));
+    let eth_client = Arc::new(MockEthereum::default().with_fee_history(test_base_fees()));
     eth_client.advance_block_number(5);
 
     let adjuster = GasAdjuster::new(
         eth_client.clone(),
-        GasAdjusterConfig {
-            default_priority_fee_per_gas: 5,
-            max_base_fee_samples: 5,
-            pricing_formula_parameter_a: 1.5,
-            pricing_formula_parameter_b: 1.0005,
-            internal_l1_pricing_multiplier: 0.8,
-            internal_enforced_l1_gas_price: None,
-            poll_period: 5,
-            max_l1_gas_price: None,
-        },
+        test_config(),
+        PubdataSendingMode::Calldata,
     )
     .await
     .unwrap();
 
-    assert_eq!(adjuster.statistics.0.read().unwrap().samples.len(), 5);
-    assert_eq!(adjuster.statistics.0.read().unwrap().median(), 6);
+    let base_fee_statistics = adjuster.base_fee_statistics.0.read().unwrap().clone();
+    assert_eq!(base_fee_statistics.samples.len(), 5);
+    assert_eq!(base_fee_statistics.median(), 6);
+    let blob_base_fee_statistics = adjuster.blob_base_fee_statistics.0.read().unwrap().clone();
+    assert_eq!(blob_base_fee_statistics.samples.len(), 3);
+    assert_eq!(blob_base_fee_statistics.median(), 393216.into());
 
     eth_client.advance_block_number(3);
     adjuster.keep_updated().await.unwrap();
 
-    assert_eq!(adjuster.statistics.0.read().unwrap().samples.len(), 5);
-    assert_eq!(adjuster.statistics.0.read().unwrap().median(), 7);
+    let base_fee_statistics = adjuster.base_fee_statistics.0.read().unwrap().clone();
+    assert_eq!(base_fee_statistics.samples.len(), 5);
+    assert_eq!(base_fee_statistics.median(), 7);
+    let blob_base_fee_statistics = adjuster.blob_base_fee_statistics.0.read().unwrap().clone();
+    assert_eq!(blob_base_fee_statistics.samples.len(), 3);
+    assert_eq!(blob_base_fee_statistics.median(), (393216 * 2).into());
+}
+
+/// Check that the pubdata price reflects the data availability mode
+#[test_casing(2, [PubdataSendingMode::Calldata, PubdataSendingMode::Blobs])]
+#[tokio::test]
+async fn estimating_pubdata_price(pubdata_sending_mode: PubdataSendingMode) {
+    let eth_client = Arc::new(MockEthereum::default().with_fee_history(test_base_fees()));
+    eth_client.advance_block_number(5);
+    let adjuster = GasAdjuster::new(eth_client, test_config(), pubdata_sending_mode)
+        .await
+        .unwrap();
+
+    let pubdata_price = adjuster.estimate_effective_pubdata_price();
+    match pubdata_sending_mode {
+        PubdataSendingMode::Calldata => {
+            let expected_price =
+                adjuster.estimate_effective_gas_price() * u64::from(L1_GAS_PER_PUBDATA_BYTE);
+            assert_eq!(pubdata_price, expected_price);
+        }
+        PubdataSendingMode::Blobs => {
+            assert_eq!(pubdata_price, 393216);
+
+            // Check that the pubdata price is bounded.
+            let adjuster = GasAdjuster {
+                config: GasAdjusterConfig {
+                    max_blob_base_fee: Some(1_000),
+                    ..adjuster.config
+                },
+                ..adjuster
+            };
+            assert_eq!(adjuster.estimate_effective_pubdata_price(), 1_000);
+        }
+    }
+}
+
+/// Check that a zero blob base fee doesn't make pubdata free
+#[tokio::test]
+async fn estimating_pubdata_price_with_zero_blob_base_fee() {
+    let base_fees: Vec<_> = TEST_BLOCK_FEES
+        .into_iter()
+        .map(|block_fee| BaseFees {
+            base_fee_per_gas: block_fee,
+            base_fee_per_blob_gas: U256::zero(),
+        })
+        .collect();
+    let eth_client = Arc::new(MockEthereum::default().with_fee_history(base_fees));
+    eth_client.advance_block_number(5);
+    let adjuster = GasAdjuster::new(eth_client, test_config(), PubdataSendingMode::Blobs)
+        .await
+        .unwrap();
+    // Uses the EIP-4844 minimum blob base fee by default.
+    assert_eq!(adjuster.estimate_effective_pubdata_price(), 1);
+
+    let adjuster = GasAdjuster {
+        config: GasAdjusterConfig {
+            min_blob_base_fee: Some(1_000),
+            ..adjuster.config
+        },
+        ..adjuster
+    };
+    assert_eq!(adjuster.estimate_effective_pubdata_price(), 1_000);
 }
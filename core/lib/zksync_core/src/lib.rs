@@ -4,7 +4,10 @@ use std::{net::Ipv4Addr, str::FromStr, sync::Arc, time::Instant};
 
 use anyhow::Context as _;
 use api_server::tx_sender::master_pool_sink::MasterPoolSink;
-use fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider, MainNodeFeeInputProvider};
+use fee_model::{
+    new_fee_input_strategy, ApiFeeInputProvider, BatchFeeModelInputProvider,
+    MainNodeFeeInputProvider,
+};
 use futures::channel::oneshot;
 use prometheus_exporter::PrometheusExporterConfig;
 use temp_config_store::TempConfigStore;
@@ -37,7 +40,7 @@ use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_queued_job_processor::JobProcessor;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
-    fee_model::FeeModelConfig,
+    fee_model::{FeeInputStrategyConfig, FeeModelConfig},
     protocol_version::{L1VerifierConfig, VerifierParams},
     system_contracts::get_system_smart_contracts,
     web3::contract::tokens::Detokenize,
@@ -51,7 +54,9 @@ use crate::{
         healthcheck::HealthCheckHandle,
         tx_sender::{ApiContracts, TxSender, TxSenderBuilder, TxSenderConfig},
         web3,
-        web3::{state::InternalApiConfig, ApiServerHandles, Namespace},
+        web3::{
+            state::InternalApiConfig, ApiServerHandles, MethodFilter, Namespace, RpcRateLimits,
+        },
     },
     basic_witness_input_producer::BasicWitnessInputProducer,
     commitment_generator::CommitmentGenerator,
@@ -74,6 +79,7 @@ use crate::{
     metrics::{InitStage, APP_METRICS},
     state_keeper::{
         create_state_keeper, MempoolFetcher, MempoolGuard, MiniblockSealer, SequencerSealer,
+        TxAccessPolicy,
     },
 };
 
@@ -83,6 +89,7 @@ pub mod block_reverter;
 pub mod commitment_generator;
 pub mod consensus;
 pub mod consistency_checker;
+pub mod db_pruner;
 pub mod eth_sender;
 pub mod eth_watch;
 pub mod fee_model;
@@ -363,8 +370,16 @@ pub async fn initialize_components(
 
     let query_client = QueryClient::new(&eth_client_config.web3_url).unwrap();
     let gas_adjuster_config = configs.gas_adjuster_config.context("gas_adjuster_config")?;
-    let mut gas_adjuster =
-        GasAdjusterSingleton::new(eth_client_config.web3_url.clone(), gas_adjuster_config);
+    let pubdata_sending_mode = configs
+        .eth_sender_config
+        .as_ref()
+        .map(|config| config.sender.pubdata_sending_mode)
+        .unwrap_or_default();
+    let mut gas_adjuster = GasAdjusterSingleton::new(
+        eth_client_config.web3_url.clone(),
+        gas_adjuster_config,
+        pubdata_sending_mode,
+    );
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (cb_sender, cb_receiver) = oneshot::channel();
@@ -392,6 +407,33 @@ pub async fn initialize_components(
         tokio::spawn(circuit_breaker_checker.run(cb_sender, stop_receiver.clone())),
     ];
 
+    // Fee input strategies may be stateful, so a single strategy is shared by the API servers and the state keeper
+    // in order for them to compute consistent fee inputs.
+    let fee_input_strategy = configs.state_keeper_config.as_ref().map(|config| {
+        new_fee_input_strategy(FeeInputStrategyConfig::from_state_keeper_config(config))
+    });
+    // Similarly, the tx access policy is shared by the API servers and the state keeper, so that the policy file
+    // is loaded and reloaded once and all components enforce the same lists.
+    let access_policy = configs
+        .state_keeper_config
+        .as_ref()
+        .map(|config| {
+            TxAccessPolicy::new(config)
+                .map(Arc::new)
+                .context("failed loading tx access policy")
+        })
+        .transpose()?;
+    let uses_access_policy = components.contains(&Component::HttpApi)
+        || components.contains(&Component::WsApi)
+        || components.contains(&Component::StateKeeper);
+    if let Some(access_policy) = &access_policy {
+        if uses_access_policy && access_policy.is_reloadable() {
+            task_futures.push(tokio::spawn(
+                access_policy.clone().run_reloader(stop_receiver.clone()),
+            ));
+        }
+    }
+
     if components.contains(&Component::WsApi)
         || components.contains(&Component::HttpApi)
         || components.contains(&Component::ContractVerificationApi)
@@ -402,9 +444,11 @@ pub async fn initialize_components(
             .clone()
             .context("state_keeper_config")?;
         let network_config = configs.network_config.clone().context("network_config")?;
+        let mempool_config = configs.mempool_config.clone().context("mempool_config")?;
         let tx_sender_config = TxSenderConfig::new(
             &state_keeper_config,
             &api_config.web3_json_rpc,
+            &mempool_config,
             network_config.zksync_network_id,
         );
         let internal_api_config = InternalApiConfig::new(
@@ -418,6 +462,8 @@ pub async fn initialize_components(
         // terminate immediately if storage caches are dropped, which will lead to the (unexpected)
         // program termination.
         let mut storage_caches = None;
+        // Rate limits are shared by HTTP and WS servers, so that per-client quotas are shared as well.
+        let rate_limits = Arc::new(build_rpc_rate_limits(&api_config.web3_json_rpc)?);
 
         if components.contains(&Component::HttpApi) {
             storage_caches = Some(
@@ -431,10 +477,13 @@ pub async fn initialize_components(
                 .get_or_init()
                 .await
                 .context("gas_adjuster.get_or_init()")?;
-            let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
-                bounded_gas_adjuster,
-                FeeModelConfig::from_state_keeper_config(&state_keeper_config),
-            ));
+            let batch_fee_input_provider = Arc::new(
+                MainNodeFeeInputProvider::new(
+                    bounded_gas_adjuster,
+                    FeeModelConfig::from_state_keeper_config(&state_keeper_config),
+                )
+                .with_strategy(fee_input_strategy.clone().context("state_keeper_config")?),
+            );
             let server_handles = run_http_api(
                 &postgres_config,
                 &tx_sender_config,
@@ -447,6 +496,8 @@ pub async fn initialize_components(
                 batch_fee_input_provider,
                 state_keeper_config.save_call_traces,
                 storage_caches.clone().unwrap(),
+                rate_limits.clone(),
+                access_policy.clone().context("state_keeper_config")?,
             )
             .await
             .context("run_http_api")?;
@@ -474,10 +525,13 @@ pub async fn initialize_components(
                 .get_or_init()
                 .await
                 .context("gas_adjuster.get_or_init()")?;
-            let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
-                bounded_gas_adjuster,
-                FeeModelConfig::from_state_keeper_config(&state_keeper_config),
-            ));
+            let batch_fee_input_provider = Arc::new(
+                MainNodeFeeInputProvider::new(
+                    bounded_gas_adjuster,
+                    FeeModelConfig::from_state_keeper_config(&state_keeper_config),
+                )
+                .with_strategy(fee_input_strategy.clone().context("state_keeper_config")?),
+            );
             let server_handles = run_ws_api(
                 &postgres_config,
                 &tx_sender_config,
@@ -489,6 +543,8 @@ pub async fn initialize_components(
                 replica_connection_pool.clone(),
                 stop_receiver.clone(),
                 storage_caches,
+                rate_limits,
+                access_policy.clone().context("state_keeper_config")?,
             )
             .await
             .context("run_ws_api")?;
@@ -535,10 +591,13 @@ pub async fn initialize_components(
             .state_keeper_config
             .clone()
             .context("state_keeper_config")?;
-        let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
-            bounded_gas_adjuster,
-            FeeModelConfig::from_state_keeper_config(&state_keeper_config),
-        ));
+        let batch_fee_input_provider = Arc::new(
+            MainNodeFeeInputProvider::new(
+                bounded_gas_adjuster,
+                FeeModelConfig::from_state_keeper_config(&state_keeper_config),
+            )
+            .with_strategy(fee_input_strategy.clone().context("state_keeper_config")?),
+        );
         add_state_keeper_to_task_futures(
             &mut task_futures,
             &postgres_config,
@@ -548,6 +607,7 @@ pub async fn initialize_components(
             &db_config,
             &configs.mempool_config.clone().context("mempool_config")?,
             batch_fee_input_provider,
+            access_policy.clone().context("state_keeper_config")?,
             store_factory.create_store().await,
             stop_receiver.clone(),
         )
@@ -786,6 +846,7 @@ async fn add_state_keeper_to_task_futures(
     db_config: &DBConfig,
     mempool_config: &MempoolConfig,
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
+    access_policy: Arc<TxAccessPolicy>,
     object_store: Arc<dyn ObjectStore>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
@@ -799,7 +860,7 @@ async fn add_state_keeper_to_task_futures(
             .access_storage()
             .await
             .context("Access storage to build mempool")?;
-        let mempool = MempoolGuard::from_storage(&mut storage, mempool_config.capacity).await;
+        let mempool = MempoolGuard::from_storage(&mut storage, mempool_config).await;
         mempool.register_metrics();
         mempool
     };
@@ -824,6 +885,7 @@ async fn add_state_keeper_to_task_futures(
         mempool.clone(),
         batch_fee_input_provider.clone(),
         miniblock_sealer_handle,
+        access_policy,
         object_store,
         stop_receiver.clone(),
     )
@@ -1114,6 +1176,7 @@ async fn build_tx_sender(
     master_pool: ConnectionPool,
     batch_fee_model_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     storage_caches: PostgresStorageCaches,
+    access_policy: Arc<TxAccessPolicy>,
 ) -> (TxSender, VmConcurrencyBarrier) {
     let sequencer_sealer = SequencerSealer::new(state_keeper_config.clone());
     let master_pool_sink = MasterPoolSink::new(master_pool);
@@ -1122,7 +1185,8 @@ async fn build_tx_sender(
         replica_pool.clone(),
         Arc::new(master_pool_sink),
     )
-    .with_sealer(Arc::new(sequencer_sealer));
+    .with_sealer(Arc::new(sequencer_sealer))
+    .with_access_policy(access_policy);
 
     let max_concurrency = web3_json_config.vm_concurrency_limit();
     let (vm_concurrency_limiter, vm_barrier) = VmConcurrencyLimiter::new(max_concurrency);
@@ -1154,6 +1218,8 @@ async fn run_http_api(
     batch_fee_model_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     with_debug_namespace: bool,
     storage_caches: PostgresStorageCaches,
+    rate_limits: Arc<RpcRateLimits>,
+    access_policy: Arc<TxAccessPolicy>,
 ) -> anyhow::Result<ApiServerHandles> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1163,6 +1229,7 @@ async fn run_http_api(
         master_connection_pool,
         batch_fee_model_input_provider,
         storage_caches,
+        access_policy,
     )
     .await;
 
@@ -1185,6 +1252,8 @@ async fn run_http_api(
             .with_tree_api(api_config.web3_json_rpc.tree_api_url())
             .with_batch_request_size_limit(api_config.web3_json_rpc.max_batch_request_size())
             .with_response_body_size_limit(api_config.web3_json_rpc.max_response_body_size())
+            .with_rate_limits(rate_limits)
+            .with_method_filter(build_method_filter(&api_config.web3_json_rpc))
             .with_tx_sender(tx_sender, vm_barrier)
             .enable_api_namespaces(namespaces);
     api_builder.build(stop_receiver).await
@@ -1202,6 +1271,8 @@ async fn run_ws_api(
     replica_connection_pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
     storage_caches: PostgresStorageCaches,
+    rate_limits: Arc<RpcRateLimits>,
+    access_policy: Arc<TxAccessPolicy>,
 ) -> anyhow::Result<ApiServerHandles> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1211,6 +1282,7 @@ async fn run_ws_api(
         master_connection_pool,
         batch_fee_model_input_provider,
         storage_caches,
+        access_policy,
     )
     .await;
     let last_miniblock_pool = ConnectionPool::singleton(postgres_config.replica_url()?)
@@ -1234,6 +1306,8 @@ async fn run_ws_api(
                     .web3_json_rpc
                     .websocket_requests_per_minute_limit(),
             )
+            .with_rate_limits(rate_limits)
+            .with_method_filter(build_method_filter(&api_config.web3_json_rpc))
             .with_polling_interval(api_config.web3_json_rpc.pubsub_interval())
             .with_tree_api(api_config.web3_json_rpc.tree_api_url())
             .with_tx_sender(tx_sender, vm_barrier)
@@ -1242,6 +1316,26 @@ async fn run_ws_api(
     api_builder.build(stop_receiver.clone()).await
 }
 
+fn build_rpc_rate_limits(config: &Web3JsonRpcConfig) -> anyhow::Result<RpcRateLimits> {
+    let rate_limits = RpcRateLimits::new(config.requests_quota_per_minute)
+        .with_method_weights(config.method_weights())
+        .with_api_keys(config.api_keys.clone().unwrap_or_default())
+        .with_trusted_proxies(config.trusted_proxies.clone().unwrap_or_default());
+    Ok(match &config.api_key_header {
+        Some(header) => rate_limits
+            .with_api_key_header(header)
+            .context("api_key_header")?,
+        None => rate_limits,
+    })
+}
+
+fn build_method_filter(config: &Web3JsonRpcConfig) -> MethodFilter {
+    MethodFilter::new(
+        config.method_allowlist.clone().unwrap_or_default(),
+        config.method_denylist.clone().unwrap_or_default(),
+    )
+}
+
 async fn circuit_breakers_for_components(
     components: &[Component],
     postgres_config: &PostgresConfig,
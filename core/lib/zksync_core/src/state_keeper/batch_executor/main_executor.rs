@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{fmt, sync::Arc, time::Instant};
 
 use async_trait::async_trait;
 use multivm::{
@@ -11,24 +11,37 @@ use multivm::{
     MultiVMTracer, VmInstance,
 };
 use once_cell::sync::OnceCell;
-use tokio::sync::{mpsc, watch};
+use tokio::{
+    runtime::Handle,
+    sync::{mpsc, oneshot, watch},
+    task::JoinHandle,
+};
 use zksync_dal::ConnectionPool;
-use zksync_state::{RocksdbStorage, StorageView, WriteStorage};
-use zksync_types::{vm_trace::Call, Transaction, U256};
+use zksync_state::{
+    PostgresStorage, ReadStorage, RocksbStorageBuilder, RocksdbStorage, StorageView, WriteStorage,
+};
+use zksync_types::{vm_trace::Call, MiniblockNumber, Transaction, U256};
 use zksync_utils::bytecode::CompressedBytecodeInfo;
 
 use super::{BatchExecutor, BatchExecutorHandle, Command, TxExecutionResult};
 use crate::{
     metrics::{InteractionType, TxStage, APP_METRICS},
     state_keeper::{
-        metrics::{TxExecutionStage, EXECUTOR_METRICS, KEEPER_METRICS},
+        metrics::{StorageInitMode, TxExecutionStage, EXECUTOR_METRICS, KEEPER_METRICS},
         types::ExecutionMetricsForCriteria,
     },
 };
 
 /// The default implementation of [`BatchExecutor`].
 /// Creates a "real" batch executor which maintains the VM (as opposed to the test builder which doesn't use the VM).
-#[derive(Debug, Clone)]
+///
+/// Once an L1 batch is initialized, the executor pre-opens RocksDB for the next batch in background. This starts
+/// as soon as the VM for the current batch releases the storage, and thus overlaps with sealing the batch.
+/// Only opening RocksDB (and catching it up with L1 batches that are already persisted in Postgres, which is usually
+/// a no-op) is performed in background. The writes of the batch being sealed are applied by
+/// [`RocksbStorageBuilder::synchronize()`] once the next batch is requested, and the VM for the next batch
+/// is created in [`BatchExecutor::init_batch()`], since its params are not known in advance.
+#[derive(Debug)]
 pub struct MainBatchExecutor {
     state_keeper_db_path: String,
     pool: ConnectionPool,
@@ -37,6 +50,10 @@ pub struct MainBatchExecutor {
     upload_witness_inputs_to_gcs: bool,
     enum_index_migration_chunk_size: usize,
     optional_bytecode_compression: bool,
+    use_historical_storage: bool,
+    next_batch_storage: Option<NextBatchStorageTask>,
+    #[cfg(test)]
+    last_storage_init_mode: Option<StorageInitMode>,
 }
 
 impl MainBatchExecutor {
@@ -57,8 +74,27 @@ impl MainBatchExecutor {
             upload_witness_inputs_to_gcs,
             enum_index_migration_chunk_size,
             optional_bytecode_compression,
+            use_historical_storage: false,
+            next_batch_storage: None,
+            #[cfg(test)]
+            last_storage_init_mode: None,
         }
     }
+
+    /// Makes the executor read the VM state directly from Postgres as of the start of each executed L1 batch,
+    /// instead of using the state keeper RocksDB cache. This is significantly slower, but allows to re-execute
+    /// L1 batches that are already sealed (e.g., using [`ReplayIO`](crate::state_keeper::ReplayIO)).
+    #[must_use]
+    pub fn with_historical_storage(mut self) -> Self {
+        self.use_historical_storage = true;
+        self
+    }
+
+    /// Returns how the state keeper storage was initialized for the last L1 batch.
+    #[cfg(test)]
+    pub(super) fn last_storage_init_mode(&self) -> Option<StorageInitMode> {
+        self.last_storage_init_mode
+    }
 }
 
 #[async_trait]
@@ -69,10 +105,30 @@ impl BatchExecutor for MainBatchExecutor {
         system_env: SystemEnv,
         stop_receiver: &watch::Receiver<bool>,
     ) -> Option<BatchExecutorHandle> {
-        let mut secondary_storage = RocksdbStorage::builder(self.state_keeper_db_path.as_ref())
-            .await
-            .expect("Failed initializing state keeper storage");
-        secondary_storage.enable_enum_index_migration(self.enum_index_migration_chunk_size);
+        if self.use_historical_storage {
+            return Some(self.init_batch_with_historical_storage(l1_batch_params, system_env));
+        }
+
+        let init_started_at = Instant::now();
+        let preopened_storage = match self.next_batch_storage.take() {
+            Some(task) => task
+                .wait(init_started_at)
+                .await
+                .expect("Failed opening state keeper storage in background"),
+            None => None,
+        };
+        let (secondary_storage, init_mode) = match preopened_storage {
+            Some(builder) => (builder, StorageInitMode::Preopened),
+            None => {
+                let builder = Self::open_storage(
+                    &self.state_keeper_db_path,
+                    self.enum_index_migration_chunk_size,
+                )
+                .await
+                .expect("Failed initializing state keeper storage");
+                (builder, StorageInitMode::Sequential)
+            }
+        };
         let mut conn = self
             .pool
             .access_storage_tagged("state_keeper")
@@ -82,7 +138,37 @@ impl BatchExecutor for MainBatchExecutor {
             .synchronize(&mut conn, stop_receiver)
             .await
             .expect("Failed synchronizing secondary state keeper storage")?;
+        EXECUTOR_METRICS.batch_storage_init_latency[&init_mode].observe(init_started_at.elapsed());
+        #[cfg(test)]
+        {
+            self.last_storage_init_mode = Some(init_mode);
+        }
+
+        let (commands_sender, executor) = self.create_command_receiver();
+        let upload_witness_inputs_to_gcs = self.upload_witness_inputs_to_gcs;
+        let (storage_released_sender, storage_released_receiver) = oneshot::channel();
+
+        let handle = tokio::task::spawn_blocking(move || {
+            executor.run(
+                secondary_storage,
+                l1_batch_params,
+                system_env,
+                upload_witness_inputs_to_gcs,
+            );
+            // The storage is dropped at this point, so it can be reopened for the next L1 batch.
+            storage_released_sender.send(()).ok();
+        });
+        self.next_batch_storage =
+            Some(self.preopen_next_batch_storage(storage_released_receiver, stop_receiver.clone()));
+        Some(BatchExecutorHandle {
+            handle,
+            commands: commands_sender,
+        })
+    }
+}
 
+impl MainBatchExecutor {
+    fn create_command_receiver(&self) -> (mpsc::Sender<Command>, CommandReceiver) {
         // Since we process `BatchExecutor` commands one-by-one (the next command is never enqueued
         // until a previous command is processed), capacity 1 is enough for the commands channel.
         let (commands_sender, commands_receiver) = mpsc::channel(1);
@@ -92,20 +178,109 @@ impl BatchExecutor for MainBatchExecutor {
             optional_bytecode_compression: self.optional_bytecode_compression,
             commands: commands_receiver,
         };
+        (commands_sender, executor)
+    }
+
+    async fn open_storage(
+        db_path: &str,
+        enum_index_migration_chunk_size: usize,
+    ) -> anyhow::Result<RocksbStorageBuilder> {
+        let mut builder = RocksdbStorage::builder(db_path.as_ref()).await?;
+        builder.enable_enum_index_migration(enum_index_migration_chunk_size);
+        Ok(builder)
+    }
+
+    /// Spawns a task opening the state keeper storage for the next L1 batch. The task waits until the storage
+    /// is released by the VM executing the current batch (RocksDB cannot be opened twice).
+    fn preopen_next_batch_storage(
+        &self,
+        storage_released_receiver: oneshot::Receiver<()>,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> NextBatchStorageTask {
+        let db_path = self.state_keeper_db_path.clone();
+        let enum_index_migration_chunk_size = self.enum_index_migration_chunk_size;
+        let pool = self.pool.clone();
+
+        NextBatchStorageTask(tokio::spawn(async move {
+            // If the VM thread has panicked, the storage is released anyway.
+            storage_released_receiver.await.ok();
+            let started_at = Instant::now();
+            let builder = Self::open_storage(&db_path, enum_index_migration_chunk_size).await?;
+            let mut conn = pool.access_storage_tagged("state_keeper").await?;
+            let builder = builder.catch_up(&mut conn, &stop_receiver).await?;
+            Ok(PreopenedStorage {
+                builder,
+                started_at,
+                finished_at: Instant::now(),
+            })
+        }))
+    }
+
+    fn init_batch_with_historical_storage(
+        &self,
+        l1_batch_params: L1BatchEnv,
+        system_env: SystemEnv,
+    ) -> BatchExecutorHandle {
+        let (commands_sender, executor) = self.create_command_receiver();
         let upload_witness_inputs_to_gcs = self.upload_witness_inputs_to_gcs;
+        // The VM must observe the state as of the end of the last miniblock in the previous L1 batch.
+        let storage_miniblock_number = MiniblockNumber(l1_batch_params.first_l2_block.number) - 1;
+        let pool = self.pool.clone();
+        let rt_handle = Handle::current();
 
         let handle = tokio::task::spawn_blocking(move || {
+            let connection = rt_handle
+                .block_on(pool.access_storage_tagged("state_keeper"))
+                .expect("Failed getting connection for historical state keeper storage");
+            let storage =
+                PostgresStorage::new(rt_handle, connection, storage_miniblock_number, true);
             executor.run(
-                secondary_storage,
+                storage,
                 l1_batch_params,
                 system_env,
                 upload_witness_inputs_to_gcs,
             )
         });
-        Some(BatchExecutorHandle {
+        BatchExecutorHandle {
             handle,
             commands: commands_sender,
-        })
+        }
+    }
+}
+
+/// State keeper storage opened in background.
+#[derive(Debug)]
+struct PreopenedStorage {
+    /// `None` if catching up was interrupted by the stop signal.
+    builder: Option<RocksbStorageBuilder>,
+    started_at: Instant,
+    finished_at: Instant,
+}
+
+/// Background task opening the state keeper storage for the next L1 batch. The task is aborted on drop
+/// so that it doesn't outlive the executor.
+#[derive(Debug)]
+struct NextBatchStorageTask(JoinHandle<anyhow::Result<PreopenedStorage>>);
+
+impl NextBatchStorageTask {
+    async fn wait(
+        mut self,
+        init_started_at: Instant,
+    ) -> anyhow::Result<Option<RocksbStorageBuilder>> {
+        let preopened = (&mut self.0)
+            .await
+            .map_err(|err| anyhow::anyhow!("storage opening task panicked: {err}"))??;
+        let overlap_end = preopened.finished_at.min(init_started_at);
+        EXECUTOR_METRICS
+            .batch_storage_open_overlap
+            .observe(overlap_end.saturating_duration_since(preopened.started_at));
+        Ok(preopened.builder)
+    }
+}
+
+impl Drop for NextBatchStorageTask {
+    fn drop(&mut self) {
+        self.0.abort();
     }
 }
 
@@ -124,9 +299,9 @@ struct CommandReceiver {
 }
 
 impl CommandReceiver {
-    pub(super) fn run(
+    pub(super) fn run<S: ReadStorage + fmt::Debug>(
         mut self,
-        secondary_storage: RocksdbStorage,
+        secondary_storage: S,
         l1_batch_params: L1BatchEnv,
         system_env: SystemEnv,
         upload_witness_inputs_to_gcs: bool,
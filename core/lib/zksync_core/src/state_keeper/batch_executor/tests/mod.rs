@@ -1,11 +1,20 @@
+use std::sync::Arc;
+
 use assert_matches::assert_matches;
 use test_casing::test_casing;
+use tokio::sync::watch;
+use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_dal::ConnectionPool;
 use zksync_test_account::Account;
-use zksync_types::{get_nonce_key, utils::storage_key_for_eth_balance, PriorityOpId};
+use zksync_types::{
+    get_nonce_key, utils::storage_key_for_eth_balance, L1BatchNumber, L2ChainId, PriorityOpId,
+};
 
-use self::tester::{AccountLoadNextExecutable, StorageSnapshot, TestConfig, Tester};
+use self::tester::{AccountLoadNextExecutable, StorageSnapshot, TestConfig, Tester, CHAIN_ID};
 use super::TxExecutionResult;
+use crate::state_keeper::{
+    metrics::StorageInitMode, seal_criteria::NoopSealer, ReplayIO, ZkSyncStateKeeper,
+};
 
 mod tester;
 
@@ -44,6 +53,80 @@ async fn execute_l2_tx() {
     executor.finish_batch().await;
 }
 
+/// Checks that the same batch executor can execute several batches in succession, with the storage
+/// for each following batch being opened in background.
+#[tokio::test]
+async fn execute_batches_with_preopened_storage() {
+    let connection_pool = ConnectionPool::constrained_test_pool(1).await;
+    let mut accounts = [Account::random(), Account::random()];
+    let tester = Tester::new(connection_pool);
+    tester.genesis().await;
+    tester
+        .fund(&accounts.iter().map(Account::address).collect::<Vec<_>>())
+        .await;
+    let mut builder = tester.create_main_batch_executor();
+
+    // Batches aren't persisted in Postgres, so each batch uses a separate account to have correct nonces.
+    for (i, account) in accounts.iter_mut().enumerate() {
+        let executor = tester.init_batch(&mut builder).await;
+        let expected_init_mode = if i == 0 {
+            StorageInitMode::Sequential
+        } else {
+            StorageInitMode::Preopened
+        };
+        assert_eq!(builder.last_storage_init_mode(), Some(expected_init_mode));
+
+        let res = executor.execute_tx(account.execute()).await;
+        assert_executed(&res);
+        executor.finish_batch().await;
+    }
+}
+
+/// Checks that an L1 batch sealed in Postgres is replayed by `ReplayIO` together with the main batch executor
+/// using historical storage, and that the replayed batch matches the stored one.
+#[tokio::test]
+async fn replaying_sealed_l1_batch() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut alice = Account::random();
+    let tester = Tester::new(connection_pool.clone());
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    tester
+        .execute_and_seal_first_l1_batch(vec![alice.execute(), alice.execute()])
+        .await;
+
+    let (io, mut diffs_receiver) = ReplayIO::new(
+        connection_pool,
+        L1BatchNumber(1),
+        L1BatchNumber(1),
+        StateKeeperConfig::for_tests().validation_computational_gas_limit,
+        L2ChainId::from(CHAIN_ID),
+    )
+    .await
+    .unwrap();
+    let batch_executor = tester
+        .create_main_batch_executor()
+        .with_historical_storage();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let state_keeper = ZkSyncStateKeeper::new(
+        stop_receiver,
+        Box::new(io),
+        Box::new(batch_executor),
+        Arc::new(NoopSealer),
+    );
+    let state_keeper_task = tokio::spawn(state_keeper.run());
+
+    let diff = diffs_receiver
+        .recv()
+        .await
+        .expect("no diff for L1 batch #1");
+    assert_eq!(diff.l1_batch_number, L1BatchNumber(1));
+    assert!(diff.is_empty(), "{:?}", diff.mismatches);
+
+    stop_sender.send_replace(true);
+    state_keeper_task.await.unwrap().unwrap();
+}
+
 #[derive(Debug, Clone, Copy)]
 enum SnapshotRecoveryMutation {
     RemoveNonce,
@@ -27,14 +27,16 @@ use crate::{
     genesis::create_genesis_l1_batch,
     state_keeper::{
         batch_executor::{BatchExecutorHandle, TxExecutionResult},
+        io::MiniblockParams,
         tests::{default_l1_batch_env, default_system_env, BASE_SYSTEM_CONTRACTS},
+        updates::UpdatesManager,
         BatchExecutor, MainBatchExecutor,
     },
     utils::testonly::prepare_recovery_snapshot,
 };
 
 const DEFAULT_GAS_PER_PUBDATA: u32 = 10000;
-const CHAIN_ID: u32 = 270;
+pub(super) const CHAIN_ID: u32 = 270;
 
 /// Representation of configuration parameters used by the state keeper.
 /// Has sensible defaults for most tests, each of which can be overridden.
@@ -103,7 +105,17 @@ impl Tester {
         l1_batch_env: L1BatchEnv,
         system_env: SystemEnv,
     ) -> BatchExecutorHandle {
-        let mut builder = MainBatchExecutor::new(
+        let mut builder = self.create_main_batch_executor();
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        builder
+            .init_batch(l1_batch_env, system_env, &stop_receiver)
+            .await
+            .expect("Batch executor was interrupted")
+    }
+
+    /// Creates a batch executor builder that can be used to execute several batches in succession.
+    pub(super) fn create_main_batch_executor(&self) -> MainBatchExecutor {
+        MainBatchExecutor::new(
             self.db_dir.path().to_str().unwrap().to_owned(),
             self.pool.clone(),
             self.config.max_allowed_tx_gas_limit.into(),
@@ -111,7 +123,12 @@ impl Tester {
             self.config.upload_witness_inputs_to_gcs,
             100,
             false,
-        );
+        )
+    }
+
+    /// Initializes a batch using the provided builder with sensible default params.
+    pub(super) async fn init_batch(&self, builder: &mut MainBatchExecutor) -> BatchExecutorHandle {
+        let (l1_batch_env, system_env) = self.batch_params(L1BatchNumber(1), 100);
         let (_stop_sender, stop_receiver) = watch::channel(false);
         builder
             .init_batch(l1_batch_env, system_env, &stop_receiver)
@@ -178,6 +195,89 @@ impl Tester {
         }
     }
 
+    /// Executes the provided transactions in L1 batch #1 consisting of a single miniblock, and persists
+    /// the batch in Postgres in the same way as the state keeper does. Expects genesis to be performed.
+    pub(super) async fn execute_and_seal_first_l1_batch(&self, txs: Vec<Transaction>) {
+        let mut storage = self
+            .pool
+            .access_storage_tagged("state_keeper")
+            .await
+            .unwrap();
+        // The hash of the genesis L1 batch is required to load params for L1 batch #1.
+        storage
+            .blocks_dal()
+            .set_l1_batch_hash(L1BatchNumber(0), H256::zero())
+            .await
+            .unwrap();
+        let genesis_miniblock_hash = storage
+            .blocks_web3_dal()
+            .get_miniblock_hash(MiniblockNumber(0))
+            .await
+            .unwrap()
+            .expect("no genesis miniblock");
+        drop(storage);
+
+        let (mut l1_batch_env, system_env) = self.batch_params(L1BatchNumber(1), 100);
+        l1_batch_env.first_l2_block.prev_block_hash = genesis_miniblock_hash;
+        let mut builder = self.create_main_batch_executor();
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let executor = builder
+            .init_batch(l1_batch_env.clone(), system_env.clone(), &stop_receiver)
+            .await
+            .expect("Batch executor was interrupted");
+
+        let mut updates = UpdatesManager::new(&l1_batch_env, &system_env);
+        for tx in txs {
+            let tx_hash = tx.hash();
+            let TxExecutionResult::Success {
+                tx_result,
+                tx_metrics,
+                compressed_bytecodes,
+                call_tracer_result,
+                ..
+            } = executor.execute_tx(tx.clone()).await
+            else {
+                panic!("Transaction {tx_hash:?} was not executed");
+            };
+            updates.extend_from_executed_transaction(
+                tx,
+                *tx_result,
+                compressed_bytecodes,
+                tx_metrics.l1_gas,
+                tx_metrics.execution_metrics,
+                call_tracer_result,
+            );
+        }
+
+        let mut storage = self
+            .pool
+            .access_storage_tagged("state_keeper")
+            .await
+            .unwrap();
+        // Transactions are not in the mempool, so they need to be inserted when sealing the miniblock.
+        updates
+            .seal_miniblock_command(L1BatchNumber(1), MiniblockNumber(1), Address::zero(), true)
+            .seal(&mut storage)
+            .await;
+        updates.push_miniblock(MiniblockParams {
+            timestamp: l1_batch_env.timestamp + 1,
+            virtual_blocks: 1,
+        });
+        executor
+            .start_next_miniblock(updates.miniblock.get_miniblock_env())
+            .await;
+        let (finished_batch, _) = executor.finish_batch().await;
+        let _fictive_miniblock = updates
+            .seal_l1_batch(
+                &mut storage,
+                MiniblockNumber(2),
+                &l1_batch_env,
+                finished_batch,
+                Address::zero(),
+            )
+            .await;
+    }
+
     /// Adds funds for specified account list.
     /// Expects genesis to be performed (i.e. `setup_storage` called beforehand).
     pub(super) async fn fund(&self, addresses: &[Address]) {
@@ -36,7 +36,7 @@ use crate::{
         metrics::KEEPER_METRICS,
         seal_criteria::{IoSealCriteria, TimeoutSealer},
         updates::{MiniblockUpdates, UpdatesManager},
-        MempoolGuard,
+        MempoolGuard, TxAccessPolicy,
     },
 };
 
@@ -64,6 +64,7 @@ pub struct MempoolIO {
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     l2_erc20_bridge_addr: Address,
     chain_id: L2ChainId,
+    access_policy: Arc<TxAccessPolicy>,
 
     virtual_blocks_interval: u32,
     virtual_blocks_per_miniblock: u32,
@@ -247,6 +248,16 @@ impl StateKeeperIO for MempoolIO {
             let res = self.mempool.next_transaction(&self.filter);
             get_latency.observe();
             if let Some(res) = res {
+                if let Err(err) = self.access_policy.check(&res) {
+                    // The transaction may have been accepted by the API before the policy was changed.
+                    if let Err(reject_err) = self.reject(&res, &err.to_string()).await {
+                        tracing::error!(
+                            "Failed rejecting transaction {} not allowed by access policy: {reject_err:#}",
+                            res.hash()
+                        );
+                    }
+                    continue;
+                }
                 return Some(res);
             } else {
                 tokio::time::sleep(self.delay_interval).await;
@@ -432,6 +443,7 @@ impl MempoolIO {
         l2_erc20_bridge_addr: Address,
         validation_computational_gas_limit: u32,
         chain_id: L2ChainId,
+        access_policy: Arc<TxAccessPolicy>,
     ) -> anyhow::Result<Self> {
         anyhow::ensure!(
             config.virtual_blocks_interval > 0,
@@ -471,6 +483,7 @@ impl MempoolIO {
             batch_fee_input_provider,
             l2_erc20_bridge_addr,
             chain_id,
+            access_policy,
             virtual_blocks_interval: config.virtual_blocks_interval,
             virtual_blocks_per_miniblock: config.virtual_blocks_per_miniblock,
         })
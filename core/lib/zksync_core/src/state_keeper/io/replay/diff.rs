@@ -0,0 +1,398 @@
+//! Comparison of replayed L1 batches with the data persisted in Postgres.
+
+use std::{collections::HashMap, fmt};
+
+use anyhow::Context as _;
+use multivm::interface::FinishedL1Batch;
+use zksync_dal::StorageProcessor;
+use zksync_types::{
+    block::L1BatchHeader, web3::signing::keccak256, zk_evm_types::LogQuery, AccountTreeId,
+    L1BatchNumber, StorageKey, StorageLogQueryType, H256, U256,
+};
+use zksync_utils::u256_to_h256;
+
+/// L1 batch data persisted in Postgres that a replayed batch is compared against.
+#[derive(Debug)]
+pub(super) struct StoredL1Batch {
+    header: L1BatchHeader,
+    bootloader_memory: Vec<(usize, U256)>,
+    events_queue: Vec<LogQuery>,
+    touched_slots: HashMap<StorageKey, H256>,
+}
+
+impl StoredL1Batch {
+    pub async fn load(
+        storage: &mut StorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Self> {
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await
+            .with_context(|| format!("failed loading header for L1 batch #{l1_batch_number}"))?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is not sealed"))?;
+        let bootloader_memory = storage
+            .blocks_dal()
+            .get_initial_bootloader_heap(l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed loading bootloader heap for L1 batch #{l1_batch_number}")
+            })?
+            .with_context(|| format!("L1 batch #{l1_batch_number} misses bootloader heap"))?;
+        let events_queue = storage
+            .blocks_dal()
+            .get_events_queue(l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed loading events queue for L1 batch #{l1_batch_number}")
+            })?
+            .unwrap_or_default();
+        let touched_slots = storage
+            .storage_logs_dal()
+            .get_touched_slots_for_l1_batch(l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed loading touched slots for L1 batch #{l1_batch_number}")
+            })?;
+
+        Ok(Self {
+            header,
+            bootloader_memory,
+            events_queue,
+            touched_slots,
+        })
+    }
+}
+
+/// Single discrepancy between a replayed L1 batch and the batch persisted in Postgres.
+#[derive(Debug, Clone, PartialEq)]
+pub enum L1BatchMismatch {
+    /// Transaction was rejected by the VM during replay.
+    RejectedTx { hash: H256, reason: String },
+    /// The batch was sealed before all its transactions were executed.
+    SkippedTxs { count: usize },
+    /// Final value of a storage slot differs. `None` means that the slot was not written to.
+    StorageSlot {
+        key: StorageKey,
+        expected: Option<H256>,
+        actual: Option<H256>,
+    },
+    /// Deduplicated events queue differs starting from the specified index.
+    Events {
+        first_mismatch_index: usize,
+        expected_len: usize,
+        actual_len: usize,
+    },
+    /// User L2-to-L1 logs differ starting from the specified index.
+    UserL2ToL1Logs {
+        first_mismatch_index: usize,
+        expected_len: usize,
+        actual_len: usize,
+    },
+    /// System L2-to-L1 logs differ starting from the specified index.
+    SystemLogs {
+        first_mismatch_index: usize,
+        expected_len: usize,
+        actual_len: usize,
+    },
+    /// Hash of the final bootloader memory differs.
+    BootloaderMemory {
+        expected_hash: H256,
+        actual_hash: H256,
+    },
+}
+
+impl fmt::Display for L1BatchMismatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RejectedTx { hash, reason } => {
+                write!(formatter, "transaction {hash:?} was rejected: {reason}")
+            }
+            Self::SkippedTxs { count } => {
+                write!(formatter, "{count} transaction(s) were not executed")
+            }
+            Self::StorageSlot {
+                key,
+                expected,
+                actual,
+            } => write!(
+                formatter,
+                "storage slot {:?}:{:?} has final value {actual:?}, expected {expected:?}",
+                key.address(),
+                key.key()
+            ),
+            Self::Events {
+                first_mismatch_index,
+                expected_len,
+                actual_len,
+            } => write!(
+                formatter,
+                "events differ starting from #{first_mismatch_index} \
+                 (got {actual_len} events, expected {expected_len})"
+            ),
+            Self::UserL2ToL1Logs {
+                first_mismatch_index,
+                expected_len,
+                actual_len,
+            } => write!(
+                formatter,
+                "user L2-to-L1 logs differ starting from #{first_mismatch_index} \
+                 (got {actual_len} logs, expected {expected_len})"
+            ),
+            Self::SystemLogs {
+                first_mismatch_index,
+                expected_len,
+                actual_len,
+            } => write!(
+                formatter,
+                "system logs differ starting from #{first_mismatch_index} \
+                 (got {actual_len} logs, expected {expected_len})"
+            ),
+            Self::BootloaderMemory {
+                expected_hash,
+                actual_hash,
+            } => write!(
+                formatter,
+                "bootloader memory hash is {actual_hash:?}, expected {expected_hash:?}"
+            ),
+        }
+    }
+}
+
+/// Result of replaying a single L1 batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1BatchDiff {
+    pub l1_batch_number: L1BatchNumber,
+    pub mismatches: Vec<L1BatchMismatch>,
+}
+
+impl L1BatchDiff {
+    /// Returns `true` if the replayed batch matches the persisted one.
+    pub fn is_empty(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Compares the replayed batch with the persisted one. `mismatches` are discrepancies
+    /// observed during transaction execution (e.g., rejected transactions).
+    pub(super) fn new(
+        stored: &StoredL1Batch,
+        finished_batch: &FinishedL1Batch,
+        mut mismatches: Vec<L1BatchMismatch>,
+    ) -> Self {
+        let execution_state = &finished_batch.final_execution_state;
+
+        let mut written_slots = HashMap::new();
+        for log in &execution_state.storage_log_queries {
+            if log.log_type != StorageLogQueryType::Read {
+                let query = &log.log_query;
+                let key =
+                    StorageKey::new(AccountTreeId::new(query.address), u256_to_h256(query.key));
+                written_slots.insert(key, u256_to_h256(query.written_value));
+            }
+        }
+        let mut slot_mismatches: Vec<_> = stored
+            .touched_slots
+            .iter()
+            .filter(|(key, value)| written_slots.get(key) != Some(value))
+            .map(|(key, value)| L1BatchMismatch::StorageSlot {
+                key: *key,
+                expected: Some(*value),
+                actual: written_slots.get(key).copied(),
+            })
+            .collect();
+        slot_mismatches.extend(
+            written_slots
+                .iter()
+                .filter(|(key, _)| !stored.touched_slots.contains_key(key))
+                .map(|(key, value)| L1BatchMismatch::StorageSlot {
+                    key: *key,
+                    expected: None,
+                    actual: Some(*value),
+                }),
+        );
+        slot_mismatches.sort_unstable_by_key(|mismatch| match mismatch {
+            L1BatchMismatch::StorageSlot { key, .. } => key.hashed_key(),
+            _ => unreachable!(),
+        });
+        mismatches.extend(slot_mismatches);
+
+        let expected_events = &stored.events_queue;
+        let actual_events = &execution_state.deduplicated_events_logs;
+        if let Some(first_mismatch_index) = first_mismatch_index(expected_events, actual_events) {
+            mismatches.push(L1BatchMismatch::Events {
+                first_mismatch_index,
+                expected_len: expected_events.len(),
+                actual_len: actual_events.len(),
+            });
+        }
+        let expected_logs = &stored.header.l2_to_l1_logs;
+        let actual_logs = &execution_state.user_l2_to_l1_logs;
+        if let Some(first_mismatch_index) = first_mismatch_index(expected_logs, actual_logs) {
+            mismatches.push(L1BatchMismatch::UserL2ToL1Logs {
+                first_mismatch_index,
+                expected_len: expected_logs.len(),
+                actual_len: actual_logs.len(),
+            });
+        }
+        let expected_logs = &stored.header.system_logs;
+        let actual_logs = &execution_state.system_logs;
+        if let Some(first_mismatch_index) = first_mismatch_index(expected_logs, actual_logs) {
+            mismatches.push(L1BatchMismatch::SystemLogs {
+                first_mismatch_index,
+                expected_len: expected_logs.len(),
+                actual_len: actual_logs.len(),
+            });
+        }
+
+        let expected_hash = bootloader_memory_hash(&stored.bootloader_memory);
+        let actual_memory = finished_batch.final_bootloader_memory.as_deref();
+        let actual_hash = bootloader_memory_hash(actual_memory.unwrap_or_default());
+        if expected_hash != actual_hash {
+            mismatches.push(L1BatchMismatch::BootloaderMemory {
+                expected_hash,
+                actual_hash,
+            });
+        }
+
+        Self {
+            l1_batch_number: stored.header.number,
+            mismatches,
+        }
+    }
+}
+
+fn first_mismatch_index<T: PartialEq>(expected: &[T], actual: &[T]) -> Option<usize> {
+    let index = expected
+        .iter()
+        .zip(actual)
+        .position(|(expected, actual)| expected != actual);
+    index.or_else(|| (expected.len() != actual.len()).then(|| expected.len().min(actual.len())))
+}
+
+fn bootloader_memory_hash(memory: &[(usize, U256)]) -> H256 {
+    let mut bytes = Vec::with_capacity(memory.len() * 40);
+    for &(index, value) in memory {
+        bytes.extend_from_slice(&(index as u64).to_be_bytes());
+        bytes.extend_from_slice(u256_to_h256(value).as_bytes());
+    }
+    H256(keccak256(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+        zk_evm_types::Timestamp,
+        Address, ProtocolVersionId, StorageLogQuery,
+    };
+
+    use super::*;
+    use crate::state_keeper::tests::default_vm_block_result;
+
+    fn storage_write(key: &StorageKey, value: H256) -> StorageLogQuery {
+        StorageLogQuery {
+            log_query: LogQuery {
+                timestamp: Timestamp(0),
+                tx_number_in_block: 0,
+                aux_byte: 0,
+                shard_id: 0,
+                address: *key.address(),
+                key: U256::from_big_endian(key.key().as_bytes()),
+                read_value: U256::zero(),
+                written_value: U256::from_big_endian(value.as_bytes()),
+                rw_flag: true,
+                rollback: false,
+                is_service: false,
+            },
+            log_type: StorageLogQueryType::InitialWrite,
+        }
+    }
+
+    fn stored_batch(finished_batch: &FinishedL1Batch) -> StoredL1Batch {
+        let execution_state = &finished_batch.final_execution_state;
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            1,
+            Default::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.l2_to_l1_logs = execution_state.user_l2_to_l1_logs.clone();
+        header.system_logs = execution_state.system_logs.clone();
+
+        StoredL1Batch {
+            header,
+            bootloader_memory: finished_batch.final_bootloader_memory.clone().unwrap(),
+            events_queue: execution_state.deduplicated_events_logs.clone(),
+            touched_slots: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn replayed_batch_matches_stored_batch() {
+        let key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        let mut finished_batch = default_vm_block_result();
+        finished_batch.final_execution_state.storage_log_queries =
+            vec![storage_write(&key, H256::repeat_byte(1))];
+        finished_batch.final_execution_state.user_l2_to_l1_logs =
+            vec![UserL2ToL1Log(L2ToL1Log::default())];
+        finished_batch.final_bootloader_memory = Some(vec![(0, U256::one())]);
+
+        let mut stored = stored_batch(&finished_batch);
+        stored.touched_slots.insert(key, H256::repeat_byte(1));
+        let diff = L1BatchDiff::new(&stored, &finished_batch, vec![]);
+        assert!(diff.is_empty(), "{diff:?}");
+    }
+
+    #[test]
+    fn replayed_batch_mismatches() {
+        let key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        let other_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        let mut finished_batch = default_vm_block_result();
+        finished_batch.final_execution_state.storage_log_queries =
+            vec![storage_write(&key, H256::repeat_byte(2))];
+        let mut stored = stored_batch(&finished_batch);
+        stored.touched_slots.insert(key, H256::repeat_byte(1));
+        stored.touched_slots.insert(other_key, H256::repeat_byte(1));
+        stored.header.l2_to_l1_logs = vec![UserL2ToL1Log(L2ToL1Log::default())];
+        stored.bootloader_memory = vec![(0, U256::one())];
+
+        let rejected_tx = L1BatchMismatch::RejectedTx {
+            hash: H256::repeat_byte(3),
+            reason: "oops".to_owned(),
+        };
+        let diff = L1BatchDiff::new(&stored, &finished_batch, vec![rejected_tx.clone()]);
+        assert_eq!(diff.l1_batch_number, L1BatchNumber(1));
+
+        let mut expected_slot_mismatches = vec![
+            L1BatchMismatch::StorageSlot {
+                key,
+                expected: Some(H256::repeat_byte(1)),
+                actual: Some(H256::repeat_byte(2)),
+            },
+            L1BatchMismatch::StorageSlot {
+                key: other_key,
+                expected: Some(H256::repeat_byte(1)),
+                actual: None,
+            },
+        ];
+        expected_slot_mismatches.sort_unstable_by_key(|mismatch| match mismatch {
+            L1BatchMismatch::StorageSlot { key, .. } => key.hashed_key(),
+            _ => unreachable!(),
+        });
+        assert_eq!(diff.mismatches[0], rejected_tx);
+        assert_eq!(diff.mismatches[1..3], expected_slot_mismatches);
+        assert_eq!(
+            diff.mismatches[3],
+            L1BatchMismatch::UserL2ToL1Logs {
+                first_mismatch_index: 0,
+                expected_len: 1,
+                actual_len: 0,
+            }
+        );
+        assert_matches::assert_matches!(
+            diff.mismatches[4],
+            L1BatchMismatch::BootloaderMemory { .. }
+        );
+        assert_eq!(diff.mismatches.len(), 5);
+    }
+}
@@ -0,0 +1,365 @@
+//! State keeper I/O replaying already sealed L1 batches without persisting anything.
+
+use std::{collections::VecDeque, mem, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use multivm::interface::{FinishedL1Batch, L1BatchEnv, SystemEnv};
+use tokio::sync::mpsc;
+use vm_utils::storage::L1BatchParamsProvider;
+use zksync_dal::ConnectionPool;
+use zksync_types::{
+    block::MiniblockExecutionData, protocol_version::ProtocolUpgradeTx,
+    witness_block_state::WitnessBlockState, L1BatchNumber, L2ChainId, MiniblockNumber,
+    ProtocolVersionId, Transaction,
+};
+
+use self::diff::StoredL1Batch;
+pub use self::diff::{L1BatchDiff, L1BatchMismatch};
+use crate::state_keeper::{
+    io::{MiniblockParams, PendingBatchData, StateKeeperIO},
+    seal_criteria::IoSealCriteria,
+    updates::UpdatesManager,
+};
+
+mod diff;
+
+/// State keeper I/O that feeds already sealed L1 batches from Postgres to the state keeper, and compares
+/// the re-execution results with the persisted batch data instead of sealing anything.
+///
+/// Batches are fed with their original miniblock boundaries, so the state keeper should be used with
+/// [`NoopSealer`](crate::state_keeper::seal_criteria::NoopSealer). For each replayed batch, an [`L1BatchDiff`]
+/// is sent to the receiver returned from [`Self::new()`]. The batch executor must execute each batch
+/// on top of the historical state; see [`MainBatchExecutor::with_historical_storage()`].
+///
+/// [`MainBatchExecutor::with_historical_storage()`]: crate::state_keeper::MainBatchExecutor::with_historical_storage()
+#[derive(Debug)]
+pub struct ReplayIO {
+    pool: ConnectionPool,
+    l1_batch_params_provider: L1BatchParamsProvider,
+    chain_id: L2ChainId,
+    validation_computational_gas_limit: u32,
+    current_l1_batch_number: L1BatchNumber,
+    last_l1_batch_number: L1BatchNumber,
+    current_miniblock_number: MiniblockNumber,
+    /// Miniblocks of the current L1 batch that were not started yet.
+    pending_miniblocks: VecDeque<MiniblockExecutionData>,
+    /// Transactions of the current miniblock that were not fed to the state keeper yet.
+    pending_txs: VecDeque<Transaction>,
+    /// Parameters of the fictive miniblock concluding the current L1 batch.
+    fictive_miniblock: Option<(MiniblockNumber, MiniblockParams)>,
+    execution_mismatches: Vec<L1BatchMismatch>,
+    diffs_sender: mpsc::UnboundedSender<L1BatchDiff>,
+}
+
+impl ReplayIO {
+    /// Creates an I/O replaying L1 batches in the specified inclusive range. Returns the I/O together with
+    /// the receiver of diffs for the replayed batches.
+    pub async fn new(
+        pool: ConnectionPool,
+        first_l1_batch_number: L1BatchNumber,
+        last_l1_batch_number: L1BatchNumber,
+        validation_computational_gas_limit: u32,
+        chain_id: L2ChainId,
+    ) -> anyhow::Result<(Self, mpsc::UnboundedReceiver<L1BatchDiff>)> {
+        anyhow::ensure!(
+            first_l1_batch_number > L1BatchNumber(0),
+            "Genesis L1 batch cannot be replayed"
+        );
+        anyhow::ensure!(
+            first_l1_batch_number <= last_l1_batch_number,
+            "Invalid L1 batch range to replay: {first_l1_batch_number}..={last_l1_batch_number}"
+        );
+
+        let mut storage = pool.access_storage_tagged("state_keeper").await?;
+        let sealed_l1_batch_number = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .context("failed getting sealed L1 batch number")?
+            .context("Postgres contains no L1 batches")?;
+        anyhow::ensure!(
+            last_l1_batch_number <= sealed_l1_batch_number,
+            "Cannot replay L1 batch #{last_l1_batch_number}: last sealed L1 batch is #{sealed_l1_batch_number}"
+        );
+        let l1_batch_params_provider = L1BatchParamsProvider::new(&mut storage)
+            .await
+            .context("failed initializing L1 batch params provider")?;
+        let current_miniblock_number = l1_batch_params_provider
+            .load_number_of_first_miniblock_in_batch(&mut storage, first_l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed loading first miniblock for L1 batch #{first_l1_batch_number}")
+            })?
+            .with_context(|| {
+                format!("no miniblocks persisted for L1 batch #{first_l1_batch_number}")
+            })?;
+        drop(storage);
+
+        let (diffs_sender, diffs_receiver) = mpsc::unbounded_channel();
+        let this = Self {
+            pool,
+            l1_batch_params_provider,
+            chain_id,
+            validation_computational_gas_limit,
+            current_l1_batch_number: first_l1_batch_number,
+            last_l1_batch_number,
+            current_miniblock_number,
+            pending_miniblocks: VecDeque::new(),
+            pending_txs: VecDeque::new(),
+            fictive_miniblock: None,
+            execution_mismatches: Vec::new(),
+            diffs_sender,
+        };
+        Ok((this, diffs_receiver))
+    }
+
+    async fn load_batch(&mut self) -> anyhow::Result<PendingBatchData> {
+        let l1_batch_number = self.current_l1_batch_number;
+        let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+        let first_miniblock_in_batch = self
+            .l1_batch_params_provider
+            .load_first_miniblock_in_batch(&mut storage, l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed loading first miniblock for L1 batch #{l1_batch_number}")
+            })?
+            .with_context(|| format!("no miniblocks persisted for L1 batch #{l1_batch_number}"))?;
+        let (system_env, l1_batch_env) = self
+            .l1_batch_params_provider
+            .load_l1_batch_params(
+                &mut storage,
+                &first_miniblock_in_batch,
+                self.validation_computational_gas_limit,
+                self.chain_id,
+            )
+            .await
+            .with_context(|| format!("failed loading params for L1 batch #{l1_batch_number}"))?;
+        let pending_miniblocks = storage
+            .transactions_dal()
+            .get_miniblocks_to_execute_for_l1_batch(l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed loading miniblocks for L1 batch #{l1_batch_number}")
+            })?;
+
+        let (_, last_miniblock_number) = storage
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed getting miniblock range for L1 batch #{l1_batch_number}")
+            })?
+            .with_context(|| format!("no miniblocks persisted for L1 batch #{l1_batch_number}"))?;
+        let fictive_miniblock = storage
+            .blocks_dal()
+            .get_miniblock_header(last_miniblock_number)
+            .await
+            .with_context(|| format!("failed loading miniblock #{last_miniblock_number}"))?
+            .with_context(|| format!("miniblock #{last_miniblock_number} disappeared"))?;
+        self.fictive_miniblock = Some((
+            fictive_miniblock.number,
+            MiniblockParams {
+                timestamp: fictive_miniblock.timestamp,
+                virtual_blocks: fictive_miniblock.virtual_blocks,
+            },
+        ));
+
+        Ok(PendingBatchData {
+            l1_batch_env,
+            system_env,
+            pending_miniblocks,
+        })
+    }
+
+    fn skip_pending_txs(&mut self) {
+        let pending_miniblocks = mem::take(&mut self.pending_miniblocks);
+        let skipped_tx_count = mem::take(&mut self.pending_txs).len()
+            + pending_miniblocks
+                .iter()
+                .map(|miniblock| miniblock.txs.len())
+                .sum::<usize>();
+        if skipped_tx_count > 0 {
+            self.execution_mismatches.push(L1BatchMismatch::SkippedTxs {
+                count: skipped_tx_count,
+            });
+        }
+    }
+}
+
+impl IoSealCriteria for ReplayIO {
+    fn should_seal_l1_batch_unconditionally(&mut self, _manager: &UpdatesManager) -> bool {
+        self.pending_txs.is_empty() && self.pending_miniblocks.is_empty()
+    }
+
+    fn should_seal_miniblock(&mut self, _manager: &UpdatesManager) -> bool {
+        self.pending_txs.is_empty() && !self.pending_miniblocks.is_empty()
+    }
+}
+
+#[async_trait]
+impl StateKeeperIO for ReplayIO {
+    fn current_l1_batch_number(&self) -> L1BatchNumber {
+        self.current_l1_batch_number
+    }
+
+    fn current_miniblock_number(&self) -> MiniblockNumber {
+        self.current_miniblock_number
+    }
+
+    async fn load_pending_batch(&mut self) -> anyhow::Result<Option<PendingBatchData>> {
+        // All batches are fed transaction by transaction, so that mismatches are reported rather than
+        // failing the state keeper as it happens for pending batches.
+        Ok(None)
+    }
+
+    async fn wait_for_new_batch_params(
+        &mut self,
+        max_wait: Duration,
+    ) -> anyhow::Result<Option<(SystemEnv, L1BatchEnv)>> {
+        if self.current_l1_batch_number > self.last_l1_batch_number {
+            // All batches are replayed; wait for the state keeper to be stopped.
+            tokio::time::sleep(max_wait).await;
+            return Ok(None);
+        }
+
+        let PendingBatchData {
+            l1_batch_env,
+            system_env,
+            pending_miniblocks,
+        } = self.load_batch().await?;
+        let mut pending_miniblocks = VecDeque::from(pending_miniblocks);
+        let first_miniblock = pending_miniblocks.pop_front().with_context(|| {
+            format!(
+                "L1 batch #{} doesn't contain transactions",
+                self.current_l1_batch_number
+            )
+        })?;
+        anyhow::ensure!(
+            first_miniblock.number.0 == l1_batch_env.first_l2_block.number,
+            "First miniblock with transactions #{} in L1 batch #{} is not the first miniblock in the batch",
+            first_miniblock.number,
+            self.current_l1_batch_number
+        );
+
+        tracing::info!(
+            "Replaying L1 batch #{} with {} miniblocks",
+            self.current_l1_batch_number,
+            pending_miniblocks.len() + 1
+        );
+        self.current_miniblock_number = first_miniblock.number;
+        self.pending_txs = first_miniblock.txs.into();
+        self.pending_miniblocks = pending_miniblocks;
+        Ok(Some((system_env, l1_batch_env)))
+    }
+
+    async fn wait_for_new_miniblock_params(
+        &mut self,
+        _max_wait: Duration,
+    ) -> anyhow::Result<Option<MiniblockParams>> {
+        if let Some(miniblock) = self.pending_miniblocks.pop_front() {
+            self.current_miniblock_number = miniblock.number;
+            self.pending_txs = miniblock.txs.into();
+            return Ok(Some(MiniblockParams {
+                timestamp: miniblock.timestamp,
+                virtual_blocks: miniblock.virtual_blocks,
+            }));
+        }
+
+        let (number, params) = self
+            .fictive_miniblock
+            .take()
+            .context("requested params for miniblock after the fictive one")?;
+        self.current_miniblock_number = number;
+        Ok(Some(params))
+    }
+
+    async fn wait_for_next_tx(&mut self, _max_wait: Duration) -> Option<Transaction> {
+        self.pending_txs.pop_front()
+    }
+
+    async fn rollback(&mut self, tx: Transaction) {
+        // The state keeper seals the L1 batch immediately after the rollback, so the remaining transactions
+        // won't be executed, and the next requested miniblock will be the fictive one.
+        self.pending_txs.push_front(tx);
+        self.skip_pending_txs();
+    }
+
+    async fn reject(&mut self, tx: &Transaction, error: &str) -> anyhow::Result<()> {
+        tracing::warn!(
+            "Transaction {:?} in L1 batch #{} was rejected during replay: {error}",
+            tx.hash(),
+            self.current_l1_batch_number
+        );
+        self.execution_mismatches.push(L1BatchMismatch::RejectedTx {
+            hash: tx.hash(),
+            reason: error.to_owned(),
+        });
+        Ok(())
+    }
+
+    async fn seal_miniblock(&mut self, _updates_manager: &UpdatesManager) {
+        // Nothing is persisted during replay.
+    }
+
+    async fn seal_l1_batch(
+        &mut self,
+        _witness_block_state: Option<WitnessBlockState>,
+        _updates_manager: UpdatesManager,
+        l1_batch_env: &L1BatchEnv,
+        finished_batch: FinishedL1Batch,
+    ) -> anyhow::Result<()> {
+        assert_eq!(
+            l1_batch_env.number, self.current_l1_batch_number,
+            "Replayed L1 batch number mismatch"
+        );
+
+        self.skip_pending_txs();
+        let execution_mismatches = mem::take(&mut self.execution_mismatches);
+        self.fictive_miniblock = None;
+
+        let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+        let stored_batch = StoredL1Batch::load(&mut storage, l1_batch_env.number).await?;
+        drop(storage);
+        let diff = L1BatchDiff::new(&stored_batch, &finished_batch, execution_mismatches);
+        if diff.is_empty() {
+            tracing::info!(
+                "Replayed L1 batch #{} matches the stored one",
+                l1_batch_env.number
+            );
+        } else {
+            tracing::warn!(
+                "Replayed L1 batch #{} has {} mismatch(es) with the stored one",
+                l1_batch_env.number,
+                diff.mismatches.len()
+            );
+        }
+        // The receiver may be dropped if the replay is aborted; this is fine.
+        self.diffs_sender.send(diff).ok();
+
+        self.current_l1_batch_number += 1;
+        self.current_miniblock_number += 1;
+        Ok(())
+    }
+
+    async fn load_previous_batch_version_id(&mut self) -> anyhow::Result<ProtocolVersionId> {
+        let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+        let prev_l1_batch_number = self.current_l1_batch_number - 1;
+        self.l1_batch_params_provider
+            .load_l1_batch_protocol_version(&mut storage, prev_l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("failed loading protocol version for L1 batch #{prev_l1_batch_number}")
+            })?
+            .with_context(|| format!("L1 batch #{prev_l1_batch_number} misses protocol version"))
+    }
+
+    async fn load_upgrade_tx(
+        &mut self,
+        _version_id: ProtocolVersionId,
+    ) -> anyhow::Result<Option<ProtocolUpgradeTx>> {
+        // Upgrade transactions are persisted together with other transactions in the batch,
+        // so they are fed to the state keeper via `wait_for_next_tx()`.
+        Ok(None)
+    }
+}
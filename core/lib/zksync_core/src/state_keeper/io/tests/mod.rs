@@ -1,11 +1,14 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
+use assert_matches::assert_matches;
 use futures::FutureExt;
 use multivm::utils::derive_base_fee_and_gas_per_pubdata;
+use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::ConnectionPool;
 use zksync_mempool::L2TxFilter;
 use zksync_types::{
+    api::TransactionStatus,
     block::{BlockGasCount, MiniblockHasher},
     fee::TransactionExecutionMetrics,
     fee_model::{BatchFeeInput, PubdataIndependentBatchFeeModelInput},
@@ -25,8 +28,9 @@ use crate::{
             default_l1_batch_env, default_system_env, default_vm_block_result, Query,
         },
         updates::{MiniblockSealCommand, MiniblockUpdates, UpdatesManager},
+        TxAccessPolicy,
     },
-    utils::testonly::prepare_recovery_snapshot,
+    utils::testonly::{create_l2_transaction, prepare_recovery_snapshot},
 };
 
 mod tester;
@@ -675,3 +679,60 @@ async fn different_timestamp_for_miniblocks_in_same_batch() {
         .expect("no new miniblock params");
     assert!(miniblock_params.timestamp > current_timestamp);
 }
+
+#[tokio::test]
+async fn transactions_not_allowed_by_access_policy_are_rejected() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let tester = Tester::new();
+    tester.genesis(&connection_pool).await;
+    let tx_filter = l2_tx_filter(
+        &tester.create_batch_fee_input_provider().await,
+        ProtocolVersionId::latest().into(),
+    )
+    .await;
+
+    let denied_address = Address::repeat_byte(1);
+    let mut denied_tx =
+        create_l2_transaction(tx_filter.fee_per_gas, tx_filter.gas_per_pubdata.into());
+    denied_tx.common_data.initiator_address = denied_address;
+    let allowed_tx = create_l2_transaction(tx_filter.fee_per_gas, tx_filter.gas_per_pubdata.into());
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    for tx in [&denied_tx, &allowed_tx] {
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(tx.clone(), TransactionExecutionMetrics::default())
+            .await;
+    }
+
+    let config = StateKeeperConfig {
+        tx_sender_denylist: vec![denied_address],
+        ..StateKeeperConfig::for_tests()
+    };
+    let access_policy = Arc::new(TxAccessPolicy::new(&config).unwrap());
+    let (mut mempool, mut guard) = tester
+        .create_test_mempool_io_with_access_policy(connection_pool.clone(), 1, access_policy)
+        .await;
+    guard.insert(
+        vec![denied_tx.clone().into(), allowed_tx.clone().into()],
+        Default::default(),
+    );
+    mempool
+        .wait_for_new_batch_params(Duration::from_secs(10))
+        .await
+        .unwrap()
+        .expect("no batch params generated");
+
+    let tx = mempool
+        .wait_for_next_tx(Duration::from_secs(10))
+        .await
+        .expect("no transaction returned");
+    assert_eq!(tx.hash(), allowed_tx.hash());
+
+    let denied_tx_details = storage
+        .transactions_web3_dal()
+        .get_transaction_details(denied_tx.hash())
+        .await
+        .unwrap()
+        .expect("denied transaction is not persisted");
+    assert_matches!(denied_tx_details.status, TransactionStatus::Failed);
+}
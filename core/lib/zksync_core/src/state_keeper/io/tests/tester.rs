@@ -3,10 +3,13 @@
 use std::{slice, sync::Arc, time::Duration};
 
 use multivm::vm_latest::constants::BLOCK_GAS_LIMIT;
-use zksync_config::{configs::chain::StateKeeperConfig, GasAdjusterConfig};
+use zksync_config::{
+    configs::{chain::StateKeeperConfig, eth_sender::PubdataSendingMode},
+    GasAdjusterConfig,
+};
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::ConnectionPool;
-use zksync_eth_client::clients::MockEthereum;
+use zksync_eth_client::{clients::MockEthereum, BaseFees};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{
     block::MiniblockHeader,
@@ -23,7 +26,7 @@ use crate::{
     fee_model::MainNodeFeeInputProvider,
     genesis::create_genesis_l1_batch,
     l1_gas_price::GasAdjuster,
-    state_keeper::{io::MiniblockSealer, MempoolGuard, MempoolIO},
+    state_keeper::{io::MiniblockSealer, MempoolGuard, MempoolIO, TxAccessPolicy},
     utils::testonly::{
         create_l1_batch, create_l2_transaction, create_miniblock, execute_l2_transaction,
     },
@@ -45,8 +48,15 @@ impl Tester {
     }
 
     async fn create_gas_adjuster(&self) -> GasAdjuster {
-        let eth_client =
-            MockEthereum::default().with_fee_history(vec![0, 4, 6, 8, 7, 5, 5, 8, 10, 9]);
+        let block_fees = vec![0, 4, 6, 8, 7, 5, 5, 8, 10, 9];
+        let base_fees = block_fees
+            .into_iter()
+            .map(|base_fee_per_gas| BaseFees {
+                base_fee_per_gas,
+                base_fee_per_blob_gas: 1.into(), // Not used in tests
+            })
+            .collect();
+        let eth_client = MockEthereum::default().with_fee_history(base_fees);
 
         let gas_adjuster_config = GasAdjusterConfig {
             default_priority_fee_per_gas: 10,
@@ -57,11 +67,19 @@ impl Tester {
             internal_enforced_l1_gas_price: None,
             poll_period: 10,
             max_l1_gas_price: None,
+            max_blob_base_fee_samples: 10,
+            internal_pubdata_pricing_multiplier: 1.0,
+            max_blob_base_fee: None,
+            min_blob_base_fee: None,
         };
 
-        GasAdjuster::new(Arc::new(eth_client), gas_adjuster_config)
-            .await
-            .unwrap()
+        GasAdjuster::new(
+            Arc::new(eth_client),
+            gas_adjuster_config,
+            PubdataSendingMode::Calldata,
+        )
+        .await
+        .unwrap()
     }
 
     pub(super) async fn create_batch_fee_input_provider(&self) -> MainNodeFeeInputProvider {
@@ -83,6 +101,20 @@ impl Tester {
         &self,
         pool: ConnectionPool,
         miniblock_sealer_capacity: usize,
+    ) -> (MempoolIO, MempoolGuard) {
+        self.create_test_mempool_io_with_access_policy(
+            pool,
+            miniblock_sealer_capacity,
+            Arc::default(),
+        )
+        .await
+    }
+
+    pub(super) async fn create_test_mempool_io_with_access_policy(
+        &self,
+        pool: ConnectionPool,
+        miniblock_sealer_capacity: usize,
+        access_policy: Arc<TxAccessPolicy>,
     ) -> (MempoolIO, MempoolGuard) {
         let gas_adjuster = Arc::new(self.create_gas_adjuster().await);
         let batch_fee_input_provider = MainNodeFeeInputProvider::new(
@@ -117,6 +149,7 @@ impl Tester {
             l2_erc20_bridge_addr,
             BLOCK_GAS_LIMIT,
             L2ChainId::from(270),
+            access_policy,
         )
         .await
         .unwrap();
@@ -723,6 +723,13 @@ impl ZkSyncStateKeeper {
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    l1_tx_count: usize::from(tx.is_l1()),
+                    first_tx_received_timestamp_ms: tx.received_timestamp_ms,
+                    events: if self.sealer.uses_tx_events() {
+                        tx_result.logs.events.clone()
+                    } else {
+                        vec![]
+                    },
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -734,6 +741,12 @@ impl ZkSyncStateKeeper {
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    l1_tx_count: tx_data.l1_tx_count
+                        + updates_manager.pending_l1_transactions_len(),
+                    first_tx_received_timestamp_ms: updates_manager
+                        .first_tx_received_timestamp_ms()
+                        .unwrap_or(tx.received_timestamp_ms),
+                    events: vec![],
                 };
 
                 self.sealer.should_seal_l1_batch(
@@ -1,4 +1,9 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use multivm::utils::derive_base_fee_and_gas_per_pubdata;
@@ -12,7 +17,9 @@ use zksync_mempool::L2TxFilter;
 use zksync_types::H256;
 use zksync_types::{get_nonce_key, Address, Nonce, Transaction, VmVersion};
 
-use super::{metrics::KEEPER_METRICS, types::MempoolGuard};
+use super::{
+    mempool_snapshot::MempoolSnapshotStorage, metrics::KEEPER_METRICS, types::MempoolGuard,
+};
 use crate::{fee_model::BatchFeeModelInputProvider, utils::pending_protocol_version};
 
 /// Creates a mempool filter for L2 transactions based on the current L1 gas price.
@@ -40,6 +47,8 @@ pub struct MempoolFetcher {
     sync_interval: Duration,
     sync_batch_size: usize,
     stuck_tx_timeout: Option<Duration>,
+    snapshot_path: Option<PathBuf>,
+    snapshot_interval: Duration,
     #[cfg(test)]
     transaction_hashes_sender: mpsc::UnboundedSender<Vec<H256>>,
 }
@@ -58,6 +67,8 @@ impl MempoolFetcher {
             sync_interval: config.sync_interval(),
             sync_batch_size: config.sync_batch_size,
             stuck_tx_timeout: config.remove_stuck_txs.then(|| config.stuck_tx_timeout()),
+            snapshot_path: config.snapshot_path.as_ref().map(PathBuf::from),
+            snapshot_interval: config.snapshot_interval(),
             #[cfg(test)]
             transaction_hashes_sender: mpsc::unbounded_channel().0,
         }
@@ -78,8 +89,18 @@ impl MempoolFetcher {
             .reset_mempool()
             .await
             .context("failed resetting mempool")?;
+        let snapshot_storage = match &self.snapshot_path {
+            Some(path) => {
+                let snapshot_storage = MempoolSnapshotStorage::open(path.clone()).await?;
+                self.restore_snapshot(&mut storage, &snapshot_storage)
+                    .await?;
+                Some(snapshot_storage)
+            }
+            None => None,
+        };
         drop(storage);
 
+        let mut last_snapshot_at = Instant::now();
         loop {
             if *stop_receiver.borrow() {
                 tracing::info!("Stop signal received, mempool is shutting down");
@@ -88,6 +109,13 @@ impl MempoolFetcher {
             let latency = KEEPER_METRICS.mempool_sync.start();
             let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
             let mempool_info = self.mempool.get_mempool_info();
+            if !mempool_info.released_transactions.is_empty() {
+                storage
+                    .transactions_dal()
+                    .reset_txs_in_mempool(&mempool_info.released_transactions)
+                    .await
+                    .context("failed resetting transactions released by mempool")?;
+            }
             let protocol_version = pending_protocol_version(&mut storage)
                 .await
                 .context("failed getting pending protocol version")?;
@@ -121,10 +149,76 @@ impl MempoolFetcher {
             self.mempool.insert(transactions, nonces);
             latency.observe();
 
+            if let Some(snapshot_storage) = &snapshot_storage {
+                if last_snapshot_at.elapsed() >= self.snapshot_interval {
+                    self.save_snapshot(snapshot_storage).await?;
+                    last_snapshot_at = Instant::now();
+                }
+            }
             if all_transactions_loaded {
                 tokio::time::sleep(self.sync_interval).await;
             }
         }
+
+        if let Some(snapshot_storage) = &snapshot_storage {
+            self.save_snapshot(snapshot_storage).await?;
+        }
+        Ok(())
+    }
+
+    /// Restores the mempool from the persisted snapshot. Only transactions that are still pending in Postgres
+    /// are restored; they are marked as present in the mempool, so that they are not loaded from Postgres again.
+    async fn restore_snapshot(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        snapshot_storage: &MempoolSnapshotStorage,
+    ) -> anyhow::Result<()> {
+        let snapshot_storage = snapshot_storage.clone();
+        let snapshot = tokio::task::spawn_blocking(move || snapshot_storage.load())
+            .await
+            .context("panicked loading mempool snapshot")??;
+        let snapshot_len = snapshot.len();
+        let transactions: Vec<_> = snapshot
+            .l1_transactions
+            .into_iter()
+            .map(Transaction::from)
+            .chain(snapshot.l2_transactions.into_iter().map(Transaction::from))
+            .map(|tx| (tx.hash(), tx))
+            .collect();
+        let tx_hashes: Vec<_> = transactions.iter().map(|(hash, _)| *hash).collect();
+
+        let pending_tx_hashes: HashSet<_> = storage
+            .transactions_dal()
+            .mark_txs_as_in_mempool(&tx_hashes)
+            .await
+            .context("failed marking restored transactions as in mempool")?
+            .into_iter()
+            .collect();
+        let transactions: Vec<_> = transactions
+            .into_iter()
+            .filter_map(|(hash, tx)| pending_tx_hashes.contains(&hash).then_some(tx))
+            .collect();
+        let nonces = get_transaction_nonces(storage, &transactions).await?;
+
+        tracing::info!(
+            "Restored {} transactions from mempool snapshot; {} transactions are not pending anymore",
+            transactions.len(),
+            snapshot_len - transactions.len()
+        );
+        self.mempool.insert(transactions, nonces);
+        Ok(())
+    }
+
+    async fn save_snapshot(&self, snapshot_storage: &MempoolSnapshotStorage) -> anyhow::Result<()> {
+        let latency = KEEPER_METRICS.mempool_snapshot_save.start();
+        let snapshot = self.mempool.snapshot();
+        let snapshot_len = snapshot.len();
+        let snapshot_storage = snapshot_storage.clone();
+        tokio::task::spawn_blocking(move || snapshot_storage.save(&snapshot))
+            .await
+            .context("panicked saving mempool snapshot")??;
+        let latency = latency.observe();
+        tracing::debug!("Saved mempool snapshot with {snapshot_len} transactions in {latency:?}");
         Ok(())
     }
 }
@@ -160,8 +254,12 @@ async fn get_transaction_nonces(
 
 #[cfg(test)]
 mod tests {
+    use tempfile::TempDir;
+    use zksync_config::configs::chain::TxOrderingPolicyKind;
+    use zksync_dal::transactions_dal::L2TxSubmissionResult;
+    use zksync_mempool::MempoolSnapshot;
     use zksync_types::{
-        fee::TransactionExecutionMetrics, L2ChainId, MiniblockNumber, PriorityOpId,
+        fee::TransactionExecutionMetrics, l2::L2Tx, L2ChainId, MiniblockNumber, PriorityOpId,
         ProtocolVersionId, StorageLog, H256,
     };
     use zksync_utils::u256_to_h256;
@@ -179,6 +277,11 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        snapshot_path: None,
+        snapshot_interval_ms: None,
+        max_pending_txs_per_account: None,
+        replacement_fee_bump_percent: None,
+        ordering_policy: TxOrderingPolicyKind::Fifo,
     };
 
     #[tokio::test]
@@ -312,6 +415,69 @@ mod tests {
         fetcher_task.await.unwrap().expect("fetcher errored");
     }
 
+    #[tokio::test]
+    async fn syncing_underpriced_replacement_persisted_in_storage() {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+        let config = MempoolConfig {
+            replacement_fee_bump_percent: Some(10),
+            ..TEST_MEMPOOL_CONFIG
+        };
+        let mempool = MempoolGuard::from_storage(&mut storage, &config).await;
+        drop(storage);
+
+        let fee_params_provider = Arc::new(MockBatchFeeParamsProvider::default());
+        let fee_input = fee_params_provider.get_batch_fee_input().await;
+        let (base_fee, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+
+        let mut fetcher =
+            MempoolFetcher::new(mempool.clone(), fee_params_provider, &config, pool.clone());
+        let (tx_hashes_sender, mut tx_hashes_receiver) = mpsc::unbounded_channel();
+        fetcher.transaction_hashes_sender = tx_hashes_sender;
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let fetcher_task = tokio::spawn(fetcher.run(stop_receiver));
+
+        let transaction = create_l2_transaction(base_fee * 2, gas_per_pubdata);
+        let mut storage = pool.access_storage().await.unwrap();
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(transaction.clone(), TransactionExecutionMetrics::default())
+            .await;
+        drop(storage);
+        let tx_hashes = wait_for_new_transactions(&mut tx_hashes_receiver).await;
+        assert_eq!(tx_hashes, [transaction.hash()]);
+
+        // Emulate an underpriced replacement that has raced past the fee bump check in the API server.
+        let mut replacement = transaction;
+        replacement.common_data.fee.max_fee_per_gas = (base_fee * 2 + 1).into();
+        replacement.set_input(H256::random().0.to_vec(), H256::random());
+        let replacement_hash = replacement.hash();
+        let mut storage = pool.access_storage().await.unwrap();
+        let submission_result = storage
+            .transactions_dal()
+            .insert_transaction_l2(replacement, TransactionExecutionMetrics::default())
+            .await;
+        assert_eq!(submission_result, L2TxSubmissionResult::Replaced);
+        drop(storage);
+
+        // The mempool must follow the storage, so that the executed transaction can be sealed.
+        let tx_hashes = wait_for_new_transactions(&mut tx_hashes_receiver).await;
+        assert_eq!(tx_hashes, [replacement_hash]);
+        assert_eq!(mempool.stats().l2_transaction_count, 1);
+        let next_tx = mempool
+            .clone()
+            .next_transaction(&L2TxFilter::default())
+            .unwrap();
+        assert_eq!(next_tx.hash(), replacement_hash);
+
+        stop_sender.send_replace(true);
+        fetcher_task.await.unwrap().expect("fetcher errored");
+    }
+
     #[tokio::test]
     async fn ignoring_transaction_with_old_nonce() {
         let pool = ConnectionPool::constrained_test_pool(1).await;
@@ -365,4 +531,67 @@ mod tests {
         stop_sender.send_replace(true);
         fetcher_task.await.unwrap().expect("fetcher errored");
     }
+
+    #[tokio::test]
+    async fn restoring_mempool_from_snapshot() {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+
+        let fee_params_provider = Arc::new(MockBatchFeeParamsProvider::default());
+        let fee_input = fee_params_provider.get_batch_fee_input().await;
+        let (base_fee, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+        let transaction = create_l2_transaction(base_fee, gas_per_pubdata);
+        let transaction_hash = transaction.hash();
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(transaction.clone(), TransactionExecutionMetrics::default())
+            .await;
+        drop(storage);
+        // This transaction is not present in Postgres, so it must not be restored.
+        let missing_transaction = create_l2_transaction(base_fee, gas_per_pubdata);
+
+        let temp_dir = TempDir::new().unwrap();
+        let snapshot_storage = MempoolSnapshotStorage::open(temp_dir.path().to_owned())
+            .await
+            .unwrap();
+        let snapshot = MempoolSnapshot {
+            l1_transactions: vec![],
+            l2_transactions: vec![transaction, missing_transaction],
+        };
+        snapshot_storage.save(&snapshot).unwrap();
+        drop(snapshot_storage);
+
+        let mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let config = MempoolConfig {
+            snapshot_path: Some(temp_dir.path().to_str().unwrap().to_owned()),
+            ..TEST_MEMPOOL_CONFIG
+        };
+        let mut fetcher =
+            MempoolFetcher::new(mempool.clone(), fee_params_provider, &config, pool.clone());
+        let (tx_hashes_sender, mut tx_hashes_receiver) = mpsc::unbounded_channel();
+        fetcher.transaction_hashes_sender = tx_hashes_sender;
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let fetcher_task = tokio::spawn(fetcher.run(stop_receiver));
+
+        // The restored transaction must not be loaded from Postgres again.
+        let tx_hashes = tx_hashes_receiver.recv().await.unwrap();
+        assert!(tx_hashes.is_empty(), "{tx_hashes:?}");
+        assert_eq!(mempool.stats().l2_transaction_count, 1);
+
+        stop_sender.send_replace(true);
+        fetcher_task.await.unwrap().expect("fetcher errored");
+
+        // The fetcher should persist the mempool on shutdown.
+        let snapshot_storage = MempoolSnapshotStorage::open(temp_dir.path().to_owned())
+            .await
+            .unwrap();
+        let snapshot = snapshot_storage.load().unwrap();
+        assert!(snapshot.l1_transactions.is_empty());
+        let snapshot_hashes: Vec<_> = snapshot.l2_transactions.iter().map(L2Tx::hash).collect();
+        assert_eq!(snapshot_hashes, [transaction_hash]);
+    }
 }
@@ -0,0 +1,161 @@
+//! RocksDB storage for mempool snapshots.
+//!
+//! ## Storage layout
+//!
+//! | Column | Key                                     | Value                  | Description                |
+//! | ------ | --------------------------------------- | ---------------------- | -------------------------- |
+//! | L1 txs | priority op ID (8 bytes, big-endian)    | JSON-serialized `L1Tx` | Pending L1 transactions    |
+//! | L2 txs | initiator address (20 bytes) ++ nonce   | JSON-serialized `L2Tx` | Pending L2 transactions    |
+//! |        | (4 bytes, big-endian)                   |                        |                            |
+//!
+//! Each snapshot fully replaces the previous one.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use zksync_mempool::MempoolSnapshot;
+use zksync_storage::{db::NamedColumnFamily, RocksDB};
+use zksync_types::{l1::L1Tx, l2::L2Tx};
+
+/// Exclusive upper bound for all keys in the snapshot DB.
+const MAX_KEY: [u8; 32] = [u8::MAX; 32];
+
+#[derive(Debug, Clone, Copy)]
+enum MempoolSnapshotColumnFamily {
+    L1Transactions,
+    L2Transactions,
+}
+
+impl NamedColumnFamily for MempoolSnapshotColumnFamily {
+    const DB_NAME: &'static str = "mempool_snapshot";
+    const ALL: &'static [Self] = &[Self::L1Transactions, Self::L2Transactions];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::L1Transactions => "l1_transactions",
+            Self::L2Transactions => "l2_transactions",
+        }
+    }
+}
+
+/// Persistent storage for [`MempoolSnapshot`]s backed by RocksDB.
+///
+/// All methods are blocking and should be wrapped in `spawn_blocking()` in the async context.
+#[derive(Debug, Clone)]
+pub(crate) struct MempoolSnapshotStorage {
+    db: RocksDB<MempoolSnapshotColumnFamily>,
+}
+
+impl MempoolSnapshotStorage {
+    pub async fn open(path: PathBuf) -> anyhow::Result<Self> {
+        tokio::task::spawn_blocking(move || Self::open_sync(&path))
+            .await
+            .context("panicked opening mempool snapshot storage")?
+    }
+
+    fn open_sync(path: &Path) -> anyhow::Result<Self> {
+        let db = RocksDB::new(path).with_context(|| {
+            format!(
+                "failed initializing mempool snapshot RocksDB at `{}`",
+                path.display()
+            )
+        })?;
+        Ok(Self {
+            db: db.with_sync_writes(),
+        })
+    }
+
+    pub fn save(&self, snapshot: &MempoolSnapshot) -> anyhow::Result<()> {
+        let mut write_batch = self.db.new_write_batch();
+        for cf in MempoolSnapshotColumnFamily::ALL {
+            write_batch.delete_range_cf(*cf, &[][..]..&MAX_KEY[..]);
+        }
+        for tx in &snapshot.l1_transactions {
+            let key = tx.common_data.serial_id.0.to_be_bytes();
+            let value = serde_json::to_vec(tx).context("failed serializing L1 transaction")?;
+            write_batch.put_cf(MempoolSnapshotColumnFamily::L1Transactions, &key, &value);
+        }
+        for tx in &snapshot.l2_transactions {
+            let mut key = Vec::with_capacity(24);
+            key.extend_from_slice(tx.initiator_account().as_bytes());
+            key.extend_from_slice(&tx.common_data.nonce.0.to_be_bytes());
+            let value = serde_json::to_vec(tx).context("failed serializing L2 transaction")?;
+            write_batch.put_cf(MempoolSnapshotColumnFamily::L2Transactions, &key, &value);
+        }
+        self.db
+            .write(write_batch)
+            .context("failed writing mempool snapshot to RocksDB")
+    }
+
+    pub fn load(&self) -> anyhow::Result<MempoolSnapshot> {
+        let l1_transactions = self
+            .db
+            .from_iterator_cf(MempoolSnapshotColumnFamily::L1Transactions, &[])
+            .map(|(_, value)| {
+                serde_json::from_slice::<L1Tx>(&value)
+                    .context("failed deserializing L1 transaction")
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let l2_transactions = self
+            .db
+            .from_iterator_cf(MempoolSnapshotColumnFamily::L2Transactions, &[])
+            .map(|(_, value)| {
+                serde_json::from_slice::<L2Tx>(&value)
+                    .context("failed deserializing L2 transaction")
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(MempoolSnapshot {
+            l1_transactions,
+            l2_transactions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zksync_types::Nonce;
+
+    use super::*;
+    use crate::utils::testonly::create_l2_transaction;
+
+    #[tokio::test]
+    async fn saving_and_loading_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = MempoolSnapshotStorage::open(temp_dir.path().to_owned())
+            .await
+            .unwrap();
+        assert!(storage.load().unwrap().is_empty());
+
+        let mut transactions: Vec<_> = (0..3)
+            .map(|nonce| {
+                let mut tx = create_l2_transaction(10, 100);
+                tx.common_data.nonce = Nonce(nonce);
+                tx
+            })
+            .collect();
+        transactions.sort_unstable_by_key(|tx| (tx.initiator_account(), tx.common_data.nonce));
+        let snapshot = MempoolSnapshot {
+            l1_transactions: vec![],
+            l2_transactions: transactions.clone(),
+        };
+        storage.save(&snapshot).unwrap();
+        let loaded_hashes: Vec<_> = storage
+            .load()
+            .unwrap()
+            .l2_transactions
+            .into_iter()
+            .map(|tx| tx.hash())
+            .collect();
+        let expected_hashes: Vec<_> = transactions.iter().map(L2Tx::hash).collect();
+        assert_eq!(loaded_hashes, expected_hashes);
+
+        // A new snapshot should fully replace the previous one.
+        let snapshot = MempoolSnapshot {
+            l1_transactions: vec![],
+            l2_transactions: transactions[..1].to_vec(),
+        };
+        storage.save(&snapshot).unwrap();
+        assert_eq!(storage.load().unwrap().l2_transactions.len(), 1);
+    }
+}
@@ -59,6 +59,9 @@ pub(crate) struct StateKeeperMetrics {
     /// Latency to synchronize the mempool with Postgres.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub mempool_sync: Histogram<Duration>,
+    /// Latency to persist a mempool snapshot.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub mempool_snapshot_save: Histogram<Duration>,
     /// Latency of the state keeper waiting for a transaction.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub waiting_for_tx: Histogram<Duration>,
@@ -372,6 +375,16 @@ pub(super) enum ExecutorCommand {
     FinishBatch,
 }
 
+/// How the state keeper storage was initialized for an L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "mode", rename_all = "snake_case")]
+pub(super) enum StorageInitMode {
+    /// RocksDB was opened in background while the previous L1 batch was being sealed.
+    Preopened,
+    /// Storage was opened and caught up with Postgres after the L1 batch was requested.
+    Sequential,
+}
+
 const GAS_PER_NANOSECOND_BUCKETS: Buckets = Buckets::values(&[
     0.01, 0.03, 0.1, 0.3, 0.5, 0.75, 1., 1.5, 3., 5., 10., 20., 50.,
 ]);
@@ -387,6 +400,15 @@ pub(super) struct ExecutorMetrics {
     /// in the batch executor.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub batch_storage_interaction_duration: Family<InteractionType, Histogram<Duration>>,
+    /// Latency of initializing the state keeper storage for an L1 batch, measured from the moment
+    /// the batch executor is requested for the batch.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub batch_storage_init_latency: Family<StorageInitMode, Histogram<Duration>>,
+    /// Part of opening RocksDB in background that has finished before the storage was requested
+    /// for the next L1 batch. Applying writes of the previous L1 batch is not included since it happens
+    /// after the batch is sealed.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub batch_storage_open_overlap: Histogram<Duration>,
     #[metrics(buckets = GAS_PER_NANOSECOND_BUCKETS)]
     pub computational_gas_per_nanosecond: Histogram<f64>,
     #[metrics(buckets = GAS_PER_NANOSECOND_BUCKETS)]
@@ -10,10 +10,15 @@ use zksync_object_store::ObjectStore;
 
 pub use self::{
     batch_executor::{main_executor::MainBatchExecutor, BatchExecutor},
-    io::{mempool::MempoolIO, MiniblockSealer, MiniblockSealerHandle, StateKeeperIO},
+    io::{
+        mempool::MempoolIO,
+        replay::{L1BatchDiff, L1BatchMismatch, ReplayIO},
+        MiniblockSealer, MiniblockSealerHandle, StateKeeperIO,
+    },
     keeper::ZkSyncStateKeeper,
     mempool_actor::MempoolFetcher,
     seal_criteria::SequencerSealer,
+    tx_access_policy::{TxAccessError, TxAccessPolicy},
     types::MempoolGuard,
 };
 use crate::fee_model::BatchFeeModelInputProvider;
@@ -23,10 +28,12 @@ pub(crate) mod extractors;
 pub(crate) mod io;
 mod keeper;
 mod mempool_actor;
+mod mempool_snapshot;
 pub(crate) mod metrics;
 pub mod seal_criteria;
 #[cfg(test)]
 pub(crate) mod tests;
+mod tx_access_policy;
 pub(crate) mod types;
 pub(crate) mod updates;
 
@@ -41,6 +48,7 @@ pub(crate) async fn create_state_keeper(
     mempool: MempoolGuard,
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     miniblock_sealer_handle: MiniblockSealerHandle,
+    access_policy: Arc<TxAccessPolicy>,
     object_store: Arc<dyn ObjectStore>,
     stop_receiver: watch::Receiver<bool>,
 ) -> ZkSyncStateKeeper {
@@ -65,6 +73,7 @@ pub(crate) async fn create_state_keeper(
         contracts_config.l2_erc20_bridge_addr,
         state_keeper_config.validation_computational_gas_limit,
         network_config.zksync_network_id,
+        access_policy,
     )
     .await
     .expect("Failed initializing main node I/O for state keeper");
@@ -6,7 +6,7 @@
 
 use std::fmt;
 
-use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_config::configs::chain::{SealCriterionConfig, StateKeeperConfig};
 use zksync_types::ProtocolVersionId;
 
 use super::{criteria, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS};
@@ -32,6 +32,12 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
         tx_data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> SealResolution;
+
+    /// Returns whether the sealer uses events emitted by transactions. If `false`, events are not collected
+    /// into [`SealData`] in order to avoid cloning them for each transaction.
+    fn uses_tx_events(&self) -> bool {
+        false
+    }
 }
 
 /// Implementation of [`ConditionalSealer`] used by the main node.
@@ -112,11 +118,16 @@ impl ConditionalSealer for SequencerSealer {
         }
         final_seal_resolution
     }
+
+    fn uses_tx_events(&self) -> bool {
+        self.sealers.iter().any(|sealer| sealer.uses_tx_events())
+    }
 }
 
 impl SequencerSealer {
     pub fn new(config: StateKeeperConfig) -> Self {
-        let sealers = Self::default_sealers();
+        let mut sealers = Self::default_sealers();
+        sealers.extend(config.custom_seal_criteria.iter().map(Self::custom_sealer));
         Self { config, sealers }
     }
 
@@ -138,6 +149,60 @@ impl SequencerSealer {
             Box::new(criteria::GasForBatchTipCriterion),
         ]
     }
+
+    fn custom_sealer(config: &SealCriterionConfig) -> Box<dyn SealCriterion> {
+        let inner: Box<dyn SealCriterion> = match *config {
+            SealCriterionConfig::MaxL1Txs { max_count } => {
+                Box::new(criteria::L1TxsCriterion { max_count })
+            }
+            SealCriterionConfig::MaxBatchAge { max_age_ms } => {
+                Box::new(criteria::BatchAgeCriterion { max_age_ms })
+            }
+            SealCriterionConfig::Event { address, topic } => {
+                Box::new(criteria::EventCriterion { address, topic })
+            }
+        };
+        // Sealers are only created on node initialization, so the number of leaked labels is bounded.
+        let prom_name = Box::leak(format!("custom_{config}").into_boxed_str());
+        Box::new(LabeledCriterion { inner, prom_name })
+    }
+}
+
+/// Custom seal criterion labeled after its config entry in metrics, so that multiple criteria
+/// of the same kind can be distinguished.
+#[derive(Debug)]
+struct LabeledCriterion {
+    inner: Box<dyn SealCriterion>,
+    prom_name: &'static str,
+}
+
+impl SealCriterion for LabeledCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        block_open_timestamp_ms: u128,
+        tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        self.inner.should_seal(
+            config,
+            block_open_timestamp_ms,
+            tx_count,
+            block_data,
+            tx_data,
+            protocol_version,
+        )
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        self.prom_name
+    }
+
+    fn uses_tx_events(&self) -> bool {
+        self.inner.uses_tx_events()
+    }
 }
 
 /// Implementation of [`ConditionalSealer`] that never seals the batch.
@@ -167,3 +232,50 @@ impl ConditionalSealer for NoopSealer {
         SealResolution::NoSeal
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use zksync_types::Address;
+
+    use super::*;
+
+    #[test]
+    fn custom_seal_criteria_are_added_to_sequencer_sealer() {
+        let config = StateKeeperConfig {
+            custom_seal_criteria: vec![
+                SealCriterionConfig::MaxL1Txs {
+                    max_count: NonZeroU32::new(5).unwrap(),
+                },
+                SealCriterionConfig::MaxBatchAge { max_age_ms: 1_000 },
+                SealCriterionConfig::Event {
+                    address: Address::repeat_byte(1),
+                    topic: None,
+                },
+            ],
+            ..StateKeeperConfig::for_tests()
+        };
+        let sealer = SequencerSealer::new(config);
+
+        let default_sealers_count = SequencerSealer::default_sealers().len();
+        assert_eq!(sealer.sealers.len(), default_sealers_count + 3);
+        let custom_names: Vec<_> = sealer.sealers[default_sealers_count..]
+            .iter()
+            .map(|sealer| sealer.prom_criterion_name())
+            .collect();
+        let expected_event_name = format!("custom_event={:?}", Address::repeat_byte(1));
+        assert_eq!(
+            custom_names,
+            [
+                "custom_max_l1_txs=5",
+                "custom_max_batch_age_ms=1000",
+                expected_event_name.as_str()
+            ]
+        );
+        assert!(sealer.uses_tx_events());
+
+        let sealer = SequencerSealer::new(StateKeeperConfig::for_tests());
+        assert!(!sealer.uses_tx_events());
+    }
+}
@@ -0,0 +1,79 @@
+use zksync_types::ProtocolVersionId;
+use zksync_utils::time::millis_since_epoch;
+
+use crate::state_keeper::seal_criteria::{
+    SealCriterion, SealData, SealResolution, StateKeeperConfig,
+};
+
+/// Checks whether we should seal the block because the configured time has passed since the first transaction
+/// in it was received. Measuring from the first transaction (rather than from opening the batch) ensures that
+/// a batch opened long before receiving any transactions isn't sealed right after its first transaction.
+///
+/// Unlike the L1 batch timeout in `TimeoutSealer`, this criterion is only checked after executing a transaction.
+#[derive(Debug)]
+pub struct BatchAgeCriterion {
+    pub max_age_ms: u64,
+}
+
+impl SealCriterion for BatchAgeCriterion {
+    fn should_seal(
+        &self,
+        _config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        block_data: &SealData,
+        _tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let first_tx_timestamp_ms = u128::from(block_data.first_tx_received_timestamp_ms);
+        let age_ms = millis_since_epoch().saturating_sub(first_tx_timestamp_ms);
+        if age_ms >= u128::from(self.max_age_ms) {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "custom_max_batch_age"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_data(first_tx_received_timestamp_ms: u128) -> SealData {
+        SealData {
+            first_tx_received_timestamp_ms: first_tx_received_timestamp_ms as u64,
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn test_batch_age_seal_criterion() {
+        let criterion = BatchAgeCriterion { max_age_ms: 60_000 };
+        let now_ms = millis_since_epoch();
+
+        // The batch was opened long ago, but the age is measured from the first transaction.
+        let resolution = criterion.should_seal(
+            &StateKeeperConfig::default(),
+            now_ms - 3_600_000,
+            1,
+            &block_data(now_ms),
+            &SealData::default(),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        let resolution = criterion.should_seal(
+            &StateKeeperConfig::default(),
+            now_ms - 3_600_000,
+            1,
+            &block_data(now_ms - 60_000),
+            &SealData::default(),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+    }
+}
@@ -0,0 +1,106 @@
+use zksync_types::{Address, ProtocolVersionId, H256};
+
+use crate::state_keeper::seal_criteria::{
+    SealCriterion, SealData, SealResolution, StateKeeperConfig,
+};
+
+/// Checks whether we should seal the block because the transaction has emitted the configured event.
+#[derive(Debug)]
+pub struct EventCriterion {
+    pub address: Address,
+    /// If set, only events with this first topic trigger the seal.
+    pub topic: Option<H256>,
+}
+
+impl SealCriterion for EventCriterion {
+    fn should_seal(
+        &self,
+        _config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        _block_data: &SealData,
+        tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let is_emitted = tx_data.events.iter().any(|event| {
+            event.address == self.address
+                && self
+                    .topic
+                    .map_or(true, |topic| event.indexed_topics.first() == Some(&topic))
+        });
+        if is_emitted {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "custom_event"
+    }
+
+    fn uses_tx_events(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{L1BatchNumber, VmEvent};
+
+    use super::*;
+
+    fn tx_data(address: Address, topic: H256) -> SealData {
+        SealData {
+            events: vec![VmEvent {
+                location: (L1BatchNumber(1), 0),
+                address,
+                indexed_topics: vec![topic],
+                value: vec![],
+            }],
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn test_event_seal_criterion() {
+        let address = Address::repeat_byte(1);
+        let topic = H256::repeat_byte(2);
+        let criterion = EventCriterion {
+            address,
+            topic: Some(topic),
+        };
+
+        let test_cases = [
+            (SealData::default(), SealResolution::NoSeal),
+            (tx_data(address, topic), SealResolution::IncludeAndSeal),
+            (tx_data(address, H256::zero()), SealResolution::NoSeal),
+            (tx_data(Address::zero(), topic), SealResolution::NoSeal),
+        ];
+        for (tx_data, expected_resolution) in test_cases {
+            let resolution = criterion.should_seal(
+                &StateKeeperConfig::default(),
+                Default::default(),
+                1,
+                &SealData::default(),
+                &tx_data,
+                ProtocolVersionId::latest(),
+            );
+            assert_eq!(resolution, expected_resolution);
+        }
+
+        let criterion = EventCriterion {
+            address,
+            topic: None,
+        };
+        let resolution = criterion.should_seal(
+            &StateKeeperConfig::default(),
+            Default::default(),
+            1,
+            &SealData::default(),
+            &tx_data(address, H256::zero()),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+    }
+}
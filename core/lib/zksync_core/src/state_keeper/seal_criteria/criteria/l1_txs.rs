@@ -0,0 +1,73 @@
+use std::num::NonZeroU32;
+
+use zksync_types::ProtocolVersionId;
+
+use crate::state_keeper::seal_criteria::{
+    SealCriterion, SealData, SealResolution, StateKeeperConfig,
+};
+
+/// Checks whether we should seal the block because it contains the configured number of L1 transactions.
+#[derive(Debug)]
+pub struct L1TxsCriterion {
+    pub max_count: NonZeroU32,
+}
+
+impl SealCriterion for L1TxsCriterion {
+    fn should_seal(
+        &self,
+        _config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        block_data: &SealData,
+        _tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        if block_data.l1_tx_count >= self.max_count.get() as usize {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "custom_max_l1_txs"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l1_txs_seal_criterion() {
+        let criterion = L1TxsCriterion {
+            max_count: NonZeroU32::new(2).unwrap(),
+        };
+
+        let resolution = criterion.should_seal(
+            &StateKeeperConfig::default(),
+            Default::default(),
+            5,
+            &SealData {
+                l1_tx_count: 1,
+                ..SealData::default()
+            },
+            &SealData::default(),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        let resolution = criterion.should_seal(
+            &StateKeeperConfig::default(),
+            Default::default(),
+            5,
+            &SealData {
+                l1_tx_count: 2,
+                ..SealData::default()
+            },
+            &SealData::default(),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+    }
+}
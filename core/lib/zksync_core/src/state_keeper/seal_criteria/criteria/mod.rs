@@ -1,12 +1,16 @@
+mod batch_age;
+mod event;
 mod gas;
 mod gas_for_batch_tip;
 mod geometry_seal_criteria;
+mod l1_txs;
 mod pubdata_bytes;
 mod slots;
 mod tx_encoding_size;
 
 pub(in crate::state_keeper) use self::{
-    gas::GasCriterion, gas_for_batch_tip::GasForBatchTipCriterion,
-    geometry_seal_criteria::CircuitsCriterion, pubdata_bytes::PubDataBytesCriterion,
-    slots::SlotsCriterion, tx_encoding_size::TxEncodingSizeCriterion,
+    batch_age::BatchAgeCriterion, event::EventCriterion, gas::GasCriterion,
+    gas_for_batch_tip::GasForBatchTipCriterion, geometry_seal_criteria::CircuitsCriterion,
+    l1_txs::L1TxsCriterion, pubdata_bytes::PubDataBytesCriterion, slots::SlotsCriterion,
+    tx_encoding_size::TxEncodingSizeCriterion,
 };
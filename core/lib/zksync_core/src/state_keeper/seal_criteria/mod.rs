@@ -18,7 +18,7 @@ use zksync_types::{
     block::BlockGasCount,
     fee::TransactionExecutionMetrics,
     tx::tx_execution_info::{DeduplicatedWritesMetrics, ExecutionMetrics},
-    ProtocolVersionId, Transaction,
+    ProtocolVersionId, Transaction, VmEvent,
 };
 use zksync_utils::time::millis_since;
 
@@ -82,6 +82,12 @@ pub struct SealData {
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
     pub(super) gas_remaining: u32,
+    pub(super) l1_tx_count: usize,
+    /// Time when the first transaction covered by this data was received, in milliseconds since UNIX epoch.
+    pub(super) first_tx_received_timestamp_ms: u64,
+    /// Events emitted by the transaction. Only tracked for a single transaction, and only if the sealer
+    /// [uses them](ConditionalSealer::uses_tx_events()); empty for the entire L1 batch.
+    pub(super) events: Vec<VmEvent>,
 }
 
 impl SealData {
@@ -102,6 +108,9 @@ impl SealData {
             cumulative_size: transaction.bootloader_encoding_size(),
             writes_metrics,
             gas_remaining: tx_metrics.gas_remaining,
+            l1_tx_count: usize::from(transaction.is_l1()),
+            first_tx_received_timestamp_ms: transaction.received_timestamp_ms,
+            events: vec![],
         }
     }
 }
@@ -120,6 +129,11 @@ pub(super) trait SealCriterion: fmt::Debug + Send + Sync + 'static {
     // We need self here only for rust restrictions for creating an object from trait
     // https://doc.rust-lang.org/reference/items/traits.html#object-safety
     fn prom_criterion_name(&self) -> &'static str;
+
+    /// Returns whether this criterion uses [`SealData::events`].
+    fn uses_tx_events(&self) -> bool {
+        false
+    }
 }
 
 /// I/O-dependent seal criteria.
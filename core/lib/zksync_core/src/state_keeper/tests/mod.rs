@@ -25,7 +25,7 @@ use zksync_types::{
     tx::tx_execution_info::ExecutionMetrics,
     zk_evm_types::{LogQuery, Timestamp},
     Address, L1BatchNumber, L2ChainId, MiniblockNumber, ProtocolVersionId, StorageLogQuery,
-    StorageLogQueryType, Transaction, H256, U256,
+    StorageLogQueryType, Transaction, VmEvent, H256, U256,
 };
 
 mod tester;
@@ -38,9 +38,10 @@ pub(crate) use self::tester::{MockBatchExecutor, TestBatchExecutorBuilder};
 use crate::{
     gas_tracker::l1_batch_base_cost,
     state_keeper::{
+        batch_executor::TxExecutionResult,
         keeper::POLL_WAIT_DURATION,
         seal_criteria::{
-            criteria::{GasCriterion, SlotsCriterion},
+            criteria::{EventCriterion, GasCriterion, SlotsCriterion},
             SequencerSealer,
         },
         types::ExecutionMetricsForCriteria,
@@ -304,6 +305,40 @@ async fn sealed_by_gas_then_by_num_tx() {
         .await;
 }
 
+#[tokio::test]
+async fn sealed_by_emitted_event() {
+    let address = Address::repeat_byte(1);
+    let criterion = EventCriterion {
+        address,
+        topic: None,
+    };
+    let sealer =
+        SequencerSealer::with_sealers(StateKeeperConfig::default(), vec![Box::new(criterion)]);
+
+    let mut execution_result = successful_exec();
+    let TxExecutionResult::Success { tx_result, .. } = &mut execution_result else {
+        unreachable!();
+    };
+    tx_result.logs.events.push(VmEvent {
+        location: (L1BatchNumber(1), 1),
+        address,
+        indexed_topics: vec![H256::repeat_byte(2)],
+        value: vec![],
+    });
+
+    TestScenario::new()
+        .seal_miniblock_when(|updates| updates.miniblock.executed_transactions.len() == 1)
+        .next_tx("First tx", random_tx(1), successful_exec())
+        .miniblock_sealed("Miniblock 1")
+        .next_tx("Tx emitting event", random_tx(2), execution_result)
+        .miniblock_sealed("Miniblock 2")
+        .batch_sealed("Batch 1")
+        .next_tx("Third tx", random_tx(3), successful_exec())
+        .miniblock_sealed("Miniblock 3")
+        .run(sealer)
+        .await;
+}
+
 #[tokio::test]
 async fn batch_sealed_before_miniblock_does() {
     let config = StateKeeperConfig {
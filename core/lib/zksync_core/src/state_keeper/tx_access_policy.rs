@@ -0,0 +1,310 @@
+//! Access policy restricting which accounts may send L2 transactions or deploy contracts.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use tokio::sync::watch;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_types::{
+    l2::L2Tx, Address, ExecuteTransactionCommon, Transaction, CONTRACT_DEPLOYER_ADDRESS,
+};
+
+/// Error returned if a transaction is not allowed by [`TxAccessPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TxAccessError {
+    #[error("account {0:?} is not allowed to send transactions")]
+    SenderNotAllowed(Address),
+    #[error("account {0:?} is not allowed to deploy contracts")]
+    DeploymentNotAllowed(Address),
+}
+
+/// Access lists, either configured statically or loaded from the policy file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TxAccessLists {
+    /// If non-empty, only these accounts may send L2 transactions.
+    sender_allowlist: HashSet<Address>,
+    /// Accounts that may not send L2 transactions.
+    sender_denylist: HashSet<Address>,
+    /// If non-empty, only these accounts may deploy contracts.
+    deployer_allowlist: HashSet<Address>,
+}
+
+impl TxAccessLists {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read(path).with_context(|| {
+            format!("failed reading tx access policy from `{}`", path.display())
+        })?;
+        serde_json::from_slice(&contents)
+            .with_context(|| format!("failed parsing tx access policy from `{}`", path.display()))
+    }
+}
+
+/// Policy restricting which accounts may send L2 transactions or deploy contracts.
+///
+/// The policy consists of static lists specified in [`StateKeeperConfig`] and, optionally, of lists loaded
+/// from a JSON policy file. Lists from both sources are merged. The policy file is reloaded periodically
+/// by [`Self::run_reloader()`], so it can be changed without restarting the node.
+///
+/// L1 and protocol upgrade transactions are never restricted.
+///
+/// # Deployment restrictions
+///
+/// The deployer allowlist is enforced on a best-effort basis. A transaction is considered a deployment
+/// only if it calls the contract deployer system contract directly; deployments performed by other contracts
+/// (e.g., factories) or by custom accounts on behalf of a transaction are **not** detected. Thus, the deployer
+/// allowlist only provides meaningful guarantees if all allowed senders are EOAs and no deployed contract
+/// can deploy other contracts.
+///
+/// The policy is supposed to be shared among all components enforcing it (API servers and the state keeper)
+/// wrapped in an `Arc`, so that the policy file is loaded and reloaded once.
+#[derive(Debug, Default)]
+pub struct TxAccessPolicy {
+    static_lists: TxAccessLists,
+    file_lists: RwLock<TxAccessLists>,
+    file_path: Option<PathBuf>,
+}
+
+impl TxAccessPolicy {
+    const RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// Creates a policy based on the provided config. If the policy file is configured,
+    /// it is loaded synchronously, so that the policy is enforced from the start.
+    pub fn new(config: &StateKeeperConfig) -> anyhow::Result<Self> {
+        let static_lists = TxAccessLists {
+            sender_allowlist: config.tx_sender_allowlist.iter().copied().collect(),
+            sender_denylist: config.tx_sender_denylist.iter().copied().collect(),
+            deployer_allowlist: config.contract_deployer_allowlist.iter().copied().collect(),
+        };
+        let file_path = config.tx_access_policy_path.as_ref().map(PathBuf::from);
+        let file_lists = match &file_path {
+            Some(path) => TxAccessLists::load(path)?,
+            None => TxAccessLists::default(),
+        };
+        Ok(Self {
+            static_lists,
+            file_lists: RwLock::new(file_lists),
+            file_path,
+        })
+    }
+
+    /// Checks whether the policy has a file that should be reloaded using [`Self::run_reloader()`].
+    pub fn is_reloadable(&self) -> bool {
+        self.file_path.is_some()
+    }
+
+    /// Checks whether the specified transaction is allowed by the policy.
+    pub fn check(&self, transaction: &Transaction) -> Result<(), TxAccessError> {
+        if !matches!(transaction.common_data, ExecuteTransactionCommon::L2(_)) {
+            return Ok(());
+        }
+        self.check_access(
+            transaction.initiator_account(),
+            transaction.execute.contract_address,
+        )
+    }
+
+    /// Checks whether the specified L2 transaction is allowed by the policy.
+    pub fn check_l2_tx(&self, transaction: &L2Tx) -> Result<(), TxAccessError> {
+        self.check_access(
+            transaction.initiator_account(),
+            transaction.execute.contract_address,
+        )
+    }
+
+    fn check_access(
+        &self,
+        initiator: Address,
+        contract_address: Address,
+    ) -> Result<(), TxAccessError> {
+        let file_lists = self
+            .file_lists
+            .read()
+            .expect("tx access policy is poisoned");
+        let lists = [&self.static_lists, &*file_lists];
+
+        let is_denied = lists
+            .iter()
+            .any(|lists| lists.sender_denylist.contains(&initiator));
+        if is_denied || !Self::is_allowed(&lists, initiator, |lists| &lists.sender_allowlist) {
+            return Err(TxAccessError::SenderNotAllowed(initiator));
+        }
+
+        // Best-effort check; see the type docs for details.
+        let is_deployment = contract_address == CONTRACT_DEPLOYER_ADDRESS;
+        if is_deployment && !Self::is_allowed(&lists, initiator, |lists| &lists.deployer_allowlist)
+        {
+            return Err(TxAccessError::DeploymentNotAllowed(initiator));
+        }
+        Ok(())
+    }
+
+    /// Checks the address against the merged allowlist. An empty allowlist allows all addresses.
+    fn is_allowed(
+        lists: &[&TxAccessLists],
+        address: Address,
+        allowlist: impl Fn(&TxAccessLists) -> &HashSet<Address>,
+    ) -> bool {
+        let mut is_restricted = false;
+        for lists in lists {
+            let allowlist = allowlist(lists);
+            if allowlist.contains(&address) {
+                return true;
+            }
+            is_restricted |= !allowlist.is_empty();
+        }
+        !is_restricted
+    }
+
+    fn reload(&self, path: &Path) -> anyhow::Result<()> {
+        let new_lists = TxAccessLists::load(path)?;
+        let mut file_lists = self
+            .file_lists
+            .write()
+            .expect("tx access policy is poisoned");
+        if *file_lists != new_lists {
+            tracing::info!("Reloaded tx access policy from `{}`", path.display());
+            *file_lists = new_lists;
+        }
+        Ok(())
+    }
+
+    /// Periodically reloads the policy file. If the file cannot be loaded, the previously loaded lists
+    /// remain in effect.
+    pub async fn run_reloader(
+        self: Arc<Self>,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let Some(path) = self.file_path.clone() else {
+            return Ok(());
+        };
+        loop {
+            if tokio::time::timeout(Self::RELOAD_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+            let this = self.clone();
+            let path = path.clone();
+            let reload_result = tokio::task::spawn_blocking(move || this.reload(&path))
+                .await
+                .context("panicked while reloading tx access policy")?;
+            if let Err(err) = reload_result {
+                tracing::warn!("Failed reloading tx access policy: {err:#}");
+            }
+        }
+        tracing::info!("Stop signal received, tx access policy reloader is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::utils::testonly::create_l2_transaction;
+
+    fn transaction(initiator: Address, contract_address: Address) -> Transaction {
+        let mut tx = create_l2_transaction(10, 100);
+        tx.common_data.initiator_address = initiator;
+        tx.execute.contract_address = contract_address;
+        tx.into()
+    }
+
+    #[test]
+    fn checking_static_lists() {
+        let allowed = Address::repeat_byte(1);
+        let deployer = Address::repeat_byte(2);
+        let denied = Address::repeat_byte(3);
+        let config = StateKeeperConfig {
+            tx_sender_allowlist: vec![allowed, deployer, denied],
+            tx_sender_denylist: vec![denied],
+            contract_deployer_allowlist: vec![deployer],
+            ..StateKeeperConfig::for_tests()
+        };
+        let policy = TxAccessPolicy::new(&config).unwrap();
+        let other_address = Address::repeat_byte(0x42);
+
+        policy.check(&transaction(allowed, other_address)).unwrap();
+        policy.check(&transaction(deployer, other_address)).unwrap();
+        policy
+            .check(&transaction(deployer, CONTRACT_DEPLOYER_ADDRESS))
+            .unwrap();
+
+        assert_eq!(
+            policy.check(&transaction(allowed, CONTRACT_DEPLOYER_ADDRESS)),
+            Err(TxAccessError::DeploymentNotAllowed(allowed))
+        );
+        assert_eq!(
+            policy.check(&transaction(denied, other_address)),
+            Err(TxAccessError::SenderNotAllowed(denied))
+        );
+        assert_eq!(
+            policy.check(&transaction(other_address, other_address)),
+            Err(TxAccessError::SenderNotAllowed(other_address))
+        );
+    }
+
+    #[test]
+    fn default_policy_allows_everything() {
+        let policy = TxAccessPolicy::new(&StateKeeperConfig::for_tests()).unwrap();
+        let address = Address::repeat_byte(1);
+        policy.check(&transaction(address, address)).unwrap();
+        policy
+            .check(&transaction(address, CONTRACT_DEPLOYER_ADDRESS))
+            .unwrap();
+    }
+
+    #[test]
+    fn loading_and_reloading_policy_file() {
+        let denied = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let mut policy_file = tempfile::NamedTempFile::new().unwrap();
+        write!(policy_file, r#"{{ "sender_denylist": ["{denied:?}"] }}"#).unwrap();
+        policy_file.flush().unwrap();
+
+        let config = StateKeeperConfig {
+            tx_access_policy_path: Some(policy_file.path().to_str().unwrap().to_owned()),
+            ..StateKeeperConfig::for_tests()
+        };
+        let policy = TxAccessPolicy::new(&config).unwrap();
+        assert!(policy.is_reloadable());
+        assert_eq!(
+            policy.check(&transaction(denied, other_address)),
+            Err(TxAccessError::SenderNotAllowed(denied))
+        );
+        policy
+            .check(&transaction(other_address, other_address))
+            .unwrap();
+
+        std::fs::write(
+            policy_file.path(),
+            format!(r#"{{ "deployer_allowlist": ["{denied:?}"] }}"#),
+        )
+        .unwrap();
+        policy.reload(policy_file.path()).unwrap();
+        policy.check(&transaction(denied, other_address)).unwrap();
+        policy
+            .check(&transaction(denied, CONTRACT_DEPLOYER_ADDRESS))
+            .unwrap();
+        assert_eq!(
+            policy.check(&transaction(other_address, CONTRACT_DEPLOYER_ADDRESS)),
+            Err(TxAccessError::DeploymentNotAllowed(other_address))
+        );
+
+        // A malformed file doesn't change the policy.
+        std::fs::write(policy_file.path(), "not a policy").unwrap();
+        policy.reload(policy_file.path()).unwrap_err();
+        policy
+            .check(&transaction(denied, CONTRACT_DEPLOYER_ADDRESS))
+            .unwrap();
+    }
+}
@@ -4,8 +4,11 @@ use std::{
 };
 
 use multivm::interface::VmExecutionResultAndLogs;
+use zksync_config::configs::chain::{MempoolConfig, TxOrderingPolicyKind};
 use zksync_dal::StorageProcessor;
-use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
+use zksync_mempool::{
+    AccountTxPolicy, L2TxFilter, MempoolInfo, MempoolSnapshot, MempoolStore, TxOrderingPolicy,
+};
 use zksync_types::{
     block::BlockGasCount, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
 };
@@ -16,13 +19,39 @@ use crate::gas_tracker::{gas_count_from_metrics, gas_count_from_tx_and_metrics};
 #[derive(Debug, Clone)]
 pub struct MempoolGuard(Arc<Mutex<MempoolStore>>);
 
+/// Extracts limits on pending transactions of a single account from the mempool config.
+pub(crate) fn account_tx_policy(config: &MempoolConfig) -> AccountTxPolicy {
+    AccountTxPolicy {
+        max_pending_txs: config.max_pending_txs_per_account,
+        min_replacement_fee_bump_percent: config.replacement_fee_bump_percent,
+    }
+}
+
+/// Converts the transaction ordering policy from the mempool config.
+pub(crate) fn tx_ordering_policy(config: &MempoolConfig) -> TxOrderingPolicy {
+    match config.ordering_policy {
+        TxOrderingPolicyKind::Fifo => TxOrderingPolicy::Fifo,
+        TxOrderingPolicyKind::FeePriority => TxOrderingPolicy::FeePriority,
+        TxOrderingPolicyKind::RoundRobin => TxOrderingPolicy::RoundRobin,
+    }
+}
+
 impl MempoolGuard {
-    pub async fn from_storage(storage_processor: &mut StorageProcessor<'_>, capacity: u64) -> Self {
+    /// Number of accounts whose transactions are cloned under a single mempool lock when taking a snapshot.
+    const SNAPSHOT_ACCOUNTS_CHUNK_SIZE: usize = 1_000;
+
+    pub async fn from_storage(
+        storage_processor: &mut StorageProcessor<'_>,
+        config: &MempoolConfig,
+    ) -> Self {
         let next_priority_id = storage_processor
             .transactions_dal()
             .next_priority_id()
             .await;
-        Self::new(next_priority_id, capacity)
+        let store = MempoolStore::new(next_priority_id, config.capacity)
+            .with_account_policy(account_tx_policy(config))
+            .with_ordering_policy(tx_ordering_policy(config));
+        Self(Arc::new(Mutex::new(store)))
     }
 
     pub(super) fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
@@ -65,6 +94,28 @@ impl MempoolGuard {
             .get_mempool_info()
     }
 
+    /// Takes a snapshot of the mempool. Transactions are cloned in chunks of accounts, releasing the lock
+    /// in between so that the state keeper isn't blocked, and are sorted after the lock is released. Thus,
+    /// the snapshot is consistent per account, but not necessarily across accounts.
+    pub fn snapshot(&self) -> MempoolSnapshot {
+        let (l1_transactions, accounts) = {
+            let mempool = self.0.lock().expect("failed to acquire mempool lock");
+            (mempool.snapshot_l1_transactions(), mempool.l2_accounts())
+        };
+        let mut l2_transactions = vec![];
+        for chunk in accounts.chunks(Self::SNAPSHOT_ACCOUNTS_CHUNK_SIZE) {
+            let mempool = self.0.lock().expect("failed to acquire mempool lock");
+            l2_transactions.extend(mempool.snapshot_accounts(chunk));
+        }
+
+        let mut snapshot = MempoolSnapshot {
+            l1_transactions,
+            l2_transactions,
+        };
+        snapshot.sort();
+        snapshot
+    }
+
     #[cfg(test)]
     pub fn stats(&self) -> zksync_mempool::MempoolStats {
         self.0
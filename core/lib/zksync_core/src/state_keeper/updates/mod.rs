@@ -98,9 +98,14 @@ impl UpdatesManager {
         tx_execution_result: VmExecutionResultAndLogs,
         compressed_bytecodes: Vec<CompressedBytecodeInfo>,
         tx_l1_gas_this_tx: BlockGasCount,
-        execution_metrics: ExecutionMetrics,
+        mut execution_metrics: ExecutionMetrics,
         call_traces: Vec<Call>,
     ) {
+        // Counted the same way as for fee estimates, i.e. regardless of other transactions in the batch.
+        let tx_writes_metrics =
+            StorageWritesDeduplicator::apply_on_empty_state(&tx_execution_result.logs.storage_logs);
+        execution_metrics.initial_storage_writes = tx_writes_metrics.initial_storage_writes;
+        execution_metrics.repeated_storage_writes = tx_writes_metrics.repeated_storage_writes;
         self.storage_writes_deduplicator
             .apply(&tx_execution_result.logs.storage_logs);
         self.miniblock.extend_from_executed_transaction(
@@ -144,6 +149,26 @@ impl UpdatesManager {
         self.l1_batch.executed_transactions.len() + self.miniblock.executed_transactions.len()
     }
 
+    /// Returns the time when the first transaction in the pending L1 batch was received.
+    pub(crate) fn first_tx_received_timestamp_ms(&self) -> Option<u64> {
+        let first_tx = self
+            .l1_batch
+            .executed_transactions
+            .first()
+            .or_else(|| self.miniblock.executed_transactions.first())?;
+        Some(first_tx.transaction.received_timestamp_ms)
+    }
+
+    pub(crate) fn pending_l1_transactions_len(&self) -> usize {
+        let miniblock_l1_txs = self
+            .miniblock
+            .executed_transactions
+            .iter()
+            .filter(|tx| tx.transaction.is_l1())
+            .count();
+        self.l1_batch.priority_ops_onchain_data.len() + miniblock_l1_txs
+    }
+
     pub(crate) fn pending_l1_gas_count(&self) -> BlockGasCount {
         self.l1_batch.l1_gas_count + self.miniblock.l1_gas_count
     }
@@ -178,11 +203,13 @@ pub(crate) struct MiniblockSealCommand {
 
 #[cfg(test)]
 mod tests {
+    use zksync_types::U256;
+
     use super::*;
     use crate::{
         gas_tracker::new_block_gas_count,
         state_keeper::tests::{
-            create_execution_result, create_transaction, create_updates_manager,
+            create_execution_result, create_transaction, create_updates_manager, Query,
         },
     };
 
@@ -220,4 +247,31 @@ mod tests {
         assert_eq!(updates_manager.miniblock.executed_transactions.len(), 0);
         assert_eq!(updates_manager.l1_batch.executed_transactions.len(), 1);
     }
+
+    #[test]
+    fn recording_storage_writes_for_tx() {
+        let mut updates_manager = create_updates_manager();
+        let tx = create_transaction(10, 100);
+        let storage_logs = [
+            (U256::from(1), Query::InitialWrite(U256::from(1))),
+            (
+                U256::from(2),
+                Query::RepeatedWrite(U256::from(1), U256::from(2)),
+            ),
+            (U256::from(3), Query::InitialWrite(U256::from(1))),
+            (U256::from(3), Query::Read(U256::from(1))),
+        ];
+        updates_manager.extend_from_executed_transaction(
+            tx,
+            create_execution_result(0, storage_logs),
+            vec![],
+            new_block_gas_count(),
+            ExecutionMetrics::default(),
+            vec![],
+        );
+
+        let tx_result = &updates_manager.miniblock.executed_transactions[0];
+        assert_eq!(tx_result.execution_info.initial_storage_writes, 2);
+        assert_eq!(tx_result.execution_info.repeated_storage_writes, 1);
+    }
 }
@@ -6,6 +6,7 @@ use anyhow::Context;
 use zksync_config::{
     configs::{
         chain::{MempoolConfig, NetworkConfig, OperationsManagerConfig, StateKeeperConfig},
+        eth_sender::SenderConfig,
         ObservabilityConfig, ProofDataHandlerConfig,
     },
     ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHWatchConfig, GasAdjusterConfig,
@@ -63,7 +64,12 @@ impl MainNodeBuilder {
     fn add_fee_input_layer(mut self) -> anyhow::Result<Self> {
         let gas_adjuster_config = GasAdjusterConfig::from_env()?;
         let state_keeper_config = StateKeeperConfig::from_env()?;
-        let fee_input_layer = SequencerFeeInputLayer::new(gas_adjuster_config, state_keeper_config);
+        let eth_sender_config = SenderConfig::from_env()?;
+        let fee_input_layer = SequencerFeeInputLayer::new(
+            gas_adjuster_config,
+            state_keeper_config,
+            eth_sender_config.pubdata_sending_mode,
+        );
         self.node.add_layer(fee_input_layer);
         Ok(self)
     }
@@ -1,9 +1,15 @@
 use std::sync::Arc;
 
 use anyhow::Context;
-use zksync_config::{configs::chain::StateKeeperConfig, GasAdjusterConfig};
-use zksync_core::{fee_model::MainNodeFeeInputProvider, l1_gas_price::GasAdjuster};
-use zksync_types::fee_model::FeeModelConfig;
+use zksync_config::{
+    configs::{chain::StateKeeperConfig, eth_sender::PubdataSendingMode},
+    GasAdjusterConfig,
+};
+use zksync_core::{
+    fee_model::{new_fee_input_strategy, MainNodeFeeInputProvider},
+    l1_gas_price::GasAdjuster,
+};
+use zksync_types::fee_model::{FeeInputStrategyConfig, FeeModelConfig};
 
 use crate::{
     implementations::resources::{
@@ -18,16 +24,19 @@ use crate::{
 pub struct SequencerFeeInputLayer {
     gas_adjuster_config: GasAdjusterConfig,
     state_keeper_config: StateKeeperConfig,
+    pubdata_sending_mode: PubdataSendingMode,
 }
 
 impl SequencerFeeInputLayer {
     pub fn new(
         gas_adjuster_config: GasAdjusterConfig,
         state_keeper_config: StateKeeperConfig,
+        pubdata_sending_mode: PubdataSendingMode,
     ) -> Self {
         Self {
             gas_adjuster_config,
             state_keeper_config,
+            pubdata_sending_mode,
         }
     }
 }
@@ -40,15 +49,21 @@ impl WiringLayer for SequencerFeeInputLayer {
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
         let client = context.get_resource::<EthInterfaceResource>().await?.0;
-        let adjuster = GasAdjuster::new(client, self.gas_adjuster_config)
-            .await
-            .context("GasAdjuster::new()")?;
+        let adjuster =
+            GasAdjuster::new(client, self.gas_adjuster_config, self.pubdata_sending_mode)
+                .await
+                .context("GasAdjuster::new()")?;
         let gas_adjuster = Arc::new(adjuster);
 
-        let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
-            gas_adjuster.clone(),
-            FeeModelConfig::from_state_keeper_config(&self.state_keeper_config),
-        ));
+        let batch_fee_input_provider = Arc::new(
+            MainNodeFeeInputProvider::new(
+                gas_adjuster.clone(),
+                FeeModelConfig::from_state_keeper_config(&self.state_keeper_config),
+            )
+            .with_strategy(new_fee_input_strategy(
+                FeeInputStrategyConfig::from_state_keeper_config(&self.state_keeper_config),
+            )),
+        );
         context.insert_resource(FeeInputResource(batch_fee_input_provider))?;
 
         context.add_task(Box::new(GasAdjusterTask { gas_adjuster }));
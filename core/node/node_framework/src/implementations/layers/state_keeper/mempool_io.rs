@@ -6,7 +6,7 @@ use zksync_config::{
     ContractsConfig,
 };
 use zksync_core::state_keeper::{
-    MempoolFetcher, MempoolGuard, MempoolIO, MiniblockSealer, SequencerSealer,
+    MempoolFetcher, MempoolGuard, MempoolIO, MiniblockSealer, SequencerSealer, TxAccessPolicy,
 };
 
 use crate::{
@@ -57,7 +57,7 @@ impl MempoolIOLayer {
             .access_storage()
             .await
             .context("Access storage to build mempool")?;
-        let mempool = MempoolGuard::from_storage(&mut storage, self.mempool_config.capacity).await;
+        let mempool = MempoolGuard::from_storage(&mut storage, &self.mempool_config).await;
         mempool.register_metrics();
         Ok(mempool)
     }
@@ -99,6 +99,15 @@ impl WiringLayer for MempoolIOLayer {
         );
         context.add_task(Box::new(MempoolFetcherTask(mempool_fetcher)));
 
+        // Create tx access policy and its reloader task, if the policy file is configured.
+        let access_policy = Arc::new(
+            TxAccessPolicy::new(&self.state_keeper_config)
+                .context("failed loading tx access policy")?,
+        );
+        if access_policy.is_reloadable() {
+            context.add_task(Box::new(TxAccessPolicyReloaderTask(access_policy.clone())));
+        }
+
         // Create mempool IO resource.
         let mempool_db_pool = master_pool
             .get_singleton()
@@ -115,6 +124,7 @@ impl WiringLayer for MempoolIOLayer {
             self.contracts_config.l2_erc20_bridge_addr,
             self.state_keeper_config.validation_computational_gas_limit,
             self.network_config.zksync_network_id,
+            access_policy,
         )
         .await?;
         context.insert_resource(StateKeeperIOResource(Unique::new(Box::new(io))))?;
@@ -155,3 +165,17 @@ impl Task for MempoolFetcherTask {
         self.0.run(stop_receiver.0).await
     }
 }
+
+#[derive(Debug)]
+struct TxAccessPolicyReloaderTask(Arc<TxAccessPolicy>);
+
+#[async_trait::async_trait]
+impl Task for TxAccessPolicyReloaderTask {
+    fn name(&self) -> &'static str {
+        "state_keeper/tx_access_policy_reloader"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run_reloader(stop_receiver.0).await
+    }
+}
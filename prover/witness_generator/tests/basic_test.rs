@@ -33,6 +33,7 @@ async fn test_leaf_witness_gen() {
             file_backed_base_path: "./tests/data/leaf/".to_owned(),
         },
         max_retries: 5,
+        enable_compression: false,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -73,6 +74,7 @@ async fn test_node_witness_gen() {
             file_backed_base_path: "./tests/data/node/".to_owned(),
         },
         max_retries: 5,
+        enable_compression: false,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -114,6 +116,7 @@ async fn test_scheduler_witness_gen() {
             file_backed_base_path: "./tests/data/scheduler/".to_owned(),
         },
         max_retries: 5,
+        enable_compression: false,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()